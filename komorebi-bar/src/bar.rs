@@ -5,6 +5,7 @@ use crate::config::PositionConfig;
 use crate::komorebi::Komorebi;
 use crate::komorebi::KomorebiNotificationState;
 use crate::process_hwnd;
+use crate::register_app_bar;
 use crate::render::Color32Ext;
 use crate::render::Grouping;
 use crate::render::RenderConfig;
@@ -12,6 +13,7 @@ use crate::render::RenderExt;
 use crate::widget::BarWidget;
 use crate::widget::WidgetConfig;
 use crate::BAR_HEIGHT;
+use crate::BAR_HWND;
 use crate::MAX_LABEL_WIDTH;
 use crate::MONITOR_LEFT;
 use crate::MONITOR_RIGHT;
@@ -38,7 +40,6 @@ use eframe::egui::Visuals;
 use font_loader::system_fonts;
 use font_loader::system_fonts::FontPropertyBuilder;
 use komorebi_client::KomorebiTheme;
-use komorebi_client::SocketMessage;
 use komorebi_themes::catppuccin_egui;
 use komorebi_themes::Base16Value;
 use komorebi_themes::Catppuccin;
@@ -151,6 +152,19 @@ pub fn apply_theme(
 
             bg_color.replace(base16.background());
         }
+        KomobarTheme::Custom { colours, accent } => {
+            ctx.set_style(colours.style());
+            let accent =
+                accent.map_or_else(|| colours.color32(Base16Value::default()), Color32::from);
+
+            ctx.style_mut(|style| {
+                style.visuals.selection.stroke.color = accent;
+                style.visuals.widgets.hovered.fg_stroke.color = accent;
+                style.visuals.widgets.active.fg_stroke.color = accent;
+            });
+
+            bg_color.replace(colours.base_00);
+        }
     }
 
     // Apply transparency_alpha
@@ -206,6 +220,8 @@ impl Komobar {
         });
 
         if let Some(hwnd) = process_hwnd() {
+            BAR_HWND.store(hwnd, Ordering::SeqCst);
+
             let start = position.start.unwrap_or(Position {
                 x: MONITOR_LEFT.load(Ordering::SeqCst) as f32,
                 y: MONITOR_TOP.load(Ordering::SeqCst) as f32,
@@ -332,25 +348,13 @@ impl Komobar {
         self.center_widgets = center_widgets;
         self.right_widgets = right_widgets;
 
-        if let (Some(prev_rect), Some(new_rect)) = (
-            &self.config.monitor.work_area_offset,
-            &config.monitor.work_area_offset,
-        ) {
-            if new_rect != prev_rect {
-                if let Err(error) = komorebi_client::send_message(
-                    &SocketMessage::MonitorWorkAreaOffset(config.monitor.index, *new_rect),
-                ) {
-                    tracing::error!(
-                        "error applying work area offset to monitor '{}': {}",
-                        config.monitor.index,
-                        error,
-                    );
-                } else {
-                    tracing::info!(
-                        "work area offset applied to monitor: {}",
-                        config.monitor.index
-                    );
-                }
+        // Registrations are keyed by the bar's hwnd, so re-registering is idempotent and an
+        // offset which has been removed from the configuration releases the reserved space
+        if config.monitor.work_area_offset.is_some()
+            || self.config.monitor.work_area_offset.is_some()
+        {
+            if let Err(error) = register_app_bar(config.monitor.work_area_offset.as_ref()) {
+                tracing::error!("error registering app bar with komorebi: {error}");
             }
         }
 
@@ -396,7 +400,7 @@ impl Komobar {
                         if let Some(theme) = config.theme {
                             apply_theme(
                                 ctx,
-                                KomobarTheme::from(theme),
+                                KomobarTheme::from(theme.clone()),
                                 self.bg_color.clone(),
                                 self.bg_color_with_alpha.clone(),
                                 bar_transparency_alpha,
@@ -413,6 +417,11 @@ impl Komobar {
                                 KomorebiTheme::Base16 {
                                     name, stack_border, ..
                                 } => stack_border.unwrap_or(Base16Value::Base0B).color32(name),
+                                KomorebiTheme::Custom {
+                                    colours,
+                                    stack_border,
+                                    ..
+                                } => stack_border.map_or(colours.base_0b, Color32::from),
                             };
 
                             if let Some(state) = &self.komorebi_notification_state {
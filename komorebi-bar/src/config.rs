@@ -3,6 +3,7 @@ use crate::widget::WidgetConfig;
 use eframe::egui::Pos2;
 use eframe::egui::TextBuffer;
 use eframe::egui::Vec2;
+use komorebi_client::Colour;
 use komorebi_client::KomorebiTheme;
 use komorebi_client::Rect;
 use schemars::JsonSchema;
@@ -94,7 +95,8 @@ pub struct FrameConfig {
 pub struct MonitorConfig {
     /// Komorebi monitor index of the monitor on which to render the bar
     pub index: usize,
-    /// Automatically apply a work area offset for this monitor to accommodate the bar
+    /// Reserve space for the bar on this monitor, using the first non-zero of top, bottom, left
+    /// and right as the edge and thickness of the reservation
     pub work_area_offset: Option<Rect>,
 }
 
@@ -157,6 +159,11 @@ pub enum KomobarTheme {
         name: komorebi_themes::Base16,
         accent: Option<komorebi_themes::Base16Value>,
     },
+    /// A theme built from a complete Base16 palette
+    Custom {
+        colours: komorebi_themes::Base16ColourPalette,
+        accent: Option<Colour>,
+    },
 }
 
 impl From<KomorebiTheme> for KomobarTheme {
@@ -174,6 +181,14 @@ impl From<KomorebiTheme> for KomobarTheme {
                 name,
                 accent: bar_accent,
             },
+            KomorebiTheme::Custom {
+                colours,
+                bar_accent,
+                ..
+            } => Self::Custom {
+                colours,
+                accent: bar_accent,
+            },
         }
     }
 }
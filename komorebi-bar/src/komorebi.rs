@@ -30,7 +30,6 @@ use eframe::egui::Vec2;
 use image::RgbaImage;
 use komorebi_client::Container;
 use komorebi_client::NotificationEvent;
-use komorebi_client::Rect;
 use komorebi_client::SocketMessage;
 use komorebi_client::Window;
 use komorebi_client::Workspace;
@@ -118,7 +117,6 @@ impl From<&KomorebiConfig> for Komorebi {
                     .map(|w| w.hide_empty_workspaces)
                     .unwrap_or_default(),
                 mouse_follows_focus: true,
-                work_area_offset: None,
                 focused_container_information: KomorebiNotificationStateContainerInformation::EMPTY,
                 stack_accent: None,
                 monitor_index: MONITOR_INDEX.load(Ordering::SeqCst),
@@ -302,55 +300,24 @@ impl BarWidget for Komorebi {
                 for (name, location) in configuration_switcher.configurations.iter() {
                     let path = PathBuf::from(location);
                     if path.is_file() {
-                        config.apply_on_widget(false, ui,|ui|{
-                    if SelectableFrame::new(false).show(ui, |ui|{
-                          ui.add(Label::new(name).selectable(false))
-                            })
-                            .clicked()
-                        {
-                            let canonicalized = dunce::canonicalize(path.clone()).unwrap_or(path);
-                            let mut proceed = true;
-                            if komorebi_client::send_message(&SocketMessage::ReplaceConfiguration(
-                                canonicalized,
-                            ))
-                            .is_err()
+                        config.apply_on_widget(false, ui, |ui| {
+                            if SelectableFrame::new(false)
+                                .show(ui, |ui| ui.add(Label::new(name).selectable(false)))
+                                .clicked()
                             {
-                                tracing::error!(
-                                    "could not send message to komorebi: ReplaceConfiguration"
-                                );
-                                proceed = false;
-                            }
-
-                            if let Some(rect) = komorebi_notification_state.work_area_offset {
-                                if proceed {
-                                    match komorebi_client::send_query(&SocketMessage::Query(
-                                        komorebi_client::StateQuery::FocusedMonitorIndex,
-                                    )) {
-                                        Ok(idx) => {
-                                            if let Ok(monitor_idx) = idx.parse::<usize>() {
-                                                if komorebi_client::send_message(
-                                                    &SocketMessage::MonitorWorkAreaOffset(
-                                                        monitor_idx,
-                                                        rect,
-                                                    ),
-                                                )
-                                                .is_err()
-                                                {
-                                                    tracing::error!(
-                                                    "could not send message to komorebi: MonitorWorkAreaOffset"
-                                                );
-                                                }
-                                            }
-                                        }
-                                        Err(_) => {
-                                            tracing::error!(
-                                                "could not send message to komorebi: Query"
-                                            );
-                                        }
-                                    }
+                                let canonicalized =
+                                    dunce::canonicalize(path.clone()).unwrap_or(path);
+                                if komorebi_client::send_message(
+                                    &SocketMessage::ReplaceConfiguration(canonicalized),
+                                )
+                                .is_err()
+                                {
+                                    tracing::error!(
+                                        "could not send message to komorebi: ReplaceConfiguration"
+                                    );
                                 }
                             }
-                        }});
+                        });
                     }
                 }
             }
@@ -481,7 +448,6 @@ pub struct KomorebiNotificationState {
     pub layout: KomorebiLayout,
     pub hide_empty_workspaces: bool,
     pub mouse_follows_focus: bool,
-    pub work_area_offset: Option<Rect>,
     pub stack_accent: Option<Color32>,
     pub monitor_index: usize,
 }
@@ -515,9 +481,9 @@ impl KomorebiNotificationState {
             },
             Ok(notification) => {
                 match notification.event {
-                    NotificationEvent::WindowManager(_) => {}
+                    NotificationEvent::WindowManager(_) | NotificationEvent::Panic(_) => {}
                     NotificationEvent::Socket(message) => match message {
-                        SocketMessage::ReloadStaticConfiguration(path) => {
+                        SocketMessage::ReloadStaticConfiguration(path, _) => {
                             if let Ok(config) = komorebi_client::StaticConfig::read(&path) {
                                 if let Some(theme) = config.theme {
                                     apply_theme(
@@ -567,8 +533,6 @@ impl KomorebiNotificationState {
                 self.mouse_follows_focus = notification.state.mouse_follows_focus;
 
                 let monitor = &notification.state.monitors.elements()[monitor_index];
-                self.work_area_offset =
-                    notification.state.monitors.elements()[monitor_index].work_area_offset();
 
                 let focused_workspace_idx = monitor.focused_workspace_idx();
 
@@ -173,6 +173,18 @@ impl KomorebiLayout {
                     painter.line_segment([c - vec2(r, 0.0), c + vec2(r, 0.0)], stroke);
                     painter.line_segment([c - vec2(0.0, r), c + vec2(0.0, r)], stroke);
                 }
+                komorebi_client::DefaultLayout::Scrolling => {
+                    painter.line_segment([c - vec2(r / 2.0, r), c + vec2(-r / 2.0, r)], stroke);
+                    painter.line_segment([c - vec2(-r / 2.0, r), c + vec2(r / 2.0, r)], stroke);
+                    painter.line_segment(
+                        [c - vec2(r / 4.0, r / 4.0), c + vec2(-r / 4.0, r / 4.0)],
+                        stroke,
+                    );
+                    painter.line_segment(
+                        [c - vec2(-r / 4.0, r / 4.0), c + vec2(r / 4.0, r / 4.0)],
+                        stroke,
+                    );
+                }
             },
             KomorebiLayout::Monocle => {}
             KomorebiLayout::Floating => {
@@ -272,6 +284,7 @@ impl KomorebiLayout {
                                 komorebi_client::DefaultLayout::UltrawideVerticalStack,
                             ),
                             KomorebiLayout::Default(komorebi_client::DefaultLayout::Grid),
+                            KomorebiLayout::Default(komorebi_client::DefaultLayout::Scrolling),
                             //KomorebiLayout::Custom,
                             KomorebiLayout::Monocle,
                             KomorebiLayout::Floating,
@@ -25,6 +25,8 @@ use font_loader::system_fonts;
 use hotwatch::EventKind;
 use hotwatch::Hotwatch;
 use image::RgbaImage;
+use komorebi_client::Rect;
+use komorebi_client::ReservedSpaceEdge;
 use komorebi_client::SocketMessage;
 use komorebi_client::SubscribeOptions;
 use schemars::gen::SchemaSettings;
@@ -33,6 +35,7 @@ use std::io::BufReader;
 use std::io::Read;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicI32;
+use std::sync::atomic::AtomicIsize;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -55,6 +58,7 @@ pub static MONITOR_LEFT: AtomicI32 = AtomicI32::new(0);
 pub static MONITOR_TOP: AtomicI32 = AtomicI32::new(0);
 pub static MONITOR_RIGHT: AtomicI32 = AtomicI32::new(0);
 pub static MONITOR_INDEX: AtomicUsize = AtomicUsize::new(0);
+pub static BAR_HWND: AtomicIsize = AtomicIsize::new(0);
 pub static BAR_HEIGHT: f32 = 50.0;
 
 pub static ICON_CACHE: LazyLock<Mutex<HashMap<String, RgbaImage>>> =
@@ -112,6 +116,46 @@ fn process_hwnd() -> Option<isize> {
     }
 }
 
+/// The edge of the monitor that the bar sits on and the thickness of the space that should be
+/// reserved for it, derived from the configured work area offset
+fn app_bar_reservation(offset: &Rect) -> Option<(ReservedSpaceEdge, i32)> {
+    if offset.top > 0 {
+        Some((ReservedSpaceEdge::Top, offset.top))
+    } else if offset.bottom > 0 {
+        Some((ReservedSpaceEdge::Bottom, offset.bottom))
+    } else if offset.left > 0 {
+        Some((ReservedSpaceEdge::Left, offset.left))
+    } else if offset.right > 0 {
+        Some((ReservedSpaceEdge::Right, offset.right))
+    } else {
+        None
+    }
+}
+
+/// Register the bar window with komorebi so that space is reserved for it alongside any other
+/// bars on the same monitor, or unregister it when it no longer has a work area offset
+fn register_app_bar(work_area_offset: Option<&Rect>) -> color_eyre::Result<()> {
+    let hwnd = BAR_HWND.load(Ordering::SeqCst);
+    if hwnd == 0 {
+        return Ok(());
+    }
+
+    match work_area_offset.and_then(app_bar_reservation) {
+        Some((edge, thickness)) => {
+            komorebi_client::send_message(&SocketMessage::RegisterAppBar(hwnd, edge, thickness))?;
+            tracing::info!(
+                "registered app bar on the {edge:?} edge with a thickness of {thickness}"
+            );
+        }
+        None => {
+            komorebi_client::send_message(&SocketMessage::UnregisterAppBar(hwnd))?;
+            tracing::info!("unregistered app bar");
+        }
+    }
+
+    Ok(())
+}
+
 fn main() -> color_eyre::Result<()> {
     unsafe { SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2) }?;
 
@@ -286,17 +330,6 @@ fn main() -> color_eyre::Result<()> {
         ..Default::default()
     };
 
-    if let Some(rect) = &config.monitor.work_area_offset {
-        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
-            config.monitor.index,
-            *rect,
-        ))?;
-        tracing::info!(
-            "work area offset applied to monitor: {}",
-            config.monitor.index
-        );
-    }
-
     let (tx_gui, rx_gui) = crossbeam_channel::unbounded();
     let (tx_config, rx_config) = crossbeam_channel::unbounded();
 
@@ -343,6 +376,7 @@ fn main() -> color_eyre::Result<()> {
 
                 let listener = komorebi_client::subscribe_with_options(&subscriber_name, SubscribeOptions {
                     filter_state_changes: true,
+                    ..Default::default()
                 })
                     .expect("could not subscribe to komorebi notifications");
 
@@ -373,12 +407,9 @@ fn main() -> color_eyre::Result<()> {
 
                                 tracing::info!("reconnected to komorebi");
 
-                                if let Some(rect) = &config_cl.monitor.work_area_offset {
-                                    while komorebi_client::send_message(
-                                        &SocketMessage::MonitorWorkAreaOffset(
-                                            config_cl.monitor.index,
-                                            *rect,
-                                        ),
+                                if config_cl.monitor.work_area_offset.is_some() {
+                                    while register_app_bar(
+                                        config_cl.monitor.work_area_offset.as_ref(),
                                     )
                                     .is_err()
                                     {
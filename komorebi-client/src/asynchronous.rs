@@ -0,0 +1,228 @@
+//! Async variants of the client functions for use from within a tokio runtime
+//!
+//! uds_windows does not expose non-blocking sockets, so these functions run their blocking
+//! counterparts on tokio's blocking thread pool, and notifications are forwarded from a
+//! dedicated thread so that a long-lived subscription never holds on to a pooled thread.
+
+use crate::Notification;
+use crate::SocketMessage;
+use crate::SubscribeOptions;
+use crate::UnixListener;
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+async fn spawn_blocking<F, T>(f: F) -> std::io::Result<T>
+where
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Sends messages and queries to komorebi listening on a given socket, which defaults to the
+/// socket returned by [`crate::socket_path`]
+#[derive(Debug, Clone)]
+pub struct AsyncClient {
+    socket: PathBuf,
+}
+
+impl Default for AsyncClient {
+    fn default() -> Self {
+        Self::new(crate::socket_path())
+    }
+}
+
+impl AsyncClient {
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+        }
+    }
+
+    pub async fn send_message(&self, message: &SocketMessage) -> std::io::Result<()> {
+        let socket = self.socket.clone();
+        let message = message.clone();
+        spawn_blocking(move || crate::send_message_to(socket, &message)).await
+    }
+
+    pub async fn send_batch(
+        &self,
+        messages: impl IntoIterator<Item = SocketMessage>,
+    ) -> std::io::Result<()> {
+        let socket = self.socket.clone();
+        let messages = messages.into_iter().collect::<Vec<_>>();
+        spawn_blocking(move || crate::send_batch_to(socket, messages)).await
+    }
+
+    pub async fn send_query(&self, message: &SocketMessage) -> std::io::Result<String> {
+        let socket = self.socket.clone();
+        let message = message.clone();
+        spawn_blocking(move || crate::send_query_to(socket, &message)).await
+    }
+}
+
+pub async fn send_message_async(message: &SocketMessage) -> std::io::Result<()> {
+    AsyncClient::default().send_message(message).await
+}
+
+pub async fn send_batch_async(
+    messages: impl IntoIterator<Item = SocketMessage>,
+) -> std::io::Result<()> {
+    AsyncClient::default().send_batch(messages).await
+}
+
+pub async fn send_query_async(message: &SocketMessage) -> std::io::Result<String> {
+    AsyncClient::default().send_query(message).await
+}
+
+/// Subscribes to komorebi notifications, which are received on the returned channel until it
+/// is dropped
+pub async fn subscribe_async(name: &str) -> std::io::Result<UnboundedReceiver<Notification>> {
+    let name = name.to_string();
+    let listener = spawn_blocking(move || crate::subscribe(&name)).await?;
+
+    Ok(forward_notifications(listener))
+}
+
+/// Subscribes to komorebi notifications with the given options, which are received on the
+/// returned channel until it is dropped
+pub async fn subscribe_with_options_async(
+    name: &str,
+    options: SubscribeOptions,
+) -> std::io::Result<UnboundedReceiver<Notification>> {
+    let name = name.to_string();
+    let listener = spawn_blocking(move || crate::subscribe_with_options(&name, options)).await?;
+
+    Ok(forward_notifications(listener))
+}
+
+fn forward_notifications(listener: UnixListener) -> UnboundedReceiver<Notification> {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    std::thread::spawn(move || {
+        for notification in crate::notifications(&listener).flatten() {
+            if tx.send(notification).is_err() {
+                // the receiver has been dropped
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MoveBehaviour;
+    use crate::OperationBehaviour;
+    use crate::Ring;
+    use crate::State;
+    use komorebi::core::WindowContainerBehaviour;
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::Shutdown;
+    use std::path::Path;
+    use std::thread::JoinHandle;
+
+    fn socket(name: &str) -> PathBuf {
+        let socket = std::env::temp_dir().join(format!(
+            "komorebi-client-{}-{name}.sock",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&socket);
+        socket
+    }
+
+    /// Accepts a single connection on the socket like komorebi would, responding to whatever is
+    /// sent with the given response and returning what was received
+    fn mock_komorebi(socket: &Path, response: &str) -> JoinHandle<String> {
+        let listener = UnixListener::bind(socket).unwrap();
+        let response = response.to_string();
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut received = String::new();
+            stream.read_to_string(&mut received).unwrap();
+
+            // Messages which are not queries close the connection without reading a response
+            let _ = stream.write_all(response.as_bytes());
+            let _ = stream.shutdown(Shutdown::Write);
+
+            received
+        })
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn query_round_trip() {
+        let socket = socket("query");
+        let state = State {
+            monitors: Ring::default(),
+            is_paused: true,
+            resize_delta: 50,
+            new_window_behaviour: WindowContainerBehaviour::Append,
+            float_override: false,
+            cross_monitor_move_behaviour: MoveBehaviour::Insert,
+            unmanaged_window_operation_behaviour: OperationBehaviour::Op,
+            work_area_offset: None,
+            focus_follows_mouse: None,
+            mouse_follows_focus: true,
+            has_pending_raise_op: false,
+        };
+        let komorebi = mock_komorebi(&socket, &serde_json::to_string(&state).unwrap());
+
+        let response =
+            block_on(AsyncClient::new(&socket).send_query(&SocketMessage::State)).unwrap();
+        let received = serde_json::from_str::<State>(&response).unwrap();
+
+        assert!(received.is_paused);
+        assert_eq!(received.resize_delta, 50);
+        assert_eq!(received.cross_monitor_move_behaviour, MoveBehaviour::Insert);
+        assert!(received.monitors.elements().is_empty());
+        assert_eq!(
+            komorebi.join().unwrap(),
+            serde_json::to_string(&SocketMessage::State).unwrap()
+        );
+    }
+
+    #[test]
+    fn batch_is_sent_as_lines() {
+        let socket = socket("batch");
+        let komorebi = mock_komorebi(&socket, "");
+
+        block_on(
+            AsyncClient::new(&socket)
+                .send_batch([SocketMessage::Retile, SocketMessage::TogglePause]),
+        )
+        .unwrap();
+
+        let received = komorebi.join().unwrap();
+        let messages = received
+            .lines()
+            .map(|line| serde_json::from_str::<SocketMessage>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(matches!(
+            messages.as_slice(),
+            [SocketMessage::Retile, SocketMessage::TogglePause]
+        ));
+    }
+
+    #[test]
+    fn unreachable_socket() {
+        let socket = socket("unreachable");
+
+        assert!(block_on(AsyncClient::new(&socket).send_message(&SocketMessage::Retile)).is_err());
+    }
+}
@@ -1,17 +1,44 @@
 #![warn(clippy::all)]
 #![allow(clippy::missing_errors_doc)]
 
+#[cfg(feature = "async")]
+mod asynchronous;
+mod notification_stream;
+mod pipe;
+mod static_config_builder;
+
+#[cfg(feature = "async")]
+pub use asynchronous::*;
+pub use notification_stream::*;
+pub use pipe::*;
+pub use static_config_builder::*;
+
 pub use komorebi::animation::prefix::AnimationPrefix;
+pub use komorebi::asc::AppSpecificConfigurationPath;
 pub use komorebi::asc::ApplicationSpecificConfiguration;
+pub use komorebi::asc::AscApplicationRulesOrSchema;
+pub use komorebi::asc::AscConflict;
+pub use komorebi::border_manager::BorderColourOverride;
+pub use komorebi::border_manager::ZOrder;
 pub use komorebi::colour::Colour;
+pub use komorebi::colour::NamedColour;
 pub use komorebi::colour::Rgb;
 pub use komorebi::config_generation::ApplicationConfiguration;
 pub use komorebi::container::Container;
+pub use komorebi::container::WorkspaceOrigin;
 pub use komorebi::core::config_generation::ApplicationConfigurationGenerator;
+pub use komorebi::core::config_generation::IdWithIdentifier;
+pub use komorebi::core::config_generation::MatchingRule;
+pub use komorebi::core::config_generation::MatchingRuleWithProvenance;
+pub use komorebi::core::config_generation::MatchingStrategy;
+pub use komorebi::core::config_generation::RuleProvenance;
+pub use komorebi::core::preview::calculate_rects as calculate_layout_rects;
+pub use komorebi::core::preview::render_ascii as render_layout_preview;
 pub use komorebi::core::resolve_home_path;
 pub use komorebi::core::AnimationStyle;
 pub use komorebi::core::ApplicationIdentifier;
 pub use komorebi::core::Arrangement;
+pub use komorebi::core::AspectRatio;
 pub use komorebi::core::Axis;
 pub use komorebi::core::BorderImplementation;
 pub use komorebi::core::BorderStyle;
@@ -19,57 +46,114 @@ pub use komorebi::core::CustomLayout;
 pub use komorebi::core::CycleDirection;
 pub use komorebi::core::DefaultLayout;
 pub use komorebi::core::Direction;
+pub use komorebi::core::DragBehaviour;
+pub use komorebi::core::DragMode;
+pub use komorebi::core::DragModifier;
 pub use komorebi::core::FocusFollowsMouseImplementation;
 pub use komorebi::core::HidingBehaviour;
+pub use komorebi::core::LastWindowCloseBehaviour;
 pub use komorebi::core::Layout;
+pub use komorebi::core::MinimumSizePolicy;
+pub use komorebi::core::MouseFollowsFocusScope;
 pub use komorebi::core::MoveBehaviour;
+pub use komorebi::core::NewWindowPlacement;
 pub use komorebi::core::OperationBehaviour;
 pub use komorebi::core::OperationDirection;
+pub use komorebi::core::PercentageRect;
+pub use komorebi::core::PredefinedPlacement;
 pub use komorebi::core::Rect;
+pub use komorebi::core::RectOrPercent;
+pub use komorebi::core::ScrollingCentering;
+pub use komorebi::core::ScrollingLayoutOptions;
 pub use komorebi::core::Sizing;
 pub use komorebi::core::SocketMessage;
 pub use komorebi::core::StackbarLabel;
 pub use komorebi::core::StackbarMode;
 pub use komorebi::core::StateQuery;
+pub use komorebi::core::WindowBasedWorkAreaOffsetCounting;
 pub use komorebi::core::WindowKind;
+pub use komorebi::core::WorkspaceLayer;
 pub use komorebi::monitor::Monitor;
+pub use komorebi::presentation_manager::PresentationModeConfig;
+pub use komorebi::reserved_space_manager::RegisteredAppBar;
+pub use komorebi::reserved_space_manager::ReservedSpaceEdge;
 pub use komorebi::ring::Ring;
+pub use komorebi::state_recorder::StateRecordingConfig;
+pub use komorebi::static_config::BorderDimension;
+pub use komorebi::static_config::MonitorConfig;
+pub use komorebi::static_config::ValidationIssue;
+pub use komorebi::static_config::WorkspaceConfig;
+pub use komorebi::watchdog::PanicNotification;
+pub use komorebi::whkdrc::generate as generate_whkdrc;
+pub use komorebi::whkdrc::GeneratedWhkdrc;
 pub use komorebi::window::Window;
 pub use komorebi::window_manager_event::WindowManagerEvent;
+pub use komorebi::workspace::FloatingWindowPreview;
 pub use komorebi::workspace::Workspace;
+pub use komorebi::workspace::WorkspacePreview;
+pub use komorebi::ActiveRules;
 pub use komorebi::BorderColours;
+pub use komorebi::EligibilityCheck;
+pub use komorebi::EligibilityCheckResult;
 pub use komorebi::GlobalState;
 pub use komorebi::KomorebiTheme;
+pub use komorebi::MonitorInformation;
 pub use komorebi::Notification;
 pub use komorebi::NotificationEvent;
+pub use komorebi::RuleCollection;
 pub use komorebi::RuleDebug;
+pub use komorebi::RuleMatch;
+pub use komorebi::ServerVersion;
+pub use komorebi::Snapshot;
 pub use komorebi::StackbarConfig;
 pub use komorebi::State;
 pub use komorebi::StaticConfig;
 pub use komorebi::SubscribeOptions;
 pub use komorebi::TabsConfig;
+pub use komorebi::WindowDetails;
+pub use komorebi::SCHEMA_VERSION;
 
 use komorebi::DATA_DIR;
 
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::io::BufReader;
 use std::io::Read;
 use std::io::Write;
 use std::net::Shutdown;
+use std::path::Path;
+use std::path::PathBuf;
 use std::time::Duration;
 pub use uds_windows::UnixListener;
 use uds_windows::UnixStream;
 
 const KOMOREBI: &str = "komorebi.sock";
 
+/// The socket on which komorebi listens for messages
+pub fn socket_path() -> PathBuf {
+    DATA_DIR.join(KOMOREBI)
+}
+
 pub fn send_message(message: &SocketMessage) -> std::io::Result<()> {
-    let socket = DATA_DIR.join(KOMOREBI);
+    send_message_to(socket_path(), message)
+}
+
+/// Sends a message to komorebi listening on the given socket
+pub fn send_message_to(socket: impl AsRef<Path>, message: &SocketMessage) -> std::io::Result<()> {
     let mut stream = UnixStream::connect(socket)?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
     stream.write_all(serde_json::to_string(message)?.as_bytes())
 }
 
 pub fn send_batch(messages: impl IntoIterator<Item = SocketMessage>) -> std::io::Result<()> {
-    let socket = DATA_DIR.join(KOMOREBI);
+    send_batch_to(socket_path(), messages)
+}
+
+/// Sends a batch of messages to komorebi listening on the given socket
+pub fn send_batch_to(
+    socket: impl AsRef<Path>,
+    messages: impl IntoIterator<Item = SocketMessage>,
+) -> std::io::Result<()> {
     let mut stream = UnixStream::connect(socket)?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
     let msgs = messages.into_iter().fold(String::new(), |mut s, m| {
@@ -83,8 +167,11 @@ pub fn send_batch(messages: impl IntoIterator<Item = SocketMessage>) -> std::io:
 }
 
 pub fn send_query(message: &SocketMessage) -> std::io::Result<String> {
-    let socket = DATA_DIR.join(KOMOREBI);
+    send_query_to(socket_path(), message)
+}
 
+/// Sends a query to komorebi listening on the given socket, returning its response
+pub fn send_query_to(socket: impl AsRef<Path>, message: &SocketMessage) -> std::io::Result<String> {
     let mut stream = UnixStream::connect(socket)?;
     stream.set_read_timeout(Some(Duration::from_secs(1)))?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
@@ -98,6 +185,61 @@ pub fn send_query(message: &SocketMessage) -> std::io::Result<String> {
     Ok(response)
 }
 
+/// Queries the window manager state, global state and monitor information in a single round trip
+pub fn send_snapshot() -> std::io::Result<Snapshot> {
+    let response = send_query(&SocketMessage::Snapshot)?;
+    Ok(serde_json::from_str(&response)?)
+}
+
+/// Queries the version of the running komorebi process
+pub fn server_version() -> std::io::Result<ServerVersion> {
+    let response = send_query(&SocketMessage::Version)?;
+    Ok(serde_json::from_str(&response)?)
+}
+
+#[derive(Debug)]
+pub enum CompatibilityError {
+    /// The version of the running komorebi process could not be queried, which usually means
+    /// that it is either not running or too old to respond to version queries
+    Query(std::io::Error),
+    /// The running komorebi process sends state and notifications with a different schema to
+    /// the one this client was built against
+    SchemaMismatch {
+        expected: u32,
+        server: ServerVersion,
+    },
+}
+
+impl Display for CompatibilityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Query(error) => write!(f, "could not query the komorebi version: {error}"),
+            Self::SchemaMismatch { expected, server } => write!(
+                f,
+                "komorebi v{} uses schema version {}, but this client expects schema version {expected}",
+                server.version, server.schema_version
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CompatibilityError {}
+
+/// Checks that the running komorebi process uses the same state and notification schema as
+/// this client, returning its version information if it does
+pub fn check_compatibility() -> Result<ServerVersion, CompatibilityError> {
+    let server = server_version().map_err(CompatibilityError::Query)?;
+
+    if server.schema_version != SCHEMA_VERSION {
+        return Err(CompatibilityError::SchemaMismatch {
+            expected: SCHEMA_VERSION,
+            server,
+        });
+    }
+
+    Ok(server)
+}
+
 pub fn subscribe(name: &str) -> std::io::Result<UnixListener> {
     let socket = DATA_DIR.join(name);
 
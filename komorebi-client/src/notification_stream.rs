@@ -0,0 +1,376 @@
+//! Resilient reading of the notifications sent to a subscriber socket
+//!
+//! komorebi writes each notification as a single JSON object without any framing, and several
+//! objects may arrive in one read, or one object may be split across many. A notification which
+//! cannot be parsed is yielded as an error carrying the raw payload, and reading carries on with
+//! the next notification.
+
+use crate::Notification;
+use crate::UnixListener;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io::ErrorKind;
+use std::io::Read;
+
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+#[derive(Debug)]
+pub enum NotificationError {
+    Io(std::io::Error),
+    /// A complete JSON object which could not be deserialized as a notification
+    Deserialize {
+        error: serde_json::Error,
+        payload: String,
+    },
+    /// Bytes which were not a JSON object, such as a notification which was cut off part way
+    /// through by a newline
+    Malformed {
+        payload: String,
+    },
+    /// The stream ended part way through a notification
+    Incomplete {
+        payload: String,
+    },
+}
+
+impl Display for NotificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Deserialize { error, payload } => {
+                write!(f, "could not deserialize notification: {error}: {payload}")
+            }
+            Self::Malformed { payload } => {
+                write!(f, "received a malformed notification: {payload}")
+            }
+            Self::Incomplete { payload } => {
+                write!(
+                    f,
+                    "the stream ended part way through a notification: {payload}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NotificationError {}
+
+/// The end of the next segment of the buffer
+enum Segment {
+    /// A complete JSON object
+    Object(usize),
+    /// Bytes which can never become a JSON object
+    Malformed(usize),
+}
+
+/// Yields every notification read from a stream, such as one accepted on a subscriber socket
+///
+/// Errors for individual notifications do not end the iterator, which only ends once the stream
+/// has been closed or has failed.
+pub struct NotificationStream<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    // How far into the current object the buffer has been scanned
+    scanned: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    finished: bool,
+}
+
+impl<R: Read> NotificationStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: vec![],
+            scanned: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            finished: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn scan(&mut self) -> Option<Segment> {
+        if self.scanned == 0 {
+            // Whitespace between objects, such as the newlines of line delimited writers, is
+            // skipped
+            let start = self
+                .buffer
+                .iter()
+                .position(|byte| !byte.is_ascii_whitespace())
+                .unwrap_or(self.buffer.len());
+
+            self.buffer.drain(..start);
+
+            // Anything which is not the start of an object is malformed up to the start of the
+            // next line or object
+            if self.buffer.first().is_some_and(|byte| *byte != b'{') {
+                return self
+                    .buffer
+                    .iter()
+                    .skip(1)
+                    .position(|byte| matches!(byte, b'{' | b'\n'))
+                    .map(|idx| Segment::Malformed(idx + 1));
+            }
+        }
+
+        // Bytes of multi-byte UTF-8 characters never match any of the ASCII bytes below, so a
+        // character split across reads does not need any special handling
+        for idx in self.scanned..self.buffer.len() {
+            let byte = self.buffer[idx];
+
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+
+                continue;
+            }
+
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.depth == 0 {
+                        self.reset();
+                        return Option::from(Segment::Object(idx + 1));
+                    }
+                }
+                // Notifications never contain raw newlines, so one can only mean that the
+                // object was cut off
+                b'\n' => {
+                    self.reset();
+                    return Option::from(Segment::Malformed(idx + 1));
+                }
+                _ => {}
+            }
+        }
+
+        self.scanned = self.buffer.len();
+        None
+    }
+
+    fn reset(&mut self) {
+        self.scanned = 0;
+        self.depth = 0;
+        self.in_string = false;
+        self.escaped = false;
+    }
+
+    fn take_payload(&mut self, end: usize) -> Vec<u8> {
+        self.buffer.drain(..end).collect()
+    }
+
+    /// Blocks until the next notification or error, returning None once the stream has ended
+    pub fn next_notification(&mut self) -> Option<Result<Notification, NotificationError>> {
+        loop {
+            match self.scan() {
+                Some(Segment::Object(end)) => {
+                    let payload = self.take_payload(end);
+                    return Option::from(serde_json::from_slice(&payload).map_err(|error| {
+                        NotificationError::Deserialize {
+                            error,
+                            payload: String::from_utf8_lossy(&payload).into_owned(),
+                        }
+                    }));
+                }
+                Some(Segment::Malformed(end)) => {
+                    let payload = self.take_payload(end);
+                    return Option::from(Err(NotificationError::Malformed {
+                        payload: String::from_utf8_lossy(&payload).trim().to_string(),
+                    }));
+                }
+                None if self.finished => {
+                    if self.buffer.is_empty() {
+                        return None;
+                    }
+
+                    self.reset();
+                    let payload = self.take_payload(self.buffer.len());
+                    return Option::from(Err(NotificationError::Incomplete {
+                        payload: String::from_utf8_lossy(&payload).into_owned(),
+                    }));
+                }
+                None => {
+                    let mut chunk = [0; READ_CHUNK_SIZE];
+                    match self.reader.read(&mut chunk) {
+                        Ok(0) => self.finished = true,
+                        Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                        Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                        Err(error) => {
+                            self.finished = true;
+                            return Option::from(Err(NotificationError::Io(error)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for NotificationStream<R> {
+    type Item = Result<Notification, NotificationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_notification()
+    }
+}
+
+/// Yields every notification sent by komorebi to a subscriber socket returned by
+/// [`crate::subscribe`] or [`crate::subscribe_with_options`]
+pub fn notifications(
+    listener: &UnixListener,
+) -> impl Iterator<Item = Result<Notification, NotificationError>> + '_ {
+    listener.incoming().flat_map(|stream| {
+        let notifications: Box<dyn Iterator<Item = Result<Notification, NotificationError>>> =
+            match stream {
+                Ok(stream) => Box::new(NotificationStream::new(stream)),
+                Err(error) => Box::new(std::iter::once(Err(NotificationError::Io(error)))),
+            };
+
+        notifications
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NotificationEvent;
+    use crate::SocketMessage;
+
+    /// A serialized notification for switching to the named workspace
+    fn notification(workspace: &str) -> String {
+        serde_json::json!({
+            "event": { "type": "FocusNamedWorkspace", "content": workspace },
+            "state": {
+                "monitors": { "elements": [], "focused": 0 },
+                "is_paused": false,
+                "resize_delta": 50,
+                "new_window_behaviour": "Create",
+                "float_override": false,
+                "cross_monitor_move_behaviour": "Swap",
+                "unmanaged_window_operation_behaviour": "Op",
+                "work_area_offset": null,
+                "focus_follows_mouse": null,
+                "mouse_follows_focus": true,
+                "has_pending_raise_op": false
+            }
+        })
+        .to_string()
+    }
+
+    /// A reader which returns each write in a separate read
+    fn reader<'a>(writes: &[&'a [u8]]) -> Box<dyn Read + 'a> {
+        writes
+            .iter()
+            .fold(Box::new(std::io::empty()), |reader, write| {
+                Box::new(reader.chain(*write))
+            })
+    }
+
+    fn workspace(result: Option<Result<Notification, NotificationError>>) -> String {
+        match result {
+            Some(Ok(Notification {
+                event: NotificationEvent::Socket(SocketMessage::FocusNamedWorkspace(name)),
+                ..
+            })) => name,
+            other => panic!("expected a notification, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn concatenated_objects() {
+        let payload = format!(
+            "{}{}\n{}",
+            notification("one"),
+            notification("two"),
+            notification("three")
+        );
+        let mut stream = NotificationStream::new(payload.as_bytes());
+
+        assert_eq!(workspace(stream.next()), "one");
+        assert_eq!(workspace(stream.next()), "two");
+        assert_eq!(workspace(stream.next()), "three");
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn objects_split_across_reads() {
+        // Every split point, including those within the multi-byte characters of the name
+        let name = "wörkspace ✓ \"quoted\" {braced}";
+        let payload = notification(name);
+        let bytes = payload.as_bytes();
+
+        for split in 1..bytes.len() {
+            let mut stream = NotificationStream::new(reader(&[&bytes[..split], &bytes[split..]]));
+
+            assert_eq!(workspace(stream.next()), name, "split at byte {split}");
+            assert!(stream.next().is_none());
+        }
+    }
+
+    #[test]
+    fn objects_split_byte_by_byte() {
+        let payload = format!("{}{}", notification("ünïcödé"), notification("two"));
+        let bytes = payload.as_bytes().chunks(1).collect::<Vec<_>>();
+        let mut stream = NotificationStream::new(reader(&bytes));
+
+        assert_eq!(workspace(stream.next()), "ünïcödé");
+        assert_eq!(workspace(stream.next()), "two");
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn empty_writes() {
+        assert!(NotificationStream::new(&b""[..]).next().is_none());
+        assert!(NotificationStream::new(reader(&[b"", b"", b""]))
+            .next()
+            .is_none());
+        assert!(NotificationStream::new(reader(&[b"\n", b"", b"  \r\n"]))
+            .next()
+            .is_none());
+
+        let payload = notification("one");
+        let (first, second) = payload.as_bytes().split_at(payload.len() / 2);
+        let mut stream = NotificationStream::new(reader(&[b"", first, b"", second, b"\n", b""]));
+
+        assert_eq!(workspace(stream.next()), "one");
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn errors_do_not_end_the_stream() {
+        let payload = format!(
+            "garbage\n{}{{\"event\":{{}}}}{}{{\"event\":",
+            notification("one"),
+            notification("two")
+        );
+        let mut stream = NotificationStream::new(payload.as_bytes());
+
+        assert!(matches!(
+            stream.next(),
+            Some(Err(NotificationError::Malformed { payload })) if payload == "garbage"
+        ));
+        assert_eq!(workspace(stream.next()), "one");
+        assert!(matches!(
+            stream.next(),
+            Some(Err(NotificationError::Deserialize { payload, .. })) if payload == "{\"event\":{}}"
+        ));
+        assert_eq!(workspace(stream.next()), "two");
+        assert!(matches!(
+            stream.next(),
+            Some(Err(NotificationError::Incomplete { payload })) if payload == "{\"event\":"
+        ));
+        assert!(stream.next().is_none());
+    }
+}
@@ -0,0 +1,203 @@
+//! Subscriptions over named pipes, for consumers which cannot listen on a Unix domain socket
+//!
+//! komorebi connects to a named pipe which has been created by the subscriber and writes each
+//! notification to it as a single line of JSON.
+
+use crate::send_message;
+use crate::Notification;
+use crate::SocketMessage;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::os::windows::io::AsRawHandle;
+use std::os::windows::io::FromRawHandle;
+use std::time::Duration;
+use windows::core::HSTRING;
+use windows::Win32::Foundation::ERROR_BROKEN_PIPE;
+use windows::Win32::Foundation::ERROR_PIPE_CONNECTED;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Storage::FileSystem::PIPE_ACCESS_DUPLEX;
+use windows::Win32::System::Pipes::ConnectNamedPipe;
+use windows::Win32::System::Pipes::CreateNamedPipeW;
+use windows::Win32::System::Pipes::PIPE_READMODE_BYTE;
+use windows::Win32::System::Pipes::PIPE_TYPE_BYTE;
+use windows::Win32::System::Pipes::PIPE_WAIT;
+
+const PIPE_BUFFER_SIZE: u32 = 64 * 1024;
+const RECONNECT_ATTEMPTS: usize = 30;
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug)]
+pub enum PipeSubscriptionError {
+    /// komorebi has closed its end of the pipe, usually because it has stopped or restarted
+    Disconnected,
+    Io(std::io::Error),
+    Deserialize(serde_json::Error),
+}
+
+impl Display for PipeSubscriptionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Disconnected => write!(f, "komorebi has disconnected from the named pipe"),
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Deserialize(error) => write!(f, "could not deserialize notification: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PipeSubscriptionError {}
+
+impl From<std::io::Error> for PipeSubscriptionError {
+    fn from(error: std::io::Error) -> Self {
+        if error.raw_os_error() == Some(ERROR_BROKEN_PIPE.0 as i32) {
+            Self::Disconnected
+        } else {
+            Self::Io(error)
+        }
+    }
+}
+
+impl From<serde_json::Error> for PipeSubscriptionError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Deserialize(error)
+    }
+}
+
+/// A named pipe subscription which yields each notification sent by komorebi
+///
+/// When komorebi disconnects, the iterator yields [`PipeSubscriptionError::Disconnected`] and
+/// then ends, unless reconnection has been enabled with [`PipeSubscription::with_reconnect`] or
+/// [`PipeSubscription::reconnect`] is called by the consumer.
+pub struct PipeSubscription {
+    name: String,
+    reader: Option<BufReader<File>>,
+    reconnect: bool,
+}
+
+/// Creates the named pipe `\\.\pipe\<name>` and subscribes it to komorebi notifications
+///
+/// This blocks until komorebi has connected to the pipe.
+pub fn subscribe_pipe(name: &str) -> std::io::Result<PipeSubscription> {
+    Ok(PipeSubscription {
+        name: name.to_string(),
+        reader: Option::from(BufReader::new(create_and_register(name)?)),
+        reconnect: false,
+    })
+}
+
+fn create_and_register(name: &str) -> std::io::Result<File> {
+    let pipe_path = format!(r"\\.\pipe\{name}");
+
+    let handle = unsafe {
+        CreateNamedPipeW(
+            &HSTRING::from(pipe_path),
+            PIPE_ACCESS_DUPLEX,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT,
+            1,
+            PIPE_BUFFER_SIZE,
+            PIPE_BUFFER_SIZE,
+            0,
+            None,
+        )
+    };
+
+    if handle.is_invalid() {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    // The file takes ownership of the handle and closes it when dropped
+    let pipe = unsafe { File::from_raw_handle(handle.0) };
+
+    send_message(&SocketMessage::AddSubscriberPipe(name.to_string()))?;
+
+    // komorebi may already have connected by the time we start waiting for it
+    if let Err(error) = unsafe { ConnectNamedPipe(HANDLE(pipe.as_raw_handle()), None) } {
+        if error.code() != ERROR_PIPE_CONNECTED.to_hresult() {
+            return Err(error.into());
+        }
+    }
+
+    Ok(pipe)
+}
+
+impl PipeSubscription {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Transparently recreates the pipe and subscribes it again when komorebi disconnects,
+    /// retrying until komorebi is accepting commands or the retry limit has been reached
+    #[must_use]
+    pub fn with_reconnect(mut self, reconnect: bool) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Recreates the pipe and subscribes it to komorebi notifications again
+    pub fn reconnect(&mut self) -> std::io::Result<()> {
+        // The previous pipe instance must be closed before a new one with the same name is created
+        self.reader = None;
+        self.reader = Option::from(BufReader::new(create_and_register(&self.name)?));
+
+        Ok(())
+    }
+
+    fn reconnect_with_retries(&mut self) -> Result<(), PipeSubscriptionError> {
+        for _ in 0..RECONNECT_ATTEMPTS {
+            if self.reconnect().is_ok() {
+                return Ok(());
+            }
+
+            std::thread::sleep(RECONNECT_INTERVAL);
+        }
+
+        Err(PipeSubscriptionError::Disconnected)
+    }
+
+    /// Blocks until the next notification is received
+    pub fn next_notification(&mut self) -> Result<Notification, PipeSubscriptionError> {
+        loop {
+            let reader = match self.reader.as_mut() {
+                Some(reader) => reader,
+                None if self.reconnect => {
+                    self.reconnect_with_retries()?;
+                    continue;
+                }
+                None => return Err(PipeSubscriptionError::Disconnected),
+            };
+
+            let mut line = String::new();
+            let result = match reader.read_line(&mut line) {
+                Ok(0) => Err(PipeSubscriptionError::Disconnected),
+                Ok(_) if line.trim().is_empty() => continue,
+                Ok(_) => return Ok(serde_json::from_str(&line)?),
+                Err(error) => Err(PipeSubscriptionError::from(error)),
+            };
+
+            if matches!(result, Err(PipeSubscriptionError::Disconnected)) {
+                self.reader = None;
+
+                if self.reconnect {
+                    continue;
+                }
+            }
+
+            return result;
+        }
+    }
+}
+
+impl Iterator for PipeSubscription {
+    type Item = Result<Notification, PipeSubscriptionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // A disconnected pipe which will not be reconnected has nothing more to yield
+        if self.reader.is_none() && !self.reconnect {
+            return None;
+        }
+
+        Some(self.next_notification())
+    }
+}
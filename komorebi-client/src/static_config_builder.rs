@@ -0,0 +1,302 @@
+use komorebi::asc::AppSpecificConfigurationPath;
+use komorebi::asc::ApplicationSpecificConfiguration;
+use komorebi::core::config_generation::MatchingRule;
+use komorebi::core::BorderImplementation;
+use komorebi::core::BorderStyle;
+use komorebi::core::DefaultLayout;
+use komorebi::core::RectOrPercent;
+use komorebi::static_config::BorderDimension;
+use komorebi::static_config::MonitorConfig;
+use komorebi::static_config::ValidationIssue;
+use komorebi::static_config::WorkspaceConfig;
+use komorebi::BorderColours;
+use komorebi::KomorebiTheme;
+use komorebi::ServerVersion;
+use komorebi::StaticConfig;
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Builds a `komorebi.json` static configuration programmatically, for tools which generate
+/// configuration files instead of having users write them by hand
+#[derive(Debug, Default)]
+pub struct StaticConfigBuilder {
+    config: StaticConfig,
+    // The file contents this builder was created from, if any
+    raw: Option<String>,
+}
+
+impl StaticConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from an existing configuration, such as one read with [`StaticConfig::read`]
+    pub fn from_config(config: StaticConfig) -> Self {
+        Self { config, raw: None }
+    }
+
+    /// Start from the contents of an existing `komorebi.json` file, which are kept so that
+    /// [`Self::validate`] can report renamed options that no longer exist once parsed
+    pub fn from_raw(raw: &str) -> serde_json::Result<Self> {
+        Ok(Self {
+            config: serde_json::from_str(raw)?,
+            raw: Option::from(raw.to_string()),
+        })
+    }
+
+    pub fn app_specific_configuration_path(mut self, path: PathBuf) -> Self {
+        self.config.app_specific_configuration_path =
+            Option::from(AppSpecificConfigurationPath::Single(path));
+        self
+    }
+
+    /// Add a monitor, in the order of the monitor indices used by komorebi
+    pub fn monitor(mut self, monitor: MonitorConfig) -> Self {
+        self.config
+            .monitors
+            .get_or_insert_with(Vec::new)
+            .push(monitor);
+        self
+    }
+
+    pub fn default_workspace_padding(mut self, padding: i32) -> Self {
+        self.config.default_workspace_padding = Option::from(padding);
+        self
+    }
+
+    pub fn default_container_padding(mut self, padding: i32) -> Self {
+        self.config.default_container_padding = Option::from(padding);
+        self
+    }
+
+    pub fn border(mut self, enabled: bool) -> Self {
+        self.config.border = Option::from(enabled);
+        self
+    }
+
+    pub fn border_width(mut self, width: BorderDimension) -> Self {
+        self.config.border_width = Option::from(width);
+        self
+    }
+
+    pub fn border_offset(mut self, offset: BorderDimension) -> Self {
+        self.config.border_offset = Option::from(offset);
+        self
+    }
+
+    pub fn border_colours(mut self, colours: BorderColours) -> Self {
+        self.config.border_colours = Option::from(colours);
+        self
+    }
+
+    pub fn border_style(mut self, style: BorderStyle) -> Self {
+        self.config.border_style = Option::from(style);
+        self
+    }
+
+    pub fn border_implementation(mut self, implementation: BorderImplementation) -> Self {
+        self.config.border_implementation = Option::from(implementation);
+        self
+    }
+
+    pub fn theme(mut self, theme: KomorebiTheme) -> Self {
+        self.config.theme = Option::from(theme);
+        self
+    }
+
+    pub fn build(self) -> StaticConfig {
+        self.config
+    }
+
+    /// Runs the same checks as `komorebic check` on the configuration: cross-referencing monitor
+    /// indices, workspace names and file paths, reporting conflicting application specific
+    /// configuration rules, and reporting renamed, deprecated and end-of-life options
+    ///
+    /// Renamed options are parsed into their current names, so they are only reported for
+    /// builders created with [`Self::from_raw`], where they are looked for in the original file
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = self.config.validate();
+
+        if let Some(path) = &self.config.app_specific_configuration_path {
+            match ApplicationSpecificConfiguration::load_merged(&path.paths()) {
+                Ok((_, conflicts)) => {
+                    issues.extend(
+                        conflicts
+                            .iter()
+                            .map(|conflict| issue(conflict.to_string(), None)),
+                    );
+                }
+                Err(error) => issues.push(issue(
+                    format!("could not load the application specific configuration: {error}"),
+                    Option::from(String::from("app_specific_configuration_path")),
+                )),
+            }
+        }
+
+        if let Some(raw) = &self.raw {
+            for (alias, canonical) in StaticConfig::renamed_options(raw) {
+                issues.push(issue(
+                    format!(r#""{alias}" is now "{canonical}""#),
+                    Option::from(alias.to_string()),
+                ));
+            }
+        }
+
+        // Deprecated and end-of-life options can still be parsed, so they are looked for in the
+        // configuration as it has been built
+        let raw = match serde_json::to_string(&self.config) {
+            Ok(raw) => raw,
+            Err(error) => {
+                issues.push(issue(
+                    format!("could not serialize the configuration: {error}"),
+                    None,
+                ));
+
+                return issues;
+            }
+        };
+
+        for deprecated in StaticConfig::deprecated_options(&raw) {
+            issues.push(issue(deprecated, None));
+        }
+
+        for feature in StaticConfig::end_of_life_features(&raw) {
+            issues.push(issue(
+                format!(r#""{feature}" is now end-of-life"#),
+                Option::from(feature.to_string()),
+            ));
+        }
+
+        issues
+    }
+
+    /// The configuration as a `komorebi.json` file, with a `$schema` header pointing at the
+    /// schema for the version of komorebi this client was built against
+    pub fn to_pretty_json(&self) -> serde_json::Result<String> {
+        let mut json = Map::new();
+        json.insert(String::from("$schema"), Value::String(schema_url()));
+
+        if let Value::Object(config) = serde_json::to_value(&self.config)? {
+            json.extend(config);
+        }
+
+        serde_json::to_string_pretty(&json)
+    }
+}
+
+/// The URL of the `komorebi.json` schema for the version of komorebi this client was built against
+pub fn schema_url() -> String {
+    format!(
+        "https://raw.githubusercontent.com/LGUG2Z/komorebi/v{}/schema.json",
+        ServerVersion::current().version
+    )
+}
+
+fn issue(message: String, needle: Option<String>) -> ValidationIssue {
+    ValidationIssue {
+        message,
+        needle,
+        occurrence: 0,
+    }
+}
+
+/// Builds the configuration of a single monitor for a [`StaticConfigBuilder`]
+#[derive(Debug, Default)]
+pub struct MonitorConfigBuilder {
+    config: MonitorConfig,
+}
+
+impl MonitorConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn workspace(mut self, workspace: WorkspaceConfig) -> Self {
+        self.config.workspaces.push(workspace);
+        self
+    }
+
+    pub fn work_area_offset(mut self, offset: RectOrPercent) -> Self {
+        self.config.work_area_offset = Option::from(offset);
+        self
+    }
+
+    pub fn container_padding(mut self, padding: i32) -> Self {
+        self.config.container_padding = Option::from(padding);
+        self
+    }
+
+    pub fn workspace_padding(mut self, padding: i32) -> Self {
+        self.config.workspace_padding = Option::from(padding);
+        self
+    }
+
+    pub fn build(self) -> MonitorConfig {
+        self.config
+    }
+}
+
+/// Builds the configuration of a single workspace for a [`MonitorConfigBuilder`]
+#[derive(Debug)]
+pub struct WorkspaceConfigBuilder {
+    config: WorkspaceConfig,
+}
+
+impl WorkspaceConfigBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            config: WorkspaceConfig {
+                name: name.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn layout(mut self, layout: DefaultLayout) -> Self {
+        self.config.layout = Option::from(layout);
+        self
+    }
+
+    /// Switch to a layout once the workspace has at least this many containers
+    pub fn layout_rule(mut self, threshold: usize, layout: DefaultLayout) -> Self {
+        self.config
+            .layout_rules
+            .get_or_insert_with(HashMap::new)
+            .insert(threshold, layout);
+        self
+    }
+
+    /// Send windows matching the rule to this workspace every time rules are enforced
+    pub fn workspace_rule(mut self, rule: MatchingRule) -> Self {
+        self.config
+            .workspace_rules
+            .get_or_insert_with(Vec::new)
+            .push(rule);
+        self
+    }
+
+    /// Send windows matching the rule to this workspace only when they are first shown
+    pub fn initial_workspace_rule(mut self, rule: MatchingRule) -> Self {
+        self.config
+            .initial_workspace_rules
+            .get_or_insert_with(Vec::new)
+            .push(rule);
+        self
+    }
+
+    pub fn container_padding(mut self, padding: i32) -> Self {
+        self.config.container_padding = Option::from(padding);
+        self
+    }
+
+    pub fn workspace_padding(mut self, padding: i32) -> Self {
+        self.config.workspace_padding = Option::from(padding);
+        self
+    }
+
+    pub fn build(self) -> WorkspaceConfig {
+        self.config
+    }
+}
@@ -126,9 +126,8 @@ struct KomorebiGui {
 
 fn colour32(colour: Option<Colour>) -> Color32 {
     match colour {
-        Some(Colour::Rgb(rgb)) => Color32::from_rgb(rgb.r as u8, rgb.g as u8, rgb.b as u8),
-        Some(Colour::Hex(hex)) => {
-            let rgb = Rgb::from(hex);
+        Some(colour) => {
+            let rgb = Rgb::from(colour);
             Color32::from_rgb(rgb.r as u8, rgb.g as u8, rgb.b as u8)
         }
         None => Color32::from_rgb(0, 0, 0),
@@ -734,6 +733,7 @@ impl eframe::App for KomorebiGui {
                                                     DefaultLayout::HorizontalStack,
                                                     DefaultLayout::UltrawideVerticalStack,
                                                     DefaultLayout::Grid,
+                                                    DefaultLayout::Scrolling,
                                                 ] {
                                                     if ui
                                                         .add(egui::SelectableLabel::new(
@@ -11,6 +11,13 @@ pub use catppuccin_egui;
 pub use eframe::egui::Color32;
 use serde_variant::to_variant_name;
 
+mod palette;
+
+pub use palette::lerp_colour;
+pub use palette::transition;
+pub use palette::Base16ColourPalette;
+pub use palette::Lerp;
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "type")]
 pub enum Theme {
@@ -0,0 +1,363 @@
+use eframe::egui::Style;
+use eframe::egui::Visuals;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Base16;
+use crate::Base16Value;
+use crate::Catppuccin;
+use crate::CatppuccinValue;
+use crate::Color32;
+
+/// A complete set of Base16 colours, which can be taken from a named theme or interpolated
+/// between two themes
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Base16ColourPalette {
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_00: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_01: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_02: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_03: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_04: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_05: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_06: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_07: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_08: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_09: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_0a: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_0b: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_0c: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_0d: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_0e: Color32,
+    #[serde(with = "hex")]
+    #[schemars(with = "String")]
+    pub base_0f: Color32,
+}
+
+impl From<Base16> for Base16ColourPalette {
+    fn from(theme: Base16) -> Self {
+        Self {
+            base_00: Base16Value::Base00.color32(theme),
+            base_01: Base16Value::Base01.color32(theme),
+            base_02: Base16Value::Base02.color32(theme),
+            base_03: Base16Value::Base03.color32(theme),
+            base_04: Base16Value::Base04.color32(theme),
+            base_05: Base16Value::Base05.color32(theme),
+            base_06: Base16Value::Base06.color32(theme),
+            base_07: Base16Value::Base07.color32(theme),
+            base_08: Base16Value::Base08.color32(theme),
+            base_09: Base16Value::Base09.color32(theme),
+            base_0a: Base16Value::Base0A.color32(theme),
+            base_0b: Base16Value::Base0B.color32(theme),
+            base_0c: Base16Value::Base0C.color32(theme),
+            base_0d: Base16Value::Base0D.color32(theme),
+            base_0e: Base16Value::Base0E.color32(theme),
+            base_0f: Base16Value::Base0F.color32(theme),
+        }
+    }
+}
+
+impl From<Catppuccin> for Base16ColourPalette {
+    /// Uses the same slot assignments as the official Catppuccin Base16 ports
+    fn from(theme: Catppuccin) -> Self {
+        let theme = theme.as_theme();
+
+        Self {
+            base_00: CatppuccinValue::Base.color32(theme),
+            base_01: CatppuccinValue::Mantle.color32(theme),
+            base_02: CatppuccinValue::Surface0.color32(theme),
+            base_03: CatppuccinValue::Surface1.color32(theme),
+            base_04: CatppuccinValue::Surface2.color32(theme),
+            base_05: CatppuccinValue::Text.color32(theme),
+            base_06: CatppuccinValue::Rosewater.color32(theme),
+            base_07: CatppuccinValue::Lavender.color32(theme),
+            base_08: CatppuccinValue::Red.color32(theme),
+            base_09: CatppuccinValue::Peach.color32(theme),
+            base_0a: CatppuccinValue::Yellow.color32(theme),
+            base_0b: CatppuccinValue::Green.color32(theme),
+            base_0c: CatppuccinValue::Teal.color32(theme),
+            base_0d: CatppuccinValue::Blue.color32(theme),
+            base_0e: CatppuccinValue::Mauve.color32(theme),
+            base_0f: CatppuccinValue::Flamingo.color32(theme),
+        }
+    }
+}
+
+impl Base16ColourPalette {
+    pub fn color32(&self, value: Base16Value) -> Color32 {
+        match value {
+            Base16Value::Base00 => self.base_00,
+            Base16Value::Base01 => self.base_01,
+            Base16Value::Base02 => self.base_02,
+            Base16Value::Base03 => self.base_03,
+            Base16Value::Base04 => self.base_04,
+            Base16Value::Base05 => self.base_05,
+            Base16Value::Base06 => self.base_06,
+            Base16Value::Base07 => self.base_07,
+            Base16Value::Base08 => self.base_08,
+            Base16Value::Base09 => self.base_09,
+            Base16Value::Base0A => self.base_0a,
+            Base16Value::Base0B => self.base_0b,
+            Base16Value::Base0C => self.base_0c,
+            Base16Value::Base0D => self.base_0d,
+            Base16Value::Base0E => self.base_0e,
+            Base16Value::Base0F => self.base_0f,
+        }
+    }
+
+    /// Whether the background of this palette is dark
+    pub fn is_dark(&self) -> bool {
+        oklab_from_srgb(self.base_00)[0] < 0.5
+    }
+
+    /// Builds an egui style from this palette for use when there is no named theme to take one
+    /// from
+    pub fn style(&self) -> Style {
+        let mut visuals = if self.is_dark() {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        };
+
+        visuals.hyperlink_color = self.base_0d;
+        visuals.faint_bg_color = self.base_01;
+        visuals.extreme_bg_color = self.base_01;
+        visuals.code_bg_color = self.base_02;
+        visuals.warn_fg_color = self.base_09;
+        visuals.error_fg_color = self.base_08;
+        visuals.window_fill = self.base_00;
+        visuals.panel_fill = self.base_00;
+        visuals.window_stroke.color = self.base_03;
+        visuals.selection.bg_fill = self.base_02;
+        visuals.selection.stroke.color = self.base_0d;
+
+        let widgets = &mut visuals.widgets;
+        for (widget, fill) in [
+            (&mut widgets.noninteractive, self.base_00),
+            (&mut widgets.inactive, self.base_01),
+            (&mut widgets.hovered, self.base_02),
+            (&mut widgets.active, self.base_03),
+            (&mut widgets.open, self.base_01),
+        ] {
+            widget.bg_fill = fill;
+            widget.weak_bg_fill = fill;
+            widget.bg_stroke.color = self.base_02;
+            widget.fg_stroke.color = self.base_05;
+        }
+
+        Style {
+            visuals,
+            ..Default::default()
+        }
+    }
+
+    /// Produces `steps` palettes which transition from this palette to `other`, ending with
+    /// `other`
+    pub fn transition(&self, other: &Self, steps: usize) -> impl Iterator<Item = Self> {
+        transition(*self, *other, steps)
+    }
+}
+
+/// Values which can be interpolated, where a `t` of 0.0 gives `self` and 1.0 gives `other`
+pub trait Lerp {
+    #[must_use]
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+/// Produces `steps` values which transition from `from` to `to`, ending with `to`, such as for
+/// animating a change between two themes
+pub fn transition<T: Lerp>(from: T, to: T, steps: usize) -> impl Iterator<Item = T> {
+    #[allow(clippy::cast_precision_loss)]
+    (1..=steps).map(move |step| from.lerp(&to, step as f32 / steps as f32))
+}
+
+impl Lerp for Color32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        lerp_colour(*self, *other, t)
+    }
+}
+
+impl Lerp for Base16ColourPalette {
+    /// Interpolates every colour of this palette towards the matching colour of `other`
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            base_00: lerp_colour(self.base_00, other.base_00, t),
+            base_01: lerp_colour(self.base_01, other.base_01, t),
+            base_02: lerp_colour(self.base_02, other.base_02, t),
+            base_03: lerp_colour(self.base_03, other.base_03, t),
+            base_04: lerp_colour(self.base_04, other.base_04, t),
+            base_05: lerp_colour(self.base_05, other.base_05, t),
+            base_06: lerp_colour(self.base_06, other.base_06, t),
+            base_07: lerp_colour(self.base_07, other.base_07, t),
+            base_08: lerp_colour(self.base_08, other.base_08, t),
+            base_09: lerp_colour(self.base_09, other.base_09, t),
+            base_0a: lerp_colour(self.base_0a, other.base_0a, t),
+            base_0b: lerp_colour(self.base_0b, other.base_0b, t),
+            base_0c: lerp_colour(self.base_0c, other.base_0c, t),
+            base_0d: lerp_colour(self.base_0d, other.base_0d, t),
+            base_0e: lerp_colour(self.base_0e, other.base_0e, t),
+            base_0f: lerp_colour(self.base_0f, other.base_0f, t),
+        }
+    }
+}
+
+/// Interpolates between two colours in the Oklab colour space, which avoids the muddy midpoints
+/// of interpolating sRGB channels directly
+pub fn lerp_colour(from: Color32, to: Color32, t: f32) -> Color32 {
+    let t = t.clamp(0.0, 1.0);
+
+    let from_lab = oklab_from_srgb(from);
+    let to_lab = oklab_from_srgb(to);
+
+    let lab = [
+        (to_lab[0] - from_lab[0]).mul_add(t, from_lab[0]),
+        (to_lab[1] - from_lab[1]).mul_add(t, from_lab[1]),
+        (to_lab[2] - from_lab[2]).mul_add(t, from_lab[2]),
+    ];
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let alpha = (f32::from(to.a()) - f32::from(from.a()))
+        .mul_add(t, f32::from(from.a()))
+        .round() as u8;
+
+    let [r, g, b] = srgb_from_oklab(lab);
+    Color32::from_rgba_unmultiplied(r, g, b, alpha)
+}
+
+fn linear_from_srgb(channel: u8) -> f32 {
+    let c = f32::from(channel) / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn srgb_from_linear(channel: f32) -> u8 {
+    let c = channel.clamp(0.0, 1.0);
+    let c = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055f32.mul_add(c.powf(1.0 / 2.4), -0.055)
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let c = (c * 255.0).round() as u8;
+    c
+}
+
+// https://bottosson.github.io/posts/oklab/
+fn oklab_from_srgb(colour: Color32) -> [f32; 3] {
+    let [r, g, b, _] = colour.to_srgba_unmultiplied();
+    let (r, g, b) = (
+        linear_from_srgb(r),
+        linear_from_srgb(g),
+        linear_from_srgb(b),
+    );
+
+    let l = 0.051_457_565f32
+        .mul_add(b, 0.412_221_46f32.mul_add(r, 0.536_332_55 * g))
+        .cbrt();
+    let m = 0.107_406_58f32
+        .mul_add(b, 0.211_903_5f32.mul_add(r, 0.680_699_5 * g))
+        .cbrt();
+    let s = 0.629_978_7f32
+        .mul_add(b, 0.088_302_46f32.mul_add(r, 0.281_718_85 * g))
+        .cbrt();
+
+    [
+        (-0.004_072_047f32).mul_add(s, 0.210_454_26f32.mul_add(l, 0.793_617_8 * m)),
+        0.450_593_7f32.mul_add(s, 1.977_998_5f32.mul_add(l, -2.428_592_2 * m)),
+        (-0.808_675_77f32).mul_add(s, 0.025_904_037f32.mul_add(l, 0.782_771_77 * m)),
+    ]
+}
+
+fn srgb_from_oklab(lab: [f32; 3]) -> [u8; 3] {
+    let [l, a, b] = lab;
+
+    let l_ = 0.215_803_76f32.mul_add(b, 0.396_337_78f32.mul_add(a, l));
+    let m_ = (-0.063_854_17f32).mul_add(b, (-0.105_561_346f32).mul_add(a, l));
+    let s_ = (-1.291_485_5f32).mul_add(b, (-0.089_484_18f32).mul_add(a, l));
+
+    let l = l_ * l_ * l_;
+    let m = m_ * m_ * m_;
+    let s = s_ * s_ * s_;
+
+    [
+        srgb_from_linear(0.230_759_05f32.mul_add(s, 4.076_741_7f32.mul_add(l, -3.307_711_6 * m))),
+        srgb_from_linear(
+            (-0.341_319_38f32).mul_add(s, (-1.268_438f32).mul_add(l, 2.609_757_4 * m)),
+        ),
+        srgb_from_linear(
+            1.707_614_7f32.mul_add(s, (-0.004_196_086_3f32).mul_add(l, -0.703_418_6 * m)),
+        ),
+    ]
+}
+
+/// Serializes colours as `#rrggbb` strings
+mod hex {
+    use serde::de::Error;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+
+    use crate::Color32;
+
+    pub fn serialize<S: Serializer>(colour: &Color32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!(
+            "#{:02x}{:02x}{:02x}",
+            colour.r(),
+            colour.g(),
+            colour.b()
+        ))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color32, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let digits = value.trim_start_matches('#');
+
+        // Slicing by byte below relies on every character being a single byte
+        if digits.len() != 6 || !digits.is_ascii() {
+            return Err(D::Error::custom(format!(
+                "expected a colour in the form #rrggbb, found {value}"
+            )));
+        }
+
+        let channel = |idx: usize| {
+            u8::from_str_radix(&digits[idx..idx + 2], 16)
+                .map_err(|error| D::Error::custom(format!("invalid colour {value}: {error}")))
+        };
+
+        Ok(Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
@@ -44,7 +44,11 @@ impl AnimationEngine {
                 ANIMATION_MANAGER.lock().end(animation_key);
             }
 
-            std::thread::sleep(Duration::from_millis(250 / 2));
+            // Poll at the frame rate, which is how often animations check for cancellation, so
+            // that rapid successive requests take over without a noticeable delay
+            std::thread::sleep(Duration::from_millis(
+                1000 / ANIMATION_FPS.load(Ordering::Relaxed),
+            ));
         }
 
         let latest_cancel_idx = ANIMATION_MANAGER.lock().latest_cancel_idx(animation_key);
@@ -56,7 +60,7 @@ impl AnimationEngine {
 
     #[allow(clippy::cast_precision_loss)]
     pub fn animate(
-        render_dispatcher: (impl RenderDispatcher + Send + 'static),
+        mut render_dispatcher: (impl RenderDispatcher + Send + 'static),
         duration: Duration,
     ) -> Result<()> {
         std::thread::spawn(move || {
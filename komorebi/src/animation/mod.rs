@@ -1,5 +1,6 @@
 use crate::animation::animation_manager::AnimationManager;
 use crate::core::animation::AnimationStyle;
+use crate::core::config_generation::MatchingRule;
 
 use lazy_static::lazy_static;
 use prefix::AnimationPrefix;
@@ -9,6 +10,7 @@ use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
+use regex::Regex;
 
 pub use engine::AnimationEngine;
 pub mod animation_manager;
@@ -49,6 +51,12 @@ lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
     pub static ref ANIMATION_DURATION_PER_ANIMATION: Arc<Mutex<HashMap<AnimationPrefix, u64>>> =
         Arc::new(Mutex::new(HashMap::new()));
+    pub static ref ANIMATIONS_BLACKLIST: Arc<Mutex<Vec<MatchingRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
+    // Kept apart from REGEX_IDENTIFIERS because the blacklist is checked when positioning windows,
+    // which often happens while REGEX_IDENTIFIERS is already locked
+    pub static ref ANIMATIONS_BLACKLIST_REGEX: Arc<Mutex<HashMap<String, Regex>>> =
+        Arc::new(Mutex::new(HashMap::new()));
 }
 
 pub static ANIMATION_FPS: AtomicU64 = AtomicU64::new(DEFAULT_ANIMATION_FPS);
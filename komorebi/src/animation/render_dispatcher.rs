@@ -2,7 +2,7 @@ use color_eyre::Result;
 
 pub trait RenderDispatcher {
     fn get_animation_key(&self) -> String;
-    fn pre_render(&self) -> Result<()>;
+    fn pre_render(&mut self) -> Result<()>;
     fn render(&self, delta: f64) -> Result<()>;
     fn post_render(&self) -> Result<()>;
 }
@@ -355,6 +355,91 @@ impl Ease for EaseInOutBounce {
     }
 }
 
+/// A cubic bézier easing with fixed end points at (0, 0) and (1, 1)
+pub struct CubicBezier {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl CubicBezier {
+    const NEWTON_ITERATIONS: usize = 8;
+    const SUBDIVISION_ITERATIONS: usize = 20;
+    const EPSILON: f64 = 1e-6;
+
+    pub const fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Self { x1, y1, x2, y2 }
+    }
+
+    // The value of one axis of the curve at parameter s, given the control point coordinates
+    // for that axis
+    fn sample(p1: f64, p2: f64, s: f64) -> f64 {
+        let c = 3.0 * p1;
+        let b = 3.0f64.mul_add(p2 - p1, -c);
+        let a = 1.0 - c - b;
+
+        a.mul_add(s, b).mul_add(s, c) * s
+    }
+
+    fn sample_derivative(p1: f64, p2: f64, s: f64) -> f64 {
+        let c = 3.0 * p1;
+        let b = 3.0f64.mul_add(p2 - p1, -c);
+        let a = 1.0 - c - b;
+
+        (3.0 * a).mul_add(s, 2.0 * b).mul_add(s, c)
+    }
+
+    // Finds the curve parameter at which the x axis, which represents time, is equal to t
+    fn solve_x(&self, t: f64) -> f64 {
+        let mut s = t;
+
+        for _ in 0..Self::NEWTON_ITERATIONS {
+            let x = Self::sample(self.x1, self.x2, s) - t;
+            if x.abs() < Self::EPSILON {
+                return s;
+            }
+
+            let derivative = Self::sample_derivative(self.x1, self.x2, s);
+            if derivative.abs() < Self::EPSILON {
+                break;
+            }
+
+            s -= x / derivative;
+        }
+
+        // Fall back to bisection when Newton's method doesn't converge, which is always possible
+        // because x is monotonic when both control points are within [0, 1] on the x axis
+        let (mut low, mut high) = (0.0, 1.0);
+        s = t;
+
+        for _ in 0..Self::SUBDIVISION_ITERATIONS {
+            let x = Self::sample(self.x1, self.x2, s);
+            if (x - t).abs() < Self::EPSILON {
+                break;
+            }
+
+            if x < t {
+                low = s;
+            } else {
+                high = s;
+            }
+
+            s = (high - low).mul_add(0.5, low);
+        }
+
+        s
+    }
+
+    pub fn evaluate(&self, t: f64) -> f64 {
+        if t <= 0.0 || t >= 1.0 {
+            return t.clamp(0.0, 1.0);
+        }
+
+        Self::sample(self.y1, self.y2, self.solve_x(t))
+    }
+}
+
 pub fn apply_ease_func(t: f64, style: AnimationStyle) -> f64 {
     match style {
         AnimationStyle::Linear => Linear::evaluate(t),
@@ -387,5 +472,6 @@ pub fn apply_ease_func(t: f64, style: AnimationStyle) -> f64 {
         AnimationStyle::EaseInBounce => EaseInBounce::evaluate(t),
         AnimationStyle::EaseOutBounce => EaseOutBounce::evaluate(t),
         AnimationStyle::EaseInOutBounce => EaseInOutBounce::evaluate(t),
+        AnimationStyle::CubicBezier(x1, y1, x2, y2) => CubicBezier::new(x1, y1, x2, y2).evaluate(t),
     }
 }
@@ -1,12 +1,18 @@
+use crate::border_manager::border_offset;
+use crate::border_manager::border_width;
 use crate::border_manager::window_kind_colour;
 use crate::border_manager::WindowKind;
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
+use crate::border_manager::BORDER_HIT_TEST;
+use crate::border_manager::COLOUR_OVERRIDES;
 use crate::border_manager::FOCUS_STATE;
 use crate::border_manager::RENDER_TARGETS;
 use crate::border_manager::STYLE;
+use crate::border_manager::Z_ORDER;
+use crate::colour::packed_alpha;
 use crate::core::BorderStyle;
 use crate::core::Rect;
+use crate::styles::ExtendedWindowStyle;
+use crate::window::Window;
 use crate::windows_api;
 use crate::WindowsApi;
 use crate::WINDOWS_11;
@@ -110,6 +116,8 @@ pub struct Border {
     pub brush_properties: D2D1_BRUSH_PROPERTIES,
     pub rounded_rect: D2D1_ROUNDED_RECT,
     pub brushes: HashMap<WindowKind, ID2D1SolidColorBrush>,
+    // Brushes for the colours of any border colour overrides matching the tracked window
+    pub override_brushes: HashMap<u32, ID2D1SolidColorBrush>,
 }
 
 impl From<isize> for Border {
@@ -121,16 +129,47 @@ impl From<isize> for Border {
             window_rect: Rect::default(),
             window_kind: WindowKind::Unfocused,
             style: STYLE.load(),
-            width: BORDER_WIDTH.load(Ordering::Relaxed),
-            offset: BORDER_OFFSET.load(Ordering::Relaxed),
+            width: border_width(WindowKind::Unfocused),
+            offset: border_offset(WindowKind::Unfocused),
             brush_properties: D2D1_BRUSH_PROPERTIES::default(),
             rounded_rect: D2D1_ROUNDED_RECT::default(),
             brushes: HashMap::new(),
+            override_brushes: HashMap::new(),
         }
     }
 }
 
+fn d2d_colour(colour: u32) -> D2D1_COLOR_F {
+    D2D1_COLOR_F {
+        r: ((colour & 0xFF) as f32) / 255.0,
+        g: (((colour >> 8) & 0xFF) as f32) / 255.0,
+        b: (((colour >> 16) & 0xFF) as f32) / 255.0,
+        a: (packed_alpha(colour) as f32) / 255.0,
+    }
+}
+
 impl Border {
+    /// The brush for a kind of border, or for the colour override of the tracked window if there
+    /// is one, which is created the first time that colour is needed
+    fn brush(&mut self, window_kind: WindowKind) -> Option<ID2D1SolidColorBrush> {
+        let Some(colour) = COLOUR_OVERRIDES.lock().get(&self.hwnd).copied() else {
+            return self.brushes.get(&window_kind).cloned();
+        };
+
+        if !self.override_brushes.contains_key(&colour) {
+            let render_target = self.render_target.get()?;
+            let brush = unsafe {
+                render_target
+                    .CreateSolidColorBrush(&d2d_colour(colour), Some(&self.brush_properties))
+            }
+            .ok()?;
+
+            self.override_brushes.insert(colour, brush);
+        }
+
+        self.override_brushes.get(&colour).cloned()
+    }
+
     pub const fn hwnd(&self) -> HWND {
         HWND(windows_api::as_ptr!(self.hwnd))
     }
@@ -162,11 +201,12 @@ impl Border {
                 window_rect: WindowsApi::window_rect(tracking_hwnd).unwrap_or_default(),
                 window_kind: WindowKind::Unfocused,
                 style: STYLE.load(),
-                width: BORDER_WIDTH.load(Ordering::Relaxed),
-                offset: BORDER_OFFSET.load(Ordering::Relaxed),
+                width: border_width(WindowKind::Unfocused),
+                offset: border_offset(WindowKind::Unfocused),
                 brush_properties: Default::default(),
                 rounded_rect: Default::default(),
                 brushes: HashMap::new(),
+                override_brushes: HashMap::new(),
             };
 
             let border_pointer = std::ptr::addr_of!(border);
@@ -174,6 +214,11 @@ impl Border {
                 WindowsApi::create_border_window(PCWSTR(name.as_ptr()), instance, border_pointer)?;
 
             border.hwnd = hwnd;
+
+            if let Err(error) = border.apply_hit_test() {
+                tracing::error!("failed to update border hit testing: {error}");
+            }
+
             border_sender.send(border_pointer as isize)?;
 
             let mut msg: MSG = MSG::default();
@@ -243,14 +288,9 @@ impl Border {
                     WindowKind::Monocle,
                     WindowKind::Unfocused,
                     WindowKind::Floating,
+                    WindowKind::Pinned,
                 ] {
-                    let color = window_kind_colour(window_kind);
-                    let color = D2D1_COLOR_F {
-                        r: ((color & 0xFF) as f32) / 255.0,
-                        g: (((color >> 8) & 0xFF) as f32) / 255.0,
-                        b: (((color >> 16) & 0xFF) as f32) / 255.0,
-                        a: 1.0,
-                    };
+                    let color = d2d_colour(window_kind_colour(window_kind));
 
                     if let Ok(brush) =
                         render_target.CreateSolidColorBrush(&color, Some(&border.brush_properties))
@@ -288,12 +328,47 @@ impl Border {
         WindowsApi::close_window(self.hwnd)
     }
 
+    /// Lets clicks pass through the border to the windows beneath it when hit testing is disabled
+    pub fn apply_hit_test(&self) -> color_eyre::Result<()> {
+        let window = Window::from(self.hwnd);
+        let mut ex_style = window.ex_style()?;
+        let click_through = ExtendedWindowStyle::TRANSPARENT | ExtendedWindowStyle::LAYERED;
+
+        if BORDER_HIT_TEST.load(Ordering::SeqCst) {
+            if ex_style.intersects(click_through) {
+                ex_style.remove(click_through);
+                window.update_ex_style(&ex_style)?;
+            }
+        } else if !ex_style.contains(click_through) {
+            ex_style.insert(click_through);
+            window.update_ex_style(&ex_style)?;
+            // Layered windows are not drawn until their attributes have been set
+            WindowsApi::set_transparent(self.hwnd, 255)?;
+        }
+
+        Ok(())
+    }
+
     pub fn set_position(&self, rect: &Rect, reference_hwnd: isize) -> color_eyre::Result<()> {
         let mut rect = *rect;
         rect.add_margin(self.width);
         rect.add_padding(-self.offset);
 
-        WindowsApi::set_border_pos(self.hwnd, &rect, reference_hwnd)?;
+        // Placing the border after the tracked window puts it below, and placing it after the
+        // window which is above the tracked window puts it above
+        let insert_after = if Z_ORDER.load().is_above() {
+            match WindowsApi::previous_window(reference_hwnd) {
+                Ok(hwnd) if hwnd == self.hwnd => {
+                    WindowsApi::previous_window(self.hwnd).unwrap_or_default()
+                }
+                Ok(hwnd) => hwnd,
+                Err(_) => 0,
+            }
+        } else {
+            reference_hwnd
+        };
+
+        WindowsApi::set_border_pos(self.hwnd, &rect, insert_after)?;
 
         Ok(())
     }
@@ -372,7 +447,7 @@ impl Border {
                             });
 
                             let window_kind = (*border_pointer).window_kind;
-                            if let Some(brush) = (*border_pointer).brushes.get(&window_kind) {
+                            if let Some(brush) = (*border_pointer).brush(window_kind) {
                                 render_target.BeginDraw();
                                 render_target.Clear(None);
 
@@ -393,7 +468,7 @@ impl Border {
                                     BorderStyle::Rounded => {
                                         render_target.DrawRoundedRectangle(
                                             &(*border_pointer).rounded_rect,
-                                            brush,
+                                            &brush,
                                             border_width as f32,
                                             None,
                                         );
@@ -401,7 +476,7 @@ impl Border {
                                     BorderStyle::Square => {
                                         render_target.DrawRectangle(
                                             &(*border_pointer).rounded_rect.rect,
-                                            brush,
+                                            &brush,
                                             border_width as f32,
                                             None,
                                         );
@@ -427,6 +502,17 @@ impl Border {
 
                         let reference_hwnd = (*border_pointer).tracking_hwnd;
 
+                        // Get window kind, which determines the colour and dimensions
+                        (*border_pointer).window_kind = FOCUS_STATE
+                            .lock()
+                            .get(&(window.0 as isize))
+                            .copied()
+                            .unwrap_or(WindowKind::Unfocused);
+
+                        let window_kind = (*border_pointer).window_kind;
+                        (*border_pointer).width = border_width(window_kind);
+                        (*border_pointer).offset = border_offset(window_kind);
+
                         // Update position to update the ZOrder
                         let border_window_rect = (*border_pointer).window_rect;
 
@@ -438,9 +524,6 @@ impl Border {
                         }
 
                         if let Some(render_target) = (*border_pointer).render_target.get() {
-                            (*border_pointer).width = BORDER_WIDTH.load(Ordering::Relaxed);
-                            (*border_pointer).offset = BORDER_OFFSET.load(Ordering::Relaxed);
-
                             let border_width = (*border_pointer).width;
                             let border_offset = (*border_pointer).offset;
 
@@ -456,16 +539,7 @@ impl Border {
                                 height: rect.bottom as u32,
                             });
 
-                            // Get window kind and color
-
-                            (*border_pointer).window_kind = FOCUS_STATE
-                                .lock()
-                                .get(&(window.0 as isize))
-                                .copied()
-                                .unwrap_or(WindowKind::Unfocused);
-
-                            let window_kind = (*border_pointer).window_kind;
-                            if let Some(brush) = (*border_pointer).brushes.get(&window_kind) {
+                            if let Some(brush) = (*border_pointer).brush(window_kind) {
                                 render_target.BeginDraw();
                                 render_target.Clear(None);
 
@@ -488,7 +562,7 @@ impl Border {
                                     BorderStyle::Rounded => {
                                         render_target.DrawRoundedRectangle(
                                             &(*border_pointer).rounded_rect,
-                                            brush,
+                                            &brush,
                                             border_width as f32,
                                             None,
                                         );
@@ -496,7 +570,7 @@ impl Border {
                                     BorderStyle::Square => {
                                         render_target.DrawRectangle(
                                             &(*border_pointer).rounded_rect.rect,
-                                            brush,
+                                            &brush,
                                             border_width as f32,
                                             None,
                                         );
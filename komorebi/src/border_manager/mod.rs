@@ -1,17 +1,23 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
 mod border;
+use crate::core::config_generation::MatchingRule;
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::WindowKind;
 use crate::ring::Ring;
+use crate::should_act;
+use crate::window::Window;
 use crate::workspace_reconciliator::ALT_TAB_HWND;
 use crate::Colour;
 use crate::Rgb;
 use crate::WindowManager;
 use crate::WindowsApi;
+use crate::PINNED_HWNDS;
+use crate::REGEX_IDENTIFIERS;
 use border::border_hwnds;
 pub use border::Border;
+use clap::ValueEnum;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicCell;
@@ -29,17 +35,26 @@ use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::OnceLock;
+use strum::Display;
 use windows::Win32::Graphics::Direct2D::ID2D1HwndRenderTarget;
 
 pub static BORDER_WIDTH: AtomicI32 = AtomicI32::new(8);
 pub static BORDER_OFFSET: AtomicI32 = AtomicI32::new(-1);
 
 pub static BORDER_ENABLED: AtomicBool = AtomicBool::new(true);
+pub static BORDER_HIT_TEST: AtomicBool = AtomicBool::new(true);
+
+/// Kinds of border which can be drawn around a tiled container; layouts leave room for the
+/// largest of these around every container so that windows don't move or resize when focus
+/// changes the kind of border drawn around them, at the cost of a gap around the smaller kinds
+pub const TILED_WINDOW_KINDS: [WindowKind; 3] =
+    [WindowKind::Single, WindowKind::Stack, WindowKind::Unfocused];
 
 lazy_static! {
     pub static ref STYLE: AtomicCell<BorderStyle> = AtomicCell::new(BorderStyle::System);
     pub static ref IMPLEMENTATION: AtomicCell<BorderImplementation> =
         AtomicCell::new(BorderImplementation::Komorebi);
+    pub static ref Z_ORDER: AtomicCell<ZOrder> = AtomicCell::new(ZOrder::Bottom);
     pub static ref FOCUSED: AtomicU32 =
         AtomicU32::new(u32::from(Colour::Rgb(Rgb::new(66, 165, 245))));
     pub static ref UNFOCUSED: AtomicU32 =
@@ -49,6 +64,9 @@ lazy_static! {
     pub static ref STACK: AtomicU32 = AtomicU32::new(u32::from(Colour::Rgb(Rgb::new(0, 165, 66))));
     pub static ref FLOATING: AtomicU32 =
         AtomicU32::new(u32::from(Colour::Rgb(Rgb::new(245, 245, 165))));
+    pub static ref PINNED: AtomicU32 =
+        AtomicU32::new(u32::from(Colour::Rgb(Rgb::new(203, 166, 247))));
+    pub static ref BORDER_COLOUR_OVERRIDES: Mutex<Vec<BorderColourOverride>> = Mutex::new(vec![]);
 }
 
 lazy_static! {
@@ -56,8 +74,26 @@ lazy_static! {
     static ref BORDER_STATE: Mutex<HashMap<String, Border>> = Mutex::new(HashMap::new());
     static ref WINDOWS_BORDERS: Mutex<HashMap<isize, Border>> = Mutex::new(HashMap::new());
     static ref FOCUS_STATE: Mutex<HashMap<isize, WindowKind>> = Mutex::new(HashMap::new());
+    // Border widths and offsets for kinds of window which do not use BORDER_WIDTH and BORDER_OFFSET
+    static ref BORDER_WIDTHS: Mutex<HashMap<WindowKind, i32>> = Mutex::new(HashMap::new());
+    static ref BORDER_OFFSETS: Mutex<HashMap<WindowKind, i32>> = Mutex::new(HashMap::new());
     static ref RENDER_TARGETS: Mutex<HashMap<isize, ID2D1HwndRenderTarget>> =
         Mutex::new(HashMap::new());
+    // Border hwnds mapped to the overridden colour of the window they are tracking
+    static ref COLOUR_OVERRIDES: Mutex<HashMap<isize, u32>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BorderColourOverride {
+    /// Rule to identify the windows whose border colours should be overridden
+    pub matching_rule: MatchingRule,
+    /// Border colour when the window is focused, whether it is in a single, stack, monocle or
+    /// floating container
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focused: Option<Colour>,
+    /// Border colour when the window is unfocused
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfocused: Option<Colour>,
 }
 
 pub struct Notification(pub Option<isize>);
@@ -86,6 +122,20 @@ pub fn send_notification(hwnd: Option<isize>) {
     }
 }
 
+/// Applies the current hit testing and z-order settings to the existing borders in place, which
+/// avoids the flicker of destroying and recreating them
+pub fn update_border_window_options() {
+    let borders = BORDER_STATE.lock();
+    for border in borders.values() {
+        if let Err(error) = border.apply_hit_test() {
+            tracing::error!("failed to update border hit testing: {error}");
+        }
+
+        // The z-order is updated when the border repaints
+        border.invalidate();
+    }
+}
+
 pub fn destroy_all_borders() -> color_eyre::Result<()> {
     let mut borders = BORDER_STATE.lock();
     tracing::info!(
@@ -101,6 +151,7 @@ pub fn destroy_all_borders() -> color_eyre::Result<()> {
     BORDERS_MONITORS.lock().clear();
     FOCUS_STATE.lock().clear();
     RENDER_TARGETS.lock().clear();
+    COLOUR_OVERRIDES.lock().clear();
 
     let mut remaining_hwnds = vec![];
 
@@ -120,6 +171,66 @@ pub fn destroy_all_borders() -> color_eyre::Result<()> {
     Ok(())
 }
 
+/// The colour of the first border colour override matching a window for a kind of border, if any
+///
+/// Pinned borders are never overridden.
+pub fn colour_override(hwnd: isize, window_kind: WindowKind) -> Option<u32> {
+    // Cloned so that the regex identifiers are never locked while holding this lock
+    let overrides = BORDER_COLOUR_OVERRIDES.lock().clone();
+    if overrides.is_empty() || window_kind == WindowKind::Pinned {
+        return None;
+    }
+
+    let window = Window::from(hwnd);
+    let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+        (window.title(), window.exe(), window.class(), window.path())
+    else {
+        return None;
+    };
+
+    let regex_identifiers = REGEX_IDENTIFIERS.lock();
+    let colour_override = overrides.iter().find(|colour_override| {
+        should_act(
+            &title,
+            &exe_name,
+            &class,
+            &path,
+            std::slice::from_ref(&colour_override.matching_rule),
+            &regex_identifiers,
+        )
+        .is_some()
+    })?;
+
+    let colour = if window_kind == WindowKind::Unfocused {
+        colour_override.unfocused
+    } else {
+        colour_override.focused
+    };
+
+    colour.map(u32::from)
+}
+
+/// The colour of a window's border, taking any border colour overrides into account
+fn window_colour(hwnd: isize, window_kind: WindowKind) -> u32 {
+    colour_override(hwnd, window_kind).unwrap_or_else(|| window_kind_colour(window_kind))
+}
+
+/// Records the colour override for a border, returning whether it has changed
+fn update_colour_override(
+    border_hwnd: isize,
+    tracking_hwnd: isize,
+    window_kind: WindowKind,
+) -> bool {
+    let colour = colour_override(tracking_hwnd, window_kind);
+    let mut colour_overrides = COLOUR_OVERRIDES.lock();
+    let previous = match colour {
+        Some(colour) => colour_overrides.insert(border_hwnd, colour),
+        None => colour_overrides.remove(&border_hwnd),
+    };
+
+    previous != colour
+}
+
 fn window_kind_colour(focus_kind: WindowKind) -> u32 {
     match focus_kind {
         WindowKind::Unfocused => UNFOCUSED.load(Ordering::Relaxed),
@@ -127,9 +238,58 @@ fn window_kind_colour(focus_kind: WindowKind) -> u32 {
         WindowKind::Stack => STACK.load(Ordering::Relaxed),
         WindowKind::Monocle => MONOCLE.load(Ordering::Relaxed),
         WindowKind::Floating => FLOATING.load(Ordering::Relaxed),
+        WindowKind::Pinned => PINNED.load(Ordering::Relaxed),
     }
 }
 
+pub fn border_width(window_kind: WindowKind) -> i32 {
+    BORDER_WIDTHS
+        .lock()
+        .get(&window_kind)
+        .copied()
+        .unwrap_or_else(|| BORDER_WIDTH.load(Ordering::Relaxed))
+}
+
+pub fn border_offset(window_kind: WindowKind) -> i32 {
+    BORDER_OFFSETS
+        .lock()
+        .get(&window_kind)
+        .copied()
+        .unwrap_or_else(|| BORDER_OFFSET.load(Ordering::Relaxed))
+}
+
+pub fn set_border_width(window_kind: WindowKind, width: Option<i32>) {
+    let mut widths = BORDER_WIDTHS.lock();
+    match width {
+        None => widths.remove(&window_kind),
+        Some(width) => widths.insert(window_kind, width),
+    };
+}
+
+pub fn set_border_offset(window_kind: WindowKind, offset: Option<i32>) {
+    let mut offsets = BORDER_OFFSETS.lock();
+    match offset {
+        None => offsets.remove(&window_kind),
+        Some(offset) => offsets.insert(window_kind, offset),
+    };
+}
+
+/// Whether any kind of window has a border width or offset which differs from the global one
+pub fn has_window_kind_dimensions() -> bool {
+    !BORDER_WIDTHS.lock().is_empty() || !BORDER_OFFSETS.lock().is_empty()
+}
+
+/// The space which must be left around a window so that a border of any of the given kinds fits
+pub fn border_padding(window_kinds: &[WindowKind]) -> i32 {
+    window_kinds
+        .iter()
+        .map(|kind| border_width(*kind) + border_offset(*kind))
+        .max()
+        .unwrap_or_else(|| {
+            BORDER_WIDTH.load(Ordering::Relaxed) + BORDER_OFFSET.load(Ordering::Relaxed)
+        })
+}
+
 pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
         match handle_notifications(wm.clone()) {
@@ -153,6 +313,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
     let mut previous_pending_move_op = None;
     let mut previous_is_paused = false;
     let mut previous_notification: Option<Notification> = None;
+    let mut previous_pinned_hwnds = vec![];
 
     'receiver: for notification in receiver {
         // Check the wm state every time we receive a notification
@@ -170,6 +331,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
             .map(|w| w.hwnd)
             .collect::<Vec<_>>();
         let foreground_window = WindowsApi::foreground_window().unwrap_or_default();
+        let pinned_hwnds = PINNED_HWNDS.lock().clone();
 
         drop(state);
 
@@ -178,6 +340,15 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 'monitors: for (monitor_idx, m) in monitors.elements().iter().enumerate() {
                     // Only operate on the focused workspace of each monitor
                     if let Some(ws) = m.focused_workspace() {
+                        // Paused monitors don't have borders
+                        if m.is_paused() {
+                            for window in ws.visible_windows().into_iter().flatten() {
+                                window.remove_accent()?;
+                            }
+
+                            continue 'monitors;
+                        }
+
                         // Handle the monocle container separately
                         if let Some(monocle) = ws.monocle_container() {
                             let window_kind = if monitor_idx != focused_monitor_idx {
@@ -186,17 +357,30 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 WindowKind::Monocle
                             };
 
-                            monocle
-                                .focused_window()
-                                .copied()
-                                .unwrap_or_default()
-                                .set_accent(window_kind_colour(window_kind))?;
+                            let window = monocle.focused_window().copied().unwrap_or_default();
+                            window.set_accent(window_colour(window.hwnd, window_kind))?;
 
                             continue 'monitors;
                         }
 
+                        // Maximized windows share the monocle colour
+                        if let Some(window) = ws.maximized_window() {
+                            let window_kind = if monitor_idx != focused_monitor_idx {
+                                WindowKind::Unfocused
+                            } else {
+                                WindowKind::Monocle
+                            };
+
+                            window.set_accent(window_colour(window.hwnd, window_kind))?;
+                        }
+
                         for (idx, c) in ws.containers().iter().enumerate() {
-                            let window_kind = if idx != ws.focused_container_idx()
+                            let window_kind = if c
+                                .focused_window()
+                                .is_some_and(|w| pinned_hwnds.contains(&w.hwnd))
+                            {
+                                WindowKind::Pinned
+                            } else if idx != ws.focused_container_idx()
                                 || monitor_idx != focused_monitor_idx
                             {
                                 WindowKind::Unfocused
@@ -206,10 +390,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 WindowKind::Single
                             };
 
-                            c.focused_window()
-                                .copied()
-                                .unwrap_or_default()
-                                .set_accent(window_kind_colour(window_kind))?;
+                            let window = c.focused_window().copied().unwrap_or_default();
+                            window.set_accent(window_colour(window.hwnd, window_kind))?;
                         }
                     }
                 }
@@ -229,6 +411,11 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     should_process_notification = true;
                 }
 
+                // handle windows being pinned or unpinned
+                if pinned_hwnds != previous_pinned_hwnds {
+                    should_process_notification = true;
+                }
+
                 // handle the unpause edge case
                 if previous_is_paused && !is_paused {
                     should_process_notification = true;
@@ -294,8 +481,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 'monitors: for (monitor_idx, m) in monitors.elements().iter().enumerate() {
                     // Only operate on the focused workspace of each monitor
                     if let Some(ws) = m.focused_workspace() {
-                        // Workspaces with tiling disabled don't have borders
-                        if !ws.tile() {
+                        // Workspaces with tiling disabled and paused monitors don't have borders
+                        if !ws.tile() || m.is_paused() {
                             let mut to_remove = vec![];
                             for (id, border) in borders.iter() {
                                 if borders_monitors.get(id).copied().unwrap_or_default()
@@ -337,6 +524,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 WindowKind::Monocle
                             };
                             border.window_kind = new_focus_state;
+                            border.width = border_width(new_focus_state);
+                            border.offset = border_offset(new_focus_state);
                             {
                                 let mut focus_state = FOCUS_STATE.lock();
                                 focus_state.insert(border.hwnd, new_focus_state);
@@ -345,6 +534,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             let reference_hwnd =
                                 monocle.focused_window().copied().unwrap_or_default().hwnd;
 
+                            update_colour_override(border.hwnd, reference_hwnd, new_focus_state);
+
                             let rect = WindowsApi::window_rect(reference_hwnd)?;
 
                             if new_border {
@@ -402,10 +593,12 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             continue 'monitors;
                         }
 
-                        // Destroy any borders not associated with the focused workspace
+                        // Destroy any borders not associated with the focused workspace, or
+                        // whose containers have scrolled out of view
                         let mut container_and_floating_window_ids = ws
                             .containers()
                             .iter()
+                            .filter(|c| !ws.is_scrolled_out_of_view(c))
                             .map(|c| c.id().clone())
                             .collect::<Vec<_>>();
 
@@ -428,6 +621,10 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                         }
 
                         for (idx, c) in ws.containers().iter().enumerate() {
+                            if ws.is_scrolled_out_of_view(c) {
+                                continue;
+                            }
+
                             // Get the border entry for this container from the map or create one
                             let mut new_border = false;
                             let border = match borders.entry(c.id().clone()) {
@@ -448,7 +645,15 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             #[allow(unused_assignments)]
                             let mut last_focus_state = None;
 
-                            let new_focus_state = if idx != ws.focused_container_idx()
+                            // The layer is derived from the foreground window rather than read from
+                            // the workspace, so a focused floating window leaves the tiled
+                            // containers unfocused
+                            let new_focus_state = if c
+                                .focused_window()
+                                .is_some_and(|w| pinned_hwnds.contains(&w.hwnd))
+                            {
+                                WindowKind::Pinned
+                            } else if idx != ws.focused_container_idx()
                                 || monitor_idx != focused_monitor_idx
                                 || c.focused_window()
                                     .map(|w| w.hwnd != foreground_window)
@@ -461,6 +666,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 WindowKind::Single
                             };
                             border.window_kind = new_focus_state;
+                            border.width = border_width(new_focus_state);
+                            border.offset = border_offset(new_focus_state);
 
                             // Update the focused state for all containers on this workspace
                             {
@@ -471,12 +678,18 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             let reference_hwnd =
                                 c.focused_window().copied().unwrap_or_default().hwnd;
 
+                            let colour_override_changed = update_colour_override(
+                                border.hwnd,
+                                reference_hwnd,
+                                new_focus_state,
+                            );
+
                             let rect = WindowsApi::window_rect(reference_hwnd)?;
 
                             let should_invalidate = match last_focus_state {
                                 None => true,
                                 Some(last_focus_state) => last_focus_state != new_focus_state,
-                            };
+                            } || colour_override_changed;
 
                             if new_border {
                                 border.set_position(&rect, reference_hwnd)?;
@@ -514,23 +727,33 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                                 let mut last_focus_state = None;
                                 let mut new_focus_state = WindowKind::Unfocused;
 
-                                if foreground_window == window.hwnd {
+                                if pinned_hwnds.contains(&window.hwnd) {
+                                    new_focus_state = WindowKind::Pinned;
+                                } else if foreground_window == window.hwnd {
                                     new_focus_state = WindowKind::Floating;
                                 }
 
                                 border.window_kind = new_focus_state;
+                                border.width = border_width(new_focus_state);
+                                border.offset = border_offset(new_focus_state);
                                 {
                                     let mut focus_state = FOCUS_STATE.lock();
                                     last_focus_state =
                                         focus_state.insert(border.hwnd, new_focus_state);
                                 }
 
+                                let colour_override_changed = update_colour_override(
+                                    border.hwnd,
+                                    window.hwnd,
+                                    new_focus_state,
+                                );
+
                                 let rect = WindowsApi::window_rect(window.hwnd)?;
 
                                 let should_invalidate = match last_focus_state {
                                     None => true,
                                     Some(last_focus_state) => last_focus_state != new_focus_state,
-                                };
+                                } || colour_override_changed;
 
                                 if new_border {
                                     border.set_position(&rect, window.hwnd)?;
@@ -553,19 +776,43 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
         previous_pending_move_op = pending_move_op;
         previous_is_paused = is_paused;
         previous_notification = Some(notification);
+        previous_pinned_hwnds = pinned_hwnds;
     }
 
     Ok(())
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Display,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    ValueEnum,
+)]
 pub enum ZOrder {
+    /// Draw borders above the windows they track
     Top,
+    /// Draw borders below the windows they track (kept for older configuration files)
     NoTopMost,
+    #[default]
+    /// Draw borders below the windows they track
     Bottom,
+    /// Draw borders above the windows they track (kept for older configuration files)
     TopMost,
 }
 
+impl ZOrder {
+    pub const fn is_above(self) -> bool {
+        matches!(self, Self::Top | Self::TopMost)
+    }
+}
+
 impl From<ZOrder> for isize {
     fn from(val: ZOrder) -> Self {
         match val {
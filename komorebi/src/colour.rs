@@ -4,56 +4,100 @@ use schemars::gen::SchemaGenerator;
 use schemars::schema::InstanceType;
 use schemars::schema::Schema;
 use schemars::schema::SchemaObject;
+use schemars::schema::StringValidation;
 use schemars::JsonSchema;
+use serde::de::Error;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
+use serde::Serializer;
+use strum::Display;
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum Colour {
     /// Colour represented as RGB
     Rgb(Rgb),
-    /// Colour represented as Hex
+    /// Colour represented as Hex, optionally with an alpha channel (#RRGGBBAA)
     Hex(Hex),
+    /// Colour represented as a CSS colour name
+    Named(NamedColour),
 }
 
-impl From<Rgb> for Colour {
-    fn from(value: Rgb) -> Self {
-        Self::Rgb(value)
+impl Colour {
+    /// The alpha channel of the colour, which is fully opaque unless it was given as #RRGGBBAA
+    pub const fn alpha(&self) -> u8 {
+        match self {
+            Self::Hex(hex) => hex.0.a,
+            Self::Rgb(_) | Self::Named(_) => u8::MAX,
+        }
+    }
+
+    /// Log a warning if the colour has an alpha channel which will be ignored where it is used
+    pub fn warn_if_translucent(&self, usage: &str) {
+        if self.alpha() < u8::MAX {
+            tracing::warn!(
+                "the alpha channel of {usage} colours is not supported and will be ignored"
+            );
+        }
     }
 }
 
-impl From<u32> for Colour {
-    fn from(value: u32) -> Self {
-        Self::Rgb(Rgb::from(value))
+impl From<Rgb> for Colour {
+    fn from(value: Rgb) -> Self {
+        Self::Rgb(value)
     }
 }
 
 impl From<Color32> for Colour {
     fn from(value: Color32) -> Self {
-        Colour::Rgb(Rgb::new(
-            value.r() as u32,
-            value.g() as u32,
-            value.b() as u32,
-        ))
+        if value.a() == u8::MAX {
+            Colour::Rgb(Rgb::new(
+                value.r() as u32,
+                value.g() as u32,
+                value.b() as u32,
+            ))
+        } else {
+            Colour::Hex(Hex(HexColor::rgba(
+                value.r(),
+                value.g(),
+                value.b(),
+                value.a(),
+            )))
+        }
     }
 }
 
 impl From<Colour> for Color32 {
     fn from(value: Colour) -> Self {
-        match value {
-            Colour::Rgb(rgb) => Color32::from_rgb(rgb.r as u8, rgb.g as u8, rgb.b as u8),
-            Colour::Hex(hex) => {
-                let rgb = Rgb::from(hex);
-                Color32::from_rgb(rgb.r as u8, rgb.g as u8, rgb.b as u8)
-            }
-        }
+        let rgb = Rgb::from(value);
+        Color32::from_rgba_unmultiplied(rgb.r as u8, rgb.g as u8, rgb.b as u8, value.alpha())
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone)]
 pub struct Hex(HexColor);
 
+impl Serialize for Hex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (r, g, b, a) = (self.0.r, self.0.g, self.0.b, self.0.a);
+
+        // Opaque colours keep the #RRGGBB form that most configuration files use
+        if a == u8::MAX {
+            serializer.serialize_str(&format!("#{r:02X}{g:02X}{b:02X}"))
+        } else {
+            serializer.serialize_str(&format!("#{r:02X}{g:02X}{b:02X}{a:02X}"))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Hex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        HexColor::parse(&value).map(Self).map_err(Error::custom)
+    }
+}
+
 impl JsonSchema for Hex {
     fn schema_name() -> String {
         String::from("Hex")
@@ -63,17 +107,140 @@ impl JsonSchema for Hex {
         SchemaObject {
             instance_type: Some(InstanceType::String.into()),
             format: Some("color-hex".to_string()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some(String::from(
+                    "^#([0-9a-fA-F]{3}|[0-9a-fA-F]{4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})$",
+                )),
+                ..Default::default()
+            })),
             ..Default::default()
         }
         .into()
     }
 }
 
+/// The packed colour format used by the border and stackbar managers, which is a Win32 COLORREF
+/// (0x00BBGGRR) with the transparency (255 minus the alpha channel) in the otherwise unused top
+/// byte, so that opaque colours are valid COLORREFs as they are
 impl From<Colour> for u32 {
     fn from(value: Colour) -> Self {
+        let transparency = (u8::MAX - value.alpha()) as u32;
+        u32::from(Rgb::from(value)) | (transparency << 24)
+    }
+}
+
+impl From<u32> for Colour {
+    fn from(value: u32) -> Self {
+        match packed_alpha(value) {
+            u8::MAX => Self::Rgb(Rgb::from(value)),
+            alpha => {
+                let rgb = Rgb::from(value);
+                Self::Hex(Hex(HexColor::rgba(
+                    rgb.r as u8,
+                    rgb.g as u8,
+                    rgb.b as u8,
+                    alpha,
+                )))
+            }
+        }
+    }
+}
+
+/// The Win32 COLORREF of a packed colour, without its alpha channel
+pub const fn strip_alpha(colour: u32) -> u32 {
+    colour & 0x00FF_FFFF
+}
+
+/// The alpha channel of a packed colour
+pub const fn packed_alpha(colour: u32) -> u8 {
+    u8::MAX - (colour >> 24) as u8
+}
+
+impl From<Colour> for Rgb {
+    fn from(value: Colour) -> Self {
+        match value {
+            Colour::Rgb(rgb) => rgb,
+            Colour::Hex(hex) => hex.into(),
+            Colour::Named(named) => named.into(),
+        }
+    }
+}
+
+/// A small set of CSS colour names for quickly trying out colours
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Display, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum NamedColour {
+    Black,
+    White,
+    #[serde(alias = "grey")]
+    Gray,
+    #[serde(alias = "darkgrey")]
+    DarkGray,
+    #[serde(alias = "lightgrey")]
+    LightGray,
+    #[serde(alias = "dimgrey")]
+    DimGray,
+    #[serde(alias = "slategrey")]
+    SlateGray,
+    Silver,
+    Red,
+    Crimson,
+    Maroon,
+    Tomato,
+    Coral,
+    Orange,
+    Gold,
+    Yellow,
+    Olive,
+    Lime,
+    Green,
+    Teal,
+    Cyan,
+    SkyBlue,
+    SteelBlue,
+    Blue,
+    Navy,
+    Indigo,
+    Purple,
+    Magenta,
+    Pink,
+    Brown,
+}
+
+impl From<NamedColour> for Rgb {
+    fn from(value: NamedColour) -> Self {
         match value {
-            Colour::Rgb(val) => val.into(),
-            Colour::Hex(val) => (Rgb::from(val)).into(),
+            NamedColour::Black => Rgb::new(0, 0, 0),
+            NamedColour::White => Rgb::new(255, 255, 255),
+            NamedColour::Gray => Rgb::new(128, 128, 128),
+            NamedColour::DarkGray => Rgb::new(169, 169, 169),
+            NamedColour::LightGray => Rgb::new(211, 211, 211),
+            NamedColour::DimGray => Rgb::new(105, 105, 105),
+            NamedColour::SlateGray => Rgb::new(112, 128, 144),
+            NamedColour::Silver => Rgb::new(192, 192, 192),
+            NamedColour::Red => Rgb::new(255, 0, 0),
+            NamedColour::Crimson => Rgb::new(220, 20, 60),
+            NamedColour::Maroon => Rgb::new(128, 0, 0),
+            NamedColour::Tomato => Rgb::new(255, 99, 71),
+            NamedColour::Coral => Rgb::new(255, 127, 80),
+            NamedColour::Orange => Rgb::new(255, 165, 0),
+            NamedColour::Gold => Rgb::new(255, 215, 0),
+            NamedColour::Yellow => Rgb::new(255, 255, 0),
+            NamedColour::Olive => Rgb::new(128, 128, 0),
+            NamedColour::Lime => Rgb::new(0, 255, 0),
+            NamedColour::Green => Rgb::new(0, 128, 0),
+            NamedColour::Teal => Rgb::new(0, 128, 128),
+            NamedColour::Cyan => Rgb::new(0, 255, 255),
+            NamedColour::SkyBlue => Rgb::new(135, 206, 235),
+            NamedColour::SteelBlue => Rgb::new(70, 130, 180),
+            NamedColour::Blue => Rgb::new(0, 0, 255),
+            NamedColour::Navy => Rgb::new(0, 0, 128),
+            NamedColour::Indigo => Rgb::new(75, 0, 130),
+            NamedColour::Purple => Rgb::new(128, 0, 128),
+            NamedColour::Magenta => Rgb::new(255, 0, 255),
+            NamedColour::Pink => Rgb::new(255, 192, 203),
+            NamedColour::Brown => Rgb::new(165, 42, 42),
         }
     }
 }
@@ -9,14 +9,24 @@ use interfaces::IApplicationViewCollection;
 use interfaces::IServiceProvider;
 
 use std::ffi::c_void;
+use std::path::Path;
+
+use crate::core::Rect;
 
 use windows::Win32::Foundation::HWND;
 use windows::Win32::System::Com::CoCreateInstance;
 use windows::Win32::System::Com::CoInitializeEx;
+use windows::Win32::System::Com::CoTaskMemFree;
 use windows::Win32::System::Com::CoUninitialize;
 use windows::Win32::System::Com::CLSCTX_ALL;
 use windows::Win32::System::Com::COINIT_APARTMENTTHREADED;
+use windows::Win32::UI::Shell::DesktopWallpaper;
+use windows::Win32::UI::Shell::IDesktopWallpaper;
+use windows::Win32::UI::Shell::ITaskbarList;
+use windows::Win32::UI::Shell::TaskbarList;
 use windows_core::Interface;
+use windows_core::HSTRING;
+use windows_core::PCWSTR;
 
 struct ComInit();
 
@@ -101,3 +111,45 @@ pub extern "C" fn SetCloak(hwnd: HWND, cloak_type: u32, flags: i32) {
         );
     });
 }
+
+/// Sets the wallpaper of the monitor which occupies `monitor_size`, leaving other monitors alone
+pub fn set_monitor_wallpaper(monitor_size: Rect, path: &Path) -> windows_core::Result<bool> {
+    COM_INIT.with(|_| unsafe {
+        let wallpaper: IDesktopWallpaper = CoCreateInstance(&DesktopWallpaper, None, CLSCTX_ALL)?;
+
+        for i in 0..wallpaper.GetMonitorDevicePathCount()? {
+            let monitor_id = wallpaper.GetMonitorDevicePathAt(i)?;
+            let monitor_id_ptr = PCWSTR(monitor_id.0);
+
+            // Monitors which are known to the shell but not attached have no rect
+            let result = match wallpaper.GetMonitorRECT(monitor_id_ptr) {
+                Ok(rect) if Rect::from(rect) == monitor_size => wallpaper
+                    .SetWallpaper(monitor_id_ptr, &HSTRING::from(path.as_os_str()))
+                    .map(|()| true),
+                _ => Ok(false),
+            };
+
+            CoTaskMemFree(Some(monitor_id.0 as *const c_void));
+
+            if result? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    })
+}
+
+/// Adds or removes the taskbar button of a window without changing its visibility
+pub fn set_taskbar_entry(hwnd: HWND, visible: bool) -> windows_core::Result<()> {
+    COM_INIT.with(|_| unsafe {
+        let taskbar: ITaskbarList = CoCreateInstance(&TaskbarList, None, CLSCTX_ALL)?;
+        taskbar.HrInit()?;
+
+        if visible {
+            taskbar.AddTab(hwnd)
+        } else {
+            taskbar.DeleteTab(hwnd)
+        }
+    })
+}
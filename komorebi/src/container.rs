@@ -1,4 +1,6 @@
+use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::num::NonZeroUsize;
 
 use getset::Getters;
 use nanoid::nanoid;
@@ -6,14 +8,42 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::CycleDirection;
+use crate::core::HidingBehaviour;
 use crate::ring::Ring;
 use crate::window::Window;
+use crate::HIDING_BEHAVIOUR;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Getters, JsonSchema)]
 pub struct Container {
     #[getset(get = "pub")]
     id: String,
     windows: Ring<Window>,
+    /// Windows in this container which have been minimized by the user, these keep their
+    /// position in the stack but are skipped when navigating and rendering the stackbar
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    #[getset(get = "pub")]
+    minimized_hwnds: HashSet<isize>,
+    /// A parent window, such as a terminal, which has been hidden after the window it launched
+    /// took over its tile, and which gets the tile back when that window is closed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub")]
+    swallowed_window: Option<Window>,
+    /// The workspace this container was on before it was last explicitly moved to another
+    /// workspace, which it can be returned to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub")]
+    previous_workspace: Option<WorkspaceOrigin>,
+}
+
+/// The location of a workspace which a container has been moved away from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WorkspaceOrigin {
+    pub monitor_idx: usize,
+    pub workspace_idx: usize,
+    /// The name of the workspace, which is preferred over the indices when a workspace with this
+    /// name still exists
+    pub workspace_name: Option<String>,
 }
 
 impl_ring_elements!(Container, Window);
@@ -23,13 +53,26 @@ impl Default for Container {
         Self {
             id: nanoid!(),
             windows: Ring::default(),
+            minimized_hwnds: HashSet::default(),
+            swallowed_window: None,
+            previous_workspace: None,
         }
     }
 }
 
 impl Container {
     pub fn hide(&self, omit: Option<isize>) {
+        let hiding_behaviour = *HIDING_BEHAVIOUR.lock();
+        self.hide_with_behaviour(omit, hiding_behaviour);
+    }
+
+    pub fn hide_with_behaviour(&self, omit: Option<isize>, hiding_behaviour: HidingBehaviour) {
         for window in self.windows().iter().rev() {
+            // Minimized windows are left alone so that they can still be restored from the taskbar
+            if self.is_minimized(window.hwnd) {
+                continue;
+            }
+
             let mut should_hide = omit.is_none();
 
             if !should_hide {
@@ -41,28 +84,62 @@ impl Container {
             }
 
             if should_hide {
-                window.hide();
+                window.hide_with_behaviour(hiding_behaviour);
             }
         }
     }
 
     pub fn restore(&self) {
+        let hiding_behaviour = *HIDING_BEHAVIOUR.lock();
+        self.restore_with_behaviour(hiding_behaviour);
+    }
+
+    pub fn restore_with_behaviour(&self, hiding_behaviour: HidingBehaviour) {
         if let Some(window) = self.focused_window() {
-            window.restore();
+            window.restore_with_behaviour(hiding_behaviour);
         }
     }
 
     pub fn load_focused_window(&mut self) {
+        let hiding_behaviour = *HIDING_BEHAVIOUR.lock();
+        self.load_focused_window_with_behaviour(hiding_behaviour);
+    }
+
+    pub fn load_focused_window_with_behaviour(&mut self, hiding_behaviour: HidingBehaviour) {
         let focused_idx = self.focused_window_idx();
         for (i, window) in self.windows_mut().iter_mut().enumerate() {
             if i == focused_idx {
-                window.restore();
-            } else {
-                window.hide();
+                window.restore_with_behaviour(hiding_behaviour);
+            } else if !self.minimized_hwnds.contains(&window.hwnd) {
+                window.hide_with_behaviour(hiding_behaviour);
             }
         }
     }
 
+    /// Replaces the only window of this container with `window`, hiding the replaced window and
+    /// keeping hold of it until it is given back its tile
+    pub fn swallow(&mut self, window: Window, hiding_behaviour: HidingBehaviour) -> Option<Window> {
+        if self.swallowed_window.is_some() || self.windows().len() != 1 {
+            return None;
+        }
+
+        let parent = self.remove_window_by_idx(0)?;
+        parent.hide_with_behaviour(hiding_behaviour);
+
+        self.add_window(window, hiding_behaviour);
+        self.swallowed_window = Option::from(parent);
+
+        Option::from(parent)
+    }
+
+    pub fn take_swallowed_window(&mut self) -> Option<Window> {
+        self.swallowed_window.take()
+    }
+
+    pub fn set_previous_workspace(&mut self, origin: Option<WorkspaceOrigin>) {
+        self.previous_workspace = origin;
+    }
+
     pub fn hwnd_from_exe(&self, exe: &str) -> Option<isize> {
         for window in self.windows() {
             if let Ok(window_exe) = window.exe() {
@@ -110,23 +187,117 @@ impl Container {
 
     pub fn remove_window_by_idx(&mut self, idx: usize) -> Option<Window> {
         let window = self.windows_mut().remove(idx);
+
+        if let Some(window) = &window {
+            self.minimized_hwnds.remove(&window.hwnd);
+        }
+
         self.focus_window(idx.saturating_sub(1));
         window
     }
 
+    pub fn is_minimized(&self, hwnd: isize) -> bool {
+        self.minimized_hwnds.contains(&hwnd)
+    }
+
+    /// Returns the number of windows in this container which have not been minimized
+    pub fn unminimized_window_count(&self) -> usize {
+        self.windows()
+            .iter()
+            .filter(|window| !self.is_minimized(window.hwnd))
+            .count()
+    }
+
+    /// Returns the index of the next window in the given direction which has not been minimized
+    /// The index of the next unminimized window in the given direction, optionally stopping at
+    /// the first and last windows of the stack instead of wrapping around
+    pub fn next_unminimized_idx(
+        &self,
+        current_idx: usize,
+        direction: CycleDirection,
+        wrap: bool,
+    ) -> Option<usize> {
+        let len = NonZeroUsize::new(self.windows().len())?;
+        let mut idx = current_idx;
+
+        for _ in 1..len.get() {
+            let next_idx = direction.next_idx(idx, len);
+
+            let wrapped = match direction {
+                CycleDirection::Previous => next_idx > idx,
+                CycleDirection::Next => next_idx < idx,
+            };
+
+            if wrapped && !wrap {
+                return None;
+            }
+
+            idx = next_idx;
+
+            if let Some(window) = self.windows().get(idx) {
+                if !self.is_minimized(window.hwnd) {
+                    return Option::from(idx);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Marks a stacked window as minimized while keeping its position in the stack, moving focus
+    /// to another window in the stack if required. Returns false if the window is not in this
+    /// container or if there are no other windows left in the stack to focus.
+    pub fn minimize_window(&mut self, hwnd: isize) -> bool {
+        let Some(idx) = self.idx_for_window(hwnd) else {
+            return false;
+        };
+
+        if self.is_minimized(hwnd) {
+            return true;
+        }
+
+        if self.unminimized_window_count() <= 1 {
+            return false;
+        }
+
+        self.minimized_hwnds.insert(hwnd);
+
+        if self.focused_window_idx() == idx {
+            if let Some(next_idx) = self.next_unminimized_idx(idx, CycleDirection::Previous, true) {
+                self.focus_window(next_idx);
+            }
+        }
+
+        true
+    }
+
+    /// Reinstates a previously minimized window at its original position in the stack and focuses
+    /// it. Returns false if the window was not minimized in this container.
+    pub fn restore_minimized_window(&mut self, hwnd: isize) -> bool {
+        if !self.minimized_hwnds.remove(&hwnd) {
+            return false;
+        }
+
+        if let Some(idx) = self.idx_for_window(hwnd) {
+            self.focus_window(idx);
+        }
+
+        true
+    }
+
     pub fn remove_focused_window(&mut self) -> Option<Window> {
         let focused_idx = self.focused_window_idx();
         self.remove_window_by_idx(focused_idx)
     }
 
-    pub fn add_window(&mut self, window: Window) {
+    pub fn add_window(&mut self, window: Window, hiding_behaviour: HidingBehaviour) {
         self.windows_mut().push_back(window);
         self.focus_window(self.windows().len().saturating_sub(1));
         let focused_window_idx = self.focused_window_idx();
 
         for (i, window) in self.windows().iter().enumerate() {
             if i != focused_window_idx {
-                window.hide();
+                window.hide_with_behaviour(hiding_behaviour);
             }
         }
     }
@@ -1,13 +1,14 @@
+use clap::builder::PossibleValue;
 use clap::ValueEnum;
+use color_eyre::eyre::bail;
+use color_eyre::Result;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use strum::Display;
 use strum::EnumString;
 
-#[derive(
-    Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, JsonSchema,
-)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, JsonSchema)]
 pub enum AnimationStyle {
     Linear,
     EaseInSine,
@@ -39,4 +40,102 @@ pub enum AnimationStyle {
     EaseInBounce,
     EaseOutBounce,
     EaseInOutBounce,
+    /// A custom cubic bézier curve, defined by the control points (x1, y1) and (x2, y2) in the
+    /// same way as the CSS cubic-bezier() function
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl AnimationStyle {
+    const PRESETS: [Self; 30] = [
+        Self::Linear,
+        Self::EaseInSine,
+        Self::EaseOutSine,
+        Self::EaseInOutSine,
+        Self::EaseInQuad,
+        Self::EaseOutQuad,
+        Self::EaseInOutQuad,
+        Self::EaseInCubic,
+        Self::EaseInOutCubic,
+        Self::EaseInQuart,
+        Self::EaseOutQuart,
+        Self::EaseInOutQuart,
+        Self::EaseInQuint,
+        Self::EaseOutQuint,
+        Self::EaseInOutQuint,
+        Self::EaseInExpo,
+        Self::EaseOutExpo,
+        Self::EaseInOutExpo,
+        Self::EaseInCirc,
+        Self::EaseOutCirc,
+        Self::EaseInOutCirc,
+        Self::EaseInBack,
+        Self::EaseOutBack,
+        Self::EaseInOutBack,
+        Self::EaseInElastic,
+        Self::EaseOutElastic,
+        Self::EaseInOutElastic,
+        Self::EaseInBounce,
+        Self::EaseOutBounce,
+        Self::EaseInOutBounce,
+    ];
+
+    /// Checks that the x coordinates of the control points of a cubic bézier curve are within
+    /// [0, 1], as the curve would otherwise not be a function of time
+    pub fn validate(self) -> Result<Self> {
+        if let Self::CubicBezier(x1, _, x2, _) = self {
+            for x in [x1, x2] {
+                if !(0.0..=1.0).contains(&x) {
+                    bail!("cubic bézier control point x coordinates must be between 0 and 1, found {x}");
+                }
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+// Only the preset easings can be selected by name on the command line, custom curves are given
+// with their control points instead
+impl ValueEnum for AnimationStyle {
+    fn value_variants<'a>() -> &'a [Self] {
+        &Self::PRESETS
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let name = match self {
+            Self::Linear => "linear",
+            Self::EaseInSine => "ease-in-sine",
+            Self::EaseOutSine => "ease-out-sine",
+            Self::EaseInOutSine => "ease-in-out-sine",
+            Self::EaseInQuad => "ease-in-quad",
+            Self::EaseOutQuad => "ease-out-quad",
+            Self::EaseInOutQuad => "ease-in-out-quad",
+            Self::EaseInCubic => "ease-in-cubic",
+            Self::EaseInOutCubic => "ease-in-out-cubic",
+            Self::EaseInQuart => "ease-in-quart",
+            Self::EaseOutQuart => "ease-out-quart",
+            Self::EaseInOutQuart => "ease-in-out-quart",
+            Self::EaseInQuint => "ease-in-quint",
+            Self::EaseOutQuint => "ease-out-quint",
+            Self::EaseInOutQuint => "ease-in-out-quint",
+            Self::EaseInExpo => "ease-in-expo",
+            Self::EaseOutExpo => "ease-out-expo",
+            Self::EaseInOutExpo => "ease-in-out-expo",
+            Self::EaseInCirc => "ease-in-circ",
+            Self::EaseOutCirc => "ease-out-circ",
+            Self::EaseInOutCirc => "ease-in-out-circ",
+            Self::EaseInBack => "ease-in-back",
+            Self::EaseOutBack => "ease-out-back",
+            Self::EaseInOutBack => "ease-in-out-back",
+            Self::EaseInElastic => "ease-in-elastic",
+            Self::EaseOutElastic => "ease-out-elastic",
+            Self::EaseInOutElastic => "ease-in-out-elastic",
+            Self::EaseInBounce => "ease-in-bounce",
+            Self::EaseOutBounce => "ease-out-bounce",
+            Self::EaseInOutBounce => "ease-in-out-bounce",
+            Self::CubicBezier(..) => return None,
+        };
+
+        Some(PossibleValue::new(name))
+    }
 }
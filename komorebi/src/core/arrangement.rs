@@ -10,11 +10,18 @@ use strum::EnumString;
 use super::custom_layout::Column;
 use super::custom_layout::ColumnSplit;
 use super::custom_layout::ColumnSplitWithCapacity;
+use super::custom_layout::ColumnWidth;
 use super::CustomLayout;
 use super::DefaultLayout;
 use super::Rect;
 
 pub trait Arrangement {
+    /// Calculates the rect of each container in the given area
+    ///
+    /// The focused container index, the rects from the last time the workspace was laid out and
+    /// the scrolling layout options are only used by layouts which scroll to keep the focused
+    /// container in view.
+    #[allow(clippy::too_many_arguments)]
     fn calculate(
         &self,
         area: &Rect,
@@ -22,11 +29,18 @@ pub trait Arrangement {
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
         resize_dimensions: &[Option<Rect>],
+        focused_idx: usize,
+        latest_layout: &[Rect],
+        scrolling_options: Option<ScrollingLayoutOptions>,
     ) -> Vec<Rect>;
 }
 
 impl Arrangement for DefaultLayout {
-    #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+    #[allow(
+        clippy::too_many_lines,
+        clippy::cognitive_complexity,
+        clippy::too_many_arguments
+    )]
     fn calculate(
         &self,
         area: &Rect,
@@ -34,6 +48,9 @@ impl Arrangement for DefaultLayout {
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
         resize_dimensions: &[Option<Rect>],
+        focused_idx: usize,
+        latest_layout: &[Rect],
+        scrolling_options: Option<ScrollingLayoutOptions>,
     ) -> Vec<Rect> {
         let len = usize::from(len);
         let mut dimensions = match self {
@@ -470,6 +487,13 @@ impl Arrangement for DefaultLayout {
 
                 layouts
             }
+            Self::Scrolling => scrolling(
+                area,
+                len,
+                focused_idx,
+                latest_layout,
+                scrolling_options.unwrap_or_default(),
+            ),
         };
 
         dimensions
@@ -481,6 +505,7 @@ impl Arrangement for DefaultLayout {
 }
 
 impl Arrangement for CustomLayout {
+    #[allow(clippy::too_many_arguments)]
     fn calculate(
         &self,
         area: &Rect,
@@ -488,6 +513,9 @@ impl Arrangement for CustomLayout {
         container_padding: Option<i32>,
         _layout_flip: Option<Axis>,
         _resize_dimensions: &[Option<Rect>],
+        _focused_idx: usize,
+        _latest_layout: &[Rect],
+        _scrolling_options: Option<ScrollingLayoutOptions>,
     ) -> Vec<Rect> {
         let mut dimensions = vec![];
         let container_count = len.get();
@@ -519,10 +547,13 @@ impl Arrangement for CustomLayout {
             };
 
             #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-            let primary_right = self.primary_width_percentage().map_or_else(
-                || area.right / self.len() as i32,
-                |percentage| (area.right / 100) * percentage as i32,
-            );
+            let primary_right = match self.primary_width() {
+                Some(ColumnWidth::WidthPercentage(percentage)) => {
+                    (area.right / 100) * percentage as i32
+                }
+                Some(ColumnWidth::Width(width)) => width.clamp(0, area.right),
+                None => area.right / self.len() as i32,
+            };
 
             for (idx, column) in self.iter().enumerate() {
                 // If we are offsetting a tertiary column for which the threshold
@@ -621,6 +652,167 @@ pub enum Axis {
     HorizontalAndVertical,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+    PartialEq,
+    Eq,
+)]
+pub enum ScrollingCentering {
+    /// Only scroll when the focused column would otherwise be out of view
+    #[default]
+    KeepInView,
+    /// Keep the focused column in the middle visible column whenever possible
+    AlwaysCentered,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(default)]
+pub struct ScrollingLayoutOptions {
+    /// Number of columns visible at once in the Scrolling layout (default: 3)
+    pub visible_columns: usize,
+    /// Share of the visible width taken by the focused column, with the remaining visible columns
+    /// sharing the rest equally (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focused_column_ratio: Option<f32>,
+    /// How the visible columns follow the focused column (default: KeepInView)
+    pub centering: ScrollingCentering,
+}
+
+impl Default for ScrollingLayoutOptions {
+    fn default() -> Self {
+        Self {
+            visible_columns: 3,
+            focused_column_ratio: None,
+            centering: ScrollingCentering::KeepInView,
+        }
+    }
+}
+
+impl ScrollingLayoutOptions {
+    pub const MIN_FOCUSED_COLUMN_RATIO: f32 = 0.1;
+    pub const MAX_FOCUSED_COLUMN_RATIO: f32 = 0.9;
+
+    /// The focused column ratio, falling back to an equal share of the visible width
+    #[allow(clippy::cast_precision_loss)]
+    pub fn effective_focused_column_ratio(&self) -> f32 {
+        self.focused_column_ratio
+            .unwrap_or(1.0 / self.visible_columns.max(1) as f32)
+            .clamp(
+                Self::MIN_FOCUSED_COLUMN_RATIO,
+                Self::MAX_FOCUSED_COLUMN_RATIO,
+            )
+    }
+}
+
+/// The index of the first visible column in the Scrolling layout
+fn scrolling_first_visible(
+    area: &Rect,
+    len: usize,
+    visible: usize,
+    focused_idx: usize,
+    latest_layout: &[Rect],
+    centering: ScrollingCentering,
+) -> usize {
+    let first = match centering {
+        ScrollingCentering::AlwaysCentered => focused_idx.saturating_sub((visible - 1) / 2),
+        ScrollingCentering::KeepInView => {
+            // The first column which was visible the last time the workspace was laid out is the
+            // first one whose centre was inside the work area
+            let previous = latest_layout
+                .iter()
+                .position(|rect| rect.left + rect.right / 2 >= area.left)
+                .unwrap_or_default();
+
+            if focused_idx < previous {
+                focused_idx
+            } else if focused_idx >= previous + visible {
+                focused_idx + 1 - visible
+            } else {
+                previous
+            }
+        }
+    };
+
+    first.min(len - visible)
+}
+
+#[must_use]
+#[allow(
+    clippy::cast_possible_wrap,
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss
+)]
+fn scrolling(
+    area: &Rect,
+    len: usize,
+    focused_idx: usize,
+    latest_layout: &[Rect],
+    options: ScrollingLayoutOptions,
+) -> Vec<Rect> {
+    let visible = options.visible_columns.clamp(1, len);
+    let focused_idx = focused_idx.min(len - 1);
+    let first = scrolling_first_visible(
+        area,
+        len,
+        visible,
+        focused_idx,
+        latest_layout,
+        options.centering,
+    );
+
+    let (focused_width, other_width) = match options.focused_column_ratio {
+        Some(_) if visible > 1 => {
+            let focused_width =
+                (area.right as f32 * options.effective_focused_column_ratio()).round() as i32;
+            (
+                focused_width,
+                (area.right - focused_width) / (visible - 1) as i32,
+            )
+        }
+        _ => {
+            let width = area.right / visible as i32;
+            (width, width)
+        }
+    };
+
+    let width = |idx: usize| {
+        if idx == focused_idx {
+            focused_width
+        } else {
+            other_width
+        }
+    };
+
+    // Columns before the first visible column continue off the left edge of the work area, and
+    // columns after the last visible column continue off the right edge
+    let mut left = area.left - (0..first).map(width).sum::<i32>();
+
+    let mut layouts: Vec<Rect> = vec![];
+    for idx in 0..len {
+        let right = width(idx);
+
+        layouts.push(Rect {
+            left,
+            top: area.top,
+            right,
+            bottom: area.bottom,
+        });
+
+        left += right;
+    }
+
+    layouts
+}
+
 #[must_use]
 fn columns(area: &Rect, len: usize) -> Vec<Rect> {
     #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
@@ -1143,3 +1335,373 @@ fn resize_top(rect: &mut Rect, resize: i32) {
 fn resize_bottom(rect: &mut Rect, resize: i32) {
     rect.bottom += resize / 2;
 }
+
+/// Grow every rect which is narrower or shorter than the minimum size of its window by moving
+/// the boundaries it shares with its neighbours, so that the neighbours give up the difference
+/// instead of being overlapped. Rects whose neighbours cannot give up enough space without
+/// going below their own minimum sizes are left as they are.
+pub fn redistribute_minimum_sizes(layouts: &mut [Rect], minimums: &[Option<(i32, i32)>], gap: i32) {
+    let min_widths = minimums
+        .iter()
+        .map(|minimum| minimum.map_or(0, |(width, _)| width))
+        .collect::<Vec<_>>();
+
+    let min_heights = minimums
+        .iter()
+        .map(|minimum| minimum.map_or(0, |(_, height)| height))
+        .collect::<Vec<_>>();
+
+    for idx in 0..layouts.len() {
+        let Some(deficit) = min_widths
+            .get(idx)
+            .map(|min_width| min_width - layouts[idx].right)
+        else {
+            continue;
+        };
+
+        if deficit > 0 {
+            grow_width(layouts, &min_widths, idx, deficit, gap);
+        }
+    }
+
+    // Heights are redistributed by swapping the axes so that rows are treated as columns
+    let mut transposed = layouts.iter().map(transpose).collect::<Vec<_>>();
+
+    for idx in 0..transposed.len() {
+        let Some(deficit) = min_heights
+            .get(idx)
+            .map(|min_height| min_height - transposed[idx].right)
+        else {
+            continue;
+        };
+
+        if deficit > 0 {
+            grow_width(&mut transposed, &min_heights, idx, deficit, gap);
+        }
+    }
+
+    for (layout, rect) in layouts.iter_mut().zip(transposed.iter()) {
+        *layout = transpose(rect);
+    }
+}
+
+const fn transpose(rect: &Rect) -> Rect {
+    Rect {
+        left: rect.top,
+        top: rect.left,
+        right: rect.bottom,
+        bottom: rect.right,
+    }
+}
+
+const fn overlaps_vertically(a: &Rect, b: &Rect) -> bool {
+    a.top < b.top + b.bottom && b.top < a.top + a.bottom
+}
+
+/// Widen the rect at `idx` by moving the boundary on its right, or failing that on its left,
+/// shrinking every rect on the other side of that boundary by the same amount
+fn grow_width(layouts: &mut [Rect], min_widths: &[i32], idx: usize, deficit: i32, gap: i32) {
+    let rect = layouts[idx];
+
+    for grow_right in [true, false] {
+        let edge = if grow_right {
+            rect.left + rect.right
+        } else {
+            rect.left
+        };
+
+        // Rects which end at the boundary on the same side as this rect, and rects which start
+        // at the boundary on the other side of it, spreading out along the boundary until every
+        // rect touching it has been found
+        let mut peers = vec![idx];
+        let mut neighbours: Vec<usize> = vec![];
+
+        loop {
+            let next_neighbours = (0..layouts.len())
+                .filter(|&j| {
+                    let distance = if grow_right {
+                        layouts[j].left - edge
+                    } else {
+                        edge - (layouts[j].left + layouts[j].right)
+                    };
+
+                    !peers.contains(&j)
+                        && (0..=gap).contains(&distance)
+                        && peers
+                            .iter()
+                            .any(|&p| overlaps_vertically(&layouts[p], &layouts[j]))
+                })
+                .collect::<Vec<_>>();
+
+            let next_peers = (0..layouts.len())
+                .filter(|&j| {
+                    let side = if grow_right {
+                        layouts[j].left + layouts[j].right
+                    } else {
+                        layouts[j].left
+                    };
+
+                    (side - edge).abs() <= 1
+                        && (j == idx
+                            || next_neighbours
+                                .iter()
+                                .any(|&n| overlaps_vertically(&layouts[n], &layouts[j])))
+                })
+                .collect::<Vec<_>>();
+
+            if next_neighbours == neighbours && next_peers == peers {
+                break;
+            }
+
+            neighbours = next_neighbours;
+            peers = next_peers;
+        }
+
+        let can_shrink = neighbours
+            .iter()
+            .all(|&j| layouts[j].right - deficit >= min_widths.get(j).copied().unwrap_or(0).max(1));
+
+        if neighbours.is_empty() || !can_shrink {
+            continue;
+        }
+
+        for j in neighbours {
+            if grow_right {
+                layouts[j].left += deficit;
+            }
+
+            layouts[j].right -= deficit;
+        }
+
+        for j in peers {
+            if !grow_right {
+                layouts[j].left -= deficit;
+            }
+
+            layouts[j].right += deficit;
+        }
+
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    const WORK_AREA: Rect = rect(0, 0, 1920, 1080);
+
+    fn scrolling_options(
+        visible_columns: usize,
+        centering: ScrollingCentering,
+    ) -> ScrollingLayoutOptions {
+        ScrollingLayoutOptions {
+            visible_columns,
+            focused_column_ratio: None,
+            centering,
+        }
+    }
+
+    fn lefts(layouts: &[Rect]) -> Vec<i32> {
+        layouts.iter().map(|rect| rect.left).collect()
+    }
+
+    #[test]
+    fn scrolling_shows_the_focused_column_and_moves_the_rest_out_of_view() {
+        for len in 1..=6 {
+            for visible_columns in 2..=3 {
+                for focused_idx in 0..len {
+                    let layouts = scrolling(
+                        &WORK_AREA,
+                        len,
+                        focused_idx,
+                        &[],
+                        scrolling_options(visible_columns, ScrollingCentering::KeepInView),
+                    );
+
+                    let visible = visible_columns.min(len);
+                    let width = WORK_AREA.right / visible as i32;
+
+                    assert_eq!(layouts.len(), len);
+
+                    for (idx, rect) in layouts.iter().enumerate() {
+                        assert_eq!(rect.right, width);
+                        assert_eq!(rect.top, WORK_AREA.top);
+                        assert_eq!(rect.bottom, WORK_AREA.bottom);
+
+                        if idx > 0 {
+                            assert_eq!(rect.left, layouts[idx - 1].left + width);
+                        }
+                    }
+
+                    let in_view = layouts
+                        .iter()
+                        .filter(|rect| {
+                            rect.left >= WORK_AREA.left
+                                && rect.left + rect.right <= WORK_AREA.left + WORK_AREA.right
+                        })
+                        .count();
+
+                    let out_of_view = layouts
+                        .iter()
+                        .filter(|rect| {
+                            rect.left + rect.right <= WORK_AREA.left
+                                || rect.left >= WORK_AREA.left + WORK_AREA.right
+                        })
+                        .count();
+
+                    assert_eq!(in_view, visible);
+                    assert_eq!(out_of_view, len - visible);
+
+                    let focused = layouts[focused_idx];
+                    assert!(focused.left >= WORK_AREA.left);
+                    assert!(focused.left + focused.right <= WORK_AREA.left + WORK_AREA.right);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scrolling_keeps_the_focused_column_in_view() {
+        let layouts = scrolling(
+            &WORK_AREA,
+            5,
+            4,
+            &[],
+            scrolling_options(3, ScrollingCentering::KeepInView),
+        );
+
+        assert_eq!(lefts(&layouts), vec![-1280, -640, 0, 640, 1280]);
+
+        // The visible columns only move once the focused column would leave the work area
+        let layouts = scrolling(
+            &WORK_AREA,
+            4,
+            1,
+            &[
+                rect(-960, 0, 960, 1080),
+                rect(0, 0, 960, 1080),
+                rect(960, 0, 960, 1080),
+                rect(1920, 0, 960, 1080),
+            ],
+            scrolling_options(2, ScrollingCentering::KeepInView),
+        );
+
+        assert_eq!(lefts(&layouts), vec![-960, 0, 960, 1920]);
+    }
+
+    #[test]
+    fn scrolling_centres_the_focused_column() {
+        let layouts = scrolling(
+            &WORK_AREA,
+            6,
+            3,
+            &[],
+            scrolling_options(3, ScrollingCentering::AlwaysCentered),
+        );
+
+        assert_eq!(lefts(&layouts), vec![-1280, -640, 0, 640, 1280, 1920]);
+
+        // The last columns can't be centred without leaving an empty space at the end
+        let layouts = scrolling(
+            &WORK_AREA,
+            6,
+            5,
+            &[],
+            scrolling_options(2, ScrollingCentering::AlwaysCentered),
+        );
+
+        assert_eq!(lefts(&layouts), vec![-3840, -2880, -1920, -960, 0, 960]);
+    }
+
+    #[test]
+    fn redistribute_minimum_width_across_column_split() {
+        let mut layouts = vec![
+            rect(0, 0, 500, 1000),
+            rect(500, 0, 500, 500),
+            rect(500, 500, 500, 500),
+        ];
+
+        redistribute_minimum_sizes(&mut layouts, &[Some((700, 0)), None, None], 0);
+
+        assert_eq!(
+            layouts,
+            vec![
+                rect(0, 0, 700, 1000),
+                rect(700, 0, 300, 500),
+                rect(700, 500, 300, 500),
+            ]
+        );
+    }
+
+    #[test]
+    fn redistribute_minimum_width_across_container_padding() {
+        let mut layouts = vec![rect(0, 0, 495, 1000), rect(505, 0, 495, 1000)];
+
+        redistribute_minimum_sizes(&mut layouts, &[Some((600, 0)), None], 11);
+
+        assert_eq!(
+            layouts,
+            vec![rect(0, 0, 600, 1000), rect(610, 0, 390, 1000)]
+        );
+    }
+
+    #[test]
+    fn redistribute_minimum_height_across_row_split() {
+        let mut layouts = vec![
+            rect(0, 0, 500, 500),
+            rect(500, 0, 500, 500),
+            rect(0, 500, 1000, 500),
+        ];
+
+        redistribute_minimum_sizes(&mut layouts, &[None, None, Some((0, 800))], 0);
+
+        assert_eq!(
+            layouts,
+            vec![
+                rect(0, 0, 500, 200),
+                rect(500, 0, 500, 200),
+                rect(0, 200, 1000, 800),
+            ]
+        );
+    }
+
+    #[test]
+    fn redistribute_minimum_width_to_the_left_when_right_neighbour_cannot_shrink() {
+        let mut layouts = vec![
+            rect(0, 0, 400, 1000),
+            rect(400, 0, 400, 1000),
+            rect(800, 0, 400, 1000),
+        ];
+
+        redistribute_minimum_sizes(&mut layouts, &[None, Some((600, 0)), Some((350, 0))], 0);
+
+        assert_eq!(
+            layouts,
+            vec![
+                rect(0, 0, 200, 1000),
+                rect(200, 0, 600, 1000),
+                rect(800, 0, 400, 1000),
+            ]
+        );
+    }
+
+    #[test]
+    fn redistribute_minimum_width_leaves_layout_when_no_neighbour_can_shrink() {
+        let original = vec![rect(0, 0, 500, 1000), rect(500, 0, 500, 1000)];
+        let mut layouts = original.clone();
+
+        redistribute_minimum_sizes(&mut layouts, &[Some((700, 0)), Some((400, 0))], 0);
+
+        assert_eq!(layouts, original);
+    }
+}
@@ -1,15 +1,71 @@
 use crate::config_generation::ApplicationConfiguration;
+use crate::config_generation::ApplicationConfigurationGenerator;
 use crate::config_generation::ApplicationOptions;
 use crate::config_generation::MatchingRule;
+use crate::core::resolve_home_path;
 use color_eyre::Result;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::path::PathBuf;
 
+/// The path to applications.json, or to several files which are merged in the order they are
+/// listed
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum AppSpecificConfigurationPath {
+    /// A single file
+    Single(PathBuf),
+    /// Multiple files, where rules in later files override the same kind of rules for the same
+    /// application in earlier files
+    Multiple(Vec<PathBuf>),
+}
+
+impl AppSpecificConfigurationPath {
+    pub fn paths(&self) -> Vec<&PathBuf> {
+        match self {
+            Self::Single(path) => vec![path],
+            Self::Multiple(paths) => paths.iter().collect(),
+        }
+    }
+
+    pub fn paths_mut(&mut self) -> Vec<&mut PathBuf> {
+        match self {
+            Self::Single(path) => vec![path],
+            Self::Multiple(paths) => paths.iter_mut().collect(),
+        }
+    }
+}
+
+/// A kind of rule for an application which was set in more than one file with different values
+#[derive(Clone, Debug)]
+pub struct AscConflict {
+    pub application: String,
+    pub rules: &'static str,
+    pub overridden: PathBuf,
+    pub winner: PathBuf,
+}
+
+impl Display for AscConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the {} rules for \"{}\" in {} are overridden by {}",
+            self.rules,
+            self.application,
+            self.overridden.display(),
+            self.winner.display()
+        )
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ApplicationSpecificConfiguration(pub BTreeMap<String, AscApplicationRulesOrSchema>);
 
@@ -43,10 +99,88 @@ impl ApplicationSpecificConfiguration {
     pub fn format(pathbuf: &PathBuf) -> Result<String> {
         Ok(serde_json::to_string_pretty(&Self::load(pathbuf)?)?)
     }
+
+    /// Loads applications.json or the older applications.yaml format, ignoring files with any
+    /// other extension
+    pub fn load_file(pathbuf: &PathBuf) -> Result<Option<Self>> {
+        let extension = pathbuf
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string());
+
+        match extension.as_deref() {
+            Some("json") => Ok(Option::from(Self::load(pathbuf)?)),
+            Some("yaml") => {
+                let content = std::fs::read_to_string(pathbuf)?;
+                Ok(Option::from(Self::from(
+                    ApplicationConfigurationGenerator::load(&content)?,
+                )))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Loads and merges files in order, returning the merged configuration along with every
+    /// kind of rule which a later file overrode
+    pub fn load_merged(paths: &[&PathBuf]) -> Result<(Self, Vec<AscConflict>)> {
+        let mut merged = Self(BTreeMap::new());
+        let mut conflicts = vec![];
+        // The file which last set each kind of rule for each application
+        let mut sources: HashMap<(String, &'static str), PathBuf> = HashMap::new();
+
+        for path in paths {
+            let path = resolve_home_path(path)?;
+            tracing::info!(
+                "loading application specific configuration from: {}",
+                path.display()
+            );
+
+            let Some(asc) = Self::load_file(&path)? else {
+                continue;
+            };
+
+            for (name, entry) in asc.0 {
+                let AscApplicationRulesOrSchema::AscApplicationRules(rules) = entry else {
+                    merged.insert(name, entry);
+                    continue;
+                };
+
+                if !matches!(
+                    merged.get(&name),
+                    Some(AscApplicationRulesOrSchema::AscApplicationRules(_))
+                ) {
+                    merged.insert(
+                        name.clone(),
+                        AscApplicationRulesOrSchema::AscApplicationRules(
+                            AscApplicationRules::default(),
+                        ),
+                    );
+                }
+
+                if let Some(AscApplicationRulesOrSchema::AscApplicationRules(existing)) =
+                    merged.get_mut(&name)
+                {
+                    for (kind, replaced) in existing.merge(rules) {
+                        let previous = sources.insert((name.clone(), kind), path.clone());
+
+                        if let (true, Some(overridden)) = (replaced, previous) {
+                            conflicts.push(AscConflict {
+                                application: name.clone(),
+                                rules: kind,
+                                overridden,
+                                winner: path.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((merged, conflicts))
+    }
 }
 
 /// Rules that determine how an application is handled
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct AscApplicationRules {
     /// Rules to ignore specific windows
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -74,6 +208,59 @@ pub struct AscApplicationRules {
     pub slow_application: Option<Vec<MatchingRule>>,
 }
 
+impl AscApplicationRules {
+    /// Overrides each kind of rule which is set in `other`, returning every kind which was set
+    /// along with whether it replaced different rules
+    fn merge(&mut self, other: Self) -> Vec<(&'static str, bool)> {
+        let mut merged = vec![];
+
+        macro_rules! merge_rules {
+            ($($kind:ident),+) => {$(
+                if let Some(rules) = other.$kind {
+                    let replaced = self.$kind.as_ref().is_some_and(|existing| *existing != rules);
+                    merged.push((stringify!($kind), replaced));
+                    self.$kind = Option::from(rules);
+                }
+            )+};
+        }
+
+        merge_rules!(
+            ignore,
+            manage,
+            floating,
+            transparency_ignore,
+            tray_and_multi_window,
+            layered,
+            object_name_change,
+            slow_application
+        );
+
+        merged
+    }
+
+    /// Adds every rule in `other` to the rules of the same kind
+    fn extend(&mut self, other: Self) {
+        macro_rules! extend_rules {
+            ($($kind:ident),+) => {$(
+                if let Some(rules) = other.$kind {
+                    self.$kind.get_or_insert_with(Vec::new).extend(rules);
+                }
+            )+};
+        }
+
+        extend_rules!(
+            ignore,
+            manage,
+            floating,
+            transparency_ignore,
+            tray_and_multi_window,
+            layered,
+            object_name_change,
+            slow_application
+        );
+    }
+}
+
 impl From<Vec<ApplicationConfiguration>> for ApplicationSpecificConfiguration {
     fn from(value: Vec<ApplicationConfiguration>) -> Self {
         let mut map = BTreeMap::new();
@@ -126,7 +313,19 @@ impl From<Vec<ApplicationConfiguration>> for ApplicationSpecificConfiguration {
                 || rules.object_name_change.is_some()
                 || rules.slow_application.is_some()
             {
-                map.insert(key, AscApplicationRulesOrSchema::AscApplicationRules(rules));
+                // Older configuration files can have several entries for the same application
+                match map.entry(key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(AscApplicationRulesOrSchema::AscApplicationRules(rules));
+                    }
+                    Entry::Occupied(mut entry) => {
+                        if let AscApplicationRulesOrSchema::AscApplicationRules(existing) =
+                            entry.get_mut()
+                        {
+                            existing.extend(rules);
+                        }
+                    }
+                }
             }
         }
 
@@ -59,12 +59,55 @@ pub enum MatchingRule {
     Composite(Vec<IdWithIdentifier>),
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct WorkspaceMatchingRule {
     pub monitor_index: usize,
     pub workspace_index: usize,
     pub matching_rule: MatchingRule,
     pub initial_only: bool,
+    /// Whether windows which have been moved manually are moved back by this rule when they send
+    /// events, as long as workspace rule enforcement is enabled globally
+    #[serde(default = "default_enforce_on_event")]
+    pub enforce_on_event: bool,
+    #[serde(default)]
+    pub provenance: RuleProvenance,
+}
+
+const fn default_enforce_on_event() -> bool {
+    true
+}
+
+// The same rule defined in more than one place is still the same rule
+impl PartialEq for WorkspaceMatchingRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.monitor_index == other.monitor_index
+            && self.workspace_index == other.workspace_index
+            && self.matching_rule == other.matching_rule
+            && self.initial_only == other.initial_only
+            && self.enforce_on_event == other.enforce_on_event
+    }
+}
+
+/// Where a rule was defined
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Display, JsonSchema,
+)]
+pub enum RuleProvenance {
+    /// Defined by komorebi itself
+    Builtin,
+    /// Defined in komorebi.json
+    #[default]
+    StaticConfig,
+    /// Defined in applications.json or applications.yaml
+    AppSpecificConfiguration,
+    /// Added by a command sent while komorebi is running
+    Runtime,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MatchingRuleWithProvenance {
+    pub matching_rule: MatchingRule,
+    pub provenance: RuleProvenance,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
@@ -8,26 +8,83 @@ use std::path::Path;
 use color_eyre::eyre::anyhow;
 use color_eyre::eyre::bail;
 use color_eyre::Result;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
 use super::Rect;
 
-#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
-pub struct CustomLayout(Vec<Column>);
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(from = "CustomLayoutDefinition", into = "CustomLayoutDefinition")]
+pub struct CustomLayout {
+    columns: Vec<Column>,
+    /// Interpret fixed column widths as DPI-independent units which are scaled to the monitor
+    dpi_aware: bool,
+}
+
+/// Custom layouts can either be a plain list of columns, or an object which also sets options
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum CustomLayoutDefinition {
+    Columns(Vec<Column>),
+    WithOptions {
+        columns: Vec<Column>,
+        #[serde(default)]
+        dpi_aware: bool,
+    },
+}
+
+impl From<CustomLayoutDefinition> for CustomLayout {
+    fn from(value: CustomLayoutDefinition) -> Self {
+        match value {
+            CustomLayoutDefinition::Columns(columns) => Self {
+                columns,
+                dpi_aware: false,
+            },
+            CustomLayoutDefinition::WithOptions { columns, dpi_aware } => {
+                Self { columns, dpi_aware }
+            }
+        }
+    }
+}
+
+impl From<CustomLayout> for CustomLayoutDefinition {
+    fn from(value: CustomLayout) -> Self {
+        // Layouts without options keep serializing in the original list format
+        if value.dpi_aware {
+            Self::WithOptions {
+                columns: value.columns,
+                dpi_aware: value.dpi_aware,
+            }
+        } else {
+            Self::Columns(value.columns)
+        }
+    }
+}
+
+impl JsonSchema for CustomLayout {
+    fn schema_name() -> String {
+        String::from("CustomLayout")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        CustomLayoutDefinition::json_schema(gen)
+    }
+}
 
 impl Deref for CustomLayout {
     type Target = Vec<Column>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.columns
     }
 }
 
 impl DerefMut for CustomLayout {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.columns
     }
 }
 
@@ -51,6 +108,32 @@ impl CustomLayout {
         Ok(layout)
     }
 
+    #[must_use]
+    pub const fn dpi_aware(&self) -> bool {
+        self.dpi_aware
+    }
+
+    /// Returns a copy of the layout with any fixed column widths converted from DPI-independent
+    /// units to physical pixels for a monitor with the given scale factor
+    #[must_use]
+    pub fn scaled(&self, scale_factor: f32) -> Self {
+        let mut scaled = self.clone();
+
+        if self.dpi_aware {
+            for column in scaled.iter_mut() {
+                if let Column::Primary(Option::Some(ColumnWidth::Width(width))) = column {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+                    let physical = (*width as f32 * scale_factor).round() as i32;
+                    *width = physical;
+                }
+            }
+
+            scaled.dpi_aware = false;
+        }
+
+        scaled
+    }
+
     #[must_use]
     pub fn column_with_idx(&self, idx: usize) -> (usize, Option<&Column>) {
         let column_idx = self.column_for_container_idx(idx);
@@ -69,6 +152,17 @@ impl CustomLayout {
         None
     }
 
+    #[must_use]
+    pub fn primary_width(&self) -> Option<ColumnWidth> {
+        for column in self.iter() {
+            if let Column::Primary(Option::Some(width)) = column {
+                return Option::from(*width);
+            }
+        }
+
+        None
+    }
+
     #[must_use]
     pub fn primary_width_percentage(&self) -> Option<f32> {
         for column in self.iter() {
@@ -89,6 +183,60 @@ impl CustomLayout {
         }
     }
 
+    /// The width of the primary column as a percentage of a work area of the given width, whether
+    /// it has been set as a percentage or as a fixed width
+    #[must_use]
+    pub fn primary_width_percentage_of(
+        &self,
+        work_area_width: i32,
+        scale_factor: f32,
+    ) -> Option<f32> {
+        match self.primary_width()? {
+            ColumnWidth::WidthPercentage(percentage) => Option::from(percentage),
+            ColumnWidth::Width(width) => {
+                #[allow(clippy::cast_precision_loss)]
+                let mut width = width as f32;
+                if self.dpi_aware {
+                    width *= scale_factor;
+                }
+
+                #[allow(clippy::cast_precision_loss)]
+                Option::from(width / work_area_width.max(1) as f32 * 100.0)
+            }
+        }
+    }
+
+    /// Sets the width of the primary column to a percentage of a work area of the given width,
+    /// keeping it as a fixed width if that is how it has been set
+    pub fn set_primary_width_percentage_of(
+        &mut self,
+        percentage: f32,
+        work_area_width: i32,
+        scale_factor: f32,
+    ) {
+        let dpi_aware = self.dpi_aware;
+
+        for column in self.iter_mut() {
+            match column {
+                Column::Primary(Option::Some(ColumnWidth::WidthPercentage(current))) => {
+                    *current = percentage;
+                }
+                Column::Primary(Option::Some(ColumnWidth::Width(current))) => {
+                    #[allow(clippy::cast_precision_loss)]
+                    let mut width = work_area_width as f32 * percentage / 100.0;
+                    if dpi_aware {
+                        width /= scale_factor;
+                    }
+
+                    #[allow(clippy::cast_possible_truncation)]
+                    let width = width.round() as i32;
+                    *current = width;
+                }
+                _ => {}
+            }
+        }
+    }
+
     #[must_use]
     pub fn is_valid(&self) -> bool {
         // A valid layout must have at least one column
@@ -261,6 +409,8 @@ pub enum Column {
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum ColumnWidth {
     WidthPercentage(f32),
+    /// A fixed width in pixels, or in DPI-independent units if the layout is `dpi_aware`
+    Width(i32),
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
@@ -31,10 +31,28 @@ pub enum DefaultLayout {
     UltrawideVerticalStack,
     Grid,
     RightMainVerticalStack,
+    Scrolling,
     // NOTE: If any new layout is added, please make sure to register the same in `DefaultLayout::cycle`
 }
 
 impl DefaultLayout {
+    /// The index of the container which occupies the given column, counting from the left before
+    /// any layout flip is applied, for layouts which are made up of columns
+    pub const fn column_slot(&self, column: usize, len: usize) -> Option<usize> {
+        match self {
+            Self::Columns if column < len => Some(column),
+            Self::VerticalStack if column < len && column < 2 => Some(column),
+            Self::UltrawideVerticalStack => match (column, len) {
+                (0, 1) => Some(0),
+                (0, _) => Some(1),
+                (1, 2..) => Some(0),
+                (2, 3..) => Some(2),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     pub fn leftmost_index(&self, len: usize) -> usize {
         match self {
             Self::UltrawideVerticalStack | Self::RightMainVerticalStack => match len {
@@ -46,7 +64,8 @@ impl DefaultLayout {
             | DefaultLayout::Rows
             | DefaultLayout::VerticalStack
             | DefaultLayout::HorizontalStack
-            | DefaultLayout::Grid => 0,
+            | DefaultLayout::Grid
+            | DefaultLayout::Scrolling => 0,
         }
     }
 
@@ -57,7 +76,8 @@ impl DefaultLayout {
             | DefaultLayout::Rows
             | DefaultLayout::VerticalStack
             | DefaultLayout::HorizontalStack
-            | DefaultLayout::Grid => len.saturating_sub(1),
+            | DefaultLayout::Grid
+            | DefaultLayout::Scrolling => len.saturating_sub(1),
             DefaultLayout::UltrawideVerticalStack => match len {
                 2 => 0,
                 _ => len.saturating_sub(1),
@@ -188,13 +208,15 @@ impl DefaultLayout {
             Self::HorizontalStack => Self::UltrawideVerticalStack,
             Self::UltrawideVerticalStack => Self::Grid,
             Self::Grid => Self::RightMainVerticalStack,
-            Self::RightMainVerticalStack => Self::BSP,
+            Self::RightMainVerticalStack => Self::Scrolling,
+            Self::Scrolling => Self::BSP,
         }
     }
 
     #[must_use]
     pub const fn cycle_previous(self) -> Self {
         match self {
+            Self::Scrolling => Self::RightMainVerticalStack,
             Self::RightMainVerticalStack => Self::Grid,
             Self::Grid => Self::UltrawideVerticalStack,
             Self::UltrawideVerticalStack => Self::HorizontalStack,
@@ -202,7 +224,7 @@ impl DefaultLayout {
             Self::VerticalStack => Self::Rows,
             Self::Rows => Self::Columns,
             Self::Columns => Self::BSP,
-            Self::BSP => Self::RightMainVerticalStack,
+            Self::BSP => Self::Scrolling,
         }
     }
 }
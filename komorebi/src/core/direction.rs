@@ -97,7 +97,7 @@ impl Direction for DefaultLayout {
         match op_direction {
             OperationDirection::Up => match self {
                 Self::BSP => idx != 0 && idx != 1,
-                Self::Columns => false,
+                Self::Columns | Self::Scrolling => false,
                 Self::Rows | Self::HorizontalStack => idx != 0,
                 Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != 1,
                 Self::UltrawideVerticalStack => idx > 2,
@@ -105,7 +105,7 @@ impl Direction for DefaultLayout {
             },
             OperationDirection::Down => match self {
                 Self::BSP => idx != count - 1 && idx % 2 != 0,
-                Self::Columns => false,
+                Self::Columns | Self::Scrolling => false,
                 Self::Rows => idx != count - 1,
                 Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != count - 1,
                 Self::HorizontalStack => idx == 0,
@@ -114,7 +114,7 @@ impl Direction for DefaultLayout {
             },
             OperationDirection::Left => match self {
                 Self::BSP => idx != 0,
-                Self::Columns | Self::VerticalStack => idx != 0,
+                Self::Columns | Self::VerticalStack | Self::Scrolling => idx != 0,
                 Self::RightMainVerticalStack => idx == 0,
                 Self::Rows => false,
                 Self::HorizontalStack => idx != 0 && idx != 1,
@@ -123,7 +123,7 @@ impl Direction for DefaultLayout {
             },
             OperationDirection::Right => match self {
                 Self::BSP => idx % 2 == 0 && idx != count - 1,
-                Self::Columns => idx != count - 1,
+                Self::Columns | Self::Scrolling => idx != count - 1,
                 Self::Rows => false,
                 Self::VerticalStack => idx == 0,
                 Self::RightMainVerticalStack => idx != 0,
@@ -151,7 +151,7 @@ impl Direction for DefaultLayout {
                     idx - 2
                 }
             }
-            Self::Columns => unreachable!(),
+            Self::Columns | Self::Scrolling => unreachable!(),
             Self::Rows
             | Self::VerticalStack
             | Self::UltrawideVerticalStack
@@ -173,7 +173,7 @@ impl Direction for DefaultLayout {
             | Self::VerticalStack
             | Self::UltrawideVerticalStack
             | Self::RightMainVerticalStack => idx + 1,
-            Self::Columns => unreachable!(),
+            Self::Columns | Self::Scrolling => unreachable!(),
             Self::HorizontalStack => 1,
             Self::Grid => grid_neighbor(op_direction, idx, count),
         }
@@ -193,7 +193,7 @@ impl Direction for DefaultLayout {
                     idx - 1
                 }
             }
-            Self::Columns | Self::HorizontalStack => idx - 1,
+            Self::Columns | Self::HorizontalStack | Self::Scrolling => idx - 1,
             Self::Rows => unreachable!(),
             Self::VerticalStack => 0,
             Self::RightMainVerticalStack => 1,
@@ -213,7 +213,7 @@ impl Direction for DefaultLayout {
         count: Option<usize>,
     ) -> usize {
         match self {
-            Self::BSP | Self::Columns | Self::HorizontalStack => idx + 1,
+            Self::BSP | Self::Columns | Self::HorizontalStack | Self::Scrolling => idx + 1,
             Self::Rows => unreachable!(),
             Self::VerticalStack => 1,
             Self::RightMainVerticalStack => 0,
@@ -13,19 +13,27 @@ use serde::Deserialize;
 use serde::Serialize;
 use strum::Display;
 use strum::EnumString;
+use strum::IntoStaticStr;
+use strum::VariantNames;
 
 use crate::animation::prefix::AnimationPrefix;
+use crate::border_manager::ZOrder;
+use crate::reserved_space_manager::ReservedSpaceEdge;
 use crate::KomorebiTheme;
 pub use animation::AnimationStyle;
 pub use arrangement::Arrangement;
 pub use arrangement::Axis;
+pub use arrangement::ScrollingCentering;
+pub use arrangement::ScrollingLayoutOptions;
 pub use custom_layout::CustomLayout;
 pub use cycle_direction::CycleDirection;
 pub use default_layout::DefaultLayout;
 pub use direction::Direction;
 pub use layout::Layout;
 pub use operation_direction::OperationDirection;
+pub use rect::PercentageRect;
 pub use rect::Rect;
+pub use rect::RectOrPercent;
 
 pub mod animation;
 pub mod arrangement;
@@ -37,9 +45,12 @@ pub mod default_layout;
 pub mod direction;
 pub mod layout;
 pub mod operation_direction;
+pub mod preview;
 pub mod rect;
 
-#[derive(Clone, Debug, Serialize, Deserialize, Display, JsonSchema)]
+#[derive(
+    Clone, Debug, Serialize, Deserialize, Display, IntoStaticStr, VariantNames, JsonSchema,
+)]
 #[serde(tag = "type", content = "content")]
 pub enum SocketMessage {
     // Window / Container Commands
@@ -47,6 +58,7 @@ pub enum SocketMessage {
     MoveWindow(OperationDirection),
     CycleFocusWindow(CycleDirection),
     CycleMoveWindow(CycleDirection),
+    FocusLastWindow,
     StackWindow(OperationDirection),
     UnstackWindow,
     CycleStack(CycleDirection),
@@ -54,16 +66,28 @@ pub enum SocketMessage {
     FocusStackWindow(usize),
     StackAll,
     UnstackAll,
+    PinContainerToColumn(usize),
+    UnpinContainer,
+    PushToScratchpad(String),
+    PopFromScratchpad(String),
+    ToggleScratchpad(String),
     ResizeWindowEdge(OperationDirection, Sizing),
+    ResizeWindowEdgePercent(OperationDirection, Sizing, f32),
     ResizeWindowAxis(Axis, Sizing),
+    CycleSplitRatio(Axis),
     MoveContainerToMonitorNumber(usize),
     CycleMoveContainerToMonitor(CycleDirection),
     MoveContainerToWorkspaceNumber(usize),
     MoveContainerToNamedWorkspace(String),
     CycleMoveContainerToWorkspace(CycleDirection),
     SendContainerToMonitorNumber(usize),
+    SendStackToMonitorNumber(usize),
     CycleSendContainerToMonitor(CycleDirection),
     SendContainerToWorkspaceNumber(usize),
+    MoveContainerToNewWorkspace,
+    ReturnContainerToPreviousWorkspace(bool),
+    Unswallow,
+    ToggleMonitorPause(usize),
     CycleSendContainerToWorkspace(CycleDirection),
     SendContainerToMonitorWorkspaceNumber(usize, usize),
     MoveContainerToMonitorWorkspaceNumber(usize, usize),
@@ -73,12 +97,21 @@ pub enum SocketMessage {
     SwapWorkspacesToMonitorNumber(usize),
     ForceFocus,
     Close,
+    CloseWorkspaceWindows,
+    CloseWindowsOnWorkspaceNumber(usize),
+    CloseWindowsOnNamedWorkspace(String),
     Minimize,
     Promote,
     PromoteFocus,
     PromoteWindow(OperationDirection),
+    SwapWithLargest,
+    MoveFloatingWindow(i32, i32),
+    PlaceFloatingWindow(PredefinedPlacement),
+    ResizeFloatingWindowTo(i32, i32),
     EagerFocus(String),
+    EagerFocusOrLaunch(String, String, Vec<String>),
     ToggleFloat,
+    ToggleAlwaysOnTop,
     ToggleMonocle,
     ToggleMaximize,
     ToggleWindowContainerBehaviour,
@@ -86,6 +119,7 @@ pub enum SocketMessage {
     WindowHidingBehaviour(HidingBehaviour),
     ToggleCrossMonitorMoveBehaviour,
     CrossMonitorMoveBehaviour(MoveBehaviour),
+    DragBehaviour(DragMode, Option<DragModifier>),
     UnmanagedWindowOperationBehaviour(OperationBehaviour),
     // Current Workspace Commands
     ManageFocusedWindow,
@@ -96,8 +130,11 @@ pub enum SocketMessage {
     CycleLayout(CycleDirection),
     ChangeLayoutCustom(PathBuf),
     FlipLayout(Axis),
+    ScrollingLayoutColumns(usize),
     ToggleWorkspaceWindowContainerBehaviour,
     ToggleWorkspaceFloatOverride,
+    WorkspaceLayer(WorkspaceLayer),
+    ToggleWorkspaceLayer,
     // Monitor and Workspace Commands
     MonitorIndexPreference(usize, i32, i32, i32, i32),
     DisplayIndexPreference(usize, String),
@@ -109,17 +146,22 @@ pub enum SocketMessage {
     StopIgnoreRestore,
     TogglePause,
     Retile,
+    GarbageCollect,
     RetileWithResizeDimensions,
     QuickSave,
     QuickLoad,
     Save(PathBuf),
+    SaveAll(PathBuf),
     Load(PathBuf),
+    SaveFloatingLayout(PathBuf),
+    LoadFloatingLayout(PathBuf),
     CycleFocusMonitor(CycleDirection),
     CycleFocusWorkspace(CycleDirection),
     FocusMonitorNumber(usize),
     FocusLastWorkspace,
     CloseWorkspace,
     FocusWorkspaceNumber(usize),
+    FocusWorkspaceNumberKeepFocus(usize),
     FocusWorkspaceNumbers(usize),
     FocusMonitorWorkspaceNumber(usize, usize),
     FocusNamedWorkspace(String),
@@ -129,9 +171,13 @@ pub enum SocketMessage {
     WorkspacePadding(usize, usize, i32),
     NamedWorkspacePadding(String, i32),
     FocusedWorkspacePadding(i32),
+    MonitorContainerPadding(usize, i32),
+    MonitorWorkspacePadding(usize, i32),
     WorkspaceTiling(usize, usize, bool),
     NamedWorkspaceTiling(String, bool),
     WorkspaceName(usize, usize, String),
+    WorkspaceWallpaper(usize, usize, PathBuf),
+    WorkspaceWindowHidingBehaviour(usize, usize, HidingBehaviour),
     WorkspaceLayout(usize, usize, DefaultLayout),
     NamedWorkspaceLayout(String, DefaultLayout),
     WorkspaceLayoutCustom(usize, usize, PathBuf),
@@ -145,7 +191,8 @@ pub enum SocketMessage {
     // Configuration
     ReloadConfiguration,
     ReplaceConfiguration(PathBuf),
-    ReloadStaticConfiguration(PathBuf),
+    ReloadStaticConfiguration(PathBuf, bool),
+    ResetInitialWorkspaceRules,
     WatchConfiguration(bool),
     CompleteConfiguration,
     AltFocusHack(bool),
@@ -158,16 +205,23 @@ pub enum SocketMessage {
     Border(bool),
     #[serde(alias = "ActiveWindowBorderColour")]
     BorderColour(WindowKind, u32, u32, u32),
+    BorderColourOverride(ApplicationIdentifier, String, u32, u32, u32),
     #[serde(alias = "ActiveWindowBorderStyle")]
     BorderStyle(BorderStyle),
     BorderWidth(i32),
     BorderOffset(i32),
+    BorderWidthForWindowKind(WindowKind, i32),
+    BorderOffsetForWindowKind(WindowKind, i32),
     BorderImplementation(BorderImplementation),
+    BorderHitTest(bool),
+    BorderZOrder(ZOrder),
     Transparency(bool),
     ToggleTransparency,
     TransparencyAlpha(u8),
+    TransparencyIgnoreMonitor(usize, bool),
     InvisibleBorders(Rect),
     StackbarMode(StackbarMode),
+    ToggleStackbar,
     StackbarLabel(StackbarLabel),
     StackbarFocusedTextColour(u32, u32, u32),
     StackbarUnfocusedTextColour(u32, u32, u32),
@@ -178,6 +232,10 @@ pub enum SocketMessage {
     StackbarFontFamily(Option<String>),
     WorkAreaOffset(Rect),
     MonitorWorkAreaOffset(usize, Rect),
+    /// Reserve space on the edge of the monitor a bar (by hwnd) is on, with the given thickness
+    RegisterAppBar(isize, ReservedSpaceEdge, i32),
+    UnregisterAppBar(isize),
+    MonitorWindowBasedWorkAreaOffsetCounting(usize, WindowBasedWorkAreaOffsetCounting),
     ResizeDelta(i32),
     InitialWorkspaceRule(ApplicationIdentifier, String, usize, usize),
     InitialNamedWorkspaceRule(ApplicationIdentifier, String, String),
@@ -187,23 +245,43 @@ pub enum SocketMessage {
     ClearNamedWorkspaceRules(String),
     ClearAllWorkspaceRules,
     EnforceWorkspaceRules,
+    EnforceWorkspaceRulesFor(
+        Option<ApplicationIdentifier>,
+        Option<String>,
+        Option<String>,
+    ),
+    ToggleWorkspaceRuleEnforcement,
+    SessionWorkspaceRule(ApplicationIdentifier, String, usize, usize),
+    FocusedWindowSessionWorkspaceRule(usize, usize),
+    SessionWorkspaceRules,
+    ClearSessionWorkspaceRules,
+    WorkspaceRules,
     #[serde(alias = "FloatRule")]
     IgnoreRule(ApplicationIdentifier, String),
     ManageRule(ApplicationIdentifier, String),
+    FloatOverrideRule(ApplicationIdentifier, String),
     IdentifyObjectNameChangeApplication(ApplicationIdentifier, String),
     IdentifyTrayApplication(ApplicationIdentifier, String),
     IdentifyLayeredApplication(ApplicationIdentifier, String),
     IdentifyBorderOverflowApplication(ApplicationIdentifier, String),
+    AnimationBlacklist(ApplicationIdentifier, String),
     State,
     GlobalState,
     VisibleWindows,
     MonitorInformation,
+    Snapshot,
+    Version,
+    LogTail(usize),
+    StateRecording(bool),
     Query(StateQuery),
     FocusFollowsMouse(FocusFollowsMouseImplementation, bool),
     ToggleFocusFollowsMouse(FocusFollowsMouseImplementation),
     MouseFollowsFocus(bool),
     ToggleMouseFollowsFocus,
+    MouseFollowsFocusScope(MouseFollowsFocusScope),
     RemoveTitleBar(ApplicationIdentifier, String),
+    RestoreTitleBar(ApplicationIdentifier, String),
+    TitleBarWhitelist,
     ToggleTitleBars,
     AddSubscriberSocket(String),
     AddSubscriberSocketWithOptions(String, SubscribeOptions),
@@ -216,12 +294,62 @@ pub enum SocketMessage {
     StaticConfigSchema,
     GenerateStaticConfig,
     DebugWindow(isize),
+    WhyIgnored(isize),
+    WhyIgnoredExe(String),
 }
 
 impl SocketMessage {
     pub fn as_bytes(&self) -> Result<Vec<u8>> {
         Ok(serde_json::to_string(self)?.as_bytes().to_vec())
     }
+
+    pub const fn is_subscription(&self) -> bool {
+        matches!(
+            self,
+            Self::AddSubscriberSocket(_)
+                | Self::AddSubscriberSocketWithOptions(_, _)
+                | Self::AddSubscriberPipe(_)
+        )
+    }
+
+    /// Whether handling this message changes the configuration reflected in `GlobalState`
+    pub const fn affects_global_state(&self) -> bool {
+        matches!(
+            self,
+            Self::Theme(_)
+                | Self::ReloadStaticConfiguration(_, _)
+                | Self::Animation(_, _)
+                | Self::AnimationDuration(_, _)
+                | Self::AnimationFps(_)
+                | Self::AnimationStyle(_, _)
+                | Self::Border(_)
+                | Self::BorderColour(_, _, _, _)
+                | Self::BorderColourOverride(_, _, _, _, _)
+                | Self::BorderStyle(_)
+                | Self::BorderWidth(_)
+                | Self::BorderOffset(_)
+                | Self::BorderWidthForWindowKind(_, _)
+                | Self::BorderOffsetForWindowKind(_, _)
+                | Self::BorderImplementation(_)
+                | Self::BorderHitTest(_)
+                | Self::BorderZOrder(_)
+                | Self::Transparency(_)
+                | Self::ToggleTransparency
+                | Self::TransparencyAlpha(_)
+                | Self::TransparencyIgnoreMonitor(_, _)
+                | Self::StackbarMode(_)
+                | Self::ToggleStackbar
+                | Self::StackbarLabel(_)
+                | Self::StackbarFocusedTextColour(_, _, _)
+                | Self::StackbarUnfocusedTextColour(_, _, _)
+                | Self::StackbarBackgroundColour(_, _, _)
+                | Self::StackbarHeight(_)
+                | Self::StackbarTabWidth(_)
+                | Self::StackbarFontSize(_)
+                | Self::StackbarFontFamily(_)
+                | Self::AnimationBlacklist(_, _)
+        )
+    }
 }
 
 impl FromStr for SocketMessage {
@@ -232,10 +360,14 @@ impl FromStr for SocketMessage {
     }
 }
 
-#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[derive(Default, Debug, Clone, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct SubscribeOptions {
     /// Only emit notifications when the window manager state has changed
     pub filter_state_changes: bool,
+    /// Only emit notifications for these event types, using the serialized names of the
+    /// WindowManagerEvent and SocketMessage variants (default: all event types)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event_types: Vec<String>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Display, Serialize, Deserialize, JsonSchema)]
@@ -318,6 +450,7 @@ pub enum WindowKind {
     Monocle,
     Unfocused,
     Floating,
+    Pinned,
 }
 
 #[derive(
@@ -328,6 +461,10 @@ pub enum StateQuery {
     FocusedWorkspaceIndex,
     FocusedContainerIndex,
     FocusedWindowIndex,
+    FocusedStackWindowCount,
+    FocusedStackWindowIndex,
+    FocusedWorkspaceLayer,
+    Version,
 }
 
 #[derive(
@@ -381,6 +518,53 @@ pub struct WindowManagementBehaviour {
     /// that can be later toggled to tiled, when false it will default to
     /// `current_behaviour` again.
     pub float_override: bool,
+    /// Where new containers are placed in the layout when `current_behaviour` is `Create`
+    #[serde(default)]
+    pub new_window_placement: NewWindowPlacement,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+    PartialEq,
+)]
+pub enum NewWindowPlacement {
+    /// Place new containers next to the focused container
+    #[default]
+    Adjacent,
+    /// Place new containers after the last container
+    End,
+    /// Place new containers next to the container occupying the largest area
+    Largest,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+    PartialEq,
+)]
+pub enum WindowBasedWorkAreaOffsetCounting {
+    /// Count each container once, regardless of how many windows it holds
+    #[default]
+    Containers,
+    /// Count every window in every container, so that stacks count towards the limit
+    VisibleWindows,
 }
 
 #[derive(
@@ -404,6 +588,27 @@ pub enum WindowContainerBehaviour {
     Append,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+)]
+pub enum MinimumSizePolicy {
+    /// Grow windows which cannot fit their tiles to their minimum size from the top-left corner
+    /// of the tile, overlapping neighbouring containers
+    Clamp,
+    /// Take the space windows need to reach their minimum size from neighbouring containers
+    /// along the same split, clamping only when the neighbours cannot give up enough space
+    Redistribute,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -425,6 +630,84 @@ pub enum MoveBehaviour {
     NoOp,
 }
 
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+)]
+pub enum LastWindowCloseBehaviour {
+    /// Stay on the workspace after its last window has been closed
+    #[default]
+    DoNothing,
+    /// Focus the workspace that was focused on the same monitor before the current one
+    FocusLastWorkspace,
+    /// Focus the most recently focused workspace on the same monitor which still has windows
+    FocusPreviousNonEmpty,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+)]
+pub enum DragMode {
+    /// Swap the dragged window container with the window container it is dropped on (or stack
+    /// them when the window container behaviour is Append)
+    #[default]
+    Swap,
+    /// Stack the dragged window on top of the window container it is dropped on
+    Stack,
+    /// Return the dragged window to its position in the layout when it is dropped
+    Disabled,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+)]
+pub enum DragModifier {
+    Alt,
+    Ctrl,
+    Shift,
+    Win,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DragBehaviour {
+    /// Determine what happens when a tiled window is dragged onto another tiled window (default: Swap)
+    pub mode: DragMode,
+    /// Modifier key which switches from swapping to stacking when held while a window is dropped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modifier: Option<DragModifier>,
+}
+
 #[derive(
     Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, JsonSchema,
 )]
@@ -436,7 +719,16 @@ pub enum CrossBoundaryBehaviour {
 }
 
 #[derive(
-    Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, JsonSchema,
+    Copy,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+    PartialEq,
 )]
 pub enum HidingBehaviour {
     /// Use the SW_HIDE flag to hide windows when switching workspaces (has issues with Electron apps)
@@ -447,6 +739,50 @@ pub enum HidingBehaviour {
     Cloak,
 }
 
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+    PartialEq,
+)]
+pub enum WorkspaceLayer {
+    /// Focus is on the tiled windows of the workspace
+    #[default]
+    Tiling,
+    /// Focus is on the floating windows of the workspace
+    Floating,
+}
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    ValueEnum,
+    JsonSchema,
+    PartialEq,
+)]
+pub enum MouseFollowsFocusScope {
+    /// Move the cursor to the focused window on every focus change
+    #[default]
+    Always,
+    /// Only move the cursor when focus moves to a different monitor
+    CrossMonitorOnly,
+    /// Never move the cursor when focus changes
+    Never,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -490,6 +826,139 @@ impl Sizing {
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, JsonSchema,
+)]
+pub enum PredefinedPlacement {
+    /// Center the window in the work area, preserving its size
+    Center,
+    /// Move the window to the top left corner of the work area, preserving its size
+    TopLeft,
+    /// Move the window to the top right corner of the work area, preserving its size
+    TopRight,
+    /// Move the window to the bottom left corner of the work area, preserving its size
+    BottomLeft,
+    /// Move the window to the bottom right corner of the work area, preserving its size
+    BottomRight,
+    /// Resize the window to fill the left half of the work area
+    LeftHalf,
+    /// Resize the window to fill the right half of the work area
+    RightHalf,
+    /// Resize the window to fill the top half of the work area
+    TopHalf,
+    /// Resize the window to fill the bottom half of the work area
+    BottomHalf,
+}
+
+impl PredefinedPlacement {
+    #[must_use]
+    pub const fn rect(&self, work_area: &Rect, window: &Rect) -> Rect {
+        let half_width = work_area.right / 2;
+        let half_height = work_area.bottom / 2;
+        let right_edge = work_area.left + work_area.right;
+        let bottom_edge = work_area.top + work_area.bottom;
+
+        match self {
+            Self::Center => Rect {
+                left: work_area.left + ((work_area.right - window.right) / 2),
+                top: work_area.top + ((work_area.bottom - window.bottom) / 2),
+                right: window.right,
+                bottom: window.bottom,
+            },
+            Self::TopLeft => Rect {
+                left: work_area.left,
+                top: work_area.top,
+                right: window.right,
+                bottom: window.bottom,
+            },
+            Self::TopRight => Rect {
+                left: right_edge - window.right,
+                top: work_area.top,
+                right: window.right,
+                bottom: window.bottom,
+            },
+            Self::BottomLeft => Rect {
+                left: work_area.left,
+                top: bottom_edge - window.bottom,
+                right: window.right,
+                bottom: window.bottom,
+            },
+            Self::BottomRight => Rect {
+                left: right_edge - window.right,
+                top: bottom_edge - window.bottom,
+                right: window.right,
+                bottom: window.bottom,
+            },
+            Self::LeftHalf => Rect {
+                left: work_area.left,
+                top: work_area.top,
+                right: half_width,
+                bottom: work_area.bottom,
+            },
+            Self::RightHalf => Rect {
+                left: right_edge - half_width,
+                top: work_area.top,
+                right: half_width,
+                bottom: work_area.bottom,
+            },
+            Self::TopHalf => Rect {
+                left: work_area.left,
+                top: work_area.top,
+                right: work_area.right,
+                bottom: half_height,
+            },
+            Self::BottomHalf => Rect {
+                left: work_area.left,
+                top: bottom_edge - half_height,
+                right: work_area.right,
+                bottom: half_height,
+            },
+        }
+    }
+}
+
+/// A width to height ratio, written as `width:height` (eg. `16:9`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AspectRatio {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl AspectRatio {
+    /// The height which preserves this ratio for the given width
+    #[must_use]
+    pub const fn height_for_width(&self, width: i32) -> i32 {
+        width * self.height / self.width
+    }
+}
+
+impl FromStr for AspectRatio {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (width, height) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("aspect ratios must be given as width:height, eg. 16:9"))?;
+
+        let width = width.trim().parse::<i32>()?;
+        let height = height.trim().parse::<i32>()?;
+
+        if width <= 0 || height <= 0 {
+            return Err(anyhow!(
+                "both sides of an aspect ratio must be greater than zero"
+            ));
+        }
+
+        Ok(Self { width, height })
+    }
+}
+
+impl std::fmt::Display for AspectRatio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.width, self.height)
+    }
+}
+
 pub fn resolve_home_path<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
     let mut resolved_path = PathBuf::new();
     let mut resolved = false;
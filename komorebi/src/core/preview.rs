@@ -0,0 +1,414 @@
+//! Offscreen previews of the default layouts, which do not need a live desktop
+
+use std::num::NonZeroUsize;
+
+use super::Arrangement;
+use super::Axis;
+use super::DefaultLayout;
+use super::Rect;
+
+/// Calculates the rects of the given number of containers arranged in an area by a default
+/// layout, exactly as they would be arranged on a workspace
+///
+/// Resize dimensions are matched to containers by index, and any containers without an entry are
+/// treated as not having been resized.
+pub fn calculate_rects(
+    layout: DefaultLayout,
+    containers: usize,
+    area: &Rect,
+    container_padding: Option<i32>,
+    layout_flip: Option<Axis>,
+    resize_dimensions: &[Option<Rect>],
+) -> Vec<Rect> {
+    let Some(len) = NonZeroUsize::new(containers) else {
+        return vec![];
+    };
+
+    let mut resize_dimensions = resize_dimensions.to_vec();
+    resize_dimensions.resize(containers, None);
+
+    layout.calculate(
+        area,
+        len,
+        container_padding,
+        layout_flip,
+        &resize_dimensions,
+        0,
+        &[],
+        None,
+    )
+}
+
+/// Renders the given number of containers arranged by a default layout as text, where each
+/// container is drawn as a box labelled with its index
+///
+/// The width and height are measured in characters, and every line of the output is trimmed of
+/// trailing whitespace.
+pub fn render_ascii(
+    layout: DefaultLayout,
+    containers: usize,
+    width: usize,
+    height: usize,
+) -> String {
+    let mut canvas = vec![vec![' '; width]; height];
+
+    let area = Rect {
+        left: 0,
+        top: 0,
+        right: i32::try_from(width).unwrap_or(i32::MAX),
+        bottom: i32::try_from(height).unwrap_or(i32::MAX),
+    };
+
+    for (idx, rect) in calculate_rects(layout, containers, &area, None, None, &[])
+        .iter()
+        .enumerate()
+    {
+        draw_rect(&mut canvas, rect, &idx.to_string());
+    }
+
+    canvas
+        .iter()
+        .map(|row| row.iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn draw_rect(canvas: &mut [Vec<char>], rect: &Rect, label: &str) {
+    let to_cell = |value: i32| usize::try_from(value).unwrap_or_default();
+
+    let left = to_cell(rect.left);
+    let top = to_cell(rect.top);
+    // Rects store their width and height in right and bottom, so these are the first cells past
+    // the edges of the box
+    let right = to_cell(rect.left + rect.right);
+    let bottom = to_cell(rect.top + rect.bottom);
+
+    if right <= left + 1 || bottom <= top + 1 {
+        return;
+    }
+
+    let (right, bottom) = (right - 1, bottom - 1);
+
+    for y in top..=bottom {
+        for x in left..=right {
+            let character = match (y == top || y == bottom, x == left || x == right) {
+                (true, true) => '+',
+                (true, false) => '-',
+                (false, true) => '|',
+                (false, false) => continue,
+            };
+
+            if let Some(cell) = canvas.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *cell = character;
+            }
+        }
+    }
+
+    // Labels are only drawn when they fit inside the box without touching its edges
+    let inner_width = right - left - 1;
+    if bottom - top < 2 || label.len() > inner_width {
+        return;
+    }
+
+    let y = top + (bottom - top) / 2;
+    let x = left + 1 + (inner_width - label.len()) / 2;
+
+    for (offset, character) in label.chars().enumerate() {
+        if let Some(cell) = canvas.get_mut(y).and_then(|row| row.get_mut(x + offset)) {
+            *cell = character;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORK_AREA: Rect = Rect {
+        left: 0,
+        top: 0,
+        right: 1920,
+        bottom: 1080,
+    };
+
+    /// Asserts the rects of 1 to 6 containers, given as (left, top, width, height)
+    fn assert_rects(layout: DefaultLayout, expected: [&[(i32, i32, i32, i32)]; 6]) {
+        for (idx, expected) in expected.iter().enumerate() {
+            let rects = calculate_rects(layout, idx + 1, &WORK_AREA, None, None, &[])
+                .iter()
+                .map(|rect| (rect.left, rect.top, rect.right, rect.bottom))
+                .collect::<Vec<_>>();
+
+            assert_eq!(rects, *expected, "{layout:?} with {} containers", idx + 1);
+        }
+    }
+
+    #[test]
+    fn no_containers() {
+        assert!(calculate_rects(DefaultLayout::BSP, 0, &WORK_AREA, None, None, &[]).is_empty());
+    }
+
+    #[test]
+    fn bsp() {
+        assert_rects(
+            DefaultLayout::BSP,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(0, 0, 960, 1080), (960, 0, 960, 1080)],
+                &[(0, 0, 960, 1080), (960, 0, 960, 540), (960, 540, 960, 540)],
+                &[
+                    (0, 0, 960, 1080),
+                    (960, 0, 960, 540),
+                    (960, 540, 480, 540),
+                    (1440, 540, 480, 540),
+                ],
+                &[
+                    (0, 0, 960, 1080),
+                    (960, 0, 960, 540),
+                    (960, 540, 480, 540),
+                    (1440, 540, 480, 270),
+                    (1440, 810, 480, 270),
+                ],
+                &[
+                    (0, 0, 960, 1080),
+                    (960, 0, 960, 540),
+                    (960, 540, 480, 540),
+                    (1440, 540, 480, 270),
+                    (1440, 810, 240, 270),
+                    (1680, 810, 240, 270),
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn columns() {
+        assert_rects(
+            DefaultLayout::Columns,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(0, 0, 960, 1080), (960, 0, 960, 1080)],
+                &[(0, 0, 640, 1080), (640, 0, 640, 1080), (1280, 0, 640, 1080)],
+                &[
+                    (0, 0, 480, 1080),
+                    (480, 0, 480, 1080),
+                    (960, 0, 480, 1080),
+                    (1440, 0, 480, 1080),
+                ],
+                &[
+                    (0, 0, 384, 1080),
+                    (384, 0, 384, 1080),
+                    (768, 0, 384, 1080),
+                    (1152, 0, 384, 1080),
+                    (1536, 0, 384, 1080),
+                ],
+                &[
+                    (0, 0, 320, 1080),
+                    (320, 0, 320, 1080),
+                    (640, 0, 320, 1080),
+                    (960, 0, 320, 1080),
+                    (1280, 0, 320, 1080),
+                    (1600, 0, 320, 1080),
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn rows() {
+        assert_rects(
+            DefaultLayout::Rows,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(0, 0, 1920, 540), (0, 540, 1920, 540)],
+                &[(0, 0, 1920, 360), (0, 360, 1920, 360), (0, 720, 1920, 360)],
+                &[
+                    (0, 0, 1920, 270),
+                    (0, 270, 1920, 270),
+                    (0, 540, 1920, 270),
+                    (0, 810, 1920, 270),
+                ],
+                &[
+                    (0, 0, 1920, 216),
+                    (0, 216, 1920, 216),
+                    (0, 432, 1920, 216),
+                    (0, 648, 1920, 216),
+                    (0, 864, 1920, 216),
+                ],
+                &[
+                    (0, 0, 1920, 180),
+                    (0, 180, 1920, 180),
+                    (0, 360, 1920, 180),
+                    (0, 540, 1920, 180),
+                    (0, 720, 1920, 180),
+                    (0, 900, 1920, 180),
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn vertical_stack() {
+        assert_rects(
+            DefaultLayout::VerticalStack,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(0, 0, 960, 1080), (960, 0, 960, 1080)],
+                &[(0, 0, 960, 1080), (960, 0, 960, 540), (960, 540, 960, 540)],
+                &[
+                    (0, 0, 960, 1080),
+                    (960, 0, 960, 360),
+                    (960, 360, 960, 360),
+                    (960, 720, 960, 360),
+                ],
+                &[
+                    (0, 0, 960, 1080),
+                    (960, 0, 960, 270),
+                    (960, 270, 960, 270),
+                    (960, 540, 960, 270),
+                    (960, 810, 960, 270),
+                ],
+                &[
+                    (0, 0, 960, 1080),
+                    (960, 0, 960, 216),
+                    (960, 216, 960, 216),
+                    (960, 432, 960, 216),
+                    (960, 648, 960, 216),
+                    (960, 864, 960, 216),
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn horizontal_stack() {
+        assert_rects(
+            DefaultLayout::HorizontalStack,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(0, 0, 1920, 540), (0, 540, 1920, 540)],
+                &[(0, 0, 1920, 540), (0, 540, 960, 540), (960, 540, 960, 540)],
+                &[
+                    (0, 0, 1920, 540),
+                    (0, 540, 640, 540),
+                    (640, 540, 640, 540),
+                    (1280, 540, 640, 540),
+                ],
+                &[
+                    (0, 0, 1920, 540),
+                    (0, 540, 480, 540),
+                    (480, 540, 480, 540),
+                    (960, 540, 480, 540),
+                    (1440, 540, 480, 540),
+                ],
+                &[
+                    (0, 0, 1920, 540),
+                    (0, 540, 384, 540),
+                    (384, 540, 384, 540),
+                    (768, 540, 384, 540),
+                    (1152, 540, 384, 540),
+                    (1536, 540, 384, 540),
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn ultrawide_vertical_stack() {
+        assert_rects(
+            DefaultLayout::UltrawideVerticalStack,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(960, 0, 960, 1080), (0, 0, 960, 1080)],
+                &[(480, 0, 960, 1080), (0, 0, 480, 1080), (1440, 0, 480, 1080)],
+                &[
+                    (480, 0, 960, 1080),
+                    (0, 0, 480, 1080),
+                    (1440, 0, 480, 540),
+                    (1440, 540, 480, 540),
+                ],
+                &[
+                    (480, 0, 960, 1080),
+                    (0, 0, 480, 1080),
+                    (1440, 0, 480, 360),
+                    (1440, 360, 480, 360),
+                    (1440, 720, 480, 360),
+                ],
+                &[
+                    (480, 0, 960, 1080),
+                    (0, 0, 480, 1080),
+                    (1440, 0, 480, 270),
+                    (1440, 270, 480, 270),
+                    (1440, 540, 480, 270),
+                    (1440, 810, 480, 270),
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn grid() {
+        assert_rects(
+            DefaultLayout::Grid,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(0, 0, 960, 1080), (960, 0, 960, 1080)],
+                &[(0, 0, 960, 1080), (960, 0, 960, 540), (960, 540, 960, 540)],
+                &[
+                    (0, 0, 960, 540),
+                    (0, 540, 960, 540),
+                    (960, 0, 960, 540),
+                    (960, 540, 960, 540),
+                ],
+                &[
+                    (0, 0, 640, 1080),
+                    (640, 0, 640, 540),
+                    (640, 540, 640, 540),
+                    (1280, 0, 640, 540),
+                    (1280, 540, 640, 540),
+                ],
+                &[
+                    (0, 0, 640, 540),
+                    (0, 540, 640, 540),
+                    (640, 0, 640, 540),
+                    (640, 540, 640, 540),
+                    (1280, 0, 640, 540),
+                    (1280, 540, 640, 540),
+                ],
+            ],
+        );
+    }
+
+    #[test]
+    fn right_main_vertical_stack() {
+        assert_rects(
+            DefaultLayout::RightMainVerticalStack,
+            [
+                &[(0, 0, 1920, 1080)],
+                &[(960, 0, 960, 1080), (0, 0, 960, 1080)],
+                &[(960, 0, 960, 1080), (0, 0, 960, 540), (0, 540, 960, 540)],
+                &[
+                    (960, 0, 960, 1080),
+                    (0, 0, 960, 360),
+                    (0, 360, 960, 360),
+                    (0, 720, 960, 360),
+                ],
+                &[
+                    (960, 0, 960, 1080),
+                    (0, 0, 960, 270),
+                    (0, 270, 960, 270),
+                    (0, 540, 960, 270),
+                    (0, 810, 960, 270),
+                ],
+                &[
+                    (960, 0, 960, 1080),
+                    (0, 0, 960, 216),
+                    (0, 216, 960, 216),
+                    (0, 432, 960, 216),
+                    (0, 648, 960, 216),
+                    (0, 864, 960, 216),
+                ],
+            ],
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::OperationDirection;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -81,6 +82,47 @@ impl Rect {
             && point.1 <= self.top + self.bottom
     }
 
+    /// the area of the intersection between self and rhs
+    #[must_use]
+    pub fn overlap_area(&self, rhs: &Self) -> i64 {
+        let width = (self.left + self.right).min(rhs.left + rhs.right) - self.left.max(rhs.left);
+        let height = (self.top + self.bottom).min(rhs.top + rhs.bottom) - self.top.max(rhs.top);
+
+        if width <= 0 || height <= 0 {
+            0
+        } else {
+            i64::from(width) * i64::from(height)
+        }
+    }
+
+    /// the squared distance between the centre points of self and rhs, as long as the centre
+    /// point of rhs lies in the given direction from the centre point of self
+    #[must_use]
+    pub fn distance_in_direction(&self, rhs: &Self, direction: OperationDirection) -> Option<i64> {
+        let origin = (
+            i64::from(self.left) + i64::from(self.right) / 2,
+            i64::from(self.top) + i64::from(self.bottom) / 2,
+        );
+
+        let target = (
+            i64::from(rhs.left) + i64::from(rhs.right) / 2,
+            i64::from(rhs.top) + i64::from(rhs.bottom) / 2,
+        );
+
+        let in_direction = match direction {
+            OperationDirection::Left => target.0 < origin.0,
+            OperationDirection::Right => target.0 > origin.0,
+            OperationDirection::Up => target.1 < origin.1,
+            OperationDirection::Down => target.1 > origin.1,
+        };
+
+        if in_direction {
+            Some((target.0 - origin.0).pow(2) + (target.1 - origin.1).pow(2))
+        } else {
+            None
+        }
+    }
+
     #[must_use]
     pub const fn scale(&self, system_dpi: i32, rect_dpi: i32) -> Rect {
         Rect {
@@ -101,3 +143,58 @@ impl Rect {
         }
     }
 }
+
+/// Offsets expressed as percentages of a monitor's dimensions
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct PercentageRect {
+    /// The left offset as a percentage of the monitor width
+    #[serde(default)]
+    pub left: f32,
+    /// The top offset as a percentage of the monitor height
+    #[serde(default)]
+    pub top: f32,
+    /// The right offset as a percentage of the monitor width
+    #[serde(default)]
+    pub right: f32,
+    /// The bottom offset as a percentage of the monitor height
+    #[serde(default)]
+    pub bottom: f32,
+}
+
+/// A Rect given either in absolute pixels or as percentages of the monitor size
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum RectOrPercent {
+    /// Absolute values in pixels
+    Rect(Rect),
+    /// Percentages of the monitor size
+    Percent { percent: PercentageRect },
+}
+
+impl From<Rect> for RectOrPercent {
+    fn from(rect: Rect) -> Self {
+        Self::Rect(rect)
+    }
+}
+
+impl RectOrPercent {
+    /// Resolve to an absolute Rect for a monitor of the given size
+    #[must_use]
+    pub fn resolve(&self, size: &Rect) -> Rect {
+        match self {
+            Self::Rect(rect) => *rect,
+            Self::Percent { percent } => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+                let scale =
+                    |value: f32, length: i32| (length as f32 * value / 100.0).round() as i32;
+
+                Rect {
+                    left: scale(percent.left, size.right),
+                    top: scale(percent.top, size.bottom),
+                    right: scale(percent.right, size.right),
+                    bottom: scale(percent.bottom, size.bottom),
+                }
+            }
+        }
+    }
+}
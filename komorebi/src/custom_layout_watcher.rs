@@ -0,0 +1,239 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::core::CustomLayout;
+use crate::core::Layout;
+use crate::workspace::Workspace;
+use crate::WindowManager;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use hotwatch::EventKind;
+use hotwatch::Hotwatch;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+pub enum Notification {
+    /// Reconcile the watched paths with the paths of the custom layouts currently in use
+    Refresh(HashSet<PathBuf>),
+    /// The custom layout file at this path has changed on disk
+    Changed(PathBuf),
+}
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+// The last valid layout loaded from each custom layout path
+static LAYOUTS: OnceLock<Mutex<HashMap<PathBuf, CustomLayout>>> = OnceLock::new();
+
+// The custom layout paths in use as of the last refresh
+static IN_USE: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+fn layouts() -> &'static Mutex<HashMap<PathBuf, CustomLayout>> {
+    LAYOUTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn in_use() -> &'static Mutex<HashSet<PathBuf>> {
+    IN_USE.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+pub fn send_notification(notification: Notification) {
+    if event_tx().try_send(notification).is_err() {
+        tracing::warn!("channel is full; dropping notification")
+    }
+}
+
+/// Records the layout that was loaded from a custom layout path so that changes to the file
+/// which do not affect the layout can be ignored
+pub fn register<P: AsRef<Path>>(path: P, layout: &CustomLayout) {
+    layouts()
+        .lock()
+        .insert(path.as_ref().to_path_buf(), layout.clone());
+}
+
+/// Sends a refresh notification if the custom layout paths in use have changed since the last
+/// refresh
+pub fn refresh(wm: &WindowManager) {
+    let paths = paths_in_use(wm);
+
+    let mut in_use = in_use().lock();
+    if *in_use != paths {
+        *in_use = paths.clone();
+        send_notification(Notification::Refresh(paths));
+    }
+}
+
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_notifications(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+    event_tx().send(Notification::Refresh(paths_in_use(&wm.lock())))?;
+
+    let mut hotwatch = Hotwatch::new()?;
+    let mut watched: HashSet<PathBuf> = HashSet::new();
+
+    for notification in receiver {
+        match notification {
+            Notification::Refresh(paths) => {
+                // Forget any paths which are no longer referenced by a workspace
+                layouts().lock().retain(|path, _| paths.contains(path));
+
+                for path in watched.clone() {
+                    if !paths.contains(&path) {
+                        tracing::info!("no longer watching custom layout: {}", path.display());
+                        hotwatch.unwatch(&path)?;
+                        watched.remove(&path);
+                    }
+                }
+
+                for path in &paths {
+                    if watched.contains(path) {
+                        continue;
+                    }
+
+                    tracing::info!("watching custom layout for changes: {}", path.display());
+
+                    let changed = path.clone();
+                    hotwatch.watch(path, move |event| match event.kind {
+                        // Editing in Notepad sends a NoticeWrite while editing in (Neo)Vim sends
+                        // a NoticeRemove, presumably because of the use of swap files?
+                        EventKind::Modify(_) | EventKind::Remove(_) => {
+                            send_notification(Notification::Changed(changed.clone()));
+                        }
+                        _ => {}
+                    })?;
+
+                    watched.insert(path.clone());
+                }
+            }
+            Notification::Changed(path) => {
+                let updated = match CustomLayout::from_path(&path) {
+                    Ok(layout) => layout,
+                    Err(error) => {
+                        tracing::error!(
+                            "keeping the previous layout, could not reload {}: {error}",
+                            path.display()
+                        );
+                        continue;
+                    }
+                };
+
+                let unchanged = layouts()
+                    .lock()
+                    .insert(path.clone(), updated.clone())
+                    .is_some_and(|previous| previous == updated);
+
+                if unchanged {
+                    continue;
+                }
+
+                tracing::info!("reloading custom layout: {}", path.display());
+
+                let mut wm = wm.lock();
+                let offset = wm.work_area_offset;
+
+                for monitor in wm.monitors_mut() {
+                    let focused_workspace_idx = monitor.focused_workspace_idx();
+                    let mut should_update = false;
+
+                    for (workspace_idx, workspace) in
+                        monitor.workspaces_mut().iter_mut().enumerate()
+                    {
+                        let mut replaced = false;
+
+                        let loaded_for = workspace
+                            .custom_layout_paths()
+                            .iter()
+                            .filter(|(_, loaded_from)| **loaded_from == path)
+                            .map(|(container_count, _)| *container_count)
+                            .collect::<Vec<_>>();
+
+                        for container_count in loaded_for {
+                            match container_count {
+                                None => {
+                                    if let Layout::Custom(layout) = workspace.layout_mut() {
+                                        *layout = updated.clone();
+                                        replaced = true;
+                                    }
+                                }
+                                Some(container_count) => {
+                                    for (count, layout) in workspace.layout_rules_mut() {
+                                        if *count == container_count {
+                                            if let Layout::Custom(rule) = layout {
+                                                *rule = updated.clone();
+                                                replaced = true;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        if replaced && workspace_idx == focused_workspace_idx {
+                            should_update = true;
+                        }
+                    }
+
+                    // Workspaces which are not visible will be laid out when they are next focused
+                    if should_update {
+                        monitor.update_focused_workspace(offset)?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn paths_in_use(wm: &WindowManager) -> HashSet<PathBuf> {
+    wm.monitors()
+        .iter()
+        .flat_map(|monitor| monitor.workspaces())
+        .flat_map(|workspace| {
+            workspace
+                .custom_layout_paths()
+                .iter()
+                .filter(|(container_count, _)| is_custom(workspace, **container_count))
+                .map(|(_, path)| path.clone())
+        })
+        .collect()
+}
+
+// A recorded path is stale once its layout or layout rule has been replaced by a default layout
+fn is_custom(workspace: &Workspace, container_count: Option<usize>) -> bool {
+    match container_count {
+        None => matches!(workspace.layout(), Layout::Custom(_)),
+        Some(container_count) => workspace
+            .layout_rules()
+            .iter()
+            .any(|(count, rule)| *count == container_count && matches!(rule, Layout::Custom(_))),
+    }
+}
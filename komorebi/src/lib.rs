@@ -8,22 +8,30 @@ pub mod ring;
 pub mod colour;
 pub mod container;
 pub mod core;
+pub mod custom_layout_watcher;
 pub mod focus_manager;
 pub mod monitor;
 pub mod monitor_reconciliator;
+pub mod presentation_manager;
 pub mod process_command;
 pub mod process_event;
 pub mod process_movement;
 pub mod reaper;
+pub mod reserved_space_manager;
 pub mod set_window_position;
 pub mod stackbar_manager;
+pub mod state_recorder;
 pub mod static_config;
 pub mod styles;
 pub mod theme_manager;
 pub mod transparency_manager;
+pub mod wallpaper_manager;
+pub mod watchdog;
+pub mod whkdrc;
 pub mod window;
 pub mod window_manager;
 pub mod window_manager_event;
+pub mod window_system;
 pub mod windows_api;
 pub mod windows_callbacks;
 pub mod winevent;
@@ -36,7 +44,6 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fs::File;
 use std::io::Write;
-use std::net::TcpStream;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::atomic::AtomicBool;
@@ -59,8 +66,14 @@ pub use windows_api::*;
 
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingRuleWithProvenance;
 use crate::core::config_generation::MatchingStrategy;
+use crate::core::config_generation::RuleProvenance;
 use crate::core::config_generation::WorkspaceMatchingRule;
+use crate::process_command::TcpConnection;
+use crate::styles::WindowStyle;
+use crate::watchdog::PanicNotification;
+use crate::workspace::TileOrigin;
 use color_eyre::Result;
 use os_info::Version;
 use parking_lot::Mutex;
@@ -68,13 +81,31 @@ use regex::Regex;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::VariantNames;
 use uds_windows::UnixStream;
 use which::which;
 use winreg::enums::HKEY_CURRENT_USER;
 use winreg::RegKey;
 
+shadow_rs::shadow!(build);
+
+/// Incremented whenever a change is made to the serialization of [`State`] or [`Notification`]
+/// which could break consumers of either
+pub const SCHEMA_VERSION: u32 = 1;
+
 lazy_static! {
     static ref HIDDEN_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
+    // Hidden windows whose taskbar buttons have been removed, which need to be added back when
+    // the windows are restored
+    static ref TASKBAR_HIDDEN_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
+    static ref AUTO_STACK_EXEMPT_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
+    // Where windows which have been floated or unmanaged were tiled, so that they can be put back
+    static ref TILE_ORIGINS: Arc<Mutex<HashMap<isize, TileOrigin>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    // The minimum size of each window which has been queried for a minimum size policy, which
+    // is refreshed whenever the window is moved or resized
+    static ref MINIMUM_SIZES: Arc<Mutex<HashMap<isize, (i32, i32)>>> =
+        Arc::new(Mutex::new(HashMap::new()));
     static ref LAYERED_WHITELIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
@@ -132,31 +163,38 @@ lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
     static ref DISPLAY_INDEX_PREFERENCES: Arc<Mutex<HashMap<usize, String>>> =
         Arc::new(Mutex::new(HashMap::new()));
+    // Serial numbers which are reported by more than one of the attached monitors, and which
+    // therefore cannot be used to tell those monitors apart
+    static ref DUPLICATE_MONITOR_SERIAL_IDS: Arc<Mutex<Vec<String>>> =
+        Arc::new(Mutex::new(Vec::new()));
     static ref WORKSPACE_MATCHING_RULES: Arc<Mutex<Vec<WorkspaceMatchingRule>>> =
         Arc::new(Mutex::new(Vec::new()));
+    static ref SESSION_WORKSPACE_RULES: Arc<Mutex<Vec<WorkspaceMatchingRule>>> =
+        Arc::new(Mutex::new(Vec::new()));
     static ref REGEX_IDENTIFIERS: Arc<Mutex<HashMap<String, Regex>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref MANAGE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
-    static ref IGNORE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
-        // mstsc.exe creates these on Windows 11 when a WSL process is launched
-        // https://github.com/LGUG2Z/komorebi/issues/74
-        MatchingRule::Simple(IdWithIdentifier {
-            kind: ApplicationIdentifier::Class,
-            id: String::from("OPContainerClass"),
-            matching_strategy: Option::from(MatchingStrategy::Equals),
-        }),
-        MatchingRule::Simple(IdWithIdentifier {
-            kind: ApplicationIdentifier::Class,
-            id: String::from("IHWindowClass"),
-            matching_strategy: Option::from(MatchingStrategy::Equals),
-        }),
-        MatchingRule::Simple(IdWithIdentifier {
-            kind: ApplicationIdentifier::Exe,
-            id: String::from("komorebi-bar.exe"),
-            matching_strategy: Option::from(MatchingStrategy::Equals),
-        })
-    ]));
+    static ref IGNORE_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> =
+        Arc::new(Mutex::new(builtin_ignore_identifiers()));
+    // Where each manage and ignore rule was first defined, recorded as the rules are added
+    static ref MANAGE_IDENTIFIERS_PROVENANCE: Arc<Mutex<Vec<MatchingRuleWithProvenance>>> =
+        Arc::new(Mutex::new(vec![]));
+    static ref IGNORE_IDENTIFIERS_PROVENANCE: Arc<Mutex<Vec<MatchingRuleWithProvenance>>> =
+        Arc::new(Mutex::new(
+            builtin_ignore_identifiers()
+                .into_iter()
+                .map(|matching_rule| MatchingRuleWithProvenance {
+                    matching_rule,
+                    provenance: RuleProvenance::Builtin,
+                })
+                .collect()
+        ));
     static ref FLOATING_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref FLOAT_OVERRIDE_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref SWALLOWING_RULES: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref AUTO_STACK_GROUPS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref FULLSCREEN_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref SPLIT_RATIO_PRESETS: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![0.5, 0.67, 0.33]));
     static ref PERMAIGNORE_CLASSES: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![
         "Chrome_RenderWidgetHostHWND".to_string(),
     ]));
@@ -171,16 +209,22 @@ lazy_static! {
             matching_strategy: Option::from(MatchingStrategy::Equals),
         }),
     ]));
+    pub static ref FOCUS_FOLLOWS_MOUSE_IGNORE: Arc<Mutex<Vec<MatchingRule>>> =
+        Arc::new(Mutex::new(vec![]));
     static ref SUBSCRIPTION_PIPES: Arc<Mutex<HashMap<String, File>>> =
         Arc::new(Mutex::new(HashMap::new()));
     pub static ref SUBSCRIPTION_SOCKETS: Arc<Mutex<HashMap<String, PathBuf>>> =
         Arc::new(Mutex::new(HashMap::new()));
     pub static ref SUBSCRIPTION_SOCKET_OPTIONS: Arc<Mutex<HashMap<String, SubscribeOptions>>> =
         Arc::new(Mutex::new(HashMap::new()));
-    static ref TCP_CONNECTIONS: Arc<Mutex<HashMap<String, TcpStream>>> =
+    static ref TCP_CONNECTIONS: Arc<Mutex<HashMap<String, TcpConnection>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref HIDING_BEHAVIOUR: Arc<Mutex<HidingBehaviour>> =
         Arc::new(Mutex::new(HidingBehaviour::Minimize));
+    static ref MOUSE_FOLLOWS_FOCUS_SCOPE: Arc<Mutex<MouseFollowsFocusScope>> =
+        Arc::new(Mutex::new(MouseFollowsFocusScope::Always));
+    static ref MINIMUM_SIZE_POLICY: Arc<Mutex<Option<MinimumSizePolicy>>> =
+        Arc::new(Mutex::new(None));
     pub static ref HOME_DIR: PathBuf = {
         std::env::var("KOMOREBI_CONFIG_HOME").map_or_else(|_| dirs::home_dir().expect("there is no home directory"), |home_path| {
             let home = PathBuf::from(&home_path);
@@ -216,11 +260,35 @@ lazy_static! {
     // Use app-specific titlebar removal options where possible
     // eg. Windows Terminal, IntelliJ IDEA, Firefox
     static ref NO_TITLEBAR: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
+    // The caption and frame styles of windows from before their title bars were removed
+    static ref TITLEBAR_ORIGINAL_STYLES: Arc<Mutex<HashMap<isize, WindowStyle>>> =
+        Arc::new(Mutex::new(HashMap::new()));
 
-    static ref WINDOWS_BY_BAR_HWNDS: Arc<Mutex<HashMap<isize, VecDeque<isize>>>> =
+    // Windows which have been pinned above all others, and which are never hidden when switching
+    // workspaces
+    pub static ref PINNED_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
+
+    // Windows which have been pushed into named scratchpads, and which are only shown on the
+    // floating layer of a workspace when they are popped from their scratchpad
+    pub static ref SCRATCHPADS: Arc<Mutex<HashMap<String, Vec<isize>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    // Scratchpad windows which are currently hidden, and the behaviour they were hidden with so
+    // that they can be shown again in the same way
+    pub static ref HIDDEN_SCRATCHPAD_HWNDS: Arc<Mutex<HashMap<isize, HidingBehaviour>>> =
         Arc::new(Mutex::new(HashMap::new()));
+    pub static ref SCRATCHPAD_ASPECT_RATIO: Arc<Mutex<Option<AspectRatio>>> =
+        Arc::new(Mutex::new(None));
+
+    // Managed windows which have stopped responding, and which are skipped when targeting focus
+    pub static ref HUNG_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
+
+    // The most recent log lines, kept in memory so that they can be queried over the socket
+    pub static ref LOG_BUFFER: Arc<Mutex<VecDeque<String>>> =
+        Arc::new(Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY)));
 }
 
+pub const LOG_BUFFER_CAPACITY: usize = 2000;
+
 pub static DEFAULT_WORKSPACE_PADDING: AtomicI32 = AtomicI32::new(10);
 pub static DEFAULT_CONTAINER_PADDING: AtomicI32 = AtomicI32::new(10);
 
@@ -229,8 +297,131 @@ pub static CUSTOM_FFM: AtomicBool = AtomicBool::new(false);
 pub static SESSION_ID: AtomicU32 = AtomicU32::new(0);
 
 pub static REMOVE_TITLEBARS: AtomicBool = AtomicBool::new(false);
+pub static WORKSPACE_RULE_ENFORCEMENT: AtomicBool = AtomicBool::new(true);
+pub static CROSS_LAYER_FOCUS: AtomicBool = AtomicBool::new(false);
+pub static CYCLE_STACK_WRAP: AtomicBool = AtomicBool::new(true);
+pub static AUTO_STACK_BY_EXE: AtomicBool = AtomicBool::new(false);
+pub static SCRATCHPAD_WIDTH_PERCENTAGE: AtomicU32 = AtomicU32::new(50);
+pub static HIDE_TASKBAR_ENTRIES_FOR_HIDDEN_WORKSPACES: AtomicBool = AtomicBool::new(false);
+pub static AUTO_WORKSPACE_NAMES: AtomicBool = AtomicBool::new(false);
 
 pub static SLOW_APPLICATION_COMPENSATION_TIME: AtomicU64 = AtomicU64::new(20);
+pub static FOCUS_FOLLOWS_MOUSE_DELAY_MS: AtomicU64 = AtomicU64::new(0);
+
+/// A tracing writer which tees formatted log lines into [`LOG_BUFFER`], discarding the oldest
+/// lines once [`LOG_BUFFER_CAPACITY`] has been reached
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogBufferWriter;
+
+impl Write for LogBufferWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        let mut log_buffer = LOG_BUFFER.lock();
+
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            if log_buffer.len() >= LOG_BUFFER_CAPACITY {
+                log_buffer.pop_front();
+            }
+
+            log_buffer.push_back(line.to_string());
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn builtin_ignore_identifiers() -> Vec<MatchingRule> {
+    vec![
+        // mstsc.exe creates these on Windows 11 when a WSL process is launched
+        // https://github.com/LGUG2Z/komorebi/issues/74
+        MatchingRule::Simple(IdWithIdentifier {
+            kind: ApplicationIdentifier::Class,
+            id: String::from("OPContainerClass"),
+            matching_strategy: Option::from(MatchingStrategy::Equals),
+        }),
+        MatchingRule::Simple(IdWithIdentifier {
+            kind: ApplicationIdentifier::Class,
+            id: String::from("IHWindowClass"),
+            matching_strategy: Option::from(MatchingStrategy::Equals),
+        }),
+        MatchingRule::Simple(IdWithIdentifier {
+            kind: ApplicationIdentifier::Exe,
+            id: String::from("komorebi-bar.exe"),
+            matching_strategy: Option::from(MatchingStrategy::Equals),
+        }),
+    ]
+}
+
+/// Records the given provenance for any of the rules which do not have one recorded yet, so
+/// that a rule which is defined in more than one place is attributed to where it was first added
+pub fn record_rule_provenance(
+    rules: &[MatchingRule],
+    registry: &mut Vec<MatchingRuleWithProvenance>,
+    provenance: RuleProvenance,
+) {
+    for rule in rules {
+        if !registry.iter().any(|r| r.matching_rule == *rule) {
+            registry.push(MatchingRuleWithProvenance {
+                matching_rule: rule.clone(),
+                provenance,
+            });
+        }
+    }
+}
+
+/// Pairs each rule with its recorded provenance, treating any unrecorded rule as a runtime rule
+fn with_provenance(
+    rules: &[MatchingRule],
+    registry: &[MatchingRuleWithProvenance],
+) -> Vec<MatchingRuleWithProvenance> {
+    rules
+        .iter()
+        .map(|rule| MatchingRuleWithProvenance {
+            matching_rule: rule.clone(),
+            provenance: registry
+                .iter()
+                .find(|r| r.matching_rule == *rule)
+                .map_or(RuleProvenance::Runtime, |r| r.provenance),
+        })
+        .collect()
+}
+
+/// The workspace, manage and ignore rules currently in effect, and where each was defined
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActiveRules {
+    pub workspace_rules: Vec<WorkspaceMatchingRule>,
+    pub session_workspace_rules: Vec<WorkspaceMatchingRule>,
+    pub manage_rules: Vec<MatchingRuleWithProvenance>,
+    pub ignore_rules: Vec<MatchingRuleWithProvenance>,
+}
+
+impl ActiveRules {
+    pub fn current() -> Self {
+        Self {
+            workspace_rules: WORKSPACE_MATCHING_RULES.lock().clone(),
+            session_workspace_rules: SESSION_WORKSPACE_RULES.lock().clone(),
+            manage_rules: with_provenance(
+                &MANAGE_IDENTIFIERS.lock(),
+                &MANAGE_IDENTIFIERS_PROVENANCE.lock(),
+            ),
+            ignore_rules: with_provenance(
+                &IGNORE_IDENTIFIERS.lock(),
+                &IGNORE_IDENTIFIERS_PROVENANCE.lock(),
+            ),
+        }
+    }
+}
+
+/// Returns up to the last `count` lines recorded in [`LOG_BUFFER`], oldest first
+pub fn log_tail(count: usize) -> Vec<String> {
+    let log_buffer = LOG_BUFFER.lock();
+    let skip = log_buffer.len().saturating_sub(count);
+    log_buffer.iter().skip(skip).cloned().collect()
+}
 
 #[must_use]
 pub fn current_virtual_desktop() -> Option<Vec<u8>> {
@@ -281,46 +472,115 @@ pub fn current_virtual_desktop() -> Option<Vec<u8>> {
 pub enum NotificationEvent {
     WindowManager(WindowManagerEvent),
     Socket(SocketMessage),
+    Panic(PanicNotification),
+}
+
+impl NotificationEvent {
+    /// The serialized name of the event type, which can be used in SubscribeOptions::event_types
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::WindowManager(event) => event.into(),
+            Self::Socket(message) => message.into(),
+            Self::Panic(_) => "Panic",
+        }
+    }
+
+    pub fn is_known_event_type(event_type: &str) -> bool {
+        WindowManagerEvent::VARIANTS.contains(&event_type)
+            || SocketMessage::VARIANTS.contains(&event_type)
+            || event_type == "Panic"
+    }
+}
+
+/// Version information for the running komorebi process, used by clients to check that they
+/// understand the state and notifications that it sends
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ServerVersion {
+    /// The semver of the running komorebi process
+    pub version: String,
+    /// The commit hash that the running komorebi process was built from
+    pub commit: String,
+    /// The version of the state and notification schema
+    pub schema_version: u32,
+}
+
+impl ServerVersion {
+    pub fn current() -> Self {
+        Self {
+            version: build::PKG_VERSION.to_string(),
+            commit: build::SHORT_COMMIT.to_string(),
+            schema_version: SCHEMA_VERSION,
+        }
+    }
+
+    /// The major component of the semver, or None if it could not be parsed
+    pub fn major(&self) -> Option<u64> {
+        self.version.split('.').next()?.parse().ok()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Notification {
     pub event: NotificationEvent,
     pub state: State,
+    /// The global state after events which change the configuration, so that subscribers don't
+    /// need to query it separately
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_state: Option<GlobalState>,
 }
 
 pub fn notify_subscribers(notification: Notification, state_has_been_modified: bool) -> Result<()> {
-    let is_override_event = matches!(
-        notification.event,
-        NotificationEvent::Socket(SocketMessage::AddSubscriberSocket(_))
-            | NotificationEvent::Socket(SocketMessage::AddSubscriberSocketWithOptions(_, _))
-            | NotificationEvent::Socket(SocketMessage::Theme(_))
-            | NotificationEvent::Socket(SocketMessage::ReloadStaticConfiguration(_))
-            | NotificationEvent::WindowManager(WindowManagerEvent::TitleUpdate(_, _))
-            | NotificationEvent::WindowManager(WindowManagerEvent::Show(_, _))
-    );
+    let is_override_event = notification.global_state.is_some()
+        || matches!(
+            notification.event,
+            NotificationEvent::Socket(SocketMessage::Theme(_))
+                | NotificationEvent::Socket(SocketMessage::ReloadStaticConfiguration(_, _))
+                | NotificationEvent::WindowManager(WindowManagerEvent::TitleUpdate(_, _))
+                | NotificationEvent::WindowManager(WindowManagerEvent::Show(_, _))
+        );
 
-    let notification = &serde_json::to_string(&notification)?;
-    let mut stale_sockets = vec![];
+    let event_type = notification.event.event_type();
     let mut sockets = SUBSCRIPTION_SOCKETS.lock();
     let options = SUBSCRIPTION_SOCKET_OPTIONS.lock();
 
-    for (socket, path) in &mut *sockets {
-        let apply_state_filter = (*options)
-            .get(socket)
-            .copied()
-            .unwrap_or_default()
-            .filter_state_changes;
-
-        if !apply_state_filter || state_has_been_modified || is_override_event {
-            match UnixStream::connect(path) {
-                Ok(mut stream) => {
-                    tracing::debug!("pushed notification to subscriber: {socket}");
-                    stream.write_all(notification.as_bytes())?;
-                }
-                Err(_) => {
-                    stale_sockets.push(socket.clone());
-                }
+    // Filter before serializing so that events which no subscriber is interested in are cheap
+    let subscribed_sockets = sockets
+        .iter()
+        .filter(|(socket, _)| {
+            let Some(options) = options.get(*socket) else {
+                return true;
+            };
+
+            let passes_state_filter =
+                !options.filter_state_changes || state_has_been_modified || is_override_event;
+
+            let passes_event_filter = options.event_types.is_empty()
+                || options.event_types.iter().any(|e| e == event_type);
+
+            passes_state_filter && passes_event_filter
+        })
+        .map(|(socket, path)| (socket.clone(), path.clone()))
+        .collect::<Vec<_>>();
+
+    let mut pipes = SUBSCRIPTION_PIPES.lock();
+    let mut tcp_connections = TCP_CONNECTIONS.lock();
+    let has_framed_tcp_connections = tcp_connections.values().any(TcpConnection::is_framed);
+
+    if subscribed_sockets.is_empty() && pipes.is_empty() && !has_framed_tcp_connections {
+        return Ok(());
+    }
+
+    let notification = &serde_json::to_string(&notification)?;
+    let mut stale_sockets = vec![];
+
+    for (socket, path) in &subscribed_sockets {
+        match UnixStream::connect(path) {
+            Ok(mut stream) => {
+                tracing::debug!("pushed notification to subscriber: {socket}");
+                stream.write_all(notification.as_bytes())?;
+            }
+            Err(_) => {
+                stale_sockets.push(socket.clone());
             }
         }
     }
@@ -338,7 +598,6 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
     }
 
     let mut stale_pipes = vec![];
-    let mut pipes = SUBSCRIPTION_PIPES.lock();
     for (subscriber, pipe) in &mut *pipes {
         match writeln!(pipe, "{notification}") {
             Ok(()) => {
@@ -366,6 +625,66 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
         pipes.remove(&subscriber);
     }
 
+    // Framed tcp clients receive notifications on the same connection as their query responses,
+    // and a failed write is the only reliable sign that one of them has gone away or has stopped
+    // reading, in which case the write times out and may have left a partial frame behind
+    let mut stale_tcp_connections = vec![];
+    for (addr, connection) in &mut *tcp_connections {
+        if !connection.is_framed() {
+            continue;
+        }
+
+        match connection.write_frame(notification.as_bytes()) {
+            Ok(()) => {
+                tracing::debug!("pushed notification to tcp client: {addr}");
+            }
+            Err(_) => {
+                stale_tcp_connections.push(addr.clone());
+            }
+        }
+    }
+
+    for addr in stale_tcp_connections {
+        tracing::warn!("removing disconnected tcp client: {addr}");
+        if let Some(connection) = tcp_connections.remove(&addr) {
+            connection.shutdown();
+        }
+    }
+
+    Ok(())
+}
+
+/// Send a notification to a single subscriber socket or pipe, regardless of any filtering options
+pub fn notify_subscriber(subscriber: &str, notification: &Notification) -> Result<()> {
+    let notification = &serde_json::to_string(notification)?;
+
+    if let Some(path) = SUBSCRIPTION_SOCKETS.lock().get(subscriber) {
+        match UnixStream::connect(path) {
+            Ok(mut stream) => {
+                tracing::debug!("pushed initial notification to subscriber: {subscriber}");
+                stream.write_all(notification.as_bytes())?;
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "could not push initial notification to subscriber {subscriber}: {error}"
+                );
+            }
+        }
+    }
+
+    if let Some(pipe) = SUBSCRIPTION_PIPES.lock().get_mut(subscriber) {
+        match writeln!(pipe, "{notification}") {
+            Ok(()) => {
+                tracing::debug!("pushed initial notification to subscriber: {subscriber}");
+            }
+            Err(error) => {
+                tracing::warn!(
+                    "could not push initial notification to subscriber {subscriber}: {error}"
+                );
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -32,23 +32,31 @@ use tracing_subscriber::EnvFilter;
 use uds_windows::UnixStream;
 
 use komorebi::border_manager;
+use komorebi::custom_layout_watcher;
 use komorebi::focus_manager;
 use komorebi::load_configuration;
 use komorebi::monitor_reconciliator;
+use komorebi::presentation_manager;
 use komorebi::process_command::listen_for_commands;
 use komorebi::process_command::listen_for_commands_tcp;
 use komorebi::process_event::listen_for_events;
 use komorebi::process_movement::listen_for_movements;
 use komorebi::reaper;
+use komorebi::reserved_space_manager;
 use komorebi::stackbar_manager;
+use komorebi::state_recorder;
 use komorebi::static_config::StaticConfig;
 use komorebi::theme_manager;
 use komorebi::transparency_manager;
+use komorebi::wallpaper_manager;
+use komorebi::watchdog;
+use komorebi::window_manager::DumpedState;
 use komorebi::window_manager::State;
 use komorebi::window_manager::WindowManager;
 use komorebi::windows_api::WindowsApi;
 use komorebi::winevent_listener;
 use komorebi::workspace_reconciliator;
+use komorebi::LogBufferWriter;
 use komorebi::CUSTOM_FFM;
 use komorebi::DATA_DIR;
 use komorebi::HOME_DIR;
@@ -86,6 +94,11 @@ fn setup() -> Result<(WorkerGuard, WorkerGuard)> {
                 tracing_subscriber::fmt::Layer::default()
                     .with_writer(color_non_blocking)
                     .with_ansi(true),
+            )
+            .with(
+                tracing_subscriber::fmt::Layer::default()
+                    .with_writer(|| LogBufferWriter)
+                    .with_ansi(false),
             ),
     )?;
 
@@ -268,12 +281,27 @@ fn main() -> Result<()> {
     let dumped_state = temp_dir().join("komorebi.state.json");
 
     if !opts.clean_state && dumped_state.is_file() {
-        let state: State = serde_json::from_str(&std::fs::read_to_string(&dumped_state)?)?;
-        wm.lock().apply_state(state);
+        // A state file which cannot be read, such as one dumped by an older version, is not worth
+        // failing to start over
+        match std::fs::read_to_string(&dumped_state)
+            .map_err(color_eyre::Report::from)
+            .and_then(|contents| Ok(serde_json::from_str::<DumpedState>(&contents)?))
+        {
+            Ok(state) => wm.lock().apply_state(state),
+            Err(error) => tracing::warn!(
+                "ignoring the dumped state at {}: {error}",
+                dumped_state.display()
+            ),
+        }
     }
 
     wm.lock().retile_all(false)?;
 
+    let (ctrlc_sender, ctrlc_receiver) = crossbeam_channel::bounded(1);
+
+    // Repeated panics are handled in the same way as ctrl-c so that hidden windows are restored
+    watchdog::set_shutdown_sender(ctrlc_sender.clone());
+
     listen_for_events(wm.clone());
 
     if CUSTOM_FFM.load(Ordering::SeqCst) {
@@ -281,15 +309,19 @@ fn main() -> Result<()> {
     }
 
     border_manager::listen_for_notifications(wm.clone());
+    custom_layout_watcher::listen_for_notifications(wm.clone());
     stackbar_manager::listen_for_notifications(wm.clone());
     transparency_manager::listen_for_notifications(wm.clone());
     workspace_reconciliator::listen_for_notifications(wm.clone());
     monitor_reconciliator::listen_for_notifications(wm.clone())?;
     reaper::watch_for_orphans(wm.clone());
+    reserved_space_manager::watch_for_reserved_space(wm.clone());
+    presentation_manager::watch_for_presentation_mode(wm.clone());
     focus_manager::listen_for_notifications(wm.clone());
-    theme_manager::listen_for_notifications();
+    theme_manager::listen_for_notifications(wm.clone());
+    wallpaper_manager::listen_for_notifications();
+    state_recorder::listen_for_notifications();
 
-    let (ctrlc_sender, ctrlc_receiver) = crossbeam_channel::bounded(1);
     ctrlc::set_handler(move || {
         ctrlc_sender
             .send(())
@@ -300,10 +332,17 @@ fn main() -> Result<()> {
         .recv()
         .expect("could not receive signal on ctrl-c channel");
 
-    tracing::error!("received ctrl-c, restoring all hidden windows and terminating process");
+    let panic_shutdown = watchdog::PANIC_SHUTDOWN.load(Ordering::SeqCst);
 
-    let state = State::from(&*wm.lock());
-    std::fs::write(dumped_state, serde_json::to_string_pretty(&state)?)?;
+    if panic_shutdown {
+        // The state which led to the panics should not be restored on the next launch
+        tracing::error!("too many panics, restoring all hidden windows and terminating process");
+    } else {
+        tracing::error!("received ctrl-c, restoring all hidden windows and terminating process");
+
+        let state = State::from(&*wm.lock());
+        std::fs::write(dumped_state, serde_json::to_string_pretty(&state)?)?;
+    }
 
     ANIMATION_ENABLED_PER_ANIMATION.lock().clear();
     ANIMATION_ENABLED_GLOBAL.store(false, Ordering::SeqCst);
@@ -324,5 +363,5 @@ fn main() -> Result<()> {
     let socket = DATA_DIR.join("komorebi.sock");
     let _ = std::fs::remove_file(socket);
 
-    std::process::exit(130);
+    std::process::exit(if panic_shutdown { 1 } else { 130 });
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
 
 use color_eyre::eyre::anyhow;
 use color_eyre::eyre::bail;
@@ -13,14 +14,20 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::core::Rect;
+use crate::core::RectOrPercent;
+use crate::core::WindowBasedWorkAreaOffsetCounting;
 
 use crate::container::Container;
 use crate::ring::Ring;
+use crate::wallpaper_manager;
 use crate::workspace::Workspace;
 use crate::DefaultLayout;
 use crate::Layout;
 use crate::OperationDirection;
 use crate::WindowsApi;
+use crate::DEFAULT_CONTAINER_PADDING;
+use crate::DEFAULT_WORKSPACE_PADDING;
+use crate::DUPLICATE_MONITOR_SERIAL_IDS;
 
 #[derive(
     Debug,
@@ -43,26 +50,79 @@ pub struct Monitor {
     device: String,
     #[getset(get = "pub", set = "pub")]
     device_id: String,
+    /// The serial number read from the monitor's EDID, if it reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     #[getset(get = "pub", set = "pub")]
-    size: Rect,
+    serial_number_id: Option<String>,
     #[getset(get = "pub", set = "pub")]
+    size: Rect,
+    /// The effective DPI of the monitor, which is 96 at 100% scaling
+    #[serde(default = "default_dpi")]
+    #[getset(get_copy = "pub")]
+    dpi: u32,
+    /// The scale factor derived from the effective DPI, which is 1.0 at 100% scaling
+    #[serde(default = "default_scale_factor")]
+    #[getset(get_copy = "pub")]
+    scale_factor: f32,
+    #[getset(get = "pub")]
     work_area_size: Rect,
-    #[getset(get_copy = "pub", set = "pub")]
+    #[getset(get_copy = "pub")]
     work_area_offset: Option<Rect>,
+    /// The work area offset as it was configured, which may be relative to the monitor size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub")]
+    configured_work_area_offset: Option<RectOrPercent>,
     #[getset(get_copy = "pub", set = "pub")]
     window_based_work_area_offset: Option<Rect>,
     #[getset(get_copy = "pub", set = "pub")]
     window_based_work_area_offset_limit: isize,
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    window_based_work_area_offset_counting: WindowBasedWorkAreaOffsetCounting,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    container_padding: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    workspace_padding: Option<i32>,
     workspaces: Ring<Workspace>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[getset(get_copy = "pub", set = "pub")]
     last_focused_workspace: Option<usize>,
     #[getset(get_mut = "pub")]
     workspace_names: HashMap<usize, String>,
+    // Workspace indices ordered from the most to the least recently focused
+    #[serde(skip)]
+    #[getset(get = "pub")]
+    workspace_focus_history: VecDeque<usize>,
+    /// Whether windows on this monitor are left entirely alone by the window manager
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    unmanaged: bool,
+    /// Whether tiling, borders, transparency and focus follows mouse have been suspended on this
+    /// monitor by the user
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    paused: bool,
+    /// A fullscreen application window in the foreground on this monitor, which suspends tiling,
+    /// borders, transparency and focus follows mouse until it is no longer fullscreen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    fullscreen_hwnd: Option<isize>,
 }
 
 impl_ring_elements!(Monitor, Workspace);
 
+const DEFAULT_DPI: u32 = 96;
+
+const fn default_dpi() -> u32 {
+    DEFAULT_DPI
+}
+
+const fn default_scale_factor() -> f32 {
+    1.0
+}
+
 pub fn new(
     id: isize,
     size: Rect,
@@ -70,43 +130,212 @@ pub fn new(
     name: String,
     device: String,
     device_id: String,
+    serial_number_id: Option<String>,
 ) -> Monitor {
     let mut workspaces = Ring::default();
     workspaces.elements_mut().push_back(Workspace::default());
 
+    let dpi = WindowsApi::effective_dpi_for_monitor(id).unwrap_or(DEFAULT_DPI);
+    #[allow(clippy::cast_precision_loss)]
+    let scale_factor = dpi as f32 / DEFAULT_DPI as f32;
+
     Monitor {
         id,
         name,
         device,
         device_id,
+        serial_number_id,
         size,
+        dpi,
+        scale_factor,
         work_area_size,
         work_area_offset: None,
+        configured_work_area_offset: None,
         window_based_work_area_offset: None,
         window_based_work_area_offset_limit: 1,
+        window_based_work_area_offset_counting: WindowBasedWorkAreaOffsetCounting::default(),
+        container_padding: None,
+        workspace_padding: None,
         workspaces,
         last_focused_workspace: None,
         workspace_names: HashMap::default(),
+        workspace_focus_history: VecDeque::default(),
+        unmanaged: false,
+        paused: false,
+        fullscreen_hwnd: None,
+    }
+}
+
+/// A display index preference, which identifies a monitor either by the serial number in its EDID
+/// when prefixed with `serial:`, or by its device id, optionally prefixed with `name:`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayPreference<'a> {
+    Serial(&'a str),
+    DeviceId(&'a str),
+}
+
+impl<'a> From<&'a str> for DisplayPreference<'a> {
+    fn from(value: &'a str) -> Self {
+        if let Some(serial) = value.strip_prefix("serial:") {
+            Self::Serial(serial.trim())
+        } else if let Some(device_id) = value.strip_prefix("name:") {
+            Self::DeviceId(device_id.trim())
+        } else {
+            Self::DeviceId(value)
+        }
     }
 }
 
 impl Monitor {
+    /// The preferred index of this monitor according to the given display index preferences
+    ///
+    /// Serial number matches take precedence over device id matches, unless the serial number is
+    /// also reported by another attached monitor, in which case only the device id can be used.
+    pub fn display_index_preference(&self, preferences: &HashMap<usize, String>) -> Option<usize> {
+        let serial_is_duplicate = self
+            .serial_number_id
+            .as_ref()
+            .is_some_and(|serial| DUPLICATE_MONITOR_SERIAL_IDS.lock().contains(serial));
+
+        let mut device_id_preference = None;
+
+        for (index, preference) in preferences {
+            match DisplayPreference::from(preference.as_str()) {
+                DisplayPreference::Serial(serial) => {
+                    if self.serial_number_id.as_deref() == Some(serial) {
+                        if serial_is_duplicate {
+                            tracing::warn!(
+                                "serial number {serial} is reported by more than one attached monitor (see duplicate monitor serial ids), ignoring the display index preference for {index} and falling back to device id and enumeration order for {}",
+                                self.device_id
+                            );
+                        } else {
+                            return Option::from(*index);
+                        }
+                    }
+                }
+                DisplayPreference::DeviceId(device_id) => {
+                    if device_id == self.device_id {
+                        device_id_preference = Option::from(*index);
+                    }
+                }
+            }
+        }
+
+        device_id_preference
+    }
+
     pub fn placeholder() -> Self {
         Self {
             id: 0,
             name: "PLACEHOLDER".to_string(),
             device: "".to_string(),
             device_id: "".to_string(),
+            serial_number_id: None,
             size: Default::default(),
+            dpi: DEFAULT_DPI,
+            scale_factor: default_scale_factor(),
             work_area_size: Default::default(),
             work_area_offset: None,
+            configured_work_area_offset: None,
             window_based_work_area_offset: None,
             window_based_work_area_offset_limit: 0,
+            window_based_work_area_offset_counting: WindowBasedWorkAreaOffsetCounting::default(),
+            container_padding: None,
+            workspace_padding: None,
             workspaces: Default::default(),
             last_focused_workspace: None,
             workspace_names: Default::default(),
+            workspace_focus_history: Default::default(),
+            unmanaged: false,
+            paused: false,
+            fullscreen_hwnd: None,
+        }
+    }
+
+    pub fn set_work_area_size(&mut self, work_area_size: Rect) -> &mut Self {
+        if self.work_area_size != work_area_size {
+            self.invalidate_workspace_previews();
+        }
+
+        self.work_area_size = work_area_size;
+        self
+    }
+
+    pub fn set_work_area_offset(&mut self, work_area_offset: Option<Rect>) -> &mut Self {
+        if self.work_area_offset != work_area_offset {
+            self.invalidate_workspace_previews();
         }
+
+        self.work_area_offset = work_area_offset;
+        self
     }
+
+    pub fn set_configured_work_area_offset(
+        &mut self,
+        work_area_offset: Option<RectOrPercent>,
+    ) -> &mut Self {
+        self.configured_work_area_offset = work_area_offset;
+        let resolved = work_area_offset.map(|offset| offset.resolve(&self.size));
+        self.set_work_area_offset(resolved)
+    }
+
+    /// Re-resolves a percentage based work area offset against the current monitor size,
+    /// returning true if the resolved offset has changed
+    pub fn resolve_work_area_offset(&mut self) -> bool {
+        if let Some(offset @ RectOrPercent::Percent { .. }) = self.configured_work_area_offset {
+            let resolved = Some(offset.resolve(&self.size));
+            if resolved != self.work_area_offset {
+                self.set_work_area_offset(resolved);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// The work area offset for this monitor, falling back to the global offset resolved
+    /// against the monitor size
+    pub fn effective_work_area_offset(&self, global: Option<RectOrPercent>) -> Option<Rect> {
+        self.work_area_offset
+            .or_else(|| global.map(|offset| offset.resolve(&self.size)))
+    }
+
+    /// Whether this monitor has been paused by the user or by a fullscreen application
+    pub const fn is_paused(&self) -> bool {
+        self.paused || self.fullscreen_hwnd.is_some()
+    }
+
+    /// Refreshes the effective DPI and scale factor, returning true if they have changed
+    pub fn update_dpi(&mut self) -> bool {
+        let dpi = WindowsApi::effective_dpi_for_monitor(self.id).unwrap_or(DEFAULT_DPI);
+        if dpi == self.dpi {
+            return false;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let scale_factor = dpi as f32 / DEFAULT_DPI as f32;
+
+        self.dpi = dpi;
+        self.scale_factor = scale_factor;
+
+        true
+    }
+
+    /// Previews of workspaces laid out against a previous work area no longer reflect where
+    /// their windows will be placed, so they are cleared until each workspace is next laid out
+    fn invalidate_workspace_previews(&mut self) {
+        for workspace in self.workspaces_mut() {
+            workspace.set_preview(None);
+        }
+    }
+
+    /// Hands every window on this monitor back to the user, for when it is marked as unmanaged
+    pub fn release_windows(&mut self) {
+        for workspace in self.workspaces_mut() {
+            workspace.release_windows();
+        }
+    }
+
     pub fn load_focused_workspace(&mut self, mouse_follows_focus: bool) -> Result<()> {
         let focused_idx = self.focused_workspace_idx();
         for (i, workspace) in self.workspaces_mut().iter_mut().enumerate() {
@@ -117,9 +346,35 @@ impl Monitor {
             }
         }
 
+        self.apply_wallpaper();
+
         Ok(())
     }
 
+    pub fn load_focused_workspace_without_focus(&mut self) {
+        let focused_idx = self.focused_workspace_idx();
+        for (i, workspace) in self.workspaces_mut().iter_mut().enumerate() {
+            if i == focused_idx {
+                workspace.restore_without_focus();
+            } else {
+                workspace.hide(None);
+            }
+        }
+
+        self.apply_wallpaper();
+    }
+
+    /// Sets the wallpaper of the focused workspace on this monitor; workspaces without a
+    /// wallpaper keep whichever wallpaper was last set
+    pub fn apply_wallpaper(&self) {
+        if let Some(wallpaper) = self
+            .focused_workspace()
+            .and_then(|workspace| workspace.wallpaper().clone())
+        {
+            wallpaper_manager::send_notification(self.size, wallpaper);
+        }
+    }
+
     pub fn add_container(
         &mut self,
         container: Container,
@@ -225,7 +480,8 @@ impl Monitor {
         }
 
         if idx == 0 {
-            self.workspaces_mut().push_back(Workspace::default());
+            let new_workspace = self.new_workspace();
+            self.workspaces_mut().push_back(new_workspace);
         } else {
             self.focus_workspace(idx.saturating_sub(1)).ok()?;
         };
@@ -235,8 +491,44 @@ impl Monitor {
 
     pub fn ensure_workspace_count(&mut self, ensure_count: usize) {
         if self.workspaces().len() < ensure_count {
-            self.workspaces_mut()
-                .resize(ensure_count, Workspace::default());
+            let new_workspace = self.new_workspace();
+            self.workspaces_mut().resize(ensure_count, new_workspace);
+        }
+    }
+
+    /// Returns a new workspace with the padding defaults of this monitor
+    fn new_workspace(&self) -> Workspace {
+        let mut workspace = Workspace::default();
+
+        if self.container_padding.is_some() {
+            workspace.set_container_padding(self.container_padding);
+        }
+
+        if self.workspace_padding.is_some() {
+            workspace.set_workspace_padding(self.workspace_padding);
+        }
+
+        workspace
+    }
+
+    /// Resolves the paddings of workspaces which do not have their own values, falling back from
+    /// the monitor defaults to the global defaults
+    pub fn apply_padding_defaults(&mut self) {
+        let container_padding = self
+            .container_padding
+            .unwrap_or_else(|| DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst));
+        let workspace_padding = self
+            .workspace_padding
+            .unwrap_or_else(|| DEFAULT_WORKSPACE_PADDING.load(Ordering::SeqCst));
+
+        for workspace in self.workspaces_mut() {
+            if !workspace.explicit_container_padding() {
+                workspace.set_container_padding(Option::from(container_padding));
+            }
+
+            if !workspace.explicit_workspace_padding() {
+                workspace.set_workspace_padding(Option::from(workspace_padding));
+            }
         }
     }
 
@@ -268,11 +560,12 @@ impl Monitor {
         if let Some(idx) = floating_window_index {
             let window = workspace.floating_windows_mut().remove(idx);
 
+            let new_workspace = self.new_workspace();
             let workspaces = self.workspaces_mut();
             #[allow(clippy::option_if_let_else)]
             let target_workspace = match workspaces.get_mut(target_workspace_idx) {
                 None => {
-                    workspaces.resize(target_workspace_idx + 1, Workspace::default());
+                    workspaces.resize(target_workspace_idx + 1, new_workspace);
                     workspaces.get_mut(target_workspace_idx).unwrap()
                 }
                 Some(workspace) => workspace,
@@ -284,12 +577,13 @@ impl Monitor {
                 .remove_focused_container()
                 .ok_or_else(|| anyhow!("there is no container"))?;
 
+            let new_workspace = self.new_workspace();
             let workspaces = self.workspaces_mut();
 
             #[allow(clippy::option_if_let_else)]
             let target_workspace = match workspaces.get_mut(target_workspace_idx) {
                 None => {
-                    workspaces.resize(target_workspace_idx + 1, Workspace::default());
+                    workspaces.resize(target_workspace_idx + 1, new_workspace);
                     workspaces.get_mut(target_workspace_idx).unwrap()
                 }
                 Some(workspace) => workspace,
@@ -318,15 +612,19 @@ impl Monitor {
         tracing::info!("focusing workspace");
 
         {
+            let new_workspace = self.new_workspace();
             let workspaces = self.workspaces_mut();
 
             if workspaces.get(idx).is_none() {
-                workspaces.resize(idx + 1, Workspace::default());
+                workspaces.resize(idx + 1, new_workspace);
             }
 
             self.workspaces.focus(idx);
         }
 
+        self.workspace_focus_history.retain(|i| *i != idx);
+        self.workspace_focus_history.push_front(idx);
+
         // Always set the latest known name when creating the workspace for the first time
         {
             let name = { self.workspace_names.get(&idx).cloned() };
@@ -341,22 +639,37 @@ impl Monitor {
         Ok(())
     }
 
+    /// The most recently focused workspace other than the focused workspace which still has
+    /// windows on it
+    pub fn previous_non_empty_workspace_idx(&self) -> Option<usize> {
+        let focused_idx = self.focused_workspace_idx();
+
+        self.workspace_focus_history.iter().copied().find(|idx| {
+            *idx != focused_idx
+                && self
+                    .workspaces()
+                    .get(*idx)
+                    .is_some_and(|workspace| !workspace.is_empty())
+        })
+    }
+
     pub fn new_workspace_idx(&self) -> usize {
         self.workspaces().len()
     }
 
-    pub fn update_focused_workspace(&mut self, offset: Option<Rect>) -> Result<()> {
+    pub fn update_focused_workspace(&mut self, offset: Option<RectOrPercent>) -> Result<()> {
+        if self.is_paused() {
+            return Ok(());
+        }
+
         let work_area = *self.work_area_size();
         let window_based_work_area_offset = (
             self.window_based_work_area_offset_limit(),
             self.window_based_work_area_offset(),
+            self.window_based_work_area_offset_counting(),
         );
 
-        let offset = if self.work_area_offset().is_some() {
-            self.work_area_offset()
-        } else {
-            offset
-        };
+        let offset = self.effective_work_area_offset(offset);
 
         self.focused_workspace_mut()
             .ok_or_else(|| anyhow!("there is no workspace"))?
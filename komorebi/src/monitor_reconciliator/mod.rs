@@ -1,25 +1,31 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
 use crate::border_manager;
+use crate::container::Container;
 use crate::core::Rect;
 use crate::monitor;
 use crate::monitor::Monitor;
 use crate::monitor_reconciliator::hidden::Hidden;
+use crate::window::Window;
 use crate::MonitorConfig;
 use crate::WindowManager;
 use crate::WindowsApi;
+use crate::DUPLICATE_MONITOR_SERIAL_IDS;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use crossbeam_utils::atomic::AtomicConsume;
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::time::Duration;
 
 pub mod hidden;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Notification {
     ResolutionScalingChanged,
     WorkAreaChanged,
@@ -30,14 +36,32 @@ pub enum Notification {
     SessionUnlocked,
 }
 
+impl Notification {
+    /// Notifications which describe changes to the monitor topology and which can be coalesced
+    /// when they arrive in quick succession
+    fn is_topology_change(self) -> bool {
+        matches!(
+            self,
+            Self::ResolutionScalingChanged | Self::WorkAreaChanged | Self::DisplayConnectionChange
+        )
+    }
+}
+
+/// How long to wait for monitor topology notifications to stop arriving before reconciling, in
+/// milliseconds
+pub static DISPLAY_CHANGE_DEBOUNCE: AtomicU64 = AtomicU64::new(500);
+
 static ACTIVE: AtomicBool = AtomicBool::new(true);
 
 static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
 
 static MONITOR_CACHE: OnceLock<Mutex<HashMap<String, MonitorConfig>>> = OnceLock::new();
 
+// The workspace contents of disconnected monitors, keyed by device id
+static WORKSPACE_CONTENTS_CACHE: OnceLock<Mutex<HashMap<String, Monitor>>> = OnceLock::new();
+
 pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
-    CHANNEL.get_or_init(|| crossbeam_channel::bounded(1))
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(50))
 }
 
 fn event_tx() -> Sender<Notification> {
@@ -63,9 +87,23 @@ pub fn insert_in_monitor_cache(device_id: &str, config: MonitorConfig) {
 }
 
 pub fn attached_display_devices() -> color_eyre::Result<Vec<Monitor>> {
-    Ok(win32_display_data::connected_displays_all()
+    let displays = win32_display_data::connected_displays_all()
         .flatten()
-        .map(|display| {
+        .collect::<Vec<_>>();
+
+    let serial_number_ids = displays
+        .iter()
+        .map(|display| WindowsApi::monitor_serial_number_id(&display.device_path))
+        .collect::<Vec<_>>();
+
+    // Monitors sharing a serial number may have been connected or disconnected since the
+    // duplicates were last recorded
+    WindowsApi::update_duplicate_monitor_serial_ids(&serial_number_ids);
+
+    Ok(displays
+        .into_iter()
+        .zip(serial_number_ids)
+        .map(|(display, serial_number_id)| {
             let path = display.device_path;
 
             let (device, device_id) = if path.is_empty() {
@@ -89,6 +127,7 @@ pub fn attached_display_devices() -> color_eyre::Result<Vec<Monitor>> {
                 name,
                 device,
                 device_id,
+                serial_number_id,
             )
         })
         .collect::<Vec<_>>())
@@ -138,183 +177,273 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
             continue 'receiver;
         }
 
+        let notifications = if notification.is_topology_change() {
+            coalesce_topology_changes(&receiver, notification)
+        } else {
+            vec![notification]
+        };
+
         let mut wm = wm.lock();
 
-        match notification {
-            Notification::EnteringSuspendedState | Notification::SessionLocked => {
-                tracing::debug!(
+        for notification in notifications {
+            handle_notification(&mut wm, notification)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects topology notifications until none have been received for the debounce window,
+/// returning each distinct notification once in the order in which it should be handled
+///
+/// Every handler reconciles against the current Win32 state rather than the contents of the
+/// notification, so handling each kind of notification once after the topology has settled
+/// gives the same result as handling every intermediate notification in sequence. Any other
+/// notification ends the debounce window early and is handled after the topology changes.
+fn coalesce_topology_changes(
+    receiver: &Receiver<Notification>,
+    first: Notification,
+) -> Vec<Notification> {
+    let debounce = Duration::from_millis(DISPLAY_CHANGE_DEBOUNCE.load(Ordering::SeqCst));
+
+    let mut received = vec![first];
+    let mut interrupted_by = None;
+
+    while !debounce.is_zero() {
+        match receiver.recv_timeout(debounce) {
+            Ok(notification) if notification.is_topology_change() => received.push(notification),
+            Ok(notification) => {
+                interrupted_by = Some(notification);
+                break;
+            }
+            Err(_) => break,
+        }
+    }
+
+    // Connection changes are reconciled first so that size and work area changes are checked
+    // against the final set of monitors
+    let mut notifications = [
+        Notification::DisplayConnectionChange,
+        Notification::ResolutionScalingChanged,
+        Notification::WorkAreaChanged,
+    ]
+    .into_iter()
+    .filter(|kind| received.contains(kind))
+    .collect::<Vec<_>>();
+
+    let collapsed = received.len() - notifications.len();
+    if collapsed > 0 {
+        tracing::info!(
+            "coalesced {} monitor topology notifications into {notifications:?} ({collapsed} collapsed)",
+            received.len()
+        );
+    }
+
+    notifications.extend(interrupted_by);
+    notifications
+}
+
+fn handle_notification(
+    wm: &mut WindowManager,
+    notification: Notification,
+) -> color_eyre::Result<()> {
+    match notification {
+        Notification::EnteringSuspendedState | Notification::SessionLocked => {
+            tracing::debug!(
                     "deactivating reconciliator until system resumes from suspended state or session is unlocked"
                 );
-                ACTIVE.store(false, Ordering::SeqCst);
-            }
-            Notification::ResumingFromSuspendedState | Notification::SessionUnlocked => {
-                // this is only handled above if the reconciliator is paused
-            }
-            Notification::WorkAreaChanged => {
-                tracing::debug!("handling work area changed notification");
-                let offset = wm.work_area_offset;
-                for monitor in wm.monitors_mut() {
-                    let mut should_update = false;
-
-                    // Update work areas as necessary
-                    if let Ok(reference) = WindowsApi::monitor(monitor.id()) {
-                        if reference.work_area_size() != monitor.work_area_size() {
-                            monitor.set_work_area_size(Rect {
-                                left: reference.work_area_size().left,
-                                top: reference.work_area_size().top,
-                                right: reference.work_area_size().right,
-                                bottom: reference.work_area_size().bottom,
-                            });
-
-                            should_update = true;
-                        }
+            ACTIVE.store(false, Ordering::SeqCst);
+        }
+        Notification::ResumingFromSuspendedState | Notification::SessionUnlocked => {
+            // this is only handled above if the reconciliator is paused
+        }
+        Notification::WorkAreaChanged => {
+            tracing::debug!("handling work area changed notification");
+            let offset = wm.work_area_offset;
+            for monitor in wm.monitors_mut() {
+                let mut should_update = false;
+
+                // Update work areas as necessary
+                if let Ok(reference) = WindowsApi::monitor(monitor.id()) {
+                    if reference.work_area_size() != monitor.work_area_size() {
+                        monitor.set_work_area_size(Rect {
+                            left: reference.work_area_size().left,
+                            top: reference.work_area_size().top,
+                            right: reference.work_area_size().right,
+                            bottom: reference.work_area_size().bottom,
+                        });
+
+                        should_update = true;
                     }
+                }
 
-                    if should_update {
-                        tracing::info!("updated work area for {}", monitor.device_id());
-                        monitor.update_focused_workspace(offset)?;
-                        border_manager::send_notification(None);
-                    } else {
-                        tracing::debug!(
-                            "work areas match, reconciliation not required for {}",
-                            monitor.device_id()
-                        );
-                    }
+                if should_update {
+                    monitor.resolve_work_area_offset();
+                    tracing::info!("updated work area for {}", monitor.device_id());
+                    monitor.update_focused_workspace(offset)?;
+                    border_manager::send_notification(None);
+                } else {
+                    tracing::debug!(
+                        "work areas match, reconciliation not required for {}",
+                        monitor.device_id()
+                    );
                 }
             }
-            Notification::ResolutionScalingChanged => {
-                tracing::debug!("handling resolution/scaling changed notification");
-                let offset = wm.work_area_offset;
-                for monitor in wm.monitors_mut() {
-                    let mut should_update = false;
-
-                    // Update sizes and work areas as necessary
-                    if let Ok(reference) = WindowsApi::monitor(monitor.id()) {
-                        if reference.work_area_size() != monitor.work_area_size() {
-                            monitor.set_work_area_size(Rect {
-                                left: reference.work_area_size().left,
-                                top: reference.work_area_size().top,
-                                right: reference.work_area_size().right,
-                                bottom: reference.work_area_size().bottom,
-                            });
-
-                            should_update = true;
-                        }
-
-                        if reference.size() != monitor.size() {
-                            monitor.set_size(Rect {
-                                left: reference.size().left,
-                                top: reference.size().top,
-                                right: reference.size().right,
-                                bottom: reference.size().bottom,
-                            });
-
-                            should_update = true;
-                        }
+        }
+        Notification::ResolutionScalingChanged => {
+            tracing::debug!("handling resolution/scaling changed notification");
+            let offset = wm.work_area_offset;
+            for monitor in wm.monitors_mut() {
+                let mut should_update = false;
+
+                // Update sizes and work areas as necessary
+                if let Ok(reference) = WindowsApi::monitor(monitor.id()) {
+                    if reference.work_area_size() != monitor.work_area_size() {
+                        monitor.set_work_area_size(Rect {
+                            left: reference.work_area_size().left,
+                            top: reference.work_area_size().top,
+                            right: reference.work_area_size().right,
+                            bottom: reference.work_area_size().bottom,
+                        });
+
+                        should_update = true;
                     }
 
-                    if should_update {
-                        tracing::info!(
-                            "updated monitor resolution/scaling for {}",
-                            monitor.device_id()
-                        );
+                    if reference.size() != monitor.size() {
+                        monitor.set_size(Rect {
+                            left: reference.size().left,
+                            top: reference.size().top,
+                            right: reference.size().right,
+                            bottom: reference.size().bottom,
+                        });
 
-                        monitor.update_focused_workspace(offset)?;
-                        border_manager::send_notification(None);
-                    } else {
-                        tracing::debug!(
-                            "resolutions match, reconciliation not required for {}",
-                            monitor.device_id()
-                        );
+                        should_update = true;
                     }
-                }
-            }
-            Notification::DisplayConnectionChange => {
-                tracing::debug!("handling display connection change notification");
-                let mut monitor_cache = MONITOR_CACHE
-                    .get_or_init(|| Mutex::new(HashMap::new()))
-                    .lock();
-
-                let initial_monitor_count = wm.monitors().len();
 
-                // Get the currently attached display devices
-                let attached_devices = attached_display_devices()?;
+                    if monitor.update_dpi() {
+                        should_update = true;
+                    }
 
-                // Make sure that in our state any attached displays have the latest Win32 data
-                for monitor in wm.monitors_mut() {
-                    for attached in &attached_devices {
-                        if attached.device_id().eq(monitor.device_id()) {
-                            monitor.set_id(attached.id());
-                            monitor.set_name(attached.name().clone());
-                            monitor.set_size(*attached.size());
-                            monitor.set_work_area_size(*attached.work_area_size());
-                        }
+                    if monitor.resolve_work_area_offset() {
+                        should_update = true;
                     }
                 }
 
-                if initial_monitor_count == attached_devices.len() {
-                    tracing::debug!("monitor counts match, reconciliation not required");
-                    continue 'receiver;
-                }
+                if should_update {
+                    tracing::info!(
+                        "updated monitor resolution/scaling for {}",
+                        monitor.device_id()
+                    );
 
-                if attached_devices.is_empty() {
+                    monitor.update_focused_workspace(offset)?;
+                    border_manager::send_notification(None);
+                } else {
                     tracing::debug!(
-                        "no devices found, skipping reconciliation to avoid breaking state"
+                        "resolutions match, reconciliation not required for {}",
+                        monitor.device_id()
                     );
-                    continue 'receiver;
                 }
+            }
+        }
+        Notification::DisplayConnectionChange => {
+            tracing::debug!("handling display connection change notification");
+            let mut monitor_cache = MONITOR_CACHE
+                .get_or_init(|| Mutex::new(HashMap::new()))
+                .lock();
+            let mut workspace_contents_cache = WORKSPACE_CONTENTS_CACHE
+                .get_or_init(|| Mutex::new(HashMap::new()))
+                .lock();
+
+            let initial_monitor_count = wm.monitors().len();
+
+            // Get the currently attached display devices
+            let attached_devices = attached_display_devices()?;
+
+            // Make sure that in our state any attached displays have the latest Win32 data
+            for monitor in wm.monitors_mut() {
+                for attached in &attached_devices {
+                    if attached.device_id().eq(monitor.device_id()) {
+                        monitor.set_id(attached.id());
+                        monitor.set_name(attached.name().clone());
+                        monitor.set_size(*attached.size());
+                        monitor.set_work_area_size(*attached.work_area_size());
+                        monitor.update_dpi();
+                        monitor.resolve_work_area_offset();
+                    }
+                }
+            }
 
-                if initial_monitor_count > attached_devices.len() {
-                    tracing::info!(
+            if initial_monitor_count == attached_devices.len() {
+                tracing::debug!("monitor counts match, reconciliation not required");
+                return Ok(());
+            }
+
+            if attached_devices.is_empty() {
+                tracing::debug!(
+                    "no devices found, skipping reconciliation to avoid breaking state"
+                );
+                return Ok(());
+            }
+
+            if initial_monitor_count > attached_devices.len() {
+                tracing::info!(
                         "monitor count mismatch ({initial_monitor_count} vs {}), removing disconnected monitors",
                         attached_devices.len()
                     );
 
-                    // Gather all the containers that will be orphaned from disconnected and invalid displays
-                    let mut orphaned_containers = vec![];
-
-                    // Collect the ids in our state which aren't in the current attached display ids
-                    // These are monitors that have been removed
-                    let mut newly_removed_displays = vec![];
-
-                    for m in wm.monitors().iter() {
-                        if !attached_devices
-                            .iter()
-                            .any(|attached| attached.device_id().eq(m.device_id()))
-                        {
-                            newly_removed_displays.push(m.device_id().clone());
-                            for workspace in m.workspaces() {
-                                for container in workspace.containers() {
-                                    // Save the orphaned containers from the removed monitor
-                                    orphaned_containers.push(container.clone());
-                                }
+                // Gather all the containers that will be orphaned from disconnected and invalid displays
+                let mut orphaned_containers = vec![];
+
+                // Collect the ids in our state which aren't in the current attached display ids
+                // These are monitors that have been removed
+                let mut newly_removed_displays = vec![];
+
+                for m in wm.monitors().iter() {
+                    if !attached_devices
+                        .iter()
+                        .any(|attached| attached.device_id().eq(m.device_id()))
+                    {
+                        newly_removed_displays.push(m.device_id().clone());
+                        for workspace in m.workspaces() {
+                            for container in workspace.containers() {
+                                // Save the orphaned containers from the removed monitor
+                                orphaned_containers.push(container.clone());
                             }
-
-                            // Let's add their state to the cache for later
-                            monitor_cache.insert(m.device_id().clone(), m.into());
                         }
-                    }
 
-                    if !orphaned_containers.is_empty() {
-                        tracing::info!(
-                            "removed orphaned containers from: {newly_removed_displays:?}"
-                        );
+                        // Let's add their state to the cache for later
+                        monitor_cache.insert(m.device_id().clone(), m.into());
+                        workspace_contents_cache.insert(m.device_id().clone(), m.clone());
                     }
+                }
 
-                    if !newly_removed_displays.is_empty() {
-                        // After we have cached them, remove them from our state
-                        wm.monitors_mut()
-                            .retain(|m| !newly_removed_displays.contains(m.device_id()));
-                    }
+                if !orphaned_containers.is_empty() {
+                    tracing::info!("removed orphaned containers from: {newly_removed_displays:?}");
+                }
+
+                if !newly_removed_displays.is_empty() {
+                    // After we have cached them, remove them from our state
+                    wm.monitors_mut()
+                        .retain(|m| !newly_removed_displays.contains(m.device_id()));
+                }
 
-                    let post_removal_monitor_count = wm.monitors().len();
-                    let focused_monitor_idx = wm.focused_monitor_idx();
-                    if focused_monitor_idx >= post_removal_monitor_count {
-                        wm.focus_monitor(0)?;
+                let post_removal_monitor_count = wm.monitors().len();
+                let focused_monitor_idx = wm.focused_monitor_idx();
+
+                // Unmanaged monitors can neither be focused nor take in the orphaned containers
+                let first_managed_monitor_idx = wm.first_managed_monitor_idx();
+                if focused_monitor_idx >= post_removal_monitor_count {
+                    if let Some(idx) = first_managed_monitor_idx {
+                        wm.focus_monitor(idx)?;
                     }
+                }
 
-                    if !orphaned_containers.is_empty() {
-                        if let Some(primary) = wm.monitors_mut().front_mut() {
+                if !orphaned_containers.is_empty() {
+                    match first_managed_monitor_idx.and_then(|idx| wm.monitors_mut().get_mut(idx)) {
+                        None => tracing::warn!(
+                            "there is no managed monitor to move the orphaned containers to"
+                        ),
+                        Some(primary) => {
                             if let Some(focused_ws) = primary.focused_workspace_mut() {
                                 let focused_container_idx = focused_ws.focused_container_idx();
 
@@ -330,88 +459,324 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                             }
                         }
                     }
+                }
 
-                    let offset = wm.work_area_offset;
+                let offset = wm.work_area_offset;
 
-                    for monitor in wm.monitors_mut() {
-                        // If we have lost a monitor, update everything to filter out any jank
-                        if initial_monitor_count != post_removal_monitor_count {
-                            monitor.update_focused_workspace(offset)?;
-                        }
+                for monitor in wm.monitors_mut() {
+                    // If we have lost a monitor, update everything to filter out any jank
+                    if initial_monitor_count != post_removal_monitor_count {
+                        monitor.update_focused_workspace(offset)?;
                     }
                 }
+            }
 
-                let post_removal_monitor_count = wm.monitors().len();
+            let post_removal_monitor_count = wm.monitors().len();
 
-                // This is the list of device ids after we have removed detached displays
-                let post_removal_device_ids = wm
-                    .monitors()
-                    .iter()
-                    .map(Monitor::device_id)
-                    .cloned()
-                    .collect::<Vec<_>>();
+            // This is the list of device ids after we have removed detached displays
+            let post_removal_device_ids = wm
+                .monitors()
+                .iter()
+                .map(Monitor::device_id)
+                .cloned()
+                .collect::<Vec<_>>();
 
-                // Check for and add any new monitors that may have been plugged in
-                // Monitor and display index preferences get applied in this function
-                WindowsApi::load_monitor_information(&mut wm.monitors)?;
+            // Check for and add any new monitors that may have been plugged in
+            // Monitor and display index preferences get applied in this function
+            WindowsApi::load_monitor_information(&mut wm.monitors)?;
 
-                let post_addition_monitor_count = wm.monitors().len();
+            let post_addition_monitor_count = wm.monitors().len();
 
-                if post_addition_monitor_count > post_removal_monitor_count {
-                    tracing::info!(
+            if post_addition_monitor_count > post_removal_monitor_count {
+                tracing::info!(
                         "monitor count mismatch ({post_removal_monitor_count} vs {post_addition_monitor_count}), adding connected monitors",
                     );
 
-                    // Look in the updated state for new monitors
-                    for m in wm.monitors_mut() {
-                        let device_id = m.device_id().clone();
-                        // We identify a new monitor when we encounter a new device id
-                        if !post_removal_device_ids.contains(&device_id) {
-                            let mut cache_hit = false;
-                            // Check if that device id exists in the cache for this session
-                            if let Some(cached) = monitor_cache.get(&device_id) {
-                                cache_hit = true;
-
-                                tracing::info!("found monitor and workspace configuration for {device_id} in the monitor cache, applying");
-
-                                // If it does, load all the monitor settings from the cache entry
-                                m.ensure_workspace_count(cached.workspaces.len());
-                                m.set_work_area_offset(cached.work_area_offset);
-                                m.set_window_based_work_area_offset(
-                                    cached.window_based_work_area_offset,
-                                );
-                                m.set_window_based_work_area_offset_limit(
-                                    cached.window_based_work_area_offset_limit.unwrap_or(1),
-                                );
-
-                                for (w_idx, workspace) in m.workspaces_mut().iter_mut().enumerate()
-                                {
-                                    if let Some(cached_workspace) = cached.workspaces.get(w_idx) {
-                                        workspace.load_static_config(cached_workspace)?;
-                                    }
+                // Monitors which were disconnected earlier in this session and have had their
+                // workspace contents cached
+                let mut reconnected_monitors = vec![];
+
+                // Look in the updated state for new monitors
+                for m in wm.monitors_mut() {
+                    let device_id = m.device_id().clone();
+                    // We identify a new monitor when we encounter a new device id
+                    if !post_removal_device_ids.contains(&device_id) {
+                        // Configurations loaded from display index preferences using a serial
+                        // number are cached under the preference itself
+                        let serial_key = m
+                            .serial_number_id()
+                            .as_ref()
+                            .filter(|serial| !DUPLICATE_MONITOR_SERIAL_IDS.lock().contains(serial))
+                            .map(|serial| format!("serial:{serial}"));
+
+                        let cache_key = serial_key
+                            .filter(|key| monitor_cache.contains_key(key))
+                            .unwrap_or_else(|| device_id.clone());
+
+                        let mut cache_hit = false;
+                        // Check if that device id exists in the cache for this session
+                        if let Some(cached) = monitor_cache.get(&cache_key) {
+                            cache_hit = true;
+
+                            tracing::info!("found monitor and workspace configuration for {device_id} in the monitor cache, applying");
+
+                            // If it does, load all the monitor settings from the cache entry
+                            m.ensure_workspace_count(cached.workspaces.len());
+                            m.set_configured_work_area_offset(cached.work_area_offset);
+                            m.set_window_based_work_area_offset(
+                                cached.window_based_work_area_offset,
+                            );
+                            m.set_window_based_work_area_offset_limit(
+                                cached.window_based_work_area_offset_limit.unwrap_or(1),
+                            );
+                            m.set_window_based_work_area_offset_counting(
+                                cached
+                                    .window_based_work_area_offset_counting
+                                    .unwrap_or_default(),
+                            );
+                            m.set_container_padding(cached.container_padding);
+                            m.set_workspace_padding(cached.workspace_padding);
+                            m.set_unmanaged(cached.unmanaged.unwrap_or_default());
+
+                            for (w_idx, workspace) in m.workspaces_mut().iter_mut().enumerate() {
+                                if let Some(cached_workspace) = cached.workspaces.get(w_idx) {
+                                    workspace.load_static_config(cached_workspace)?;
                                 }
                             }
 
-                            // Entries in the cache should only be used once; remove the entry there was a cache hit
-                            if cache_hit {
-                                monitor_cache.remove(&device_id);
-                            }
+                            m.apply_padding_defaults();
+                        }
+
+                        // Entries in the cache should only be used once; remove the entry there was a cache hit
+                        if cache_hit {
+                            monitor_cache.remove(&cache_key);
+                        }
+
+                        if let Some(cached) = workspace_contents_cache.remove(&device_id) {
+                            reconnected_monitors.push(cached);
                         }
                     }
                 }
 
-                let final_count = wm.monitors().len();
+                for cached in reconnected_monitors {
+                    restore_workspace_contents(wm, &cached)?;
+                }
+            }
 
-                if post_removal_monitor_count != final_count {
-                    wm.retile_all(true)?;
-                    // Second retile to fix DPI/resolution related jank
-                    wm.retile_all(true)?;
-                    // Border updates to fix DPI/resolution related jank
-                    border_manager::send_notification(None);
+            let final_count = wm.monitors().len();
+
+            if post_removal_monitor_count != final_count {
+                wm.retile_all(true)?;
+                // Second retile to fix DPI/resolution related jank
+                wm.retile_all(true)?;
+                // Border updates to fix DPI/resolution related jank
+                border_manager::send_notification(None);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves windows which were on a monitor when it was disconnected back to their original
+/// workspaces, container positions and monocle or maximized states on that monitor; windows
+/// which have been closed since the monitor was disconnected are dropped
+fn restore_workspace_contents(wm: &mut WindowManager, cached: &Monitor) -> color_eyre::Result<()> {
+    let device_id = cached.device_id();
+
+    let Some(monitor_idx) = wm
+        .monitors()
+        .iter()
+        .position(|m| m.device_id() == device_id)
+    else {
+        return Ok(());
+    };
+
+    if wm.is_unmanaged_monitor(monitor_idx) {
+        return Ok(());
+    }
+
+    tracing::info!("restoring cached workspace contents for {device_id}");
+
+    let mut restored_windows = 0;
+
+    for (workspace_idx, cached_workspace) in cached.workspaces().iter().enumerate() {
+        let mut containers = vec![];
+
+        for (idx, cached_container) in cached_workspace.containers().iter().enumerate() {
+            if let Some(container) = take_container(wm, cached_container)? {
+                containers.push((idx, container));
+            }
+        }
+
+        let monocle_container = match cached_workspace.monocle_container() {
+            Some(cached_container) => take_container(wm, cached_container)?,
+            None => None,
+        };
+
+        let mut floating_windows = vec![];
+
+        for window in cached_workspace.floating_windows() {
+            if take_window(wm, window.hwnd)? {
+                floating_windows.push(*window);
+            }
+        }
+
+        let mut maximized_window = None;
+
+        if let Some(window) = cached_workspace.maximized_window() {
+            if take_window(wm, window.hwnd)? {
+                maximized_window = Option::from(*window);
+            }
+        }
+
+        let Some(monitor) = wm.monitors_mut().get_mut(monitor_idx) else {
+            break;
+        };
+
+        let work_area = *monitor.work_area_size();
+        let is_focused_workspace = monitor.focused_workspace_idx() == workspace_idx;
+
+        let Some(workspace) = monitor.workspaces_mut().get_mut(workspace_idx) else {
+            continue;
+        };
+
+        let hiding_behaviour = workspace.hiding_behaviour();
+
+        let show_or_hide = |container: &mut Container| {
+            if is_focused_workspace {
+                container.load_focused_window_with_behaviour(hiding_behaviour);
+            } else {
+                container.hide_with_behaviour(None, hiding_behaviour);
+            }
+        };
+
+        // Containers go back to their original indices, or to the back if containers have been
+        // closed on this workspace since the monitor was disconnected
+        let restored_containers = !containers.is_empty();
+
+        for (idx, mut container) in containers {
+            restored_windows += container.windows().len();
+            show_or_hide(&mut container);
+
+            let idx = idx.min(workspace.containers().len());
+            workspace.insert_container_at_idx(idx, container);
+        }
+
+        if restored_containers {
+            let focused_idx = cached_workspace
+                .focused_container_idx()
+                .min(workspace.containers().len() - 1);
+
+            workspace.focus_container(focused_idx);
+        }
+
+        if let Some(mut container) = monocle_container {
+            restored_windows += container.windows().len();
+            show_or_hide(&mut container);
+
+            if workspace.monocle_container().is_none() {
+                let restore_idx = cached_workspace
+                    .monocle_container_restore_idx()
+                    .unwrap_or_default()
+                    .min(workspace.containers().len());
+
+                workspace.set_monocle_container(Option::from(container));
+                workspace.set_monocle_container_restore_idx(Option::from(restore_idx));
+            } else {
+                workspace.add_container_to_back(container);
+            }
+        }
+
+        for window in floating_windows {
+            restored_windows += 1;
+
+            if is_focused_workspace {
+                window.restore_with_behaviour(hiding_behaviour);
+            } else {
+                window.hide_with_behaviour(hiding_behaviour);
+            }
+
+            workspace.floating_windows_mut().push(window);
+        }
+
+        if let Some(window) = maximized_window {
+            restored_windows += 1;
+
+            // Windows are maximized on whichever monitor they are currently on
+            window.set_position(&work_area, true)?;
+
+            if is_focused_workspace {
+                window.restore_with_behaviour(hiding_behaviour);
+            } else {
+                window.hide_with_behaviour(hiding_behaviour);
+            }
+
+            if workspace.maximized_window().is_none() {
+                let restore_idx = cached_workspace
+                    .maximized_window_restore_idx()
+                    .unwrap_or_default()
+                    .min(workspace.containers().len());
+
+                if is_focused_workspace {
+                    window.maximize();
                 }
+
+                workspace.set_maximized_window(Option::from(window));
+                workspace.set_maximized_window_restore_idx(Option::from(restore_idx));
+            } else {
+                workspace.floating_windows_mut().push(window);
             }
         }
     }
 
+    tracing::info!("restored {restored_windows} windows to {device_id}");
+
     Ok(())
 }
+
+/// Takes the windows of a cached container which are still alive back from wherever they are
+/// currently managed, returning None if none of them are
+fn take_container(
+    wm: &mut WindowManager,
+    cached: &Container,
+) -> color_eyre::Result<Option<Container>> {
+    let mut container = Container::default();
+
+    for window in cached.windows() {
+        if take_window(wm, window.hwnd)? {
+            container.windows_mut().push_back(*window);
+        }
+    }
+
+    if container.windows().is_empty() {
+        return Ok(None);
+    }
+
+    let focused_idx = cached
+        .focused_window_idx()
+        .min(container.windows().len() - 1);
+
+    container.focus_window(focused_idx);
+
+    Ok(Some(container))
+}
+
+/// Removes a window which is still alive from wherever it is currently managed, returning
+/// whether it was found
+fn take_window(wm: &mut WindowManager, hwnd: isize) -> color_eyre::Result<bool> {
+    if !Window::from(hwnd).is_window() {
+        return Ok(false);
+    }
+
+    for monitor in wm.monitors_mut() {
+        for workspace in monitor.workspaces_mut() {
+            if workspace.contains_window(hwnd) {
+                workspace.remove_window(hwnd)?;
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
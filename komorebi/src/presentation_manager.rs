@@ -0,0 +1,161 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::animation::prefix::AnimationPrefix;
+use crate::animation::ANIMATION_ENABLED_GLOBAL;
+use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
+use crate::border_manager;
+use crate::border_manager::IMPLEMENTATION;
+use crate::core::BorderImplementation;
+use crate::transparency_manager;
+use crate::transparency_manager::TRANSPARENCY_ENABLED;
+use crate::WindowManager;
+use crate::WindowsApi;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub static PRESENTATION_MODE_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    pub static ref PRESENTATION_MODE_CONFIG: Mutex<Option<PresentationModeConfig>> =
+        Mutex::new(None);
+    // The values of the suppressed subsystems from before presentation mode was entered
+    static ref SAVED_STATE: Mutex<Option<SavedState>> = Mutex::new(None);
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PresentationModeConfig {
+    /// Disable borders while a fullscreen application, game or presentation is running (default: false)
+    #[serde(default)]
+    pub disable_borders: bool,
+    /// Disable animations while a fullscreen application, game or presentation is running (default: false)
+    #[serde(default)]
+    pub disable_animations: bool,
+    /// Disable transparency while a fullscreen application, game or presentation is running (default: false)
+    #[serde(default)]
+    pub disable_transparency: bool,
+}
+
+#[derive(Debug, Default)]
+struct SavedState {
+    border_enabled: Option<bool>,
+    animation_enabled: Option<(bool, HashMap<AnimationPrefix, bool>)>,
+    transparency_enabled: Option<bool>,
+}
+
+pub fn watch_for_presentation_mode(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_presentation_mode(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    tracing::error!("restarting failed thread: {:?}", error)
+                } else {
+                    tracing::error!("restarting failed thread: {}", error)
+                }
+            }
+        }
+    });
+}
+
+pub fn handle_presentation_mode(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
+    tracing::info!("watching");
+
+    loop {
+        std::thread::sleep(Duration::from_secs(1));
+
+        let config = *PRESENTATION_MODE_CONFIG.lock();
+        let active = PRESENTATION_MODE_ACTIVE.load(Ordering::SeqCst);
+
+        // Always give back anything that was suppressed if the configuration has been removed
+        let presenting = match config {
+            Some(_) => WindowsApi::is_presenting()?,
+            None => false,
+        };
+
+        match (presenting, active, config) {
+            (true, false, Some(config)) => {
+                tracing::info!("entering presentation mode");
+                suppress(config, &wm)?;
+                PRESENTATION_MODE_ACTIVE.store(true, Ordering::SeqCst);
+            }
+            (false, true, _) => {
+                tracing::info!("leaving presentation mode");
+                restore();
+                PRESENTATION_MODE_ACTIVE.store(false, Ordering::SeqCst);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn suppress(
+    config: PresentationModeConfig,
+    wm: &Arc<Mutex<WindowManager>>,
+) -> color_eyre::Result<()> {
+    let mut saved = SavedState::default();
+
+    if config.disable_borders {
+        saved.border_enabled =
+            Option::from(border_manager::BORDER_ENABLED.swap(false, Ordering::SeqCst));
+
+        match IMPLEMENTATION.load() {
+            BorderImplementation::Komorebi => {
+                border_manager::destroy_all_borders()?;
+            }
+            BorderImplementation::Windows => {
+                wm.lock().remove_all_accents()?;
+            }
+        }
+    }
+
+    if config.disable_animations {
+        let mut per_animation = ANIMATION_ENABLED_PER_ANIMATION.lock();
+        saved.animation_enabled = Option::from((
+            ANIMATION_ENABLED_GLOBAL.swap(false, Ordering::SeqCst),
+            per_animation.clone(),
+        ));
+
+        per_animation.clear();
+    }
+
+    if config.disable_transparency {
+        saved.transparency_enabled =
+            Option::from(TRANSPARENCY_ENABLED.swap(false, Ordering::SeqCst));
+        transparency_manager::send_notification();
+    }
+
+    *SAVED_STATE.lock() = Option::from(saved);
+
+    Ok(())
+}
+
+fn restore() {
+    let Some(saved) = SAVED_STATE.lock().take() else {
+        return;
+    };
+
+    if let Some(enabled) = saved.border_enabled {
+        border_manager::BORDER_ENABLED.store(enabled, Ordering::SeqCst);
+        border_manager::send_notification(None);
+    }
+
+    if let Some((enabled, per_animation)) = saved.animation_enabled {
+        ANIMATION_ENABLED_GLOBAL.store(enabled, Ordering::SeqCst);
+        *ANIMATION_ENABLED_PER_ANIMATION.lock() = per_animation;
+    }
+
+    if let Some(enabled) = saved.transparency_enabled {
+        TRANSPARENCY_ENABLED.store(enabled, Ordering::SeqCst);
+        transparency_manager::send_notification();
+    }
+}
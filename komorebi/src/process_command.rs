@@ -1,18 +1,20 @@
 use std::collections::HashMap;
-use std::fs::File;
-use std::fs::OpenOptions;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
 use std::net::TcpListener;
 use std::net::TcpStream;
 use std::num::NonZeroUsize;
+use std::process::Command;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
 use color_eyre::Result;
 use miow::pipe::connect;
 use net2::TcpStreamExt;
@@ -21,6 +23,7 @@ use schemars::gen::SchemaSettings;
 use schemars::schema_for;
 use uds_windows::UnixStream;
 
+use crate::animation::ANIMATIONS_BLACKLIST;
 use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
 use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
@@ -28,60 +31,85 @@ use crate::core::config_generation::ApplicationConfiguration;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
+use crate::core::config_generation::RuleProvenance;
 use crate::core::ApplicationIdentifier;
 use crate::core::Axis;
 use crate::core::BorderImplementation;
+use crate::core::DefaultLayout;
+use crate::core::DragBehaviour;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::Layout;
 use crate::core::MoveBehaviour;
 use crate::core::OperationDirection;
 use crate::core::Rect;
+use crate::core::RectOrPercent;
 use crate::core::Sizing;
 use crate::core::SocketMessage;
 use crate::core::StateQuery;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowKind;
+use crate::core::WorkspaceLayer;
 
 use crate::animation::ANIMATION_DURATION_GLOBAL;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
 use crate::animation::ANIMATION_FPS;
 use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::border_manager;
+use crate::border_manager::BorderColourOverride;
 use crate::border_manager::IMPLEMENTATION;
 use crate::border_manager::STYLE;
+use crate::colour::Colour;
 use crate::colour::Rgb;
 use crate::config_generation::WorkspaceMatchingRule;
 use crate::current_virtual_desktop;
+use crate::custom_layout_watcher;
+use crate::log_tail;
+use crate::notify_subscriber;
 use crate::notify_subscribers;
+use crate::reaper;
+use crate::record_rule_provenance;
+use crate::reserved_space_manager;
 use crate::stackbar_manager;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
+use crate::state_recorder;
 use crate::static_config::StaticConfig;
 use crate::theme_manager;
 use crate::transparency_manager;
+use crate::watchdog;
+use crate::window::should_act;
 use crate::window::RuleDebug;
 use crate::window::Window;
 use crate::window_manager;
 use crate::window_manager::WindowManager;
+use crate::window_manager::WorkspaceRuleScope;
 use crate::windows_api::WindowsApi;
 use crate::winevent_listener;
-use crate::workspace::WorkspaceWindowLocation;
+use crate::ActiveRules;
 use crate::GlobalState;
 use crate::Notification;
 use crate::NotificationEvent;
+use crate::ServerVersion;
+use crate::Snapshot;
 use crate::State;
 use crate::CUSTOM_FFM;
 use crate::DATA_DIR;
 use crate::DISPLAY_INDEX_PREFERENCES;
+use crate::FLOAT_OVERRIDE_APPLICATIONS;
 use crate::HIDING_BEHAVIOUR;
 use crate::IGNORE_IDENTIFIERS;
+use crate::IGNORE_IDENTIFIERS_PROVENANCE;
 use crate::INITIAL_CONFIGURATION_LOADED;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MANAGE_IDENTIFIERS_PROVENANCE;
 use crate::MONITOR_INDEX_PREFERENCES;
+use crate::MOUSE_FOLLOWS_FOCUS_SCOPE;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::REGEX_IDENTIFIERS;
 use crate::REMOVE_TITLEBARS;
+use crate::SESSION_WORKSPACE_RULES;
 use crate::SUBSCRIPTION_PIPES;
 use crate::SUBSCRIPTION_SOCKETS;
 use crate::SUBSCRIPTION_SOCKET_OPTIONS;
@@ -89,9 +117,9 @@ use crate::TCP_CONNECTIONS;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WINDOWS_11;
 use crate::WORKSPACE_MATCHING_RULES;
+use crate::WORKSPACE_RULE_ENFORCEMENT;
 use stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use stackbar_manager::STACKBAR_LABEL;
-use stackbar_manager::STACKBAR_MODE;
 use stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
 use stackbar_manager::STACKBAR_TAB_HEIGHT;
 use stackbar_manager::STACKBAR_TAB_WIDTH;
@@ -119,9 +147,11 @@ pub fn listen_for_commands(wm: Arc<Mutex<WindowManager>>) {
                                 Ok(()) => {}
                                 Err(error) => tracing::error!("{}", error),
                             }
-                            match read_commands_uds(&wm_clone, stream) {
-                                Ok(()) => {}
-                                Err(error) => tracing::error!("{}", error),
+                            match watchdog::catch_panic("command processing", &wm_clone, || {
+                                read_commands_uds(&wm_clone, stream)
+                            }) {
+                                Some(Ok(())) | None => {}
+                                Some(Err(error)) => tracing::error!("{}", error),
                             }
                         });
                     }
@@ -138,13 +168,55 @@ pub fn listen_for_commands(wm: Arc<Mutex<WindowManager>>) {
     });
 }
 
+/// Sent by a TCP client as the first bytes on a connection to switch it to framed mode, where
+/// every message in either direction is a 4-byte big-endian length followed by a JSON payload
+pub const FRAMED_PREAMBLE: &[u8; 4] = b"KMF1";
+
+// Frames announcing a larger payload than this are treated as a corrupt connection
+const MAXIMUM_FRAME_LENGTH: usize = 16 * 1024 * 1024;
+
+// Notifications are written while the window manager lock is held, so a client which stops
+// reading must not be able to block those writes for longer than this
+const TCP_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A client connected to the TCP server started with --tcp-port
+pub struct TcpConnection {
+    stream: TcpStream,
+    /// Whether the client negotiated framed mode, in which it also receives notifications
+    framed: bool,
+}
+
+impl TcpConnection {
+    pub const fn is_framed(&self) -> bool {
+        self.framed
+    }
+
+    /// Writes a single frame to the connection
+    pub fn write_frame(&mut self, payload: &[u8]) -> std::io::Result<()> {
+        let length = u32::try_from(payload.len()).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "frame is too large")
+        })?;
+
+        let mut frame = Vec::with_capacity(payload.len() + 4);
+        frame.extend_from_slice(&length.to_be_bytes());
+        frame.extend_from_slice(payload);
+
+        self.stream.write_all(&frame)
+    }
+
+    /// Closes the connection, which also ends the thread reading commands from it
+    pub fn shutdown(&self) {
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
 #[tracing::instrument]
 pub fn listen_for_commands_tcp(wm: Arc<Mutex<WindowManager>>, port: usize) {
     let listener =
         TcpListener::bind(format!("0.0.0.0:{port}")).expect("could not start tcp server");
 
     std::thread::spawn(move || {
-        tracing::info!("listening on 0.0.0.0:43663");
+        tracing::info!("listening on 0.0.0.0:{port}");
         for client in listener.incoming() {
             match client {
                 Ok(mut stream) => {
@@ -152,6 +224,10 @@ pub fn listen_for_commands_tcp(wm: Arc<Mutex<WindowManager>>, port: usize) {
                         .set_keepalive(Some(Duration::from_secs(30)))
                         .expect("TCP keepalive should be set");
 
+                    stream
+                        .set_write_timeout(Some(TCP_WRITE_TIMEOUT))
+                        .expect("TCP write timeout should be set");
+
                     let addr = stream
                         .peer_addr()
                         .expect("incoming connection should have an address")
@@ -161,15 +237,27 @@ pub fn listen_for_commands_tcp(wm: Arc<Mutex<WindowManager>>, port: usize) {
 
                     connections.insert(
                         addr.clone(),
-                        stream.try_clone().expect("stream should be cloneable"),
+                        TcpConnection {
+                            stream: stream.try_clone().expect("stream should be cloneable"),
+                            framed: false,
+                        },
                     );
 
+                    drop(connections);
+
                     tracing::info!("listening for incoming tcp messages from {}", &addr);
 
-                    match read_commands_tcp(&wm, &mut stream, &addr) {
-                        Ok(()) => {}
-                        Err(error) => tracing::error!("{}", error),
-                    }
+                    // Framed clients keep their connection open, so each client is read on its
+                    // own thread to avoid blocking any others
+                    let wm = wm.clone();
+                    std::thread::spawn(move || {
+                        match watchdog::catch_panic("command processing", &wm, || {
+                            read_commands_tcp(&wm, &mut stream, &addr)
+                        }) {
+                            Some(Ok(())) | None => {}
+                            Some(Err(error)) => tracing::error!("{}", error),
+                        }
+                    });
                 }
                 Err(error) => {
                     tracing::error!("{}", error);
@@ -207,7 +295,9 @@ impl WindowManager {
         let initial_state = State::from(self.as_ref());
 
         match message {
-            SocketMessage::CycleFocusWorkspace(_) | SocketMessage::FocusWorkspaceNumber(_) => {
+            SocketMessage::CycleFocusWorkspace(_)
+            | SocketMessage::FocusWorkspaceNumber(_)
+            | SocketMessage::FocusWorkspaceNumberKeepFocus(_) => {
                 if let Some(monitor) = self.focused_monitor_mut() {
                     let idx = monitor.focused_workspace_idx();
                     monitor.set_last_focused_workspace(Option::from(idx));
@@ -230,63 +320,27 @@ impl WindowManager {
                 self.focus_container_in_direction(direction)?;
                 self.promote_container_to_front()?
             }
+            SocketMessage::SwapWithLargest => self.swap_with_largest_container()?,
+            SocketMessage::MoveFloatingWindow(x, y) => self.move_floating_window(x, y)?,
+            SocketMessage::PlaceFloatingWindow(placement) => {
+                self.place_floating_window(placement)?;
+            }
+            SocketMessage::ResizeFloatingWindowTo(width, height) => {
+                self.resize_floating_window_to(width, height)?;
+            }
             SocketMessage::EagerFocus(ref exe) => {
-                let focused_monitor_idx = self.focused_monitor_idx();
-                let focused_workspace_idx = self.focused_workspace_idx()?;
-
-                let mut window_location = None;
-                let mut monitor_workspace_indices = None;
-
-                'search: for (monitor_idx, monitor) in self.monitors().iter().enumerate() {
-                    for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
-                        if let Some(location) = workspace.location_from_exe(exe) {
-                            window_location = Some(location);
-                            monitor_workspace_indices = Some((monitor_idx, workspace_idx));
-                            break 'search;
-                        }
-                    }
-                }
-
-                if let Some((monitor_idx, workspace_idx)) = monitor_workspace_indices {
-                    if monitor_idx != focused_monitor_idx {
-                        self.focus_monitor(monitor_idx)?;
-                    }
-
-                    if workspace_idx != focused_workspace_idx {
-                        self.focus_workspace(workspace_idx)?;
-                    }
-                }
+                self.eager_focus(exe)?;
+            }
+            SocketMessage::EagerFocusOrLaunch(ref exe, ref program, ref args) => {
+                if !self.eager_focus(exe)? {
+                    tracing::info!("no window found for {exe}, launching: {program} {args:?}");
 
-                if let Some(location) = window_location {
-                    match location {
-                        WorkspaceWindowLocation::Monocle(window_idx) => {
-                            self.focus_container_window(window_idx)?;
-                        }
-                        WorkspaceWindowLocation::Maximized => {
-                            if let Some(window) =
-                                self.focused_workspace_mut()?.maximized_window_mut()
-                            {
-                                window.focus(self.mouse_follows_focus)?;
-                            }
-                        }
-                        WorkspaceWindowLocation::Container(container_idx, window_idx) => {
-                            let focused_container_idx = self.focused_container_idx()?;
-                            if container_idx != focused_container_idx {
-                                self.focused_workspace_mut()?.focus_container(container_idx);
-                            }
+                    let home =
+                        dirs::home_dir().ok_or_else(|| anyhow!("there is no home directory"))?;
 
-                            self.focus_container_window(window_idx)?;
-                        }
-                        WorkspaceWindowLocation::Floating(window_idx) => {
-                            if let Some(window) = self
-                                .focused_workspace_mut()?
-                                .floating_windows_mut()
-                                .get_mut(window_idx)
-                            {
-                                window.focus(self.mouse_follows_focus)?;
-                            }
-                        }
-                    }
+                    // The program is spawned directly rather than through a shell so that the
+                    // arguments are never interpreted as further commands
+                    Command::new(program).args(args).current_dir(home).spawn()?;
                 }
             }
             SocketMessage::FocusWindow(direction) => {
@@ -301,10 +355,20 @@ impl WindowManager {
             SocketMessage::CycleMoveWindow(direction) => {
                 self.move_container_in_cycle_direction(direction)?;
             }
+            SocketMessage::FocusLastWindow => {
+                self.focus_last_container()?;
+            }
             SocketMessage::StackWindow(direction) => self.add_window_to_container(direction)?,
             SocketMessage::UnstackWindow => self.remove_window_from_container()?,
             SocketMessage::StackAll => self.stack_all()?,
             SocketMessage::UnstackAll => self.unstack_all()?,
+            SocketMessage::PinContainerToColumn(column) => {
+                self.pin_focused_container_to_column(column)?;
+            }
+            SocketMessage::UnpinContainer => self.unpin_focused_container()?,
+            SocketMessage::PushToScratchpad(ref name) => self.push_to_scratchpad(name)?,
+            SocketMessage::PopFromScratchpad(ref name) => self.pop_from_scratchpad(name)?,
+            SocketMessage::ToggleScratchpad(ref name) => self.toggle_scratchpad(name)?,
             SocketMessage::CycleStack(direction) => {
                 self.cycle_container_window_in_direction(direction)?;
                 self.focused_window()?.focus(self.mouse_follows_focus)?;
@@ -333,10 +397,25 @@ impl WindowManager {
             SocketMessage::Close => {
                 Window::from(WindowsApi::foreground_window()?).close()?;
             }
+            SocketMessage::CloseWorkspaceWindows => {
+                let monitor_idx = self.focused_monitor_idx();
+                let workspace_idx = self.focused_workspace_idx()?;
+                self.close_workspace_windows(monitor_idx, workspace_idx)?;
+            }
+            SocketMessage::CloseWindowsOnWorkspaceNumber(workspace_idx) => {
+                self.close_workspace_windows(self.focused_monitor_idx(), workspace_idx)?;
+            }
+            SocketMessage::CloseWindowsOnNamedWorkspace(ref name) => {
+                let (monitor_idx, workspace_idx) = self
+                    .monitor_workspace_index_by_name(name)
+                    .ok_or_else(|| anyhow!("there is no workspace named {name}"))?;
+                self.close_workspace_windows(monitor_idx, workspace_idx)?;
+            }
             SocketMessage::Minimize => {
                 Window::from(WindowsApi::foreground_window()?).minimize();
             }
             SocketMessage::ToggleFloat => self.toggle_float()?,
+            SocketMessage::ToggleAlwaysOnTop => self.toggle_always_on_top()?,
             SocketMessage::ToggleMonocle => self.toggle_monocle()?,
             SocketMessage::ToggleMaximize => self.toggle_maximize()?,
             SocketMessage::ContainerPadding(monitor_idx, workspace_idx, size) => {
@@ -359,6 +438,12 @@ impl WindowManager {
                     self.set_workspace_padding(monitor_idx, workspace_idx, size)?;
                 }
             }
+            SocketMessage::MonitorContainerPadding(monitor_idx, size) => {
+                self.set_monitor_container_padding(monitor_idx, size)?;
+            }
+            SocketMessage::MonitorWorkspacePadding(monitor_idx, size) => {
+                self.set_monitor_workspace_padding(monitor_idx, size)?;
+            }
             SocketMessage::InitialWorkspaceRule(identifier, ref id, monitor_idx, workspace_idx) => {
                 let mut workspace_rules = WORKSPACE_MATCHING_RULES.lock();
                 let workspace_matching_rule = WorkspaceMatchingRule {
@@ -370,6 +455,8 @@ impl WindowManager {
                         matching_strategy: Some(MatchingStrategy::Legacy),
                     }),
                     initial_only: true,
+                    enforce_on_event: true,
+                    provenance: RuleProvenance::Runtime,
                 };
 
                 if !workspace_rules.contains(&workspace_matching_rule) {
@@ -390,6 +477,8 @@ impl WindowManager {
                             matching_strategy: Some(MatchingStrategy::Legacy),
                         }),
                         initial_only: true,
+                        enforce_on_event: true,
+                        provenance: RuleProvenance::Runtime,
                     };
 
                     if !workspace_rules.contains(&workspace_matching_rule) {
@@ -408,6 +497,8 @@ impl WindowManager {
                         matching_strategy: Some(MatchingStrategy::Legacy),
                     }),
                     initial_only: false,
+                    enforce_on_event: true,
+                    provenance: RuleProvenance::Runtime,
                 };
 
                 if !workspace_rules.contains(&workspace_matching_rule) {
@@ -428,6 +519,8 @@ impl WindowManager {
                             matching_strategy: Some(MatchingStrategy::Legacy),
                         }),
                         initial_only: false,
+                        enforce_on_event: true,
+                        provenance: RuleProvenance::Runtime,
                     };
 
                     if !workspace_rules.contains(&workspace_matching_rule) {
@@ -456,13 +549,75 @@ impl WindowManager {
                 let mut workspace_rules = WORKSPACE_MATCHING_RULES.lock();
                 workspace_rules.clear();
             }
+            SocketMessage::SessionWorkspaceRule(identifier, ref id, monitor_idx, workspace_idx) => {
+                self.add_session_workspace_rule(identifier, id, monitor_idx, workspace_idx)?;
+            }
+            SocketMessage::FocusedWindowSessionWorkspaceRule(monitor_idx, workspace_idx) => {
+                let exe = Window::from(WindowsApi::foreground_window()?).exe()?;
+                self.add_session_workspace_rule(
+                    ApplicationIdentifier::Exe,
+                    &exe,
+                    monitor_idx,
+                    workspace_idx,
+                )?;
+            }
+            SocketMessage::SessionWorkspaceRules => {
+                let rules = serde_json::to_string_pretty(&*SESSION_WORKSPACE_RULES.lock())?;
+                reply.write_all(rules.as_bytes())?;
+            }
+            SocketMessage::WorkspaceRules => {
+                let rules = serde_json::to_string_pretty(&ActiveRules::current())?;
+                reply.write_all(rules.as_bytes())?;
+            }
+            SocketMessage::ClearSessionWorkspaceRules => {
+                SESSION_WORKSPACE_RULES.lock().clear();
+            }
+            SocketMessage::ResetInitialWorkspaceRules => {
+                // Each window is recorded again as soon as an initial rule has been applied to it,
+                // so windows are only moved once more rather than every time rules are enforced
+                self.already_moved_window_handles.lock().clear();
+            }
             SocketMessage::EnforceWorkspaceRules => {
                 {
                     let mut already_moved = self.already_moved_window_handles.lock();
                     already_moved.clear();
                 }
+                {
+                    let mut manually_moved = self.manually_moved_window_handles.lock();
+                    manually_moved.clear();
+                }
                 self.enforce_workspace_rules()?;
             }
+            SocketMessage::EnforceWorkspaceRulesFor(identifier, ref id, ref workspace) => {
+                let scope = WorkspaceRuleScope::new(identifier, id.clone(), workspace.clone());
+
+                // Only the windows being re-sorted forget that they have been moved before
+                let mut in_scope = vec![];
+                for monitor in self.monitors() {
+                    for workspace in monitor.workspaces() {
+                        for window in workspace.all_windows() {
+                            if self.is_in_workspace_rule_scope(window, &scope) {
+                                in_scope.push(window.hwnd);
+                            }
+                        }
+                    }
+                }
+
+                {
+                    let mut already_moved = self.already_moved_window_handles.lock();
+                    already_moved.retain(|hwnd| !in_scope.contains(hwnd));
+                }
+                {
+                    let mut manually_moved = self.manually_moved_window_handles.lock();
+                    manually_moved.retain(|hwnd| !in_scope.contains(hwnd));
+                }
+
+                self.enforce_workspace_rules_for(&scope)?;
+            }
+            SocketMessage::ToggleWorkspaceRuleEnforcement => {
+                let current = WORKSPACE_RULE_ENFORCEMENT.load(Ordering::SeqCst);
+                WORKSPACE_RULE_ENFORCEMENT.store(!current, Ordering::SeqCst);
+            }
             SocketMessage::ManageRule(identifier, ref id) => {
                 let mut manage_identifiers = MANAGE_IDENTIFIERS.lock();
 
@@ -476,7 +631,35 @@ impl WindowManager {
                 }
 
                 if should_push {
-                    manage_identifiers.push(MatchingRule::Simple(IdWithIdentifier {
+                    let rule = MatchingRule::Simple(IdWithIdentifier {
+                        kind: identifier,
+                        id: id.clone(),
+                        matching_strategy: Option::from(MatchingStrategy::Legacy),
+                    });
+
+                    record_rule_provenance(
+                        &[rule.clone()],
+                        &mut MANAGE_IDENTIFIERS_PROVENANCE.lock(),
+                        RuleProvenance::Runtime,
+                    );
+
+                    manage_identifiers.push(rule);
+                }
+            }
+            SocketMessage::FloatOverrideRule(identifier, ref id) => {
+                let mut float_override_applications = FLOAT_OVERRIDE_APPLICATIONS.lock();
+
+                let mut should_push = true;
+                for f in &*float_override_applications {
+                    if let MatchingRule::Simple(f) = f {
+                        if f.id.eq(id) {
+                            should_push = false;
+                        }
+                    }
+                }
+
+                if should_push {
+                    float_override_applications.push(MatchingRule::Simple(IdWithIdentifier {
                         kind: identifier,
                         id: id.clone(),
                         matching_strategy: Option::from(MatchingStrategy::Legacy),
@@ -496,11 +679,19 @@ impl WindowManager {
                 }
 
                 if should_push {
-                    ignore_identifiers.push(MatchingRule::Simple(IdWithIdentifier {
+                    let rule = MatchingRule::Simple(IdWithIdentifier {
                         kind: identifier,
                         id: id.clone(),
                         matching_strategy: Option::from(MatchingStrategy::Legacy),
-                    }));
+                    });
+
+                    record_rule_provenance(
+                        &[rule.clone()],
+                        &mut IGNORE_IDENTIFIERS_PROVENANCE.lock(),
+                        RuleProvenance::Runtime,
+                    );
+
+                    ignore_identifiers.push(rule);
                 }
 
                 let offset = self.work_area_offset;
@@ -608,11 +799,7 @@ impl WindowManager {
                 self.swap_focused_monitor(monitor_idx)?;
             }
             SocketMessage::CycleMoveContainerToMonitor(direction) => {
-                let monitor_idx = direction.next_idx(
-                    self.focused_monitor_idx(),
-                    NonZeroUsize::new(self.monitors().len())
-                        .ok_or_else(|| anyhow!("there must be at least one monitor"))?,
-                );
+                let monitor_idx = self.cycle_monitor_idx(direction)?;
 
                 let direction = self.direction_from_monitor_idx(monitor_idx);
                 self.move_container_to_monitor(monitor_idx, None, true, direction)?;
@@ -620,6 +807,18 @@ impl WindowManager {
             SocketMessage::SendContainerToWorkspaceNumber(workspace_idx) => {
                 self.move_container_to_workspace(workspace_idx, false, None)?;
             }
+            SocketMessage::MoveContainerToNewWorkspace => {
+                self.move_container_to_new_workspace()?;
+            }
+            SocketMessage::ReturnContainerToPreviousWorkspace(follow) => {
+                self.return_container_to_previous_workspace(follow)?;
+            }
+            SocketMessage::Unswallow => {
+                self.unswallow_focused_container()?;
+            }
+            SocketMessage::ToggleMonitorPause(monitor_idx) => {
+                self.toggle_monitor_pause(monitor_idx)?;
+            }
             SocketMessage::CycleSendContainerToWorkspace(direction) => {
                 let focused_monitor = self
                     .focused_monitor()
@@ -640,12 +839,22 @@ impl WindowManager {
                 let direction = self.direction_from_monitor_idx(monitor_idx);
                 self.move_container_to_monitor(monitor_idx, None, false, direction)?;
             }
+            SocketMessage::SendStackToMonitorNumber(monitor_idx) => {
+                let foreground_hwnd = WindowsApi::foreground_window()?;
+                if self
+                    .focused_workspace()?
+                    .floating_windows()
+                    .iter()
+                    .any(|window| window.hwnd == foreground_hwnd)
+                {
+                    bail!("the focused window is floating and is not part of a stack");
+                }
+
+                let direction = self.direction_from_monitor_idx(monitor_idx);
+                self.move_container_to_monitor(monitor_idx, None, false, direction)?;
+            }
             SocketMessage::CycleSendContainerToMonitor(direction) => {
-                let monitor_idx = direction.next_idx(
-                    self.focused_monitor_idx(),
-                    NonZeroUsize::new(self.monitors().len())
-                        .ok_or_else(|| anyhow!("there must be at least one monitor"))?,
-                );
+                let monitor_idx = self.cycle_monitor_idx(direction)?;
 
                 let direction = self.direction_from_monitor_idx(monitor_idx);
                 self.move_container_to_monitor(monitor_idx, None, false, direction)?;
@@ -699,11 +908,7 @@ impl WindowManager {
                 self.move_workspace_to_monitor(monitor_idx)?;
             }
             SocketMessage::CycleMoveWorkspaceToMonitor(direction) => {
-                let monitor_idx = direction.next_idx(
-                    self.focused_monitor_idx(),
-                    NonZeroUsize::new(self.monitors().len())
-                        .ok_or_else(|| anyhow!("there must be at least one monitor"))?,
-                );
+                let monitor_idx = self.cycle_monitor_idx(direction)?;
 
                 self.move_workspace_to_monitor(monitor_idx)?;
             }
@@ -721,11 +926,7 @@ impl WindowManager {
                 self.toggle_tiling()?;
             }
             SocketMessage::CycleFocusMonitor(direction) => {
-                let monitor_idx = direction.next_idx(
-                    self.focused_monitor_idx(),
-                    NonZeroUsize::new(self.monitors().len())
-                        .ok_or_else(|| anyhow!("there must be at least one monitor"))?,
-                );
+                let monitor_idx = self.cycle_monitor_idx(direction)?;
 
                 self.focus_monitor(monitor_idx)?;
                 self.update_focused_workspace(self.mouse_follows_focus, true)?;
@@ -738,6 +939,11 @@ impl WindowManager {
                 border_manager::destroy_all_borders()?;
                 self.retile_all(false)?
             }
+            SocketMessage::GarbageCollect => {
+                let removed = reaper::collect_garbage(self)?;
+                tracing::info!("garbage collection removed {removed} entries");
+                reply.write_all(removed.to_string().as_bytes())?;
+            }
             SocketMessage::RetileWithResizeDimensions => {
                 border_manager::destroy_all_borders()?;
                 self.retile_all(true)?
@@ -745,6 +951,11 @@ impl WindowManager {
             SocketMessage::FlipLayout(layout_flip) => self.flip_layout(layout_flip)?,
             SocketMessage::ChangeLayout(layout) => self.change_workspace_layout_default(layout)?,
             SocketMessage::CycleLayout(direction) => self.cycle_layout(direction)?,
+            SocketMessage::ScrollingLayoutColumns(count) => {
+                self.focused_workspace_mut()?
+                    .set_scrolling_visible_columns(count);
+                self.update_focused_workspace(false, false)?;
+            }
             SocketMessage::ChangeLayoutCustom(ref path) => {
                 self.change_workspace_custom_layout(path)?;
             }
@@ -885,7 +1096,7 @@ impl WindowManager {
                             && workspace.floating_windows().is_empty()
                             && workspace.monocle_container().is_none()
                             && workspace.maximized_window().is_none()
-                            && workspace.name().is_none()
+                            && workspace.explicit_name().is_none()
                         {
                             can_close = true;
                         }
@@ -936,6 +1147,18 @@ impl WindowManager {
                     self.focus_workspace(workspace_idx)?;
                 }
             }
+            SocketMessage::FocusWorkspaceNumberKeepFocus(workspace_idx) => {
+                // This is to ensure that even on an empty workspace on a secondary monitor, the
+                // secondary monitor where the cursor is focused will be used as the target for
+                // the workspace switch op
+                if let Some(monitor_idx) = self.monitor_idx_from_current_pos() {
+                    self.focus_monitor(monitor_idx)?;
+                }
+
+                if self.focused_workspace_idx().unwrap_or_default() != workspace_idx {
+                    self.focus_workspace_keep_focus(workspace_idx)?;
+                }
+            }
             SocketMessage::FocusWorkspaceNumbers(workspace_idx) => {
                 // This is to ensure that even on an empty workspace on a secondary monitor, the
                 // secondary monitor where the cursor is focused will be used as the target for
@@ -1008,6 +1231,9 @@ impl WindowManager {
             SocketMessage::WorkspaceName(monitor_idx, workspace_idx, ref name) => {
                 self.set_workspace_name(monitor_idx, workspace_idx, name.to_string())?;
             }
+            SocketMessage::WorkspaceWallpaper(monitor_idx, workspace_idx, ref path) => {
+                self.set_workspace_wallpaper(monitor_idx, workspace_idx, path)?;
+            }
             SocketMessage::State => {
                 let state = match serde_json::to_string_pretty(&window_manager::State::from(&*self))
                 {
@@ -1051,40 +1277,83 @@ impl WindowManager {
                 reply.write_all(visible_windows_state.as_bytes())?;
             }
             SocketMessage::MonitorInformation => {
-                let mut monitors = HashMap::new();
-                for monitor in self.monitors() {
-                    monitors.insert(monitor.device_id(), monitor.size());
-                }
-
-                let monitors_state = serde_json::to_string_pretty(&monitors)
+                let monitors_state = serde_json::to_string_pretty(&self.monitor_information())
                     .unwrap_or_else(|error| error.to_string());
 
                 reply.write_all(monitors_state.as_bytes())?;
             }
+            SocketMessage::Snapshot => {
+                let snapshot = serde_json::to_string_pretty(&Snapshot::from(&*self))
+                    .unwrap_or_else(|error| error.to_string());
+
+                reply.write_all(snapshot.as_bytes())?;
+            }
+            SocketMessage::Version => {
+                let version = serde_json::to_string_pretty(&ServerVersion::current())?;
+                reply.write_all(version.as_bytes())?;
+            }
+            SocketMessage::LogTail(count) => {
+                let lines = log_tail(count).join("\n");
+                reply.write_all(lines.as_bytes())?;
+            }
+            SocketMessage::StateRecording(enable) => {
+                state_recorder::set_enabled(enable);
+            }
             SocketMessage::Query(query) => {
                 let response = match query {
-                    StateQuery::FocusedMonitorIndex => self.focused_monitor_idx(),
+                    StateQuery::FocusedMonitorIndex => self.focused_monitor_idx().to_string(),
                     StateQuery::FocusedWorkspaceIndex => self
                         .focused_monitor()
                         .ok_or_else(|| anyhow!("there is no monitor"))?
-                        .focused_workspace_idx(),
-                    StateQuery::FocusedContainerIndex => {
-                        self.focused_workspace()?.focused_container_idx()
-                    }
+                        .focused_workspace_idx()
+                        .to_string(),
+                    StateQuery::FocusedContainerIndex => self
+                        .focused_workspace()?
+                        .focused_container_idx()
+                        .to_string(),
                     StateQuery::FocusedWindowIndex => {
-                        self.focused_container()?.focused_window_idx()
+                        self.focused_container()?.focused_window_idx().to_string()
                     }
-                }
-                .to_string();
+                    StateQuery::FocusedStackWindowCount => {
+                        let workspace = self.focused_workspace()?;
+                        match workspace.monocle_container() {
+                            Some(container) => container.windows().len().to_string(),
+                            None => self.focused_container()?.windows().len().to_string(),
+                        }
+                    }
+                    StateQuery::FocusedStackWindowIndex => {
+                        let workspace = self.focused_workspace()?;
+                        match workspace.monocle_container() {
+                            Some(container) => container.focused_window_idx().to_string(),
+                            None => self.focused_container()?.focused_window_idx().to_string(),
+                        }
+                    }
+                    StateQuery::FocusedWorkspaceLayer => {
+                        self.focused_workspace()?.layer().to_string()
+                    }
+                    StateQuery::Version => ServerVersion::current().version,
+                };
 
                 reply.write_all(response.as_bytes())?;
             }
             SocketMessage::ResizeWindowEdge(direction, sizing) => {
                 self.resize_window(direction, sizing, self.resize_delta, true)?;
             }
+            SocketMessage::ResizeWindowEdgePercent(direction, sizing, percent) => {
+                self.resize_window_percent(direction, sizing, percent)?;
+            }
+            SocketMessage::CycleSplitRatio(axis) => {
+                self.cycle_split_ratio(axis)?;
+            }
             SocketMessage::ResizeWindowAxis(axis, sizing) => {
                 // If the user has a custom layout, allow for the resizing of the primary column
                 // with this signal
+                let monitor = self
+                    .focused_monitor()
+                    .ok_or_else(|| anyhow!("there is no monitor"))?;
+                let work_area_width = monitor.work_area_size().right;
+                let scale_factor = monitor.scale_factor();
+
                 let workspace = self.focused_workspace_mut()?;
                 let container_len = workspace.containers().len();
                 let no_layout_rules = workspace.layout_rules().is_empty();
@@ -1093,37 +1362,54 @@ impl WindowManager {
                     if matches!(axis, Axis::Horizontal) {
                         #[allow(clippy::cast_precision_loss)]
                         let percentage = custom
-                            .primary_width_percentage()
+                            .primary_width_percentage_of(work_area_width, scale_factor)
                             .unwrap_or(100.0 / (custom.len() as f32));
 
                         if no_layout_rules {
                             match sizing {
-                                Sizing::Increase => {
-                                    custom.set_primary_width_percentage(percentage + 5.0);
-                                }
-                                Sizing::Decrease => {
-                                    custom.set_primary_width_percentage(percentage - 5.0);
-                                }
+                                Sizing::Increase => custom.set_primary_width_percentage_of(
+                                    percentage + 5.0,
+                                    work_area_width,
+                                    scale_factor,
+                                ),
+                                Sizing::Decrease => custom.set_primary_width_percentage_of(
+                                    percentage - 5.0,
+                                    work_area_width,
+                                    scale_factor,
+                                ),
                             }
                         } else {
                             for rule in workspace.layout_rules_mut() {
                                 if container_len >= rule.0 {
                                     if let Layout::Custom(ref mut custom) = rule.1 {
                                         match sizing {
-                                            Sizing::Increase => {
-                                                custom
-                                                    .set_primary_width_percentage(percentage + 5.0);
-                                            }
-                                            Sizing::Decrease => {
-                                                custom
-                                                    .set_primary_width_percentage(percentage - 5.0);
-                                            }
+                                            Sizing::Increase => custom
+                                                .set_primary_width_percentage_of(
+                                                    percentage + 5.0,
+                                                    work_area_width,
+                                                    scale_factor,
+                                                ),
+                                            Sizing::Decrease => custom
+                                                .set_primary_width_percentage_of(
+                                                    percentage - 5.0,
+                                                    work_area_width,
+                                                    scale_factor,
+                                                ),
                                         }
                                     }
                                 }
                             }
                         }
                     }
+                    // The Scrolling layout resizes the focused column by adjusting its share of the
+                    // visible width
+                } else if matches!(
+                    workspace.layout(),
+                    Layout::Default(DefaultLayout::Scrolling)
+                ) {
+                    if matches!(axis, Axis::Horizontal | Axis::HorizontalAndVertical) {
+                        workspace.resize_focused_column(sizing);
+                    }
                     // Otherwise proceed with the resizing logic for individual window containers in the
                     // assumed BSP layout
                 } else {
@@ -1305,6 +1591,11 @@ impl WindowManager {
                     // Initialize the new wm
                     wm.init()?;
 
+                    // Windows which have already been placed by initial workspace rules should not
+                    // be moved again just because the configuration has been replaced
+                    wm.already_moved_window_handles = self.already_moved_window_handles.clone();
+                    wm.manually_moved_window_handles = self.manually_moved_window_handles.clone();
+
                     // This is equivalent to StaticConfig::postload for this use case
                     StaticConfig::reload(config, &mut wm)?;
 
@@ -1312,7 +1603,11 @@ impl WindowManager {
                     *self = wm;
                 }
             }
-            SocketMessage::ReloadStaticConfiguration(ref pathbuf) => {
+            SocketMessage::ReloadStaticConfiguration(ref pathbuf, clear_session_rules) => {
+                if clear_session_rules {
+                    SESSION_WORKSPACE_RULES.lock().clear();
+                }
+
                 self.reload_static_configuration(pathbuf)?;
             }
             SocketMessage::CompleteConfiguration => {
@@ -1383,6 +1678,26 @@ impl WindowManager {
                     }));
                 }
             }
+            SocketMessage::AnimationBlacklist(identifier, ref id) => {
+                let mut identifiers = ANIMATIONS_BLACKLIST.lock();
+
+                let mut should_push = true;
+                for i in &*identifiers {
+                    if let MatchingRule::Simple(i) = i {
+                        if i.id.eq(id) {
+                            should_push = false;
+                        }
+                    }
+                }
+
+                if should_push {
+                    identifiers.push(MatchingRule::Simple(IdWithIdentifier {
+                        kind: identifier,
+                        id: id.clone(),
+                        matching_strategy: Option::from(MatchingStrategy::Legacy),
+                    }));
+                }
+            }
             SocketMessage::ManageFocusedWindow => {
                 self.manage_focused_window()?;
             }
@@ -1391,77 +1706,99 @@ impl WindowManager {
             }
             SocketMessage::InvisibleBorders(_rect) => {}
             SocketMessage::WorkAreaOffset(rect) => {
-                self.work_area_offset = Option::from(rect);
+                self.work_area_offset = Option::from(RectOrPercent::from(rect));
                 self.retile_all(false)?;
             }
             SocketMessage::MonitorWorkAreaOffset(monitor_idx, rect) => {
                 if let Some(monitor) = self.monitors_mut().get_mut(monitor_idx) {
-                    monitor.set_work_area_offset(Option::from(rect));
+                    monitor
+                        .set_configured_work_area_offset(Option::from(RectOrPercent::from(rect)));
                     self.retile_all(false)?;
                 }
             }
+            SocketMessage::RegisterAppBar(hwnd, edge, thickness) => {
+                reserved_space_manager::register_app_bar(hwnd, edge, thickness);
+            }
+            SocketMessage::UnregisterAppBar(hwnd) => {
+                reserved_space_manager::unregister_app_bar(hwnd);
+            }
+            SocketMessage::MonitorWindowBasedWorkAreaOffsetCounting(monitor_idx, counting) => {
+                let offset = self.work_area_offset;
+                if let Some(monitor) = self.monitors_mut().get_mut(monitor_idx) {
+                    monitor.set_window_based_work_area_offset_counting(counting);
+                    monitor.update_focused_workspace(offset)?;
+                }
+            }
             SocketMessage::QuickSave => {
-                let workspace = self.focused_workspace()?;
-                let resize = workspace.resize_dimensions();
-
                 let quicksave_json = std::env::temp_dir().join("komorebi.quicksave.json");
-
-                let file = OpenOptions::new()
-                    .write(true)
-                    .truncate(true)
-                    .create(true)
-                    .open(quicksave_json)?;
-
-                serde_json::to_writer_pretty(&file, &resize)?;
+                self.save_resize_dimensions(&quicksave_json)?;
             }
             SocketMessage::QuickLoad => {
-                let workspace = self.focused_workspace_mut()?;
-
                 let quicksave_json = std::env::temp_dir().join("komorebi.quicksave.json");
-
-                let file = File::open(&quicksave_json)
-                    .map_err(|_| anyhow!("no quicksave found at {}", quicksave_json.display()))?;
-
-                let resize: Vec<Option<Rect>> = serde_json::from_reader(file)?;
-
-                workspace.set_resize_dimensions(resize);
-                self.update_focused_workspace(false, false)?;
+                if let Err(error) = self.load_resize_dimensions(&quicksave_json) {
+                    reply.write_all(error.to_string().as_bytes())?;
+                    return Err(error);
+                }
             }
             SocketMessage::Save(ref path) => {
-                let workspace = self.focused_workspace_mut()?;
-                let resize = workspace.resize_dimensions();
-
-                let file = OpenOptions::new()
-                    .write(true)
-                    .truncate(true)
-                    .create(true)
-                    .open(path)?;
-
-                serde_json::to_writer_pretty(&file, &resize)?;
+                self.save_resize_dimensions(path)?;
+            }
+            SocketMessage::SaveAll(ref path) => {
+                self.save_all_resize_dimensions(path)?;
             }
             SocketMessage::Load(ref path) => {
-                let workspace = self.focused_workspace_mut()?;
-
-                let file =
-                    File::open(path).map_err(|_| anyhow!("no file found at {}", path.display()))?;
-
-                let resize: Vec<Option<Rect>> = serde_json::from_reader(file)?;
-
-                workspace.set_resize_dimensions(resize);
-                self.update_focused_workspace(false, false)?;
+                if let Err(error) = self.load_resize_dimensions(path) {
+                    reply.write_all(error.to_string().as_bytes())?;
+                    return Err(error);
+                }
+            }
+            SocketMessage::SaveFloatingLayout(ref path) => {
+                self.save_floating_layout(path)?;
+            }
+            SocketMessage::LoadFloatingLayout(ref path) => {
+                self.load_floating_layout(path)?;
             }
             SocketMessage::AddSubscriberSocket(ref socket) => {
-                let mut sockets = SUBSCRIPTION_SOCKETS.lock();
                 let socket_path = DATA_DIR.join(socket);
-                sockets.insert(socket.clone(), socket_path);
+                SUBSCRIPTION_SOCKETS
+                    .lock()
+                    .insert(socket.clone(), socket_path);
+
+                if let Err(error) = self.send_initial_notification(socket, &message) {
+                    tracing::warn!(
+                        "could not send initial notification to subscriber {socket}: {error}"
+                    );
+                }
             }
-            SocketMessage::AddSubscriberSocketWithOptions(ref socket, options) => {
-                let mut sockets = SUBSCRIPTION_SOCKETS.lock();
+            SocketMessage::AddSubscriberSocketWithOptions(ref socket, ref options) => {
+                let unknown_event_types = options
+                    .event_types
+                    .iter()
+                    .filter(|event_type| !NotificationEvent::is_known_event_type(event_type))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if !unknown_event_types.is_empty() {
+                    let error = format!("unknown event types: {}", unknown_event_types.join(", "));
+
+                    reply.write_all(error.as_bytes())?;
+                    bail!(error);
+                }
+
                 let socket_path = DATA_DIR.join(socket);
-                sockets.insert(socket.clone(), socket_path);
+                SUBSCRIPTION_SOCKETS
+                    .lock()
+                    .insert(socket.clone(), socket_path);
+
+                SUBSCRIPTION_SOCKET_OPTIONS
+                    .lock()
+                    .insert(socket.clone(), options.clone());
 
-                let mut socket_options = SUBSCRIPTION_SOCKET_OPTIONS.lock();
-                socket_options.insert(socket.clone(), options);
+                if let Err(error) = self.send_initial_notification(socket, &message) {
+                    tracing::warn!(
+                        "could not send initial notification to subscriber {socket}: {error}"
+                    );
+                }
             }
             SocketMessage::RemoveSubscriberSocket(ref socket) => {
                 let mut sockets = SUBSCRIPTION_SOCKETS.lock();
@@ -1475,6 +1812,13 @@ impl WindowManager {
                 })?;
 
                 pipes.insert(subscriber.clone(), pipe);
+                drop(pipes);
+
+                if let Err(error) = self.send_initial_notification(subscriber, &message) {
+                    tracing::warn!(
+                        "could not send initial notification to subscriber {subscriber}: {error}"
+                    );
+                }
             }
             SocketMessage::RemoveSubscriberPipe(ref subscriber) => {
                 let mut pipes = SUBSCRIPTION_PIPES.lock();
@@ -1483,6 +1827,10 @@ impl WindowManager {
             SocketMessage::MouseFollowsFocus(enable) => {
                 self.mouse_follows_focus = enable;
             }
+            SocketMessage::MouseFollowsFocusScope(scope) => {
+                let mut mouse_follows_focus_scope = MOUSE_FOLLOWS_FOCUS_SCOPE.lock();
+                *mouse_follows_focus_scope = scope;
+            }
             SocketMessage::ToggleMouseFollowsFocus => {
                 self.mouse_follows_focus = !self.mouse_follows_focus;
             }
@@ -1536,10 +1884,26 @@ impl WindowManager {
                         .set_float_override(Some(!current_global_override));
                 };
             }
+            SocketMessage::WorkspaceLayer(layer) => self.set_workspace_layer(layer)?,
+            SocketMessage::ToggleWorkspaceLayer => {
+                let layer = match self.focused_workspace()?.layer() {
+                    WorkspaceLayer::Tiling => WorkspaceLayer::Floating,
+                    WorkspaceLayer::Floating => WorkspaceLayer::Tiling,
+                };
+
+                self.set_workspace_layer(layer)?;
+            }
             SocketMessage::WindowHidingBehaviour(behaviour) => {
                 let mut hiding_behaviour = HIDING_BEHAVIOUR.lock();
                 *hiding_behaviour = behaviour;
             }
+            SocketMessage::WorkspaceWindowHidingBehaviour(
+                monitor_idx,
+                workspace_idx,
+                behaviour,
+            ) => {
+                self.set_workspace_window_hiding_behaviour(monitor_idx, workspace_idx, behaviour)?;
+            }
             SocketMessage::ToggleCrossMonitorMoveBehaviour => {
                 match self.cross_monitor_move_behaviour {
                     MoveBehaviour::Swap => {
@@ -1554,6 +1918,9 @@ impl WindowManager {
             SocketMessage::CrossMonitorMoveBehaviour(behaviour) => {
                 self.cross_monitor_move_behaviour = behaviour;
             }
+            SocketMessage::DragBehaviour(mode, modifier) => {
+                self.drag_behaviour = DragBehaviour { mode, modifier };
+            }
             SocketMessage::UnmanagedWindowOperationBehaviour(behaviour) => {
                 self.unmanaged_window_operation_behaviour = behaviour;
             }
@@ -1589,6 +1956,14 @@ impl WindowManager {
                     border_manager::send_notification(None);
                 }
             }
+            SocketMessage::BorderHitTest(enable) => {
+                border_manager::BORDER_HIT_TEST.store(enable, Ordering::SeqCst);
+                border_manager::update_border_window_options();
+            }
+            SocketMessage::BorderZOrder(z_order) => {
+                border_manager::Z_ORDER.store(z_order);
+                border_manager::update_border_window_options();
+            }
             SocketMessage::BorderColour(kind, r, g, b) => match kind {
                 WindowKind::Single => {
                     border_manager::FOCUSED.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
@@ -1605,7 +1980,42 @@ impl WindowManager {
                 WindowKind::Floating => {
                     border_manager::FLOATING.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
                 }
+                WindowKind::Pinned => {
+                    border_manager::PINNED.store(Rgb::new(r, g, b).into(), Ordering::SeqCst);
+                }
             },
+            SocketMessage::BorderColourOverride(identifier, ref id, r, g, b) => {
+                let colour = Colour::Rgb(Rgb::new(r, g, b));
+                let mut overrides = border_manager::BORDER_COLOUR_OVERRIDES.lock();
+
+                let existing = overrides.iter_mut().find(|colour_override| {
+                    matches!(
+                        &colour_override.matching_rule,
+                        MatchingRule::Simple(rule) if rule.kind == identifier && rule.id.eq(id)
+                    )
+                });
+
+                match existing {
+                    Some(colour_override) => colour_override.focused = Option::from(colour),
+                    None => overrides.push(BorderColourOverride {
+                        matching_rule: MatchingRule::Simple(IdWithIdentifier {
+                            kind: identifier,
+                            id: id.clone(),
+                            matching_strategy: Option::from(MatchingStrategy::Legacy),
+                        }),
+                        focused: Option::from(colour),
+                        unfocused: None,
+                    }),
+                }
+
+                drop(overrides);
+
+                // Borders are recreated so that the new colour is picked up even if nothing else
+                // about the window manager state has changed
+                if matches!(IMPLEMENTATION.load(), BorderImplementation::Komorebi) {
+                    border_manager::destroy_all_borders()?;
+                }
+            }
             SocketMessage::BorderStyle(style) => {
                 STYLE.store(style);
             }
@@ -1615,6 +2025,12 @@ impl WindowManager {
             SocketMessage::BorderOffset(offset) => {
                 border_manager::BORDER_OFFSET.store(offset, Ordering::SeqCst);
             }
+            SocketMessage::BorderWidthForWindowKind(window_kind, width) => {
+                border_manager::set_border_width(window_kind, Some(width));
+            }
+            SocketMessage::BorderOffsetForWindowKind(window_kind, offset) => {
+                border_manager::set_border_offset(window_kind, Some(offset));
+            }
             SocketMessage::Animation(enable, prefix) => match prefix {
                 Some(prefix) => {
                     ANIMATION_ENABLED_PER_ANIMATION
@@ -1642,11 +2058,13 @@ impl WindowManager {
             }
             SocketMessage::AnimationStyle(style, prefix) => match prefix {
                 Some(prefix) => {
-                    ANIMATION_STYLE_PER_ANIMATION.lock().insert(prefix, style);
+                    ANIMATION_STYLE_PER_ANIMATION
+                        .lock()
+                        .insert(prefix, style.validate()?);
                 }
                 None => {
                     let mut animation_style = ANIMATION_STYLE_GLOBAL.lock();
-                    *animation_style = style;
+                    *animation_style = style.validate()?;
                     ANIMATION_STYLE_PER_ANIMATION.lock().clear();
                 }
             },
@@ -1660,8 +2078,14 @@ impl WindowManager {
             SocketMessage::TransparencyAlpha(alpha) => {
                 transparency_manager::TRANSPARENCY_ALPHA.store(alpha, Ordering::SeqCst);
             }
+            SocketMessage::TransparencyIgnoreMonitor(monitor_idx, ignore) => {
+                transparency_manager::set_monitor_ignored(monitor_idx, ignore);
+            }
             SocketMessage::StackbarMode(mode) => {
-                STACKBAR_MODE.store(mode);
+                stackbar_manager::set_mode(mode);
+            }
+            SocketMessage::ToggleStackbar => {
+                stackbar_manager::toggle_mode();
             }
             SocketMessage::StackbarLabel(label) => {
                 STACKBAR_LABEL.store(label);
@@ -1747,6 +2171,46 @@ impl WindowManager {
                     }));
                 }
             }
+            SocketMessage::RestoreTitleBar(identifier, ref id) => {
+                NO_TITLEBAR.lock().retain(|rule| {
+                    !matches!(rule, MatchingRule::Simple(i) if i.kind == identifier && i.id.eq(id))
+                });
+
+                // Windows of the application which are still managed get their title bars back
+                let rules = vec![MatchingRule::Simple(IdWithIdentifier {
+                    kind: identifier,
+                    id: id.clone(),
+                    matching_strategy: Option::from(MatchingStrategy::Legacy),
+                })];
+
+                let regex_identifiers = REGEX_IDENTIFIERS.lock().clone();
+
+                for monitor in self.monitors() {
+                    for workspace in monitor.workspaces() {
+                        for window in workspace.all_windows() {
+                            let matches = should_act(
+                                &window.title().unwrap_or_default(),
+                                &window.exe().unwrap_or_default(),
+                                &window.class().unwrap_or_default(),
+                                &window.path().unwrap_or_default(),
+                                &rules,
+                                &regex_identifiers,
+                            )
+                            .is_some();
+
+                            if matches {
+                                window.restore_title_bar()?;
+                            }
+                        }
+                    }
+                }
+
+                self.update_focused_workspace(false, false)?;
+            }
+            SocketMessage::TitleBarWhitelist => {
+                let whitelist = serde_json::to_string_pretty(&*NO_TITLEBAR.lock())?;
+                reply.write_all(whitelist.as_bytes())?;
+            }
             SocketMessage::ToggleTitleBars => {
                 let current = REMOVE_TITLEBARS.load(Ordering::SeqCst);
                 REMOVE_TITLEBARS.store(!current, Ordering::SeqCst);
@@ -1754,35 +2218,113 @@ impl WindowManager {
             }
             SocketMessage::DebugWindow(hwnd) => {
                 let window = Window::from(hwnd);
-                let mut rule_debug = RuleDebug::default();
+                let mut rule_debug = RuleDebug::with_matched_rules();
                 let _ = window.should_manage(None, &mut rule_debug);
                 let schema = serde_json::to_string_pretty(&rule_debug)?;
 
                 reply.write_all(schema.as_bytes())?;
             }
-            SocketMessage::Theme(theme) => {
-                theme_manager::send_notification(theme);
+            SocketMessage::WhyIgnored(hwnd) => {
+                let window = Window::from(hwnd);
+                let mut rule_debug = RuleDebug::with_matched_rules();
+                let _ = window.should_manage(None, &mut rule_debug);
+
+                reply.write_all(serde_json::to_string_pretty(&vec![rule_debug])?.as_bytes())?;
+            }
+            SocketMessage::WhyIgnoredExe(ref exe) => {
+                let mut explanations = vec![];
+
+                for window in WindowsApi::visible_windows()? {
+                    if window
+                        .exe()
+                        .is_ok_and(|window_exe| window_exe.eq_ignore_ascii_case(exe))
+                    {
+                        let mut rule_debug = RuleDebug::with_matched_rules();
+                        let _ = window.should_manage(None, &mut rule_debug);
+                        explanations.push(rule_debug);
+                    }
+                }
+
+                reply.write_all(serde_json::to_string_pretty(&explanations)?.as_bytes())?;
+            }
+            SocketMessage::Theme(ref theme) => {
+                theme_manager::send_socket_notification(theme.clone());
             }
             // Deprecated commands
             SocketMessage::AltFocusHack(_)
             | SocketMessage::IdentifyBorderOverflowApplication(_, _) => {}
         };
 
-        notify_subscribers(
-            Notification {
-                event: NotificationEvent::Socket(message.clone()),
-                state: self.as_ref().into(),
-            },
-            initial_state.has_been_modified(self.as_ref()),
-        )?;
+        self.update_container_focus_history();
+
+        // New subscribers have already been sent an initial notification with the current state,
+        // and themes are published by the theme manager once they have actually been applied, so
+        // that the global state sent along with them is up to date
+        let published_by_theme_manager = matches!(message, SocketMessage::Theme(_));
+
+        state_recorder::record(NotificationEvent::Socket(message.clone()), self);
+
+        if !message.is_subscription() && !published_by_theme_manager {
+            notify_subscribers(
+                Notification {
+                    event: NotificationEvent::Socket(message.clone()),
+                    state: self.as_ref().into(),
+                    global_state: message.affects_global_state().then(GlobalState::default),
+                },
+                initial_state.has_been_modified(self.as_ref()),
+            )?;
+        }
+
+        if let Err(error) = self.apply_focus_follows_mouse_override() {
+            tracing::error!("failed to apply focus follows mouse override: {error}");
+        }
 
         border_manager::send_notification(None);
         transparency_manager::send_notification();
         stackbar_manager::send_notification();
+        custom_layout_watcher::refresh(self);
 
         tracing::info!("processed");
         Ok(())
     }
+
+    fn send_initial_notification(&self, subscriber: &str, message: &SocketMessage) -> Result<()> {
+        notify_subscriber(
+            subscriber,
+            &Notification {
+                event: NotificationEvent::Socket(message.clone()),
+                state: self.into(),
+                global_state: None,
+            },
+        )
+    }
+}
+
+/// Whether a message can be sent by a TCP client, which may be on another machine when
+/// --tcp-port is used, as opposed to a local socket client
+const fn is_allowed_over_tcp(message: &SocketMessage) -> bool {
+    !matches!(message, SocketMessage::EagerFocusOrLaunch(..))
+}
+
+/// Whether a message is still processed while the window manager is paused
+const fn is_allowed_while_paused(message: &SocketMessage) -> bool {
+    matches!(
+        message,
+        SocketMessage::TogglePause
+            | SocketMessage::State
+            | SocketMessage::GlobalState
+            | SocketMessage::Snapshot
+            | SocketMessage::Version
+            | SocketMessage::WorkspaceRules
+            | SocketMessage::LogTail(_)
+            | SocketMessage::StateRecording(_)
+            | SocketMessage::TitleBarWhitelist
+            | SocketMessage::WhyIgnored(_)
+            | SocketMessage::WhyIgnoredExe(_)
+            | SocketMessage::Stop
+    )
+        // Subscribers reconnecting while paused should still receive the current state
+        || message.is_subscription()
 }
 
 pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream) -> Result<()> {
@@ -1802,15 +2344,11 @@ pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream)
             }
             Some(mut wm) => {
                 if wm.is_paused {
-                    return match message {
-                        SocketMessage::TogglePause
-                        | SocketMessage::State
-                        | SocketMessage::GlobalState
-                        | SocketMessage::Stop => Ok(wm.process_command(message, &mut stream)?),
-                        _ => {
-                            tracing::trace!("ignoring while paused");
-                            Ok(())
-                        }
+                    return if is_allowed_while_paused(&message) {
+                        Ok(wm.process_command(message, &mut stream)?)
+                    } else {
+                        tracing::trace!("ignoring while paused");
+                        Ok(())
                     };
                 }
 
@@ -1829,6 +2367,26 @@ pub fn read_commands_tcp(
 ) -> Result<()> {
     let mut reader = BufReader::new(stream.try_clone()?);
 
+    // Every valid message is longer than the preamble, so legacy clients can be told apart by
+    // their first four bytes
+    let mut preamble = [0; 4];
+    if reader.read_exact(&mut preamble).is_err() {
+        tracing::warn!("removing disconnected tcp client: {addr}");
+        TCP_CONNECTIONS.lock().remove(addr);
+        return Ok(());
+    }
+
+    if &preamble == FRAMED_PREAMBLE {
+        if let Some(connection) = TCP_CONNECTIONS.lock().get_mut(addr) {
+            connection.framed = true;
+        }
+
+        tracing::info!("tcp client negotiated framed mode: {addr}");
+        return read_framed_commands_tcp(wm, &mut reader, addr);
+    }
+
+    let mut pending = preamble.to_vec();
+
     loop {
         let mut buf = vec![0; 1024];
         match reader.read(&mut buf) {
@@ -1839,7 +2397,8 @@ pub fn read_commands_tcp(
                 break;
             }
             Ok(size) => {
-                let Ok(message) = SocketMessage::from_str(&String::from_utf8_lossy(&buf[..size]))
+                pending.extend_from_slice(&buf[..size]);
+                let Ok(message) = SocketMessage::from_str(&String::from_utf8_lossy(&pending))
                 else {
                     tracing::warn!("client sent an invalid message, disconnecting: {addr}");
                     let mut connections = TCP_CONNECTIONS.lock();
@@ -1847,18 +2406,21 @@ pub fn read_commands_tcp(
                     break;
                 };
 
+                pending.clear();
+
+                if !is_allowed_over_tcp(&message) {
+                    tracing::warn!("ignoring message which is not allowed over tcp from {addr}");
+                    continue;
+                }
+
                 let mut wm = wm.lock();
 
                 if wm.is_paused {
-                    return match message {
-                        SocketMessage::TogglePause
-                        | SocketMessage::State
-                        | SocketMessage::GlobalState
-                        | SocketMessage::Stop => Ok(wm.process_command(message, stream)?),
-                        _ => {
-                            tracing::trace!("ignoring while paused");
-                            Ok(())
-                        }
+                    return if is_allowed_while_paused(&message) {
+                        Ok(wm.process_command(message, stream)?)
+                    } else {
+                        tracing::trace!("ignoring while paused");
+                        Ok(())
                     };
                 }
 
@@ -1869,3 +2431,63 @@ pub fn read_commands_tcp(
 
     Ok(())
 }
+
+fn read_framed_commands_tcp(
+    wm: &Arc<Mutex<WindowManager>>,
+    reader: &mut impl Read,
+    addr: &str,
+) -> Result<()> {
+    loop {
+        let mut length = [0; 4];
+        if reader.read_exact(&mut length).is_err() {
+            break;
+        }
+
+        let length = u32::from_be_bytes(length) as usize;
+        if length > MAXIMUM_FRAME_LENGTH {
+            tracing::warn!("client sent a frame of {length} bytes, disconnecting: {addr}");
+            break;
+        }
+
+        let mut payload = vec![0; length];
+        if reader.read_exact(&mut payload).is_err() {
+            break;
+        }
+
+        let Ok(message) = SocketMessage::from_str(&String::from_utf8_lossy(&payload)) else {
+            tracing::warn!("client sent an invalid message, disconnecting: {addr}");
+            break;
+        };
+
+        // Replies are buffered so that they can be written as a single frame, without being
+        // interleaved with notifications pushed to the same connection
+        let mut response = vec![];
+
+        if is_allowed_over_tcp(&message) {
+            let mut wm = wm.lock();
+            if wm.is_paused && !is_allowed_while_paused(&message) {
+                tracing::trace!("ignoring while paused");
+            } else {
+                wm.process_command(message, &mut response)?;
+            }
+        } else {
+            tracing::warn!("ignoring message which is not allowed over tcp from {addr}");
+        }
+
+        if !response.is_empty() {
+            let written = TCP_CONNECTIONS
+                .lock()
+                .get_mut(addr)
+                .is_some_and(|connection| connection.write_frame(&response).is_ok());
+
+            if !written {
+                break;
+            }
+        }
+    }
+
+    tracing::warn!("removing disconnected tcp client: {addr}");
+    TCP_CONNECTIONS.lock().remove(addr);
+
+    Ok(())
+}
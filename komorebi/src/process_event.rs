@@ -1,4 +1,5 @@
 use std::fs::OpenOptions;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
@@ -6,21 +7,26 @@ use std::time::Instant;
 
 use color_eyre::eyre::anyhow;
 use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_utils::atomic::AtomicCell;
 use crossbeam_utils::atomic::AtomicConsume;
 use parking_lot::Mutex;
 
+use crate::core::DragMode;
 use crate::core::OperationDirection;
 use crate::core::Rect;
 use crate::core::Sizing;
 use crate::core::WindowContainerBehaviour;
+use crate::core::WorkspaceLayer;
 
 use crate::border_manager;
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
+use crate::border_manager::TILED_WINDOW_KINDS;
 use crate::current_virtual_desktop;
 use crate::notify_subscribers;
 use crate::stackbar_manager;
+use crate::state_recorder;
 use crate::transparency_manager;
+use crate::watchdog;
 use crate::window::should_act;
 use crate::window::RuleDebug;
 use crate::window_manager::WindowManager;
@@ -33,10 +39,16 @@ use crate::workspace_reconciliator::ALT_TAB_HWND_INSTANT;
 use crate::Notification;
 use crate::NotificationEvent;
 use crate::State;
+use crate::AUTO_STACK_EXEMPT_HWNDS;
+use crate::AUTO_WORKSPACE_NAMES;
 use crate::DATA_DIR;
 use crate::FLOATING_APPLICATIONS;
+use crate::FLOAT_OVERRIDE_APPLICATIONS;
 use crate::HIDDEN_HWNDS;
+use crate::MINIMUM_SIZE_POLICY;
 use crate::REGEX_IDENTIFIERS;
+use crate::TASKBAR_HIDDEN_HWNDS;
+use crate::TILE_ORIGINS;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 
 #[tracing::instrument]
@@ -44,25 +56,118 @@ pub fn listen_for_events(wm: Arc<Mutex<WindowManager>>) {
     let receiver = wm.lock().incoming_events.clone();
 
     std::thread::spawn(move || {
-        tracing::info!("listening");
-        loop {
-            if let Ok(event) = receiver.recv() {
+        // A panic while processing an event restarts the loop instead of leaving every window
+        // frozen in place, unless there have been too many panics in a short time
+        while !watchdog::is_shutting_down() {
+            tracing::info!("listening");
+            watchdog::catch_panic("event processing", &wm, || handle_events(&wm, &receiver));
+        }
+    });
+}
+
+fn handle_events(wm: &Arc<Mutex<WindowManager>>, receiver: &Receiver<WindowManagerEvent>) {
+    loop {
+        if let Ok(event) = receiver.recv() {
+            for event in coalesce_event_storm(receiver, event) {
                 let mut guard = wm.lock();
-                match guard.process_event(event) {
-                    Ok(()) => {}
-                    Err(error) => {
-                        if cfg!(debug_assertions) {
-                            tracing::error!("{:?}", error)
-                        } else {
-                            tracing::error!("{}", error)
-                        }
-                    }
+                handle_event(&mut guard, event);
+
+                // Events which were deferred while a window was in a move/size loop are handled
+                // straight after the event which ended the loop, rather than being sent back to
+                // this thread's own queue, which could block if the queue is full
+                let released = std::mem::take(&mut *MOVE_SIZE_RELEASED_EVENTS.lock());
+                for event in released {
+                    handle_event(&mut guard, event);
                 }
             }
         }
-    });
+    }
+}
+
+fn handle_event(wm: &mut WindowManager, event: WindowManagerEvent) {
+    match wm.process_event(event) {
+        Ok(()) => {}
+        Err(error) => {
+            if cfg!(debug_assertions) {
+                tracing::error!("{:?}", error)
+            } else {
+                tracing::error!("{}", error)
+            }
+        }
+    }
 }
 
+/// How long to collect coalescable events after the first one arrives before handling them, in
+/// milliseconds
+pub static WINDOW_EVENT_DEBOUNCE: AtomicU64 = AtomicU64::new(50);
+
+/// Collects coalescable events for up to the debounce window after the first one arrives,
+/// keeping only the most recent event of each kind for each window.
+///
+/// The handlers of coalescable events read the current state of the window rather than the
+/// contents of the event, so handling the most recent one gives the same result as handling
+/// every event in the storm. Any other event ends the debounce window early and is handled
+/// after the collected events, which preserves the order of events that change the layout.
+fn coalesce_event_storm(
+    receiver: &Receiver<WindowManagerEvent>,
+    first: WindowManagerEvent,
+) -> Vec<WindowManagerEvent> {
+    let debounce = Duration::from_millis(WINDOW_EVENT_DEBOUNCE.load(Ordering::SeqCst));
+    if debounce.is_zero() || !first.is_coalescable() {
+        return vec![first];
+    }
+
+    let deadline = Instant::now() + debounce;
+    let mut events = vec![first];
+    let mut received = 1;
+    let mut interrupted_by = None;
+
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        match receiver.recv_timeout(remaining) {
+            Ok(event) if event.is_coalescable() => {
+                received += 1;
+                match events
+                    .iter_mut()
+                    .find(|e| e.hwnd() == event.hwnd() && e.title() == event.title())
+                {
+                    None => events.push(event),
+                    Some(existing) => *existing = event,
+                }
+            }
+            Ok(event) => {
+                interrupted_by = Some(event);
+                break;
+            }
+            Err(_) => break,
+        }
+    }
+
+    let collapsed = received - events.len();
+    if collapsed > 0 {
+        tracing::debug!(
+            "coalesced {received} window events into {} ({collapsed} collapsed)",
+            events.len()
+        );
+    }
+
+    events.extend(interrupted_by);
+    events
+}
+
+// Coalescable events emitted by a window while it is being moved or resized by the user, which
+// are handled once the move or resize ends
+static MOVE_SIZE_DEFERRED_EVENTS: Mutex<Vec<WindowManagerEvent>> = Mutex::new(Vec::new());
+
+// Deferred events of a window whose move/size loop has just ended, which are waiting to be handled
+static MOVE_SIZE_RELEASED_EVENTS: Mutex<Vec<WindowManagerEvent>> = Mutex::new(Vec::new());
+
+// The window currently being moved by the mouse from a monitor which is not being managed
+static UNMANAGED_MONITOR_MOVE: AtomicCell<Option<isize>> = AtomicCell::new(None);
+
+// When workspaces were last named after their focused windows
+static AUTO_WORKSPACE_NAMES_INSTANT: AtomicCell<Option<Instant>> = AtomicCell::new(None);
+const AUTO_WORKSPACE_NAMES_DEBOUNCE: Duration = Duration::from_millis(500);
+
 impl WindowManager {
     #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
     #[tracing::instrument(skip(self, event), fields(event = event.title(), winevent = event.winevent(), hwnd = event.hwnd()))]
@@ -72,6 +177,87 @@ impl WindowManager {
             return Ok(());
         }
 
+        // Windows on unmanaged monitors are left entirely alone, and any managed window which has
+        // been moved onto one is forgotten about
+        if self.window_is_on_unmanaged_monitor(event.window()) {
+            match event {
+                WindowManagerEvent::MoveResizeStart(_, window) => {
+                    UNMANAGED_MONITOR_MOVE.store(Some(window.hwnd));
+                }
+                WindowManagerEvent::MoveResizeEnd(_, _) => {
+                    UNMANAGED_MONITOR_MOVE.store(None);
+                    let pending_move_op = Arc::make_mut(&mut self.pending_move_op);
+                    *pending_move_op = None;
+                }
+                _ => {}
+            }
+
+            self.release_window_on_unmanaged_monitor(event.hwnd())?;
+            return Ok(());
+        }
+
+        // A window which has been moved off an unmanaged monitor is picked up as if it had just
+        // been shown on the monitor it was dropped on
+        let event = match event {
+            WindowManagerEvent::MoveResizeEnd(_, window)
+                if UNMANAGED_MONITOR_MOVE.load() == Some(window.hwnd) =>
+            {
+                UNMANAGED_MONITOR_MOVE.store(None);
+                WindowManagerEvent::Show(WinEvent::ObjectShow, window)
+            }
+            _ => event,
+        };
+
+        if let Err(error) = self.update_fullscreen_pauses() {
+            tracing::error!("failed to update fullscreen pauses: {error}");
+        }
+
+        // Fullscreen application windows which have paused their monitor are left alone
+        if self
+            .monitors()
+            .iter()
+            .any(|monitor| monitor.fullscreen_hwnd() == Some(event.hwnd()))
+        {
+            tracing::trace!("ignoring event for fullscreen window on paused monitor");
+            return Ok(());
+        }
+
+        // While a window is in a modal move/size loop, intermediate events which it emits are
+        // deferred so that the layout is only reconciled once, when the loop ends
+        match event {
+            WindowManagerEvent::MoveResizeEnd(_, window)
+            | WindowManagerEvent::Destroy(_, window) => {
+                let mut deferred = MOVE_SIZE_DEFERRED_EVENTS.lock();
+                let (ready, pending): (Vec<_>, Vec<_>) = deferred
+                    .drain(..)
+                    .partition(|deferred| deferred.hwnd() == window.hwnd);
+                *deferred = pending;
+
+                // These are handled by the event loop after this event has been handled
+                if matches!(event, WindowManagerEvent::MoveResizeEnd(..)) {
+                    MOVE_SIZE_RELEASED_EVENTS.lock().extend(ready);
+                }
+            }
+            _ if event.is_coalescable()
+                && self
+                    .pending_move_op
+                    .is_some_and(|(_, _, hwnd)| hwnd == event.hwnd()) =>
+            {
+                tracing::trace!("deferring event for window in a move/size loop");
+                let mut deferred = MOVE_SIZE_DEFERRED_EVENTS.lock();
+                match deferred
+                    .iter_mut()
+                    .find(|e| e.hwnd() == event.hwnd() && e.title() == event.title())
+                {
+                    None => deferred.push(event),
+                    Some(existing) => *existing = event,
+                }
+
+                return Ok(());
+            }
+            _ => {}
+        }
+
         let mut rule_debug = RuleDebug::default();
 
         let should_manage = event.window().should_manage(Some(event), &mut rule_debug)?;
@@ -150,6 +336,7 @@ impl WindowManager {
                     if let Ok(class) = window.class() {
                         if class != "OleMainThreadWndClass"
                             && self.focused_monitor_idx() != monitor_idx
+                            && !self.is_unmanaged_monitor(monitor_idx)
                         {
                             self.focus_monitor(monitor_idx)?;
                         }
@@ -174,13 +361,48 @@ impl WindowManager {
                 self.has_pending_raise_op = false;
             }
             WindowManagerEvent::Destroy(_, window) | WindowManagerEvent::Unmanage(window) => {
+                if matches!(event, WindowManagerEvent::Unmanage(_)) {
+                    if let Some(origin) = self.focused_workspace()?.tile_origin(window.hwnd) {
+                        TILE_ORIGINS.lock().insert(window.hwnd, origin);
+                    }
+                }
+
                 if self.focused_workspace()?.contains_window(window.hwnd) {
                     self.focused_workspace_mut()?.remove_window(window.hwnd)?;
                     self.update_focused_workspace(false, false)?;
 
-                    let mut already_moved_window_handles = self.already_moved_window_handles.lock();
+                    {
+                        let mut already_moved_window_handles =
+                            self.already_moved_window_handles.lock();
+
+                        already_moved_window_handles.remove(&window.hwnd);
+                    }
 
-                    already_moved_window_handles.remove(&window.hwnd);
+                    // Windows which are unmanaged or moved elsewhere leave the workspace empty
+                    // deliberately, so only closing the last window should move focus away
+                    if matches!(event, WindowManagerEvent::Destroy(..))
+                        && self.focused_workspace()?.is_empty()
+                    {
+                        self.handle_last_window_closed()?;
+                    }
+                }
+
+                // Manual moves are forgotten when the window is destroyed, wherever it was
+                if matches!(event, WindowManagerEvent::Destroy(..)) {
+                    let mut manually_moved_window_handles =
+                        self.manually_moved_window_handles.lock();
+
+                    manually_moved_window_handles.remove(&window.hwnd);
+
+                    TILE_ORIGINS.lock().remove(&window.hwnd);
+
+                    AUTO_STACK_EXEMPT_HWNDS
+                        .lock()
+                        .retain(|hwnd| *hwnd != window.hwnd);
+
+                    TASKBAR_HIDDEN_HWNDS
+                        .lock()
+                        .retain(|hwnd| *hwnd != window.hwnd);
                 }
             }
             WindowManagerEvent::Minimize(_, window) => {
@@ -194,7 +416,23 @@ impl WindowManager {
                 }
 
                 if hide {
-                    self.focused_workspace_mut()?.remove_window(window.hwnd)?;
+                    let workspace = self.focused_workspace_mut()?;
+                    let hiding_behaviour = workspace.hiding_behaviour();
+
+                    // Windows minimized out of a stack keep their place in the container so that
+                    // they can be reinstated at the same index when they are restored
+                    let mut minimized_in_stack = false;
+                    if let Some(container) = workspace.container_for_window_mut(window.hwnd) {
+                        if container.minimize_window(window.hwnd) {
+                            container.load_focused_window_with_behaviour(hiding_behaviour);
+                            minimized_in_stack = true;
+                        }
+                    }
+
+                    if !minimized_in_stack {
+                        workspace.remove_window(window.hwnd)?;
+                    }
+
                     self.update_focused_workspace(false, false)?;
                 }
             }
@@ -267,11 +505,15 @@ impl WindowManager {
                         } else {
                             workspace.focus_container_by_window(window.hwnd)?;
                         }
+
+                        workspace.set_layer(WorkspaceLayer::Tiling);
                     }
                     Some(idx) => {
                         if let Some(window) = workspace.floating_windows().get(idx) {
                             window.focus(false)?;
                         }
+
+                        workspace.set_layer(WorkspaceLayer::Floating);
                     }
                 }
             }
@@ -284,6 +526,13 @@ impl WindowManager {
                     tracing::info!("ignoring uncloak after monocle move by mouse across monitors");
                     self.uncloack_to_ignore = self.uncloack_to_ignore.saturating_sub(1);
                 } else {
+                    if matches!(
+                        event,
+                        WindowManagerEvent::Show(WinEvent::SystemMinimizeEnd, _)
+                    ) {
+                        self.restore_minimized_stack_window(window.hwnd)?;
+                    }
+
                     let focused_monitor_idx = self.focused_monitor_idx();
                     let focused_workspace_idx =
                         self.focused_workspace_idx_for_monitor_idx(focused_monitor_idx)?;
@@ -336,7 +585,7 @@ impl WindowManager {
                                     "ignoring show event for window already associated with another workspace"
                                 );
 
-                                window.hide();
+                                window.hide_with_behaviour(workspace.hiding_behaviour());
                                 proceed = false;
                             }
                         }
@@ -347,15 +596,29 @@ impl WindowManager {
                             focused_monitor_idx,
                             focused_workspace_idx,
                         );
+
+                        // Windows which a workspace rule is about to move elsewhere are stacked
+                        // on their target workspace when the rule is enforced instead
+                        let should_auto_stack =
+                            self.focused_workspace()?.should_auto_stack(window.hwnd)
+                                && self
+                                    .workspace_rule_target(&window)
+                                    .is_none_or(|target| target == focused_pair);
+
                         let workspace = self.focused_workspace_mut()?;
                         let workspace_contains_window = workspace.contains_window(window.hwnd);
+                        let workspace_float_override = *workspace.float_override();
                         let monocle_container = workspace.monocle_container().clone();
 
                         if !workspace_contains_window && !needs_reconciliation {
                             let floating_applications = FLOATING_APPLICATIONS.lock();
+                            let float_override_applications = FLOAT_OVERRIDE_APPLICATIONS.lock();
                             let mut should_float = false;
+                            let mut should_float_override = false;
 
-                            if !floating_applications.is_empty() {
+                            if !floating_applications.is_empty()
+                                || !float_override_applications.is_empty()
+                            {
                                 let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
                                 if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
@@ -370,29 +633,63 @@ impl WindowManager {
                                         &regex_identifiers,
                                     )
                                     .is_some();
+
+                                    // A float override set on the workspace always takes
+                                    // precedence over application-specific float overrides
+                                    should_float_override = workspace_float_override.is_none()
+                                        && should_act(
+                                            &title,
+                                            &exe_name,
+                                            &class,
+                                            &path,
+                                            &float_override_applications,
+                                            &regex_identifiers,
+                                        )
+                                        .is_some();
                                 }
                             }
 
                             behaviour.float_override = behaviour.float_override
-                                || (should_float
+                                || ((should_float || should_float_override)
                                     && !matches!(event, WindowManagerEvent::Manage(_)));
 
                             if behaviour.float_override {
                                 workspace.floating_windows_mut().push(window);
                                 self.update_focused_workspace(false, false)?;
+                            } else if matches!(event, WindowManagerEvent::Show(..))
+                                && workspace.swallow_window(window)
+                            {
+                                self.update_focused_workspace(false, false)?;
+                            } else if let Some(origin) =
+                                matches!(event, WindowManagerEvent::Manage(_))
+                                    .then(|| TILE_ORIGINS.lock().remove(&window.hwnd))
+                                    .flatten()
+                            {
+                                // Windows which are managed again go back to where they were
+                                // tiled before they were unmanaged
+                                workspace.restore_window_to_tile_origin(window, origin);
+                                self.update_focused_workspace(true, false)?;
+                                stackbar_manager::send_notification();
+                            } else if should_auto_stack && workspace.auto_stack_window(window) {
+                                self.update_focused_workspace(true, false)?;
+                                stackbar_manager::send_notification();
                             } else {
                                 match behaviour.current_behaviour {
                                     WindowContainerBehaviour::Create => {
-                                        workspace.new_container_for_window(window);
+                                        workspace.new_container_for_window_with_placement(
+                                            window,
+                                            behaviour.new_window_placement,
+                                        );
                                         self.update_focused_workspace(false, false)?;
                                     }
                                     WindowContainerBehaviour::Append => {
+                                        let hiding_behaviour = workspace.hiding_behaviour();
                                         workspace
                                             .focused_container_mut()
                                             .ok_or_else(|| {
                                                 anyhow!("there is no focused container")
                                             })?
-                                            .add_window(window);
+                                            .add_window(window, hiding_behaviour);
                                         self.update_focused_workspace(true, false)?;
                                         stackbar_manager::send_notification();
                                     }
@@ -426,7 +723,9 @@ impl WindowManager {
                             }
 
                             if !monocle_window_event && monocle_container.is_some() {
-                                window.hide();
+                                window.hide_with_behaviour(
+                                    self.focused_workspace()?.hiding_behaviour(),
+                                );
                             }
                         }
                     }
@@ -445,6 +744,12 @@ impl WindowManager {
                 *pending_move_op = Option::from((monitor_idx, workspace_idx, window.hwnd));
             }
             WindowManagerEvent::MoveResizeEnd(_, window) => {
+                // Windows can change their minimum size as they are moved between monitors or
+                // resized
+                if MINIMUM_SIZE_POLICY.lock().is_some() {
+                    window.refresh_minimum_size();
+                }
+
                 // We need this because if the event ends on a different monitor,
                 // that monitor will already have been focused and updated in the state
                 let pending = *self.pending_move_op;
@@ -530,6 +835,24 @@ impl WindowManager {
                     }
                 }
 
+                let drag_behaviour = self.drag_behaviour;
+                let modifier_is_pressed = drag_behaviour
+                    .modifier
+                    .is_some_and(WindowsApi::drag_modifier_is_pressed);
+
+                let drag_mode = match drag_behaviour.mode {
+                    DragMode::Swap
+                        if modifier_is_pressed
+                            || matches!(
+                                window_management_behaviour.current_behaviour,
+                                WindowContainerBehaviour::Append
+                            ) =>
+                    {
+                        DragMode::Stack
+                    }
+                    mode => mode,
+                };
+
                 let workspace = self.focused_workspace_mut()?;
                 if (*workspace.tile() && workspace.contains_managed_window(window.hwnd))
                     || moved_across_monitors
@@ -552,7 +875,18 @@ impl WindowManager {
                     if is_move {
                         tracing::info!("moving with mouse");
 
-                        if moved_across_monitors {
+                        if moved_across_monitors && matches!(drag_mode, DragMode::Disabled) {
+                            // Put the window back where it came from on the origin monitor
+                            if let Some((origin_monitor_idx, origin_workspace_idx, _)) = pending {
+                                self.focus_monitor(origin_monitor_idx)?;
+                                let origin_monitor = self
+                                    .monitors_mut()
+                                    .get_mut(origin_monitor_idx)
+                                    .ok_or_else(|| anyhow!("there is no monitor at this idx"))?;
+                                origin_monitor.focus_workspace(origin_workspace_idx)?;
+                                self.update_focused_workspace(self.mouse_follows_focus, false)?;
+                            }
+                        } else if moved_across_monitors {
                             if let Some((origin_monitor_idx, origin_workspace_idx, w_hwnd)) =
                                 pending
                             {
@@ -608,8 +942,11 @@ impl WindowManager {
                             workspace.floating_windows_mut().push(window);
                             self.update_focused_workspace(false, false)?;
                         } else {
-                            match window_management_behaviour.current_behaviour {
-                                WindowContainerBehaviour::Create => {
+                            match drag_mode {
+                                DragMode::Disabled => {
+                                    self.update_focused_workspace(self.mouse_follows_focus, false)?;
+                                }
+                                DragMode::Swap => {
                                     match workspace.container_idx_from_current_point() {
                                         Some(target_idx) => {
                                             workspace
@@ -624,7 +961,7 @@ impl WindowManager {
                                         }
                                     }
                                 }
-                                WindowContainerBehaviour::Append => {
+                                DragMode::Stack => {
                                     match workspace.container_idx_from_current_point() {
                                         Some(target_idx) => {
                                             workspace.move_window_to_container(target_idx)?;
@@ -668,8 +1005,7 @@ impl WindowManager {
                         }
 
                         // TODO: Determine if this is still needed
-                        let top_left_constant = BORDER_WIDTH.load(Ordering::SeqCst)
-                            + BORDER_OFFSET.load(Ordering::SeqCst);
+                        let top_left_constant = border_manager::border_padding(&TILED_WINDOW_KINDS);
 
                         if resize.right != 0
                             && (resize.left == top_left_constant || resize.left == 0)
@@ -736,14 +1072,42 @@ impl WindowManager {
 
         serde_json::to_writer_pretty(&file, &known_hwnds)?;
 
+        self.update_container_focus_history();
+
+        if AUTO_WORKSPACE_NAMES.load(Ordering::SeqCst) {
+            // Some applications such as terminals and browsers update their titles many times a
+            // second, so title changes rename workspaces at most once every debounce period
+            let update = match event {
+                WindowManagerEvent::FocusChange(..) | WindowManagerEvent::Destroy(..) => true,
+                WindowManagerEvent::TitleUpdate(..) => AUTO_WORKSPACE_NAMES_INSTANT
+                    .load()
+                    .is_none_or(|instant| instant.elapsed() >= AUTO_WORKSPACE_NAMES_DEBOUNCE),
+                _ => false,
+            };
+
+            if update {
+                self.update_auto_workspace_names();
+                AUTO_WORKSPACE_NAMES_INSTANT.store(Option::from(Instant::now()));
+            }
+        }
+
+        state_recorder::record(NotificationEvent::WindowManager(event), self);
+
         notify_subscribers(
             Notification {
                 event: NotificationEvent::WindowManager(event),
                 state: self.as_ref().into(),
+                global_state: None,
             },
             initial_state.has_been_modified(self.as_ref()),
         )?;
 
+        // Focusing a window on another monitor or workspace may have changed the effective focus
+        // follows mouse state
+        if let Err(error) = self.apply_focus_follows_mouse_override() {
+            tracing::error!("failed to apply focus follows mouse override: {error}");
+        }
+
         border_manager::send_notification(Some(event.hwnd()));
         transparency_manager::send_notification();
         stackbar_manager::send_notification();
@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
+use crossbeam_channel::Receiver;
+use crossbeam_channel::RecvTimeoutError;
 use parking_lot::Mutex;
 use winput::message_loop;
 use winput::message_loop::Event;
@@ -7,17 +13,47 @@ use winput::Action;
 
 use crate::core::FocusFollowsMouseImplementation;
 
+use crate::window::Window;
 use crate::window_manager::WindowManager;
+use crate::FOCUS_FOLLOWS_MOUSE_DELAY_MS;
+
+/// Whether the windows under the cursor match the focus follows mouse ignore rules, which would
+/// otherwise be matched again on every cursor movement
+#[derive(Debug, Default)]
+pub struct FocusFollowsMouseIgnoreCache(HashMap<isize, bool>);
+
+impl FocusFollowsMouseIgnoreCache {
+    /// Whether either the window to raise or the window directly under the cursor is ignored;
+    /// windows are forgotten once the cursor leaves them so that changes to the rules or to their
+    /// titles are picked up when the cursor returns
+    pub fn any_ignored(&mut self, hwnd: isize, cursor_pos_hwnd: isize) -> bool {
+        self.0
+            .retain(|cached, _| *cached == hwnd || *cached == cursor_pos_hwnd);
+
+        let mut is_ignored = |hwnd: isize| {
+            *self
+                .0
+                .entry(hwnd)
+                .or_insert_with(|| Window::from(hwnd).is_focus_follows_mouse_ignored())
+        };
+
+        is_ignored(hwnd) || (cursor_pos_hwnd != hwnd && is_ignored(cursor_pos_hwnd))
+    }
+}
 
 #[tracing::instrument]
 pub fn listen_for_movements(wm: Arc<Mutex<WindowManager>>) {
+    let (hover_sender, hover_receiver) = crossbeam_channel::unbounded();
+    listen_for_hovers(wm.clone(), hover_receiver);
+
     std::thread::spawn(move || {
         let mut ignore_movement = false;
+        let mut ignored = FocusFollowsMouseIgnoreCache::default();
 
         let receiver = message_loop::start().expect("could not start winput message loop");
 
         loop {
-            let focus_follows_mouse = wm.lock().focus_follows_mouse;
+            let focus_follows_mouse = wm.lock().effective_focus_follows_mouse();
             if matches!(
                 focus_follows_mouse,
                 Some(FocusFollowsMouseImplementation::Komorebi)
@@ -25,14 +61,30 @@ pub fn listen_for_movements(wm: Arc<Mutex<WindowManager>>) {
                 match receiver.next_event() {
                     // Don't want to send any raise events while we are dragging or resizing
                     Event::MouseButton { action, .. } => match action {
-                        Action::Press => ignore_movement = true,
+                        Action::Press => {
+                            ignore_movement = true;
+                            let _ = hover_sender.send(None);
+                        }
                         Action::Release => ignore_movement = false,
                     },
                     Event::MouseMoveRelative { .. } => {
                         if !ignore_movement {
-                            match wm.lock().raise_window_at_cursor_pos() {
-                                Ok(()) => {}
-                                Err(error) => tracing::error!("{}", error),
+                            if FOCUS_FOLLOWS_MOUSE_DELAY_MS.load(Ordering::SeqCst) == 0 {
+                                match wm.lock().raise_window_at_cursor_pos(&mut ignored) {
+                                    Ok(()) => {}
+                                    Err(error) => tracing::error!("{}", error),
+                                }
+                            } else {
+                                let hwnd =
+                                    match wm.lock().window_to_raise_at_cursor_pos(&mut ignored) {
+                                        Ok(hwnd) => hwnd,
+                                        Err(error) => {
+                                            tracing::error!("{}", error);
+                                            None
+                                        }
+                                    };
+
+                                let _ = hover_sender.send(hwnd);
                             }
                         }
                     }
@@ -42,3 +94,58 @@ pub fn listen_for_movements(wm: Arc<Mutex<WindowManager>>) {
         }
     });
 }
+
+/// Raise windows once the cursor has rested over them for the focus follows mouse delay,
+/// receiving the window to raise for every cursor movement, or None when there is nothing
+/// to raise and any pending raise should be cancelled
+fn listen_for_hovers(wm: Arc<Mutex<WindowManager>>, receiver: Receiver<Option<isize>>) {
+    std::thread::spawn(move || {
+        let mut pending: Option<(isize, Instant)> = None;
+        let mut ignored = FocusFollowsMouseIgnoreCache::default();
+
+        loop {
+            let message = match pending {
+                Some((_, deadline)) => receiver.recv_deadline(deadline),
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match message {
+                // Only restart the delay when the cursor has moved onto a different window
+                Ok(Some(hwnd)) => {
+                    if pending.is_none_or(|(pending_hwnd, _)| pending_hwnd != hwnd) {
+                        let delay = Duration::from_millis(
+                            FOCUS_FOLLOWS_MOUSE_DELAY_MS.load(Ordering::SeqCst),
+                        );
+
+                        pending = Option::from((hwnd, Instant::now() + delay));
+                    }
+                }
+                Ok(None) => pending = None,
+                Err(RecvTimeoutError::Timeout) => {
+                    if let Some((hwnd, _)) = pending.take() {
+                        // The delay may have been removed by a configuration reload while we
+                        // were waiting, in which case windows are already raised as the cursor
+                        // moves and this raise is stale
+                        if FOCUS_FOLLOWS_MOUSE_DELAY_MS.load(Ordering::SeqCst) == 0 {
+                            continue;
+                        }
+
+                        let mut wm = wm.lock();
+
+                        // The window may have been focused or moved while we were waiting
+                        match wm.window_to_raise_at_cursor_pos(&mut ignored) {
+                            Ok(Some(current)) if current == hwnd => {
+                                if let Err(error) = wm.raise_window(hwnd) {
+                                    tracing::error!("{}", error);
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(error) => tracing::error!("{}", error),
+                        }
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+}
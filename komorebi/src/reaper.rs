@@ -1,10 +1,32 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
 use crate::border_manager;
+use crate::stackbar_manager;
 use crate::WindowManager;
+use crate::WindowsApi;
+use crate::HIDDEN_HWNDS;
+use crate::HIDDEN_SCRATCHPAD_HWNDS;
+use crate::HUNG_HWNDS;
+use crate::MINIMUM_SIZES;
+use crate::PINNED_HWNDS;
+use crate::SCRATCHPADS;
+use crate::TASKBAR_HIDDEN_HWNDS;
+use crate::TILE_ORIGINS;
+use crate::TITLEBAR_ORIGINAL_STYLES;
 use parking_lot::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
+
+pub static HUNG_WINDOW_DETECTION: AtomicBool = AtomicBool::new(true);
+pub static HUNG_WINDOW_PROBE_INTERVAL: AtomicU64 = AtomicU64::new(5);
+pub static REAPER_INTERVAL_MS: AtomicU64 = AtomicU64::new(1000);
+
+/// Shorter intervals would keep the window manager locked for too much of the time
+pub const MINIMUM_REAPER_INTERVAL_MS: u64 = 100;
 
 pub fn watch_for_orphans(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
@@ -27,40 +49,163 @@ pub fn find_orphans(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
     tracing::info!("watching");
 
     let arc = wm.clone();
+    let mut last_probe = Instant::now();
 
     loop {
-        std::thread::sleep(Duration::from_secs(1));
+        std::thread::sleep(Duration::from_millis(
+            REAPER_INTERVAL_MS.load(Ordering::SeqCst),
+        ));
 
         let mut wm = arc.lock();
-        let offset = wm.work_area_offset;
-
-        for (i, monitor) in wm.monitors_mut().iter_mut().enumerate() {
-            let work_area = *monitor.work_area_size();
-            let window_based_work_area_offset = (
-                monitor.window_based_work_area_offset_limit(),
-                monitor.window_based_work_area_offset(),
-            );
-
-            let offset = if monitor.work_area_offset().is_some() {
-                monitor.work_area_offset()
-            } else {
-                offset
-            };
-
-            for (j, workspace) in monitor.workspaces_mut().iter_mut().enumerate() {
-                let reaped_orphans = workspace.reap_orphans()?;
-                if reaped_orphans.0 > 0 || reaped_orphans.1 > 0 {
-                    workspace.update(&work_area, offset, window_based_work_area_offset)?;
-                    border_manager::send_notification(None);
-                    tracing::info!(
-                        "reaped {} orphan window(s) and {} orphaned container(s) on monitor: {}, workspace: {}",
-                        reaped_orphans.0,
-                        reaped_orphans.1,
-                        i,
-                        j
-                    );
+
+        if HUNG_WINDOW_DETECTION.load(Ordering::SeqCst) {
+            let interval = HUNG_WINDOW_PROBE_INTERVAL.load(Ordering::SeqCst);
+            if last_probe.elapsed() >= Duration::from_secs(interval) {
+                last_probe = Instant::now();
+                probe_hung_windows(&wm);
+            }
+        } else {
+            let mut hung_hwnds = HUNG_HWNDS.lock();
+            if !hung_hwnds.is_empty() {
+                hung_hwnds.clear();
+                border_manager::send_notification(None);
+            }
+        }
+
+        collect_garbage(&mut wm)?;
+    }
+}
+
+/// Removes every window which has since been closed from the window manager and from the state
+/// tracked alongside it, returning how many entries were removed
+pub fn collect_garbage(wm: &mut WindowManager) -> color_eyre::Result<usize> {
+    let mut removed = 0;
+
+    for hwnds in [
+        &PINNED_HWNDS,
+        &HIDDEN_HWNDS,
+        &TASKBAR_HIDDEN_HWNDS,
+        &HUNG_HWNDS,
+    ] {
+        let mut hwnds = hwnds.lock();
+        let before = hwnds.len();
+        hwnds.retain(|hwnd| WindowsApi::is_window(*hwnd));
+        removed += before - hwnds.len();
+    }
+
+    TILE_ORIGINS.lock().retain(|hwnd, _| {
+        let exists = WindowsApi::is_window(*hwnd);
+        if !exists {
+            removed += 1;
+        }
+
+        exists
+    });
+
+    MINIMUM_SIZES.lock().retain(|hwnd, _| {
+        let exists = WindowsApi::is_window(*hwnd);
+        if !exists {
+            removed += 1;
+        }
+
+        exists
+    });
+
+    TITLEBAR_ORIGINAL_STYLES.lock().retain(|hwnd, _| {
+        let exists = WindowsApi::is_window(*hwnd);
+        if !exists {
+            removed += 1;
+        }
+
+        exists
+    });
+
+    SCRATCHPADS.lock().retain(|_, hwnds| {
+        let before = hwnds.len();
+        hwnds.retain(|hwnd| WindowsApi::is_window(*hwnd));
+        removed += before - hwnds.len();
+        !hwnds.is_empty()
+    });
+
+    HIDDEN_SCRATCHPAD_HWNDS
+        .lock()
+        .retain(|hwnd, _| WindowsApi::is_window(*hwnd));
+
+    let offset = wm.work_area_offset;
+
+    for (i, monitor) in wm.monitors_mut().iter_mut().enumerate() {
+        let work_area = *monitor.work_area_size();
+        let window_based_work_area_offset = (
+            monitor.window_based_work_area_offset_limit(),
+            monitor.window_based_work_area_offset(),
+            monitor.window_based_work_area_offset_counting(),
+        );
+
+        let offset = monitor.effective_work_area_offset(offset);
+
+        for (j, workspace) in monitor.workspaces_mut().iter_mut().enumerate() {
+            let reaped_orphans = workspace.reap_orphans()?;
+            if reaped_orphans.0 > 0 || reaped_orphans.1 > 0 {
+                workspace.update(&work_area, offset, window_based_work_area_offset)?;
+                removed += reaped_orphans.0 + reaped_orphans.1;
+                tracing::info!(
+                    "reaped {} orphan window(s) and {} orphaned container(s) on monitor: {}, workspace: {}",
+                    reaped_orphans.0,
+                    reaped_orphans.1,
+                    i,
+                    j
+                );
+            }
+        }
+    }
+
+    if removed > 0 {
+        border_manager::send_notification(None);
+        stackbar_manager::send_notification();
+    }
+
+    Ok(removed)
+}
+
+/// Marks managed windows which have stopped responding, and unmarks any which have recovered
+fn probe_hung_windows(wm: &WindowManager) {
+    let mut hung = vec![];
+
+    for monitor in wm.monitors() {
+        for workspace in monitor.workspaces() {
+            let containers = workspace
+                .containers()
+                .iter()
+                .chain(workspace.monocle_container());
+
+            let windows = containers
+                .flat_map(|container| container.windows())
+                .chain(workspace.floating_windows())
+                .chain(workspace.maximized_window());
+
+            for window in windows {
+                if WindowsApi::is_hung_app_window(window.hwnd) {
+                    hung.push(window.hwnd);
                 }
             }
         }
     }
+
+    let mut hung_hwnds = HUNG_HWNDS.lock();
+    if *hung_hwnds == hung {
+        return;
+    }
+
+    for hwnd in hung.iter().filter(|hwnd| !hung_hwnds.contains(hwnd)) {
+        tracing::warn!("window has stopped responding: {hwnd}");
+    }
+
+    for hwnd in hung_hwnds.iter().filter(|hwnd| !hung.contains(hwnd)) {
+        tracing::info!("window is responding again: {hwnd}");
+    }
+
+    *hung_hwnds = hung;
+    drop(hung_hwnds);
+
+    border_manager::send_notification(None);
 }
@@ -0,0 +1,252 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::core::config_generation::MatchingRule;
+use crate::core::Rect;
+use crate::should_act;
+use crate::WindowManager;
+use crate::WindowsApi;
+use crate::REGEX_IDENTIFIERS;
+use clap::ValueEnum;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use strum::Display;
+
+lazy_static! {
+    pub static ref RESERVED_SPACE_RULES: Mutex<Vec<ReservedSpaceRule>> = Mutex::new(vec![]);
+    // Bars which have explicitly registered the space they need (by hwnd)
+    pub static ref REGISTERED_APP_BARS: Mutex<HashMap<isize, RegisteredAppBar>> =
+        Mutex::new(HashMap::new());
+    // Monitors (by hmonitor) which currently have space reserved on them
+    static ref RESERVING_MONITORS: Mutex<HashSet<isize>> = Mutex::new(HashSet::new());
+}
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Display, Serialize, Deserialize, JsonSchema, ValueEnum,
+)]
+pub enum ReservedSpaceEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ReservedSpaceRule {
+    /// Rule to identify the window (eg. a third-party status bar) to reserve space for
+    pub matching_rule: MatchingRule,
+    /// The monitor edge that the window is docked to
+    pub edge: ReservedSpaceEdge,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RegisteredAppBar {
+    /// The monitor edge that the bar is docked to
+    pub edge: ReservedSpaceEdge,
+    /// The thickness of the bar in pixels, measured from the edge of the work area
+    pub thickness: i32,
+}
+
+// The space claimed on a monitor edge, either by a window matched by a rule or by a bar which
+// has registered its thickness
+#[derive(Debug, Copy, Clone)]
+enum Reservation {
+    Window(Rect),
+    Thickness(i32),
+}
+
+/// Register a bar docked to `edge` of the monitor its hwnd is on, so that `thickness` pixels are
+/// reserved on that edge until the bar unregisters or its hwnd is destroyed
+pub fn register_app_bar(hwnd: isize, edge: ReservedSpaceEdge, thickness: i32) {
+    tracing::info!("registering app bar {hwnd} on the {edge} edge with thickness {thickness}");
+    REGISTERED_APP_BARS.lock().insert(
+        hwnd,
+        RegisteredAppBar {
+            edge,
+            thickness: thickness.max(0),
+        },
+    );
+}
+
+pub fn unregister_app_bar(hwnd: isize) {
+    if REGISTERED_APP_BARS.lock().remove(&hwnd).is_some() {
+        tracing::info!("unregistering app bar {hwnd}");
+    }
+}
+
+pub fn watch_for_reserved_space(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match reserve_space(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    tracing::error!("restarting failed thread: {:?}", error)
+                } else {
+                    tracing::error!("restarting failed thread: {}", error)
+                }
+            }
+        }
+    });
+}
+
+pub fn reserve_space(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
+    tracing::info!("watching");
+
+    loop {
+        std::thread::sleep(Duration::from_secs(1));
+
+        let rules = RESERVED_SPACE_RULES.lock().clone();
+        let app_bars = {
+            let mut registered = REGISTERED_APP_BARS.lock();
+            registered.retain(|hwnd, _| {
+                let exists = WindowsApi::is_window(*hwnd);
+                if !exists {
+                    tracing::info!("unregistering app bar {hwnd} as it no longer exists");
+                }
+
+                exists
+            });
+
+            registered.clone()
+        };
+
+        if rules.is_empty() && app_bars.is_empty() && RESERVING_MONITORS.lock().is_empty() {
+            continue;
+        }
+
+        // hmonitor -> (edge, reservation) of every matching window and registered bar currently
+        // on screen, which are enumerated and queried before taking the window manager lock so
+        // that slow or hung windows don't hold up the handling of events and commands
+        let mut reserved_windows: HashMap<isize, Vec<(ReservedSpaceEdge, Reservation)>> =
+            HashMap::new();
+
+        for (hwnd, app_bar) in &app_bars {
+            reserved_windows
+                .entry(WindowsApi::monitor_from_window(*hwnd))
+                .or_default()
+                .push((app_bar.edge, Reservation::Thickness(app_bar.thickness)));
+        }
+
+        if !rules.is_empty() {
+            let regex_identifiers = REGEX_IDENTIFIERS.lock();
+
+            for window in WindowsApi::visible_windows()? {
+                if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+                    (window.title(), window.exe(), window.class(), window.path())
+                {
+                    for rule in &rules {
+                        let is_match = should_act(
+                            &title,
+                            &exe_name,
+                            &class,
+                            &path,
+                            std::slice::from_ref(&rule.matching_rule),
+                            &regex_identifiers,
+                        )
+                        .is_some();
+
+                        if is_match {
+                            if let Ok(rect) = WindowsApi::window_rect(window.hwnd) {
+                                reserved_windows
+                                    .entry(WindowsApi::monitor_from_window(window.hwnd))
+                                    .or_default()
+                                    .push((rule.edge, Reservation::Window(rect)));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut wm = wm.lock();
+        let mut reserving_monitors = RESERVING_MONITORS.lock();
+
+        let global_offset = wm.work_area_offset;
+        let mut should_retile = false;
+
+        for monitor in wm.monitors_mut() {
+            let id = monitor.id();
+
+            // Space is reserved on top of the offset which is currently configured, so that
+            // changes made at runtime or by reloading the configuration are picked up
+            let baseline = monitor
+                .configured_work_area_offset()
+                .map(|offset| offset.resolve(monitor.size()));
+
+            match reserved_windows.get(&id) {
+                Some(windows) => {
+                    reserving_monitors.insert(id);
+
+                    let mut offset = baseline
+                        .or_else(|| global_offset.map(|offset| offset.resolve(monitor.size())))
+                        .unwrap_or_default();
+                    let work_area = *monitor.work_area_size();
+
+                    for (edge, reservation) in windows {
+                        let reserved = match (edge, reservation) {
+                            (_, Reservation::Thickness(thickness)) => *thickness,
+                            (ReservedSpaceEdge::Top, Reservation::Window(rect)) => {
+                                rect.top + rect.bottom - work_area.top
+                            }
+                            (ReservedSpaceEdge::Bottom, Reservation::Window(rect)) => {
+                                work_area.top + work_area.bottom - rect.top
+                            }
+                            (ReservedSpaceEdge::Left, Reservation::Window(rect)) => {
+                                rect.left + rect.right - work_area.left
+                            }
+                            (ReservedSpaceEdge::Right, Reservation::Window(rect)) => {
+                                work_area.left + work_area.right - rect.left
+                            }
+                        }
+                        .max(0);
+
+                        // offsets are applied to the work area as left/top adjustments and
+                        // width/height reductions
+                        match edge {
+                            ReservedSpaceEdge::Top => {
+                                offset.top += reserved;
+                                offset.bottom += reserved;
+                            }
+                            ReservedSpaceEdge::Bottom => {
+                                offset.bottom += reserved;
+                            }
+                            ReservedSpaceEdge::Left => {
+                                offset.left += reserved;
+                                offset.right += reserved;
+                            }
+                            ReservedSpaceEdge::Right => {
+                                offset.right += reserved;
+                            }
+                        }
+                    }
+
+                    if monitor.work_area_offset() != Option::from(offset) {
+                        tracing::info!("reserving space on monitor {id}: {offset:?}");
+                        monitor.set_work_area_offset(Option::from(offset));
+                        should_retile = true;
+                    }
+                }
+                None => {
+                    // the reserved window has gone away, restore the configured offset
+                    if reserving_monitors.remove(&id) {
+                        tracing::info!("releasing reserved space on monitor {id}");
+                        monitor.set_work_area_offset(baseline);
+                        should_retile = true;
+                    }
+                }
+            }
+        }
+
+        if should_retile {
+            wm.retile_all(true)?;
+        }
+    }
+}
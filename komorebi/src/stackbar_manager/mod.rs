@@ -1,9 +1,11 @@
 mod stackbar;
 
 use crate::container::Container;
+use crate::core::HidingBehaviour;
 use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
 use crate::stackbar_manager::stackbar::Stackbar;
+use crate::static_config::WorkspaceStackbarConfig;
 use crate::WindowManager;
 use crate::WindowsApi;
 use crate::DEFAULT_CONTAINER_PADDING;
@@ -28,8 +30,12 @@ pub static STACKBAR_UNFOCUSED_TEXT_COLOUR: AtomicU32 = AtomicU32::new(11776947);
 pub static STACKBAR_TAB_BACKGROUND_COLOUR: AtomicU32 = AtomicU32::new(3355443); // gray
 pub static STACKBAR_TAB_HEIGHT: AtomicI32 = AtomicI32::new(40);
 pub static STACKBAR_TAB_WIDTH: AtomicI32 = AtomicI32::new(200);
+pub static STACKBAR_TAB_CLOSE_BUTTON: AtomicBool = AtomicBool::new(false);
 pub static STACKBAR_LABEL: AtomicCell<StackbarLabel> = AtomicCell::new(StackbarLabel::Process);
 pub static STACKBAR_MODE: AtomicCell<StackbarMode> = AtomicCell::new(StackbarMode::OnStack);
+// The mode to return to when the stackbar is toggled back on
+pub static STACKBAR_LAST_ENABLED_MODE: AtomicCell<StackbarMode> =
+    AtomicCell::new(StackbarMode::OnStack);
 
 pub static STACKBAR_TEMPORARILY_DISABLED: AtomicBool = AtomicBool::new(false);
 
@@ -38,6 +44,14 @@ lazy_static! {
     pub static ref STACKBAR_FONT_FAMILY: Mutex<Option<String>> = Mutex::new(None);
     static ref STACKBARS_MONITORS: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
     static ref STACKBARS_CONTAINERS: Mutex<HashMap<isize, Container>> = Mutex::new(HashMap::new());
+    // The scale factor that each stackbar was last drawn with, keyed by stackbar hwnd
+    static ref STACKBARS_SCALE_FACTORS: Mutex<HashMap<isize, f32>> = Mutex::new(HashMap::new());
+    // The unscaled tab height that each stackbar was last drawn with, keyed by stackbar hwnd
+    static ref STACKBARS_TAB_HEIGHTS: Mutex<HashMap<isize, i32>> = Mutex::new(HashMap::new());
+    // The hiding behaviour of the workspace that each stackbar was last drawn on, keyed by
+    // stackbar hwnd
+    static ref STACKBARS_HIDING_BEHAVIOURS: Mutex<HashMap<isize, HidingBehaviour>> =
+        Mutex::new(HashMap::new());
 }
 
 pub struct Notification;
@@ -62,14 +76,88 @@ pub fn send_notification() {
     }
 }
 
-pub fn should_have_stackbar(window_count: usize) -> bool {
+pub fn set_mode(mode: StackbarMode) {
+    if !matches!(mode, StackbarMode::Never) {
+        STACKBAR_LAST_ENABLED_MODE.store(mode);
+    }
+
+    STACKBAR_MODE.store(mode);
+}
+
+pub fn toggle_mode() {
     match STACKBAR_MODE.load() {
-        StackbarMode::Always => true,
-        StackbarMode::OnStack => window_count > 1,
-        StackbarMode::Never => false,
+        StackbarMode::Never => STACKBAR_MODE.store(STACKBAR_LAST_ENABLED_MODE.load()),
+        mode => {
+            STACKBAR_LAST_ENABLED_MODE.store(mode);
+            STACKBAR_MODE.store(StackbarMode::Never);
+        }
+    }
+}
+
+/// The stackbar mode, label and height in effect on a workspace, which are the global values
+/// unless the workspace has been configured to override them
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StackbarOptions {
+    pub mode: StackbarMode,
+    pub label: StackbarLabel,
+    pub height: i32,
+}
+
+impl StackbarOptions {
+    pub fn global() -> Self {
+        Self {
+            mode: STACKBAR_MODE.load(),
+            label: STACKBAR_LABEL.load(),
+            height: STACKBAR_TAB_HEIGHT.load_consume(),
+        }
+    }
+
+    pub fn with_overrides(overrides: Option<WorkspaceStackbarConfig>) -> Self {
+        let global = Self::global();
+
+        overrides.map_or(global, |overrides| Self {
+            mode: overrides.mode.unwrap_or(global.mode),
+            label: overrides.label.unwrap_or(global.label),
+            height: overrides.height.unwrap_or(global.height),
+        })
+    }
+
+    pub const fn should_have_stackbar(&self, window_count: usize) -> bool {
+        match self.mode {
+            StackbarMode::Always => true,
+            StackbarMode::OnStack => window_count > 1,
+            StackbarMode::Never => false,
+        }
+    }
+
+    /// The height of a stackbar on a monitor with the given DPI
+    pub fn tab_height_for_dpi(&self, dpi: u32) -> i32 {
+        scaled(self.height, scale_factor_for_dpi(dpi))
     }
 }
 
+/// The factor by which stackbar dimensions, which are configured for the system DPI, should be
+/// scaled on a monitor with the given DPI
+pub fn scale_factor_for_dpi(dpi: u32) -> f32 {
+    let system_dpi = WindowsApi::system_dpi();
+
+    if system_dpi == 0 || dpi == 0 {
+        return 1.0;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let scale_factor = dpi as f32 / system_dpi as f32;
+
+    scale_factor
+}
+
+pub fn scaled(value: i32, scale_factor: f32) -> i32 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    let scaled = (value as f32 * scale_factor).round() as i32;
+
+    scaled
+}
+
 pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
         match handle_notifications(wm.clone()) {
@@ -95,10 +183,14 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
         // Check the wm state every time we receive a notification
         let mut state = wm.lock();
 
+        // Workspaces can turn stackbars back on even when they are globally disabled
+        let enabled_on_focused_workspace = state.monitors().iter().any(|m| {
+            m.focused_workspace()
+                .is_some_and(|ws| !matches!(ws.stackbar_options().mode, StackbarMode::Never))
+        });
+
         // If stackbars are disabled
-        if matches!(STACKBAR_MODE.load(), StackbarMode::Never)
-            || STACKBAR_TEMPORARILY_DISABLED.load(Ordering::SeqCst)
-        {
+        if !enabled_on_focused_workspace || STACKBAR_TEMPORARILY_DISABLED.load(Ordering::SeqCst) {
             for (_, stackbar) in stackbars.iter() {
                 stackbar.destroy()?;
             }
@@ -107,14 +199,35 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
             continue 'receiver;
         }
 
+        // Stackbars of containers which have moved to the focused workspace of another monitor
+        // are reassociated with that monitor below instead of being destroyed and recreated
+        let mut visible_container_monitors = HashMap::new();
+        for (monitor_idx, m) in state.monitors().iter().enumerate() {
+            if let Some(ws) = m.focused_workspace() {
+                for container in ws.containers() {
+                    visible_container_monitors.insert(container.id().clone(), monitor_idx);
+                }
+            }
+        }
+
+        let is_on_other_monitor = |id: &String, monitor_idx: usize| {
+            visible_container_monitors
+                .get(id)
+                .is_some_and(|idx| *idx != monitor_idx)
+        };
+
         for (monitor_idx, m) in state.monitors_mut().iter_mut().enumerate() {
+            let scale_factor = scale_factor_for_dpi(m.dpi());
+
             // Only operate on the focused workspace of each monitor
             if let Some(ws) = m.focused_workspace_mut() {
                 // Workspaces with tiling disabled don't have stackbars
                 if !ws.tile() {
                     let mut to_remove = vec![];
                     for (id, border) in stackbars.iter() {
-                        if stackbars_monitors.get(id).copied().unwrap_or_default() == monitor_idx {
+                        if stackbars_monitors.get(id).copied().unwrap_or_default() == monitor_idx
+                            && !is_on_other_monitor(id, monitor_idx)
+                        {
                             border.destroy()?;
                             to_remove.push(id.clone());
                         }
@@ -135,7 +248,9 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     // Destroy any stackbars associated with the focused workspace
                     let mut to_remove = vec![];
                     for (id, stackbar) in stackbars.iter() {
-                        if stackbars_monitors.get(id).copied().unwrap_or_default() == monitor_idx {
+                        if stackbars_monitors.get(id).copied().unwrap_or_default() == monitor_idx
+                            && !is_on_other_monitor(id, monitor_idx)
+                        {
                             stackbar.destroy()?;
                             to_remove.push(id.clone());
                         }
@@ -159,6 +274,7 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                 for (id, stackbar) in stackbars.iter() {
                     if stackbars_monitors.get(id).copied().unwrap_or_default() == monitor_idx
                         && !container_ids.contains(id)
+                        && !is_on_other_monitor(id, monitor_idx)
                     {
                         stackbar.destroy()?;
                         to_remove.push(id.clone());
@@ -173,12 +289,19 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
                     .container_padding()
                     .unwrap_or_else(|| DEFAULT_CONTAINER_PADDING.load_consume());
 
-                'containers: for container in ws.containers_mut() {
-                    let should_add_stackbar = match STACKBAR_MODE.load() {
-                        StackbarMode::Always => true,
-                        StackbarMode::OnStack => container.windows().len() > 1,
-                        StackbarMode::Never => false,
-                    };
+                let latest_layout = ws.latest_layout().clone();
+                let options = ws.stackbar_options();
+                let hiding_behaviour = ws.hiding_behaviour();
+                let scrolled_out_of_view = ws
+                    .containers()
+                    .iter()
+                    .map(|container| ws.is_scrolled_out_of_view(container))
+                    .collect::<Vec<_>>();
+
+                'containers: for (i, container) in ws.containers_mut().iter_mut().enumerate() {
+                    let should_add_stackbar = options
+                        .should_have_stackbar(container.unminimized_window_count())
+                        && !scrolled_out_of_view.get(i).copied().unwrap_or_default();
 
                     if !should_add_stackbar {
                         if let Some(stackbar) = stackbars.get(container.id()) {
@@ -204,11 +327,23 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
 
                     stackbars_monitors.insert(container.id().clone(), monitor_idx);
 
-                    let rect = WindowsApi::window_rect(
-                        container.focused_window().copied().unwrap_or_default().hwnd,
-                    )?;
+                    // The effective rect from the last layout accounts for windows which have
+                    // been grown to their minimum size beyond their tiles
+                    let rect = match latest_layout.get(i) {
+                        Some(rect) => *rect,
+                        None => WindowsApi::window_rect(
+                            container.focused_window().copied().unwrap_or_default().hwnd,
+                        )?,
+                    };
 
-                    stackbar.update(container_padding, container, &rect)?;
+                    stackbar.update(
+                        container_padding,
+                        container,
+                        &rect,
+                        scale_factor,
+                        options,
+                        hiding_behaviour,
+                    )?;
                 }
             }
         }
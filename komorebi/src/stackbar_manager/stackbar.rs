@@ -1,22 +1,33 @@
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
+use crate::border_manager;
 use crate::border_manager::STYLE;
+use crate::border_manager::TILED_WINDOW_KINDS;
+use crate::colour::packed_alpha;
+use crate::colour::strip_alpha;
 use crate::container::Container;
 use crate::core::BorderStyle;
+use crate::core::CycleDirection;
+use crate::core::HidingBehaviour;
 use crate::core::Rect;
 use crate::core::StackbarLabel;
+use crate::stackbar_manager::scaled;
+use crate::stackbar_manager::StackbarOptions;
 use crate::stackbar_manager::STACKBARS_CONTAINERS;
+use crate::stackbar_manager::STACKBARS_HIDING_BEHAVIOURS;
+use crate::stackbar_manager::STACKBARS_SCALE_FACTORS;
+use crate::stackbar_manager::STACKBARS_TAB_HEIGHTS;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
-use crate::stackbar_manager::STACKBAR_LABEL;
 use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
+use crate::stackbar_manager::STACKBAR_TAB_CLOSE_BUTTON;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
 use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
 use crate::windows_api;
 use crate::WindowsApi;
+use crate::CYCLE_STACK_WRAP;
 use crate::DEFAULT_CONTAINER_PADDING;
+use crate::HIDING_BEHAVIOUR;
 use crate::WINDOWS_11;
 use crossbeam_utils::atomic::AtomicConsume;
 use std::os::windows::ffi::OsStrExt;
@@ -63,16 +74,29 @@ use windows::Win32::UI::WindowsAndMessaging::SetLayeredWindowAttributes;
 use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
 use windows::Win32::UI::WindowsAndMessaging::CS_HREDRAW;
 use windows::Win32::UI::WindowsAndMessaging::CS_VREDRAW;
+use windows::Win32::UI::WindowsAndMessaging::LWA_ALPHA;
 use windows::Win32::UI::WindowsAndMessaging::LWA_COLORKEY;
 use windows::Win32::UI::WindowsAndMessaging::MSG;
 use windows::Win32::UI::WindowsAndMessaging::WM_DESTROY;
 use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_MBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_MOUSEWHEEL;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
 use windows::Win32::UI::WindowsAndMessaging::WS_EX_LAYERED;
 use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOOLWINDOW;
 use windows::Win32::UI::WindowsAndMessaging::WS_POPUP;
 use windows::Win32::UI::WindowsAndMessaging::WS_VISIBLE;
 
+const CLOSE_BUTTON_GLYPH: &str = "\u{2715}";
+
+/// The part of a stackbar that has been clicked, identified by the index of the tab's window in
+/// its container
+#[derive(Debug, Clone, Copy)]
+enum TabHit {
+    Tab(usize),
+    CloseButton(usize),
+}
+
 #[derive(Debug)]
 pub struct Stackbar {
     pub hwnd: isize,
@@ -162,27 +186,47 @@ impl Stackbar {
         container_padding: i32,
         container: &mut Container,
         layout: &Rect,
+        scale_factor: f32,
+        options: StackbarOptions,
+        hiding_behaviour: HidingBehaviour,
     ) -> color_eyre::Result<()> {
-        let width = STACKBAR_TAB_WIDTH.load_consume();
-        let height = STACKBAR_TAB_HEIGHT.load_consume();
-        let gap = DEFAULT_CONTAINER_PADDING.load_consume();
         let background = STACKBAR_TAB_BACKGROUND_COLOUR.load_consume();
-        let focused_text_colour = STACKBAR_FOCUSED_TEXT_COLOUR.load_consume();
-        let unfocused_text_colour = STACKBAR_UNFOCUSED_TEXT_COLOUR.load_consume();
+        let focused_text_colour = strip_alpha(STACKBAR_FOCUSED_TEXT_COLOUR.load_consume());
+        let unfocused_text_colour = strip_alpha(STACKBAR_UNFOCUSED_TEXT_COLOUR.load_consume());
+
+        // GDI has no alpha channel, so a translucent background is applied to the whole stackbar
+        let background_alpha = packed_alpha(background);
+        let background = strip_alpha(background);
 
         let mut stackbars_containers = STACKBARS_CONTAINERS.lock();
         stackbars_containers.insert(self.hwnd, container.clone());
+        STACKBARS_SCALE_FACTORS
+            .lock()
+            .insert(self.hwnd, scale_factor);
+        STACKBARS_TAB_HEIGHTS
+            .lock()
+            .insert(self.hwnd, options.height);
+        STACKBARS_HIDING_BEHAVIOURS
+            .lock()
+            .insert(self.hwnd, hiding_behaviour);
 
         let mut layout = *layout;
         let workspace_specific_offset =
-            BORDER_WIDTH.load_consume() + BORDER_OFFSET.load_consume() + container_padding;
+            border_manager::border_padding(&TILED_WINDOW_KINDS) + container_padding;
 
-        layout.top -= workspace_specific_offset + STACKBAR_TAB_HEIGHT.load_consume();
+        layout.top -= workspace_specific_offset + scaled(options.height, scale_factor);
         layout.left -= workspace_specific_offset;
 
         WindowsApi::position_window(self.hwnd, &layout, false)?;
 
         unsafe {
+            SetLayeredWindowAttributes(
+                self.hwnd(),
+                COLORREF(0),
+                background_alpha,
+                LWA_COLORKEY | LWA_ALPHA,
+            )?;
+
             let hdc = GetDC(self.hwnd());
 
             let hpen = CreatePen(PS_SOLID, 0, COLORREF(background));
@@ -212,26 +256,27 @@ impl Stackbar {
                 GetDeviceCaps(hdc, LOGPIXELSY),
             );
 
-            logfont.lfHeight = logical_height;
+            // The font size is given for the system DPI, like the other stackbar dimensions
+            logfont.lfHeight = scaled(logical_height, scale_factor);
 
             let hfont = CreateFontIndirectW(&logfont);
 
             SelectObject(hdc, hfont);
 
-            for (i, window) in container.windows().iter().enumerate() {
+            // Minimized windows keep their place in the stack but don't get a tab
+            for (i, window) in container
+                .windows()
+                .iter()
+                .filter(|window| !container.is_minimized(window.hwnd))
+                .enumerate()
+            {
                 if window.hwnd == container.focused_window().copied().unwrap_or_default().hwnd {
                     SetTextColor(hdc, COLORREF(focused_text_colour));
                 } else {
                     SetTextColor(hdc, COLORREF(unfocused_text_colour));
                 }
 
-                let left = gap + (i as i32 * (width + gap));
-                let mut rect = Rect {
-                    top: 0,
-                    left,
-                    right: left + width,
-                    bottom: height,
-                };
+                let mut rect = tab_rect(i as i32, scale_factor, options.height);
 
                 match STYLE.load() {
                     BorderStyle::System => {
@@ -262,7 +307,7 @@ impl Stackbar {
                     }
                 }
 
-                let label = match STACKBAR_LABEL.load() {
+                let label = match options.label {
                     StackbarLabel::Process => {
                         let exe = window.exe()?;
                         exe.trim_end_matches(".exe").to_string()
@@ -272,6 +317,21 @@ impl Stackbar {
 
                 let mut tab_title: Vec<u16> = label.encode_utf16().collect();
 
+                if STACKBAR_TAB_CLOSE_BUTTON.load_consume() {
+                    let close_button = close_button_rect(&rect);
+                    let mut glyph: Vec<u16> = CLOSE_BUTTON_GLYPH.encode_utf16().collect();
+
+                    DrawTextW(
+                        hdc,
+                        &mut glyph,
+                        &mut close_button.into(),
+                        DT_SINGLELINE | DT_CENTER | DT_VCENTER,
+                    );
+
+                    // Keep the label clear of the close button
+                    rect.right = close_button.left;
+                }
+
                 rect.left_padding(10);
                 rect.right_padding(10);
 
@@ -302,6 +362,104 @@ impl Stackbar {
         }
     }
 
+    fn hit_test(
+        container: &Container,
+        scale_factor: f32,
+        height: i32,
+        x: i32,
+        y: i32,
+    ) -> Option<TabHit> {
+        let close_button = STACKBAR_TAB_CLOSE_BUTTON.load_consume();
+
+        // Minimized windows don't have a tab, so tab positions skip over them
+        for (tab_idx, (index, _)) in container
+            .windows()
+            .iter()
+            .enumerate()
+            .filter(|(_, window)| !container.is_minimized(window.hwnd))
+            .enumerate()
+        {
+            let rect = tab_rect(tab_idx as i32, scale_factor, height);
+
+            if !contains_point(&rect, x, y) {
+                continue;
+            }
+
+            if close_button && contains_point(&close_button_rect(&rect), x, y) {
+                return Some(TabHit::CloseButton(index));
+            }
+
+            return Some(TabHit::Tab(index));
+        }
+
+        None
+    }
+
+    /// The hiding behaviour of the workspace that the stackbar was last drawn on
+    fn hiding_behaviour(hwnd: HWND) -> HidingBehaviour {
+        STACKBARS_HIDING_BEHAVIOURS
+            .lock()
+            .get(&(hwnd.0 as isize))
+            .copied()
+            .unwrap_or_else(|| *HIDING_BEHAVIOUR.lock())
+    }
+
+    fn focus_tab(container: &Container, clicked_idx: usize, hiding_behaviour: HidingBehaviour) {
+        let focused_window_idx = container.focused_window_idx();
+        let focused_window_rect =
+            WindowsApi::window_rect(container.focused_window().cloned().unwrap_or_default().hwnd)
+                .unwrap_or_default();
+
+        for (index, window) in container.windows().iter().enumerate() {
+            if container.is_minimized(window.hwnd) {
+                continue;
+            }
+
+            if index == clicked_idx {
+                // If we are focusing a window that isn't currently focused in the
+                // stackbar, make sure we update its location so that it doesn't render
+                // on top of other tiles before eventually ending up in the correct
+                // tile
+                if index != focused_window_idx {
+                    if let Err(err) = window.set_position(&focused_window_rect, false) {
+                        tracing::error!(
+                            "stackbar WM_LBUTTONDOWN repositioning error: hwnd {} ({})",
+                            *window,
+                            err
+                        );
+                    }
+                }
+
+                // Restore the window corresponding to the tab we have clicked
+                window.restore_with_behaviour(hiding_behaviour);
+                if let Err(err) = window.focus(false) {
+                    tracing::error!(
+                        "stackbar WMLBUTTONDOWN focus error: hwnd {} ({})",
+                        *window,
+                        err
+                    );
+                }
+            } else {
+                // Hide any windows in the stack that don't correspond to the window
+                // we have clicked
+                window.hide_with_behaviour(hiding_behaviour);
+            }
+        }
+    }
+
+    fn close_tab(container: &Container, index: usize) {
+        let Some(window) = container.windows().get(index).copied() else {
+            return;
+        };
+
+        // This is the same as SocketMessage::Close, but for the window of the clicked tab
+        // The tab is only removed once the destroy event for the window has been processed and
+        // the stackbar has been updated, as the application may still cancel the close
+        if let Err(err) = window.close() {
+            tracing::error!("stackbar close error: hwnd {} ({})", window, err);
+        }
+    }
+
     unsafe extern "system" fn callback(
         hwnd: HWND,
         msg: u32,
@@ -310,59 +468,56 @@ impl Stackbar {
     ) -> LRESULT {
         unsafe {
             match msg {
-                WM_LBUTTONDOWN => {
+                WM_LBUTTONDOWN | WM_MBUTTONDOWN => {
                     let stackbars_containers = STACKBARS_CONTAINERS.lock();
                     if let Some(container) = stackbars_containers.get(&(hwnd.0 as isize)) {
                         let x = l_param.0 as i32 & 0xFFFF;
                         let y = (l_param.0 as i32 >> 16) & 0xFFFF;
 
-                        let width = STACKBAR_TAB_WIDTH.load_consume();
-                        let height = STACKBAR_TAB_HEIGHT.load_consume();
-                        let gap = DEFAULT_CONTAINER_PADDING.load_consume();
-
-                        let focused_window_idx = container.focused_window_idx();
-                        let focused_window_rect = WindowsApi::window_rect(
-                            container.focused_window().cloned().unwrap_or_default().hwnd,
-                        )
-                        .unwrap_or_default();
-
-                        for (index, window) in container.windows().iter().enumerate() {
-                            let left = gap + (index as i32 * (width + gap));
-                            let right = left + width;
-                            let top = 0;
-                            let bottom = height;
-
-                            if x >= left && x <= right && y >= top && y <= bottom {
-                                // If we are focusing a window that isn't currently focused in the
-                                // stackbar, make sure we update its location so that it doesn't render
-                                // on top of other tiles before eventually ending up in the correct
-                                // tile
-                                if index != focused_window_idx {
-                                    if let Err(err) =
-                                        window.set_position(&focused_window_rect, false)
-                                    {
-                                        tracing::error!(
-                                        "stackbar WM_LBUTTONDOWN repositioning error: hwnd {} ({})",
-                                        *window,
-                                        err
-                                    );
-                                    }
-                                }
-
-                                // Restore the window corresponding to the tab we have clicked
-                                window.restore();
-                                if let Err(err) = window.focus(false) {
-                                    tracing::error!(
-                                        "stackbar WMLBUTTONDOWN focus error: hwnd {} ({})",
-                                        *window,
-                                        err
-                                    );
-                                }
-                            } else {
-                                // Hide any windows in the stack that don't correspond to the window
-                                // we have clicked
-                                window.hide();
+                        let scale_factor = STACKBARS_SCALE_FACTORS
+                            .lock()
+                            .get(&(hwnd.0 as isize))
+                            .copied()
+                            .unwrap_or(1.0);
+
+                        let height = STACKBARS_TAB_HEIGHTS
+                            .lock()
+                            .get(&(hwnd.0 as isize))
+                            .copied()
+                            .unwrap_or_else(|| STACKBAR_TAB_HEIGHT.load_consume());
+
+                        match Self::hit_test(container, scale_factor, height, x, y) {
+                            Some(TabHit::CloseButton(index)) => Self::close_tab(container, index),
+                            Some(TabHit::Tab(index)) if msg == WM_MBUTTONDOWN => {
+                                Self::close_tab(container, index);
+                            }
+                            Some(TabHit::Tab(index)) => {
+                                Self::focus_tab(container, index, Self::hiding_behaviour(hwnd));
                             }
+                            None => {}
+                        }
+                    }
+
+                    LRESULT(0)
+                }
+                WM_MOUSEWHEEL => {
+                    let stackbars_containers = STACKBARS_CONTAINERS.lock();
+                    if let Some(container) = stackbars_containers.get(&(hwnd.0 as isize)) {
+                        // The high word of wParam is the signed wheel delta, positive when the
+                        // wheel is rotated away from the user
+                        let delta = (w_param.0 >> 16) as u16 as i16;
+                        let direction = if delta > 0 {
+                            CycleDirection::Previous
+                        } else {
+                            CycleDirection::Next
+                        };
+
+                        if let Some(index) = container.next_unminimized_idx(
+                            container.focused_window_idx(),
+                            direction,
+                            CYCLE_STACK_WRAP.load(Ordering::SeqCst),
+                        ) {
+                            Self::focus_tab(container, index, Self::hiding_behaviour(hwnd));
                         }
                     }
 
@@ -378,6 +533,36 @@ impl Stackbar {
     }
 }
 
+/// The rect of the tab at the given position in the stackbar, relative to the stackbar window
+fn tab_rect(position: i32, scale_factor: f32, height: i32) -> Rect {
+    let width = scaled(STACKBAR_TAB_WIDTH.load_consume(), scale_factor);
+    let height = scaled(height, scale_factor);
+    let gap = scaled(DEFAULT_CONTAINER_PADDING.load_consume(), scale_factor);
+
+    let left = gap + (position * (width + gap));
+
+    Rect {
+        top: 0,
+        left,
+        right: left + width,
+        bottom: height,
+    }
+}
+
+/// The close button occupies a square on the right side of a tab
+fn close_button_rect(tab: &Rect) -> Rect {
+    let size = (tab.bottom - tab.top).min((tab.right - tab.left) / 4);
+
+    Rect {
+        left: tab.right - size,
+        ..*tab
+    }
+}
+
+fn contains_point(rect: &Rect, x: i32, y: i32) -> bool {
+    x >= rect.left && x <= rect.right && y >= rect.top && y <= rect.bottom
+}
+
 fn wide_string(s: &str) -> Vec<u16> {
     std::ffi::OsStr::new(s)
         .encode_wide()
@@ -0,0 +1,270 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::NotificationEvent;
+use crate::WindowManager;
+use crate::DATA_DIR;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+pub static STATE_RECORDING_ENABLED: AtomicBool = AtomicBool::new(false);
+pub static STATE_RECORDING_MAX_SIZE_MB: AtomicU64 = AtomicU64::new(10);
+
+lazy_static! {
+    pub static ref STATE_RECORDING_PATH: Mutex<PathBuf> =
+        Mutex::new(DATA_DIR.join("state_recording.jsonl"));
+    // The state as of the last recorded entry, which the next entry is diffed against
+    static ref PREVIOUS_STATE: Mutex<Option<CompactState>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateRecordingConfig {
+    /// Record every processed window manager event and socket message along with the changes it
+    /// made to the state (default: false)
+    pub enabled: bool,
+    /// The JSON Lines file to record to (default: state_recording.jsonl in the data directory)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// The size in megabytes after which the recording is rotated to a .1 file (default: 10)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+}
+
+/// The subset of the window manager state which is needed to follow where windows moved
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CompactState {
+    focused_monitor_idx: usize,
+    monitors: Vec<CompactMonitor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CompactMonitor {
+    focused_workspace_idx: usize,
+    // The hwnds of each container on each workspace
+    workspaces: Vec<Vec<Vec<isize>>>,
+}
+
+impl From<&WindowManager> for CompactState {
+    fn from(wm: &WindowManager) -> Self {
+        Self {
+            focused_monitor_idx: wm.focused_monitor_idx(),
+            monitors: wm
+                .monitors()
+                .iter()
+                .map(|monitor| CompactMonitor {
+                    focused_workspace_idx: monitor.focused_workspace_idx(),
+                    workspaces: monitor
+                        .workspaces()
+                        .iter()
+                        .map(|workspace| {
+                            workspace
+                                .containers()
+                                .iter()
+                                .map(|container| {
+                                    container.windows().iter().map(|w| w.hwnd).collect()
+                                })
+                                .collect()
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CompactState {
+    /// Only the focus indices and container hwnd lists which differ from the previous state, or
+    /// everything if there is no previous state
+    fn diff(&self, previous: Option<&Self>) -> Value {
+        let mut diff = Map::new();
+
+        if previous.is_none_or(|previous| previous.focused_monitor_idx != self.focused_monitor_idx)
+        {
+            diff.insert(
+                String::from("focused_monitor_idx"),
+                json!(self.focused_monitor_idx),
+            );
+        }
+
+        if let Some(previous) = previous {
+            if previous.monitors.len() != self.monitors.len() {
+                diff.insert(String::from("monitor_count"), json!(self.monitors.len()));
+            }
+        }
+
+        let mut monitors = Map::new();
+        for (i, monitor) in self.monitors.iter().enumerate() {
+            let previous_monitor = previous.and_then(|previous| previous.monitors.get(i));
+            let mut monitor_diff = Map::new();
+
+            if previous_monitor.is_none_or(|previous| {
+                previous.focused_workspace_idx != monitor.focused_workspace_idx
+            }) {
+                monitor_diff.insert(
+                    String::from("focused_workspace_idx"),
+                    json!(monitor.focused_workspace_idx),
+                );
+            }
+
+            let mut workspaces = Map::new();
+            for (j, containers) in monitor.workspaces.iter().enumerate() {
+                let previous_containers =
+                    previous_monitor.and_then(|previous| previous.workspaces.get(j));
+
+                if previous_containers != Some(containers) {
+                    workspaces.insert(j.to_string(), json!(containers));
+                }
+            }
+
+            if !workspaces.is_empty() {
+                monitor_diff.insert(String::from("workspaces"), Value::Object(workspaces));
+            }
+
+            if !monitor_diff.is_empty() {
+                monitors.insert(i.to_string(), Value::Object(monitor_diff));
+            }
+        }
+
+        if !monitors.is_empty() {
+            diff.insert(String::from("monitors"), Value::Object(monitors));
+        }
+
+        Value::Object(diff)
+    }
+}
+
+pub struct Notification {
+    timestamp: u128,
+    event: NotificationEvent,
+    state: CompactState,
+}
+
+#[derive(Serialize)]
+struct Entry<'a> {
+    timestamp: u128,
+    event: &'a NotificationEvent,
+    diff: Value,
+}
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(100))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+pub fn set_enabled(enable: bool) {
+    // Every recording session starts with the full compact state
+    if enable && !STATE_RECORDING_ENABLED.load(Ordering::SeqCst) {
+        *PREVIOUS_STATE.lock() = None;
+    }
+
+    STATE_RECORDING_ENABLED.store(enable, Ordering::SeqCst);
+}
+
+/// Queue an entry for the event which has just been processed, without blocking if the
+/// recording thread has fallen behind
+pub fn record(event: NotificationEvent, wm: &WindowManager) {
+    if !STATE_RECORDING_ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis());
+
+    if event_tx()
+        .try_send(Notification {
+            timestamp,
+            event,
+            state: CompactState::from(wm),
+        })
+        .is_err()
+    {
+        tracing::warn!("channel is full; dropping notification")
+    }
+}
+
+pub fn listen_for_notifications() {
+    std::thread::spawn(move || loop {
+        match handle_notifications() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_notifications() -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    for notification in receiver {
+        let diff = {
+            let mut previous = PREVIOUS_STATE.lock();
+            let diff = notification.state.diff(previous.as_ref());
+            *previous = Option::from(notification.state);
+            diff
+        };
+
+        let entry = Entry {
+            timestamp: notification.timestamp,
+            event: &notification.event,
+            diff,
+        };
+
+        if let Err(error) = write_entry(&entry) {
+            tracing::error!("failed to record state: {error}");
+        }
+    }
+
+    Ok(())
+}
+
+fn write_entry(entry: &Entry) -> color_eyre::Result<()> {
+    let path = STATE_RECORDING_PATH.lock().clone();
+    let max_size = STATE_RECORDING_MAX_SIZE_MB
+        .load(Ordering::SeqCst)
+        .saturating_mul(1024 * 1024);
+
+    if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() >= max_size) {
+        let mut rotated = path.clone().into_os_string();
+        rotated.push(".1");
+        std::fs::rename(&path, rotated)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use crate::animation::PerAnimationPrefixConfig;
+use crate::animation::ANIMATIONS_BLACKLIST;
+use crate::animation::ANIMATIONS_BLACKLIST_REGEX;
 use crate::animation::ANIMATION_DURATION_GLOBAL;
 use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
@@ -8,6 +10,7 @@ use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
 use crate::animation::DEFAULT_ANIMATION_FPS;
 use crate::border_manager;
+use crate::border_manager::BorderColourOverride;
 use crate::border_manager::ZOrder;
 use crate::border_manager::IMPLEMENTATION;
 use crate::border_manager::STYLE;
@@ -16,18 +19,30 @@ use crate::core::BorderImplementation;
 use crate::core::StackbarLabel;
 use crate::core::StackbarMode;
 use crate::current_virtual_desktop;
+use crate::monitor::DisplayPreference;
 use crate::monitor::Monitor;
 use crate::monitor_reconciliator;
+use crate::presentation_manager::PresentationModeConfig;
+use crate::presentation_manager::PRESENTATION_MODE_CONFIG;
+use crate::process_event;
+use crate::reaper;
+use crate::record_rule_provenance;
+use crate::reserved_space_manager::ReservedSpaceRule;
+use crate::reserved_space_manager::RESERVED_SPACE_RULES;
 use crate::ring::Ring;
+use crate::stackbar_manager;
+use crate::stackbar_manager::StackbarOptions;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
 use crate::stackbar_manager::STACKBAR_LABEL;
-use crate::stackbar_manager::STACKBAR_MODE;
 use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
+use crate::stackbar_manager::STACKBAR_TAB_CLOSE_BUTTON;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
 use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
+use crate::state_recorder;
+use crate::state_recorder::StateRecordingConfig;
 use crate::theme_manager;
 use crate::transparency_manager;
 use crate::window;
@@ -37,46 +52,74 @@ use crate::windows_api::WindowsApi;
 use crate::workspace::Workspace;
 use crate::Axis;
 use crate::CrossBoundaryBehaviour;
+use crate::DragBehaviour;
+use crate::LastWindowCloseBehaviour;
+use crate::AUTO_STACK_BY_EXE;
+use crate::AUTO_STACK_GROUPS;
+use crate::AUTO_WORKSPACE_NAMES;
+use crate::CROSS_LAYER_FOCUS;
+use crate::CYCLE_STACK_WRAP;
 use crate::DATA_DIR;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::FLOATING_APPLICATIONS;
+use crate::FLOAT_OVERRIDE_APPLICATIONS;
+use crate::FOCUS_FOLLOWS_MOUSE_DELAY_MS;
+use crate::FOCUS_FOLLOWS_MOUSE_IGNORE;
+use crate::FULLSCREEN_APPLICATIONS;
+use crate::HIDE_TASKBAR_ENTRIES_FOR_HIDDEN_WORKSPACES;
 use crate::HIDING_BEHAVIOUR;
 use crate::IGNORE_IDENTIFIERS;
+use crate::IGNORE_IDENTIFIERS_PROVENANCE;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MANAGE_IDENTIFIERS_PROVENANCE;
+use crate::MINIMUM_SIZE_POLICY;
 use crate::MONITOR_INDEX_PREFERENCES;
+use crate::MOUSE_FOLLOWS_FOCUS_SCOPE;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
 use crate::REGEX_IDENTIFIERS;
+use crate::SCRATCHPAD_ASPECT_RATIO;
+use crate::SCRATCHPAD_WIDTH_PERCENTAGE;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
+use crate::SPLIT_RATIO_PRESETS;
+use crate::SWALLOWING_RULES;
 use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WINDOWS_11;
 use crate::WORKSPACE_MATCHING_RULES;
+use crate::WORKSPACE_RULE_ENFORCEMENT;
 
+use crate::asc::AppSpecificConfigurationPath;
 use crate::asc::ApplicationSpecificConfiguration;
 use crate::asc::AscApplicationRulesOrSchema;
 use crate::config_generation::WorkspaceMatchingRule;
-use crate::core::config_generation::ApplicationConfiguration;
-use crate::core::config_generation::ApplicationConfigurationGenerator;
-use crate::core::config_generation::ApplicationOptions;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
+use crate::core::config_generation::RuleProvenance;
 use crate::core::resolve_home_path;
 use crate::core::AnimationStyle;
+use crate::core::AspectRatio;
 use crate::core::BorderStyle;
 use crate::core::DefaultLayout;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::HidingBehaviour;
 use crate::core::Layout;
+use crate::core::MinimumSizePolicy;
+use crate::core::MouseFollowsFocusScope;
 use crate::core::MoveBehaviour;
+use crate::core::NewWindowPlacement;
 use crate::core::OperationBehaviour;
 use crate::core::Rect;
+use crate::core::RectOrPercent;
+use crate::core::ScrollingLayoutOptions;
 use crate::core::SocketMessage;
+use crate::core::WindowBasedWorkAreaOffsetCounting;
 use crate::core::WindowContainerBehaviour;
+use crate::core::WindowKind;
 use crate::core::WindowManagementBehaviour;
 use color_eyre::Result;
 use crossbeam_channel::Receiver;
@@ -92,12 +135,13 @@ use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicI32;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use uds_windows::UnixListener;
 use uds_windows::UnixStream;
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct BorderColours {
     /// Border colour when the container contains a single window
     pub single: Option<Colour>,
@@ -107,11 +151,107 @@ pub struct BorderColours {
     pub monocle: Option<Colour>,
     /// Border colour when the container is in floating mode
     pub floating: Option<Colour>,
+    /// Border colour when the window is pinned to always be on top
+    pub pinned: Option<Colour>,
     /// Border colour when the container is unfocused
     pub unfocused: Option<Colour>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+/// A border width or offset which can be the same for every kind of window, or set separately for
+/// each kind of window
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum BorderDimension {
+    /// The same value for every kind of window
+    Global(i32),
+    /// Values for specific kinds of window, with the default used for any which are not set
+    PerWindowKind(WindowKindBorderDimensions),
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct WindowKindBorderDimensions {
+    /// Value when the container contains a single window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single: Option<i32>,
+    /// Value when the container contains multiple windows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack: Option<i32>,
+    /// Value when the container is in monocle mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monocle: Option<i32>,
+    /// Value when the container is in floating mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating: Option<i32>,
+    /// Value when the window is pinned to always be on top
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<i32>,
+    /// Value when the container is unfocused
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unfocused: Option<i32>,
+}
+
+impl WindowKindBorderDimensions {
+    fn get(&self, window_kind: WindowKind) -> Option<i32> {
+        match window_kind {
+            WindowKind::Single => self.single,
+            WindowKind::Stack => self.stack,
+            WindowKind::Monocle => self.monocle,
+            WindowKind::Floating => self.floating,
+            WindowKind::Pinned => self.pinned,
+            WindowKind::Unfocused => self.unfocused,
+        }
+    }
+
+    fn current(dimension: fn(WindowKind) -> i32) -> Self {
+        Self {
+            single: Some(dimension(WindowKind::Single)),
+            stack: Some(dimension(WindowKind::Stack)),
+            monocle: Some(dimension(WindowKind::Monocle)),
+            floating: Some(dimension(WindowKind::Floating)),
+            pinned: Some(dimension(WindowKind::Pinned)),
+            unfocused: Some(dimension(WindowKind::Unfocused)),
+        }
+    }
+}
+
+impl BorderDimension {
+    /// Stores the default value in the given global, and any per window kind values using the
+    /// given setter
+    fn apply(
+        value: Option<Self>,
+        default: i32,
+        global: &AtomicI32,
+        set_window_kind: fn(WindowKind, Option<i32>),
+    ) {
+        let per_window_kind = match value {
+            None => {
+                global.store(default, Ordering::SeqCst);
+                WindowKindBorderDimensions::default()
+            }
+            Some(Self::Global(value)) => {
+                global.store(value, Ordering::SeqCst);
+                WindowKindBorderDimensions::default()
+            }
+            Some(Self::PerWindowKind(dimensions)) => {
+                global.store(default, Ordering::SeqCst);
+                dimensions
+            }
+        };
+
+        for window_kind in [
+            WindowKind::Single,
+            WindowKind::Stack,
+            WindowKind::Monocle,
+            WindowKind::Floating,
+            WindowKind::Pinned,
+            WindowKind::Unfocused,
+        ] {
+            set_window_kind(window_kind, per_window_kind.get(window_kind));
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorkspaceConfig {
     /// Name
     pub name: String,
@@ -139,19 +279,64 @@ pub struct WorkspaceConfig {
     /// Permanent workspace application rules
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace_rules: Option<Vec<MatchingRule>>,
+    /// Move windows which have been moved away manually back to this workspace when they send
+    /// events, as long as workspace rule enforcement is enabled globally (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_workspace_rules_on_event: Option<bool>,
     /// Apply this monitor's window-based work area offset (default: true)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub apply_window_based_work_area_offset: Option<bool>,
+    /// How windows are counted towards the window based work area offset limit (default: monitor)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_based_work_area_offset_counting: Option<WindowBasedWorkAreaOffsetCounting>,
     /// Determine what happens when a new window is opened (default: Create)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_container_behaviour: Option<WindowContainerBehaviour>,
+    /// Determine where new containers are placed in the layout (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_window_placement: Option<NewWindowPlacement>,
     /// Enable or disable float override, which makes it so every new window opens in floating mode
     /// (default: false)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub float_override: Option<bool>,
+    /// Add new windows to an existing container on this workspace which holds windows of the same
+    /// application (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_stack_by_exe: Option<bool>,
     /// Specify an axis on which to flip the selected layout (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layout_flip: Option<Axis>,
+    /// Options for the Scrolling layout (default: 3 equal visible columns which keep the focused
+    /// column in view)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scrolling: Option<ScrollingLayoutOptions>,
+    /// Which Windows signal to use when hiding windows on this workspace (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_hiding_behaviour: Option<HidingBehaviour>,
+    /// Enable or disable focus follows mouse while this workspace is focused, overriding the
+    /// global implementation state (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_follows_mouse: Option<bool>,
+    /// Desktop wallpaper to set on the monitor when this workspace is focused (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallpaper: Option<PathBuf>,
+    /// Stackbar mode, label and height to use on this workspace instead of the global stackbar
+    /// configuration (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stackbar: Option<WorkspaceStackbarConfig>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WorkspaceStackbarConfig {
+    /// Stackbar mode (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<StackbarMode>,
+    /// Stackbar label (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<StackbarLabel>,
+    /// Stackbar height (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
 }
 
 impl From<&Workspace> for WorkspaceConfig {
@@ -166,29 +351,23 @@ impl From<&Workspace> for WorkspaceConfig {
             }
         }
 
-        let default_container_padding = DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst);
-        let default_workspace_padding = DEFAULT_WORKSPACE_PADDING.load(Ordering::SeqCst);
-
-        let container_padding = value.container_padding().and_then(|container_padding| {
-            if container_padding == default_container_padding {
-                None
-            } else {
-                Option::from(container_padding)
-            }
-        });
+        // Paddings inherited from the monitor or global defaults are not workspace configuration
+        let container_padding = if value.explicit_container_padding() {
+            value.container_padding()
+        } else {
+            None
+        };
 
-        let workspace_padding = value.workspace_padding().and_then(|workspace_padding| {
-            if workspace_padding == default_workspace_padding {
-                None
-            } else {
-                Option::from(workspace_padding)
-            }
-        });
+        let workspace_padding = if value.explicit_workspace_padding() {
+            value.workspace_padding()
+        } else {
+            None
+        };
 
         Self {
             name: value
-                .name()
-                .clone()
+                .explicit_name()
+                .cloned()
                 .unwrap_or_else(|| String::from("unnamed")),
             layout: match value.layout() {
                 Layout::Default(layout) => Option::from(*layout),
@@ -203,27 +382,102 @@ impl From<&Workspace> for WorkspaceConfig {
             workspace_padding,
             initial_workspace_rules: None,
             workspace_rules: None,
+            enforce_workspace_rules_on_event: None,
             apply_window_based_work_area_offset: Some(value.apply_window_based_work_area_offset()),
+            window_based_work_area_offset_counting: value.window_based_work_area_offset_counting(),
             window_container_behaviour: *value.window_container_behaviour(),
+            new_window_placement: value.new_window_placement(),
             float_override: *value.float_override(),
+            auto_stack_by_exe: value.auto_stack_by_exe(),
             layout_flip: value.layout_flip(),
+            scrolling: value.scrolling_options(),
+            window_hiding_behaviour: value.window_hiding_behaviour(),
+            focus_follows_mouse: value.focus_follows_mouse(),
+            wallpaper: value.wallpaper().clone(),
+            stackbar: value.stackbar_overrides().and_then(|overrides| {
+                // Only overrides which differ from the global values are workspace configuration
+                let global = StackbarOptions::global();
+                let overrides = WorkspaceStackbarConfig {
+                    mode: overrides.mode.filter(|mode| *mode != global.mode),
+                    label: overrides.label.filter(|label| *label != global.label),
+                    height: overrides.height.filter(|height| *height != global.height),
+                };
+
+                (overrides.mode.is_some()
+                    || overrides.label.is_some()
+                    || overrides.height.is_some())
+                .then_some(overrides)
+            }),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MonitorConfig {
     /// Workspace configurations
     pub workspaces: Vec<WorkspaceConfig>,
-    /// Monitor-specific work area offset (default: None)
+    /// Monitor-specific work area offset, in pixels or as percentages of the monitor size (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub work_area_offset: Option<Rect>,
+    pub work_area_offset: Option<RectOrPercent>,
     /// Window based work area offset (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_based_work_area_offset: Option<Rect>,
     /// Open window limit after which the window based work area offset will no longer be applied (default: 1)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_based_work_area_offset_limit: Option<isize>,
+    /// How windows are counted towards the window based work area offset limit (default: Containers)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_based_work_area_offset_counting: Option<WindowBasedWorkAreaOffsetCounting>,
+    /// Container padding for all workspaces on this monitor which do not set their own (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_padding: Option<i32>,
+    /// Workspace padding for all workspaces on this monitor which do not set their own (default: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_padding: Option<i32>,
+    /// Leave every window on this monitor alone, without tiling, borders, transparency or
+    /// workspaces, and exclude the monitor from cross-monitor commands (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unmanaged: Option<bool>,
+    /// Index of the workspace to focus on this monitor when komorebi starts, once initial
+    /// workspace rules have been enforced (default: 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_workspace_index: Option<usize>,
+    /// Name of the workspace to focus on this monitor when komorebi starts, which takes precedence
+    /// over initial_workspace_index (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_workspace_name: Option<String>,
+}
+
+impl MonitorConfig {
+    /// The index of the workspace which should be focused on the given monitor on startup, if
+    /// one has been configured
+    fn initial_workspace_idx(&self, monitor_idx: usize, monitor: &Monitor) -> Option<usize> {
+        let by_name = self.initial_workspace_name.as_ref().and_then(|name| {
+            let idx = monitor
+                .workspaces()
+                .iter()
+                .position(|workspace| workspace.explicit_name() == Some(name));
+
+            if idx.is_none() {
+                tracing::warn!(
+                    "there is no workspace named {name} on monitor {monitor_idx} to focus on startup"
+                );
+            }
+
+            idx
+        });
+
+        match by_name.or(self.initial_workspace_index)? {
+            idx if idx < monitor.workspaces().len() => Option::from(idx),
+            idx => {
+                tracing::warn!(
+                    "initial workspace index {idx} is out of range for monitor {monitor_idx}, falling back to 0"
+                );
+
+                Option::from(0)
+            }
+        }
+    }
 }
 
 impl From<&Monitor> for MonitorConfig {
@@ -235,14 +489,22 @@ impl From<&Monitor> for MonitorConfig {
 
         Self {
             workspaces,
-            work_area_offset: value.work_area_offset(),
+            work_area_offset: value.configured_work_area_offset(),
             window_based_work_area_offset: value.window_based_work_area_offset(),
             window_based_work_area_offset_limit: Some(value.window_based_work_area_offset_limit()),
+            window_based_work_area_offset_counting: Some(
+                value.window_based_work_area_offset_counting(),
+            ),
+            container_padding: value.container_padding(),
+            workspace_padding: value.workspace_padding(),
+            unmanaged: value.unmanaged().then_some(true),
+            initial_workspace_index: None,
+            initial_workspace_name: None,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 /// The `komorebi.json` static configuration file reference for `v0.1.33`
 pub struct StaticConfig {
     /// DEPRECATED from v0.1.22: no longer required
@@ -257,9 +519,16 @@ pub struct StaticConfig {
     /// Delta to resize windows by (default 50)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resize_delta: Option<i32>,
+    /// Ratios of the work area which the cycle-split-ratio command cycles the primary split
+    /// through (default: [0.5, 0.67, 0.33])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_ratio_presets: Option<Vec<f32>>,
     /// Determine what happens when a new window is opened (default: Create)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_container_behaviour: Option<WindowContainerBehaviour>,
+    /// Determine where new containers are placed in the layout (default: Adjacent)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_window_placement: Option<NewWindowPlacement>,
     /// Enable or disable float override, which makes it so every new window opens in floating mode
     /// (default: false)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -270,26 +539,64 @@ pub struct StaticConfig {
     /// Determine what happens when an action is called on a window at a monitor boundary (default: Monitor)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cross_boundary_behaviour: Option<CrossBoundaryBehaviour>,
+    /// Focus the nearest floating window when there is no tiled window in the direction of a
+    /// focus command, and the nearest tiled window when focusing from a floating window in a
+    /// direction without other floating windows (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cross_layer_focus: Option<bool>,
+    /// Wrap around to the first window of a stack when cycling past the last window and vice
+    /// versa, both with komorebic cycle-stack and when scrolling on a stackbar (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_stack_wrap: Option<bool>,
+    /// Add new windows to an existing container on the workspace which holds windows of the same
+    /// application, or of the same auto stack group (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_stack_by_exe: Option<bool>,
+    /// Identify groups of applications whose windows are stacked together when auto stacking,
+    /// instead of being grouped by exe
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_stack_groups: Option<Vec<MatchingRule>>,
+    /// Determine what happens when a tiled window is dragged and dropped with the mouse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drag_behaviour: Option<DragBehaviour>,
+    /// Determine what happens when the last window on a workspace is closed (default: DoNothing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_last_window_close: Option<LastWindowCloseBehaviour>,
     /// Determine what happens when commands are sent while an unmanaged window is in the foreground (default: Op)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unmanaged_window_operation_behaviour: Option<OperationBehaviour>,
     /// END OF LIFE FEATURE: Use https://github.com/LGUG2Z/masir instead
     #[serde(skip_serializing_if = "Option::is_none")]
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
+    /// How long in milliseconds the cursor must rest over the same window before focus follows
+    /// mouse raises it (default: 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_follows_mouse_delay_ms: Option<u64>,
+    /// Individual window identification rules for windows which focus follows mouse should never
+    /// raise, or raise other windows from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_follows_mouse_ignore: Option<Vec<MatchingRule>>,
     /// Enable or disable mouse follows focus (default: true)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mouse_follows_focus: Option<bool>,
-    /// Path to applications.json from komorebi-application-specific-configurations (default: None)
+    /// When mouse follows focus should move the cursor (default: Always)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mouse_follows_focus_scope: Option<MouseFollowsFocusScope>,
+    /// What to do when a layout gives a window a tile smaller than its minimum size (default: None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_size_policy: Option<MinimumSizePolicy>,
+    /// Path to applications.json from komorebi-application-specific-configurations, or a list of
+    /// paths which are merged in order so that later files override earlier ones (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub app_specific_configuration_path: Option<PathBuf>,
-    /// Width of the window border (default: 8)
+    pub app_specific_configuration_path: Option<AppSpecificConfigurationPath>,
+    /// Width of the window border, either for all kinds of window or per kind of window (default: 8)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "active_window_border_width")]
-    pub border_width: Option<i32>,
-    /// Offset of the window border (default: -1)
+    pub border_width: Option<BorderDimension>,
+    /// Offset of the window border, either for all kinds of window or per kind of window (default: -1)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "active_window_border_offset")]
-    pub border_offset: Option<i32>,
+    pub border_offset: Option<BorderDimension>,
     /// Display an active window border (default: false)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "active_window_border")]
@@ -298,13 +605,21 @@ pub struct StaticConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "active_window_border_colours")]
     pub border_colours: Option<BorderColours>,
+    /// Border colours for specific applications, which take precedence over the border colours
+    /// for different container types, with the first matching override being used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_colour_overrides: Option<Vec<BorderColourOverride>>,
     /// Active window border style (default: System)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "active_window_border_style")]
     pub border_style: Option<BorderStyle>,
-    /// DEPRECATED from v0.1.31: no longer required
+    /// Whether borders are drawn above or below the windows they track (default: Bottom)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_z_order: Option<ZOrder>,
+    /// Whether borders receive clicks, rather than letting them pass through to the windows
+    /// beneath (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_hit_test: Option<bool>,
     /// Active window border implementation (default: Komorebi)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_implementation: Option<BorderImplementation>,
@@ -317,6 +632,9 @@ pub struct StaticConfig {
     /// Individual window transparency ignore rules
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transparency_ignore_rules: Option<Vec<MatchingRule>>,
+    /// Indices of monitors on which windows are always opaque, regardless of focus
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transparency_ignore_monitors: Option<Vec<usize>>,
     /// Global default workspace padding (default: 10)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_workspace_padding: Option<i32>,
@@ -329,9 +647,17 @@ pub struct StaticConfig {
     /// Which Windows signal to use when hiding windows (default: Cloak)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_hiding_behaviour: Option<HidingBehaviour>,
-    /// Global work area (space used for tiling) offset (default: None)
+    /// Remove the taskbar buttons of windows on hidden workspaces, so that they do not show up in
+    /// the taskbar or in alt-tab replacements (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_taskbar_entries_for_hidden_workspaces: Option<bool>,
+    /// Name workspaces without a configured name after the application of their focused window
+    /// (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_workspace_names: Option<bool>,
+    /// Global work area (space used for tiling) offset, in pixels or as percentages of the monitor size (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub global_work_area_offset: Option<Rect>,
+    pub global_work_area_offset: Option<RectOrPercent>,
     /// Individual window floating rules
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(alias = "float_rules")]
@@ -342,6 +668,18 @@ pub struct StaticConfig {
     /// Identify applications which should be managed as floating windows
     #[serde(skip_serializing_if = "Option::is_none")]
     pub floating_applications: Option<Vec<MatchingRule>>,
+    /// Identify applications whose new windows should always open in floating mode, unless a
+    /// workspace float override is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub float_override_applications: Option<Vec<MatchingRule>>,
+    /// Identify parent applications, such as terminals, whose tiles are taken over by the windows
+    /// of applications launched from them until those windows are closed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swallowing_rules: Option<Vec<MatchingRule>>,
+    /// Identify fullscreen applications, such as games, which pause tiling, borders, transparency
+    /// and focus follows mouse on their monitor while they cover it in the foreground
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fullscreen_applications: Option<Vec<MatchingRule>>,
     /// Identify border overflow applications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_overflow_applications: Option<Vec<MatchingRule>>,
@@ -357,7 +695,9 @@ pub struct StaticConfig {
     /// Set monitor index preferences
     #[serde(skip_serializing_if = "Option::is_none")]
     pub monitor_index_preferences: Option<HashMap<usize, Rect>>,
-    /// Set display index preferences
+    /// Set display index preferences, identifying each display by its device id (optionally
+    /// prefixed with `name:`) or by the serial number in its EDID prefixed with `serial:`, which
+    /// takes precedence when it is unique among the attached monitors
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_index_preferences: Option<HashMap<usize, String>>,
     /// Stackbar configuration options
@@ -366,15 +706,45 @@ pub struct StaticConfig {
     /// Animations configuration options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub animation: Option<AnimationsConfig>,
+    /// Scratchpad configuration options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scratchpad: Option<ScratchpadConfig>,
     /// Theme configuration options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub theme: Option<KomorebiTheme>,
+    /// Duration in ms of the transition between the previous and the new theme when the theme is
+    /// changed (default: 0)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme_transition_duration: Option<u64>,
+    /// Move windows which have been moved away from the workspace of a matching workspace rule
+    /// back when they send events (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_rule_enforcement: Option<bool>,
     /// Identify applications which are slow to send initial event notifications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slow_application_identifiers: Option<Vec<MatchingRule>>,
     /// How long to wait when compensating for slow applications, in milliseconds (default: 20)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slow_application_compensation_time: Option<u64>,
+    /// Detect managed windows which have stopped responding and skip them when targeting focus
+    /// (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hung_window_detection: Option<bool>,
+    /// How often to check whether managed windows have stopped responding, in seconds (default: 5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hung_window_probe_interval: Option<u64>,
+    /// How often to remove windows which have since been closed, in milliseconds (default: 1000,
+    /// minimum: 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reaper_interval_ms: Option<u64>,
+    /// How long to wait for monitor topology changes to settle before reconciling, in
+    /// milliseconds (default: 500)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_change_debounce: Option<u64>,
+    /// How long to collect storms of title, name change and mouse capture events from a window
+    /// before handling the most recent of them, in milliseconds (default: 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window_event_debounce: Option<u64>,
     /// Komorebi status bar configuration files for multiple instances on different monitors
     #[serde(skip_serializing_if = "Option::is_none")]
     // this option is a little special because it is only consumed by komorebic
@@ -382,6 +752,16 @@ pub struct StaticConfig {
     /// HEAVILY DISCOURAGED: Identify applications for which komorebi should forcibly remove title bars
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remove_titlebar_applications: Option<Vec<MatchingRule>>,
+    /// Identify third-party bar windows and the monitor edge for which space should be reserved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserved_space_rules: Option<Vec<ReservedSpaceRule>>,
+    /// Subsystems to suppress while Windows reports a fullscreen application, game or
+    /// presentation (ie. when focus assist would silence notifications)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_mode: Option<PresentationModeConfig>,
+    /// Record processed events and the state changes they made to a JSON Lines file for debugging
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_recording: Option<StateRecordingConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -394,8 +774,11 @@ pub struct AnimationsConfig {
     style: Option<PerAnimationPrefixConfig<AnimationStyle>>,
     /// Set the animation FPS (default: 60)
     fps: Option<u64>,
+    /// Individual window animation blacklist rules, matching windows are moved without animation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blacklist: Option<Vec<MatchingRule>>,
 }
-#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "palette")]
 pub enum KomorebiTheme {
     /// A theme from catppuccin-egui
@@ -410,6 +793,8 @@ pub enum KomorebiTheme {
         monocle_border: Option<komorebi_themes::CatppuccinValue>,
         /// Border colour when the window is floating (default: Yellow)
         floating_border: Option<komorebi_themes::CatppuccinValue>,
+        /// Border colour when the window is pinned to always be on top (default: Mauve)
+        pinned_border: Option<komorebi_themes::CatppuccinValue>,
         /// Border colour when the container is unfocused (default: Base)
         unfocused_border: Option<komorebi_themes::CatppuccinValue>,
         /// Stackbar focused tab text colour (default: Green)
@@ -420,6 +805,12 @@ pub enum KomorebiTheme {
         stackbar_background: Option<komorebi_themes::CatppuccinValue>,
         /// Komorebi status bar accent (default: Blue)
         bar_accent: Option<komorebi_themes::CatppuccinValue>,
+        /// Stackbar tab font family (default: stackbar configuration)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stackbar_font_family: Option<String>,
+        /// Stackbar tab font size (default: stackbar configuration)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stackbar_font_size: Option<i32>,
     },
     /// A theme from base16-egui-themes
     Base16 {
@@ -433,6 +824,8 @@ pub enum KomorebiTheme {
         monocle_border: Option<komorebi_themes::Base16Value>,
         /// Border colour when the window is floating (default: Base09)
         floating_border: Option<komorebi_themes::Base16Value>,
+        /// Border colour when the window is pinned to always be on top (default: Base0E)
+        pinned_border: Option<komorebi_themes::Base16Value>,
         /// Border colour when the container is unfocused (default: Base01)
         unfocused_border: Option<komorebi_themes::Base16Value>,
         /// Stackbar focused tab text colour (default: Base0B)
@@ -443,27 +836,72 @@ pub enum KomorebiTheme {
         stackbar_background: Option<komorebi_themes::Base16Value>,
         /// Komorebi status bar accent (default: Base0D)
         bar_accent: Option<komorebi_themes::Base16Value>,
+        /// Stackbar tab font family (default: stackbar configuration)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stackbar_font_family: Option<String>,
+        /// Stackbar tab font size (default: stackbar configuration)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stackbar_font_size: Option<i32>,
+    },
+    /// A theme built from a complete Base16 palette
+    Custom {
+        /// Base16 palette used for any colours which are not set explicitly
+        colours: komorebi_themes::Base16ColourPalette,
+        /// Border colour when the container contains a single window (default: Base0D)
+        single_border: Option<Colour>,
+        /// Border colour when the container contains multiple windows (default: Base0B)
+        stack_border: Option<Colour>,
+        /// Border colour when the container is in monocle mode (default: Base0F)
+        monocle_border: Option<Colour>,
+        /// Border colour when the window is floating (default: Base09)
+        floating_border: Option<Colour>,
+        /// Border colour when the window is pinned to always be on top (default: Base0E)
+        pinned_border: Option<Colour>,
+        /// Border colour when the container is unfocused (default: Base01)
+        unfocused_border: Option<Colour>,
+        /// Stackbar focused tab text colour (default: Base0B)
+        stackbar_focused_text: Option<Colour>,
+        /// Stackbar unfocused tab text colour (default: Base05)
+        stackbar_unfocused_text: Option<Colour>,
+        /// Stackbar tab background colour (default: Base01)
+        stackbar_background: Option<Colour>,
+        /// Komorebi status bar accent (default: Base06)
+        bar_accent: Option<Colour>,
+        /// Stackbar tab font family (default: stackbar configuration)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stackbar_font_family: Option<String>,
+        /// Stackbar tab font size (default: stackbar configuration)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stackbar_font_size: Option<i32>,
     },
 }
 
 impl StaticConfig {
-    pub fn end_of_life(raw: &str) {
+    /// The end-of-life features used in the raw contents of a configuration file
+    pub fn end_of_life_features(raw: &str) -> Vec<&'static str> {
         let features = vec![
             "focus_follows_mouse",
             "custom_layout",
             "custom_layout_rules",
         ];
 
+        features
+            .into_iter()
+            // Match the quoted key so that options such as focus_follows_mouse_delay_ms are not
+            // reported along with focus_follows_mouse
+            .filter(|feature| raw.contains(&format!(r#""{feature}""#)))
+            .collect()
+    }
+
+    pub fn end_of_life(raw: &str) {
         let mut display = false;
 
-        for feature in features {
-            if raw.contains(feature) {
-                if !display {
-                    display = true;
-                    println!("\n\"{feature}\" is now end-of-life");
-                } else {
-                    println!(r#""{feature}" is now end-of-life"#);
-                }
+        for feature in Self::end_of_life_features(raw) {
+            if !display {
+                display = true;
+                println!("\n\"{feature}\" is now end-of-life");
+            } else {
+                println!(r#""{feature}" is now end-of-life"#);
             }
         }
 
@@ -472,58 +910,200 @@ impl StaticConfig {
         }
     }
 
-    pub fn aliases(raw: &str) {
-        let mut map = HashMap::new();
-        map.insert("border", ["active_window_border"]);
-        map.insert("border_width", ["active_window_border_width"]);
-        map.insert("border_offset", ["active_window_border_offset"]);
-        map.insert("border_colours", ["active_window_border_colours"]);
-        map.insert("border_style", ["active_window_border_style"]);
-        map.insert("applications.json", ["applications.yaml"]);
-        map.insert("ignore_rules", ["float_rules"]);
+    /// The renamed options used in the raw contents of a configuration file, as pairs of the old
+    /// name and the name that it should be replaced with
+    pub fn renamed_options(raw: &str) -> Vec<(&'static str, &'static str)> {
+        let aliases = vec![
+            ("active_window_border", "border"),
+            ("active_window_border_width", "border_width"),
+            ("active_window_border_offset", "border_offset"),
+            ("active_window_border_colours", "border_colours"),
+            ("active_window_border_style", "border_style"),
+            ("applications.yaml", "applications.json"),
+            ("float_rules", "ignore_rules"),
+        ];
 
-        let mut display = false;
+        aliases
+            .into_iter()
+            .filter(|(alias, _)| raw.contains(alias))
+            .collect()
+    }
 
-        for aliases in map.values() {
-            for a in aliases {
-                if raw.contains(a) {
-                    display = true;
-                }
-            }
-        }
+    pub fn aliases(raw: &str) {
+        let renamed = Self::renamed_options(raw);
 
-        if display {
+        if !renamed.is_empty() {
             println!("\nYour configuration file contains some options that have been renamed or deprecated:\n");
-            for (canonical, aliases) in map {
-                for alias in aliases {
-                    if raw.contains(alias) {
-                        println!(r#""{alias}" is now "{canonical}""#);
-                    }
-                }
+            for (alias, canonical) in renamed {
+                println!(r#""{alias}" is now "{canonical}""#);
             }
         }
     }
 
-    pub fn deprecated(raw: &str) {
-        let deprecated_options = ["invisible_borders", "border_z_order"];
+    /// Descriptions of the deprecated options and option values used in the raw contents of a
+    /// configuration file
+    pub fn deprecated_options(raw: &str) -> Vec<String> {
+        let deprecated_options = ["invisible_borders"];
         let deprecated_variants = vec![
             ("Hide", "window_hiding_behaviour", "Cloak"),
             ("Minimize", "window_hiding_behaviour", "Cloak"),
         ];
 
+        let mut deprecated = vec![];
+
         for option in deprecated_options {
             if raw.contains(option) {
-                println!(r#""{option}" is deprecated and can be removed"#);
+                deprecated.push(format!(r#""{option}" is deprecated and can be removed"#));
             }
         }
 
         for (variant, option, recommended) in deprecated_variants {
             if raw.contains(option) && raw.contains(variant) {
-                println!(
+                deprecated.push(format!(
                     r#"The "{variant}" option for "{option}" is deprecated and can be removed or replaced with "{recommended}""#
-                );
+                ));
             }
         }
+
+        deprecated
+    }
+
+    pub fn deprecated(raw: &str) {
+        for deprecated in Self::deprecated_options(raw) {
+            println!("{deprecated}");
+        }
+    }
+
+    /// Cross-references monitor indices, workspace names and file paths, which are otherwise
+    /// accepted without complaint and then fail silently at runtime
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+        let monitors = self.monitors.as_deref().unwrap_or_default();
+
+        let mut check_monitor_idx = |key: &str, idx: usize| {
+            if idx >= monitors.len() {
+                issues.push(ValidationIssue::new(
+                    format!(
+                        "{key} references monitor {idx}, but only {} monitors are configured",
+                        monitors.len()
+                    ),
+                    Some(format!("\"{key}\"")),
+                ));
+            }
+        };
+
+        for idx in self.transparency_ignore_monitors.iter().flatten() {
+            check_monitor_idx("transparency_ignore_monitors", *idx);
+        }
+
+        for idx in self
+            .monitor_index_preferences
+            .iter()
+            .flat_map(HashMap::keys)
+        {
+            check_monitor_idx("monitor_index_preferences", *idx);
+        }
+
+        for idx in self
+            .display_index_preferences
+            .iter()
+            .flat_map(HashMap::keys)
+        {
+            check_monitor_idx("display_index_preferences", *idx);
+        }
+
+        for ratio in self.split_ratio_presets.iter().flatten() {
+            if *ratio <= 0.0 || *ratio >= 1.0 {
+                issues.push(ValidationIssue::new(
+                    format!("split ratio preset {ratio} must be between 0 and 1"),
+                    Option::from(String::from("split_ratio_presets")),
+                ));
+            }
+        }
+
+        // Workspace names mapped to the monitor they were first seen on and how often they are used
+        let mut workspace_names: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (monitor_idx, monitor) in monitors.iter().enumerate() {
+            for (workspace_idx, workspace) in monitor.workspaces.iter().enumerate() {
+                let name = workspace.name.as_str();
+                let (first_monitor_idx, count) =
+                    workspace_names.entry(name).or_insert((monitor_idx, 0));
+
+                if *count > 0 {
+                    issues.push(ValidationIssue {
+                        occurrence: *count,
+                        ..ValidationIssue::new(
+                            format!(
+                                "workspace {workspace_idx} on monitor {monitor_idx} is named \"{name}\", which is already used on monitor {first_monitor_idx}; commands targeting this name will be ambiguous"
+                            ),
+                            serde_json::to_string(name).ok(),
+                        )
+                    });
+                }
+
+                *count += 1;
+            }
+        }
+
+        let mut paths = vec![];
+        for path in self
+            .app_specific_configuration_path
+            .iter()
+            .flat_map(AppSpecificConfigurationPath::paths)
+        {
+            paths.push(("app_specific_configuration_path", path));
+        }
+
+        for path in self.bar_configurations.iter().flatten() {
+            paths.push(("bar_configurations", path));
+        }
+
+        for workspace in monitors.iter().flat_map(|monitor| &monitor.workspaces) {
+            if let Some(path) = &workspace.custom_layout {
+                paths.push(("custom_layout", path));
+            }
+
+            for path in workspace
+                .custom_layout_rules
+                .iter()
+                .flat_map(HashMap::values)
+            {
+                paths.push(("custom_layout_rules", path));
+            }
+        }
+
+        for (key, path) in paths {
+            let exists = resolve_home_path(path).is_ok_and(|resolved| resolved.exists());
+            if !exists {
+                issues.push(ValidationIssue::new(
+                    format!("{key} file '{}' does not exist", path.display()),
+                    serde_json::to_string(path).ok(),
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+/// A problem found when validating a static configuration file
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+    pub message: String,
+    /// Text which can be searched for in the configuration file to highlight the problem
+    pub needle: Option<String>,
+    /// The occurrence of the needle to highlight, as duplicated values are reported after the
+    /// first time they appear
+    pub occurrence: usize,
+}
+
+impl ValidationIssue {
+    fn new(message: String, needle: Option<String>) -> Self {
+        Self {
+            message,
+            needle,
+            occurrence: 0,
+        }
     }
 }
 
@@ -541,6 +1121,8 @@ pub struct TabsConfig {
     font_family: Option<String>,
     /// Font size
     font_size: Option<i32>,
+    /// Show a button on each tab which closes its window (default: false)
+    close_button: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -555,6 +1137,17 @@ pub struct StackbarConfig {
     pub tabs: Option<TabsConfig>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ScratchpadConfig {
+    /// Width of a window popped from a scratchpad as a percentage of the work area (default: 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width_percentage: Option<u32>,
+    /// Aspect ratio used to calculate the height of a window popped from a scratchpad from its
+    /// width (default: the aspect ratio of the work area)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<AspectRatio>,
+}
+
 impl From<&WindowManager> for StaticConfig {
     #[allow(clippy::too_many_lines)]
     fn from(value: &WindowManager) -> Self {
@@ -563,6 +1156,26 @@ impl From<&WindowManager> for StaticConfig {
             monitors.push(MonitorConfig::from(m));
         }
 
+        let (border_width, border_offset) = if border_manager::has_window_kind_dimensions() {
+            (
+                Option::from(BorderDimension::PerWindowKind(
+                    WindowKindBorderDimensions::current(border_manager::border_width),
+                )),
+                Option::from(BorderDimension::PerWindowKind(
+                    WindowKindBorderDimensions::current(border_manager::border_offset),
+                )),
+            )
+        } else {
+            (
+                Option::from(BorderDimension::Global(
+                    border_manager::BORDER_WIDTH.load(Ordering::SeqCst),
+                )),
+                Option::from(BorderDimension::Global(
+                    border_manager::BORDER_OFFSET.load(Ordering::SeqCst),
+                )),
+            )
+        };
+
         let border_colours = if border_manager::FOCUSED.load(Ordering::SeqCst) == 0 {
             None
         } else {
@@ -573,6 +1186,7 @@ impl From<&WindowManager> for StaticConfig {
                 floating: Option::from(Colour::from(
                     border_manager::FLOATING.load(Ordering::SeqCst),
                 )),
+                pinned: Option::from(Colour::from(border_manager::PINNED.load(Ordering::SeqCst))),
                 unfocused: Option::from(Colour::from(
                     border_manager::UNFOCUSED.load(Ordering::SeqCst),
                 )),
@@ -582,24 +1196,43 @@ impl From<&WindowManager> for StaticConfig {
         Self {
             invisible_borders: None,
             resize_delta: Option::from(value.resize_delta),
+            split_ratio_presets: Option::from(SPLIT_RATIO_PRESETS.lock().clone()),
             window_container_behaviour: Option::from(
                 value.window_management_behaviour.current_behaviour,
             ),
+            new_window_placement: Option::from(
+                value.window_management_behaviour.new_window_placement,
+            ),
             float_override: Option::from(value.window_management_behaviour.float_override),
             cross_monitor_move_behaviour: Option::from(value.cross_monitor_move_behaviour),
             cross_boundary_behaviour: Option::from(value.cross_boundary_behaviour),
+            cross_layer_focus: Option::from(CROSS_LAYER_FOCUS.load(Ordering::SeqCst)),
+            cycle_stack_wrap: Option::from(CYCLE_STACK_WRAP.load(Ordering::SeqCst)),
+            auto_stack_by_exe: Option::from(AUTO_STACK_BY_EXE.load(Ordering::SeqCst)),
+            auto_stack_groups: None,
+            drag_behaviour: Option::from(value.drag_behaviour),
+            on_last_window_close: Option::from(value.on_last_window_close),
             unmanaged_window_operation_behaviour: Option::from(
                 value.unmanaged_window_operation_behaviour,
             ),
             minimum_window_height: Some(window::MINIMUM_HEIGHT.load(Ordering::SeqCst)),
             minimum_window_width: Some(window::MINIMUM_WIDTH.load(Ordering::SeqCst)),
             focus_follows_mouse: value.focus_follows_mouse,
+            focus_follows_mouse_delay_ms: Option::from(
+                FOCUS_FOLLOWS_MOUSE_DELAY_MS.load(Ordering::SeqCst),
+            ),
+            focus_follows_mouse_ignore: Option::from(FOCUS_FOLLOWS_MOUSE_IGNORE.lock().clone()),
             mouse_follows_focus: Option::from(value.mouse_follows_focus),
+            mouse_follows_focus_scope: Option::from(*MOUSE_FOLLOWS_FOCUS_SCOPE.lock()),
+            minimum_size_policy: *MINIMUM_SIZE_POLICY.lock(),
             app_specific_configuration_path: None,
-            border_width: Option::from(border_manager::BORDER_WIDTH.load(Ordering::SeqCst)),
-            border_offset: Option::from(border_manager::BORDER_OFFSET.load(Ordering::SeqCst)),
+            border_width,
+            border_offset,
             border: Option::from(border_manager::BORDER_ENABLED.load(Ordering::SeqCst)),
             border_colours,
+            border_colour_overrides: Option::from(
+                border_manager::BORDER_COLOUR_OVERRIDES.lock().clone(),
+            ),
             transparency: Option::from(
                 transparency_manager::TRANSPARENCY_ENABLED.load(Ordering::SeqCst),
             ),
@@ -607,8 +1240,10 @@ impl From<&WindowManager> for StaticConfig {
                 transparency_manager::TRANSPARENCY_ALPHA.load(Ordering::SeqCst),
             ),
             transparency_ignore_rules: None,
+            transparency_ignore_monitors: Option::from(transparency_manager::ignored_monitors()),
             border_style: Option::from(STYLE.load()),
-            border_z_order: None,
+            border_z_order: Option::from(border_manager::Z_ORDER.load()),
+            border_hit_test: Option::from(border_manager::BORDER_HIT_TEST.load(Ordering::SeqCst)),
             border_implementation: Option::from(IMPLEMENTATION.load()),
             default_workspace_padding: Option::from(
                 DEFAULT_WORKSPACE_PADDING.load(Ordering::SeqCst),
@@ -618,9 +1253,16 @@ impl From<&WindowManager> for StaticConfig {
             ),
             monitors: Option::from(monitors),
             window_hiding_behaviour: Option::from(*HIDING_BEHAVIOUR.lock()),
+            hide_taskbar_entries_for_hidden_workspaces: Option::from(
+                HIDE_TASKBAR_ENTRIES_FOR_HIDDEN_WORKSPACES.load(Ordering::SeqCst),
+            ),
+            auto_workspace_names: Option::from(AUTO_WORKSPACE_NAMES.load(Ordering::SeqCst)),
             global_work_area_offset: value.work_area_offset,
             ignore_rules: None,
             floating_applications: None,
+            float_override_applications: None,
+            swallowing_rules: None,
+            fullscreen_applications: None,
             manage_rules: None,
             border_overflow_applications: None,
             tray_and_multi_window_applications: None,
@@ -629,14 +1271,46 @@ impl From<&WindowManager> for StaticConfig {
             monitor_index_preferences: Option::from(MONITOR_INDEX_PREFERENCES.lock().clone()),
             display_index_preferences: Option::from(DISPLAY_INDEX_PREFERENCES.lock().clone()),
             stackbar: None,
+            scratchpad: Option::from(ScratchpadConfig {
+                width_percentage: Option::from(SCRATCHPAD_WIDTH_PERCENTAGE.load(Ordering::SeqCst)),
+                aspect_ratio: *SCRATCHPAD_ASPECT_RATIO.lock(),
+            }),
             animation: None,
             theme: None,
+            workspace_rule_enforcement: Option::from(
+                WORKSPACE_RULE_ENFORCEMENT.load(Ordering::SeqCst),
+            ),
+            theme_transition_duration: Option::from(
+                theme_manager::TRANSITION_DURATION.load(Ordering::SeqCst),
+            ),
             slow_application_compensation_time: Option::from(
                 SLOW_APPLICATION_COMPENSATION_TIME.load(Ordering::SeqCst),
             ),
             slow_application_identifiers: Option::from(SLOW_APPLICATION_IDENTIFIERS.lock().clone()),
+            hung_window_detection: Option::from(
+                reaper::HUNG_WINDOW_DETECTION.load(Ordering::SeqCst),
+            ),
+            hung_window_probe_interval: Option::from(
+                reaper::HUNG_WINDOW_PROBE_INTERVAL.load(Ordering::SeqCst),
+            ),
+            reaper_interval_ms: Option::from(reaper::REAPER_INTERVAL_MS.load(Ordering::SeqCst)),
+            window_event_debounce: Option::from(
+                process_event::WINDOW_EVENT_DEBOUNCE.load(Ordering::SeqCst),
+            ),
+            display_change_debounce: Option::from(
+                monitor_reconciliator::DISPLAY_CHANGE_DEBOUNCE.load(Ordering::SeqCst),
+            ),
             bar_configurations: None,
             remove_titlebar_applications: Option::from(NO_TITLEBAR.lock().clone()),
+            reserved_space_rules: Option::from(RESERVED_SPACE_RULES.lock().clone()),
+            presentation_mode: *PRESENTATION_MODE_CONFIG.lock(),
+            state_recording: Option::from(StateRecordingConfig {
+                enabled: state_recorder::STATE_RECORDING_ENABLED.load(Ordering::SeqCst),
+                path: Option::from(state_recorder::STATE_RECORDING_PATH.lock().clone()),
+                max_size_mb: Option::from(
+                    state_recorder::STATE_RECORDING_MAX_SIZE_MB.load(Ordering::SeqCst),
+                ),
+            }),
         }
     }
 }
@@ -654,11 +1328,36 @@ impl StaticConfig {
             preferences.clone_from(display_index_preferences);
         }
 
+        if let Some(presets) = &self.split_ratio_presets {
+            let mut split_ratio_presets = SPLIT_RATIO_PRESETS.lock();
+            split_ratio_presets.clone_from(presets);
+        }
+
         if let Some(behaviour) = self.window_hiding_behaviour {
             let mut window_hiding_behaviour = HIDING_BEHAVIOUR.lock();
             *window_hiding_behaviour = behaviour;
         }
 
+        if let Some(scope) = self.mouse_follows_focus_scope {
+            let mut mouse_follows_focus_scope = MOUSE_FOLLOWS_FOCUS_SCOPE.lock();
+            *mouse_follows_focus_scope = scope;
+        }
+
+        FOCUS_FOLLOWS_MOUSE_DELAY_MS.store(
+            self.focus_follows_mouse_delay_ms.unwrap_or(0),
+            Ordering::SeqCst,
+        );
+
+        *MINIMUM_SIZE_POLICY.lock() = self.minimum_size_policy;
+
+        HIDE_TASKBAR_ENTRIES_FOR_HIDDEN_WORKSPACES.store(
+            self.hide_taskbar_entries_for_hidden_workspaces
+                .unwrap_or(false),
+            Ordering::SeqCst,
+        );
+
+        AUTO_WORKSPACE_NAMES.store(self.auto_workspace_names.unwrap_or(false), Ordering::SeqCst);
+
         if let Some(height) = self.minimum_window_height {
             window::MINIMUM_HEIGHT.store(height, Ordering::SeqCst);
         }
@@ -680,11 +1379,15 @@ impl StaticConfig {
 
             match &animations.style {
                 Some(PerAnimationPrefixConfig::Prefix(style)) => {
+                    for style in style.values() {
+                        style.validate()?;
+                    }
+
                     ANIMATION_STYLE_PER_ANIMATION.lock().clone_from(style);
                 }
                 Some(PerAnimationPrefixConfig::Global(style)) => {
                     let mut animation_style = ANIMATION_STYLE_GLOBAL.lock();
-                    *animation_style = *style;
+                    *animation_style = style.validate()?;
                     ANIMATION_STYLE_PER_ANIMATION.lock().clear();
                 }
                 None => {}
@@ -715,8 +1418,41 @@ impl StaticConfig {
             DEFAULT_WORKSPACE_PADDING.store(workspace, Ordering::SeqCst);
         }
 
-        border_manager::BORDER_WIDTH.store(self.border_width.unwrap_or(8), Ordering::SeqCst);
-        border_manager::BORDER_OFFSET.store(self.border_offset.unwrap_or(-1), Ordering::SeqCst);
+        BorderDimension::apply(
+            self.border_width,
+            8,
+            &border_manager::BORDER_WIDTH,
+            border_manager::set_border_width,
+        );
+        BorderDimension::apply(
+            self.border_offset,
+            -1,
+            &border_manager::BORDER_OFFSET,
+            border_manager::set_border_offset,
+        );
+
+        reaper::HUNG_WINDOW_DETECTION
+            .store(self.hung_window_detection.unwrap_or(true), Ordering::SeqCst);
+
+        reaper::HUNG_WINDOW_PROBE_INTERVAL.store(
+            self.hung_window_probe_interval.unwrap_or(5).max(1),
+            Ordering::SeqCst,
+        );
+
+        reaper::REAPER_INTERVAL_MS.store(
+            self.reaper_interval_ms
+                .unwrap_or(1000)
+                .max(reaper::MINIMUM_REAPER_INTERVAL_MS),
+            Ordering::SeqCst,
+        );
+
+        monitor_reconciliator::DISPLAY_CHANGE_DEBOUNCE.store(
+            self.display_change_debounce.unwrap_or(500),
+            Ordering::SeqCst,
+        );
+
+        process_event::WINDOW_EVENT_DEBOUNCE
+            .store(self.window_event_debounce.unwrap_or(50), Ordering::SeqCst);
 
         if let Some(enabled) = &self.border {
             border_manager::BORDER_ENABLED.store(*enabled, Ordering::SeqCst);
@@ -739,12 +1475,19 @@ impl StaticConfig {
                 border_manager::FLOATING.store(u32::from(floating), Ordering::SeqCst);
             }
 
+            if let Some(pinned) = colours.pinned {
+                border_manager::PINNED.store(u32::from(pinned), Ordering::SeqCst);
+            }
+
             if let Some(unfocused) = colours.unfocused {
                 border_manager::UNFOCUSED.store(u32::from(unfocused), Ordering::SeqCst);
             }
         }
 
         STYLE.store(self.border_style.unwrap_or_default());
+        border_manager::Z_ORDER.store(self.border_z_order.unwrap_or_default());
+        border_manager::BORDER_HIT_TEST
+            .store(self.border_hit_test.unwrap_or(true), Ordering::SeqCst);
 
         if !*WINDOWS_11
             && matches!(
@@ -763,6 +1506,23 @@ impl StaticConfig {
                 }
                 BorderImplementation::Windows => {
                     // TODO: figure out how to call wm.remove_all_accents here
+
+                    // Only the borders drawn by komorebi can be translucent
+                    if let Some(colours) = &self.border_colours {
+                        for colour in [
+                            colours.single,
+                            colours.stack,
+                            colours.monocle,
+                            colours.floating,
+                            colours.pinned,
+                            colours.unfocused,
+                        ]
+                        .into_iter()
+                        .flatten()
+                        {
+                            colour.warn_if_translucent("windows border");
+                        }
+                    }
                 }
             }
 
@@ -773,6 +1533,11 @@ impl StaticConfig {
             .store(self.transparency.unwrap_or(false), Ordering::SeqCst);
         transparency_manager::TRANSPARENCY_ALPHA
             .store(self.transparency_alpha.unwrap_or(200), Ordering::SeqCst);
+        transparency_manager::set_ignored_monitors(
+            self.transparency_ignore_monitors
+                .as_deref()
+                .unwrap_or_default(),
+        );
 
         let mut ignore_identifiers = IGNORE_IDENTIFIERS.lock();
         let mut regex_identifiers = REGEX_IDENTIFIERS.lock();
@@ -783,18 +1548,54 @@ impl StaticConfig {
         let mut transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
         let mut slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
         let mut floating_applications = FLOATING_APPLICATIONS.lock();
+        let mut float_override_applications = FLOAT_OVERRIDE_APPLICATIONS.lock();
+        let mut swallowing_rules = SWALLOWING_RULES.lock();
+        let mut auto_stack_groups = AUTO_STACK_GROUPS.lock();
+        let mut fullscreen_applications = FULLSCREEN_APPLICATIONS.lock();
         let mut no_titlebar_applications = NO_TITLEBAR.lock();
+        let mut animations_blacklist = ANIMATIONS_BLACKLIST.lock();
+        let mut animations_blacklist_regex = ANIMATIONS_BLACKLIST_REGEX.lock();
 
         if let Some(rules) = &mut self.ignore_rules {
             populate_rules(rules, &mut ignore_identifiers, &mut regex_identifiers)?;
+            record_rule_provenance(
+                rules,
+                &mut IGNORE_IDENTIFIERS_PROVENANCE.lock(),
+                RuleProvenance::StaticConfig,
+            );
         }
 
         if let Some(rules) = &mut self.floating_applications {
             populate_rules(rules, &mut floating_applications, &mut regex_identifiers)?;
         }
 
+        if let Some(rules) = &mut self.float_override_applications {
+            populate_rules(
+                rules,
+                &mut float_override_applications,
+                &mut regex_identifiers,
+            )?;
+        }
+
+        if let Some(rules) = &mut self.swallowing_rules {
+            populate_rules(rules, &mut swallowing_rules, &mut regex_identifiers)?;
+        }
+
+        if let Some(rules) = &mut self.auto_stack_groups {
+            populate_rules(rules, &mut auto_stack_groups, &mut regex_identifiers)?;
+        }
+
+        if let Some(rules) = &mut self.fullscreen_applications {
+            populate_rules(rules, &mut fullscreen_applications, &mut regex_identifiers)?;
+        }
+
         if let Some(rules) = &mut self.manage_rules {
             populate_rules(rules, &mut manage_identifiers, &mut regex_identifiers)?;
+            record_rule_provenance(
+                rules,
+                &mut MANAGE_IDENTIFIERS_PROVENANCE.lock(),
+                RuleProvenance::StaticConfig,
+            );
         }
 
         if let Some(rules) = &mut self.object_name_change_applications {
@@ -821,6 +1622,18 @@ impl StaticConfig {
             populate_rules(rules, &mut transparency_blacklist, &mut regex_identifiers)?;
         }
 
+        if let Some(rules) = self
+            .animation
+            .as_mut()
+            .and_then(|animations| animations.blacklist.as_mut())
+        {
+            populate_rules(
+                rules,
+                &mut animations_blacklist,
+                &mut animations_blacklist_regex,
+            )?;
+        }
+
         if let Some(rules) = &mut self.slow_application_identifiers {
             populate_rules(
                 rules,
@@ -833,6 +1646,83 @@ impl StaticConfig {
             populate_rules(rules, &mut no_titlebar_applications, &mut regex_identifiers)?;
         }
 
+        let mut focus_follows_mouse_ignore = FOCUS_FOLLOWS_MOUSE_IGNORE.lock();
+        focus_follows_mouse_ignore.clear();
+
+        if let Some(rules) = &mut self.focus_follows_mouse_ignore {
+            populate_rules(
+                rules,
+                &mut focus_follows_mouse_ignore,
+                &mut regex_identifiers,
+            )?;
+        }
+
+        let mut border_colour_overrides = border_manager::BORDER_COLOUR_OVERRIDES.lock();
+        border_colour_overrides.clear();
+
+        if let Some(overrides) = &self.border_colour_overrides {
+            for colour_override in overrides {
+                let mut identifiers = vec![];
+                populate_rules(
+                    &mut vec![colour_override.matching_rule.clone()],
+                    &mut identifiers,
+                    &mut regex_identifiers,
+                )?;
+
+                for matching_rule in identifiers {
+                    border_colour_overrides.push(BorderColourOverride {
+                        matching_rule,
+                        focused: colour_override.focused,
+                        unfocused: colour_override.unfocused,
+                    });
+                }
+            }
+        }
+
+        let mut reserved_space_rules = RESERVED_SPACE_RULES.lock();
+        reserved_space_rules.clear();
+
+        if let Some(rules) = &self.reserved_space_rules {
+            for rule in rules {
+                let mut identifiers = vec![];
+                populate_rules(
+                    &mut vec![rule.matching_rule.clone()],
+                    &mut identifiers,
+                    &mut regex_identifiers,
+                )?;
+
+                for matching_rule in identifiers {
+                    reserved_space_rules.push(ReservedSpaceRule {
+                        matching_rule,
+                        edge: rule.edge,
+                    });
+                }
+            }
+        }
+
+        *PRESENTATION_MODE_CONFIG.lock() = self.presentation_mode;
+
+        if let Some(state_recording) = &self.state_recording {
+            if let Some(path) = &state_recording.path {
+                *state_recorder::STATE_RECORDING_PATH.lock() = resolve_home_path(path)?;
+            }
+
+            if let Some(max_size_mb) = state_recording.max_size_mb {
+                state_recorder::STATE_RECORDING_MAX_SIZE_MB.store(max_size_mb, Ordering::SeqCst);
+            }
+
+            state_recorder::set_enabled(state_recording.enabled);
+        }
+
+        if let Some(scratchpad) = &self.scratchpad {
+            SCRATCHPAD_WIDTH_PERCENTAGE.store(
+                scratchpad.width_percentage.unwrap_or(50).clamp(1, 100),
+                Ordering::SeqCst,
+            );
+
+            *SCRATCHPAD_ASPECT_RATIO.lock() = scratchpad.aspect_ratio;
+        }
+
         if let Some(stackbar) = &self.stackbar {
             if let Some(height) = &stackbar.height {
                 STACKBAR_TAB_HEIGHT.store(*height, Ordering::SeqCst);
@@ -843,7 +1733,7 @@ impl StaticConfig {
             }
 
             if let Some(mode) = &stackbar.mode {
-                STACKBAR_MODE.store(*mode);
+                stackbar_manager::set_mode(*mode);
             }
 
             #[allow(clippy::assigning_clones)]
@@ -853,10 +1743,12 @@ impl StaticConfig {
                 }
 
                 if let Some(colour) = &tabs.focused_text {
+                    colour.warn_if_translucent("stackbar text");
                     STACKBAR_FOCUSED_TEXT_COLOUR.store((*colour).into(), Ordering::SeqCst);
                 }
 
                 if let Some(colour) = &tabs.unfocused_text {
+                    colour.warn_if_translucent("stackbar text");
                     STACKBAR_UNFOCUSED_TEXT_COLOUR.store((*colour).into(), Ordering::SeqCst);
                 }
 
@@ -865,150 +1757,112 @@ impl StaticConfig {
                 }
 
                 STACKBAR_FONT_SIZE.store(tabs.font_size.unwrap_or(0), Ordering::SeqCst);
+                STACKBAR_TAB_CLOSE_BUTTON
+                    .store(tabs.close_button.unwrap_or(false), Ordering::SeqCst);
                 *STACKBAR_FONT_FAMILY.lock() = tabs.font_family.clone();
             }
         }
 
+        WORKSPACE_RULE_ENFORCEMENT.store(
+            self.workspace_rule_enforcement.unwrap_or(true),
+            Ordering::SeqCst,
+        );
+
+        CROSS_LAYER_FOCUS.store(self.cross_layer_focus.unwrap_or(false), Ordering::SeqCst);
+        CYCLE_STACK_WRAP.store(self.cycle_stack_wrap.unwrap_or(true), Ordering::SeqCst);
+        AUTO_STACK_BY_EXE.store(self.auto_stack_by_exe.unwrap_or(false), Ordering::SeqCst);
+
+        theme_manager::TRANSITION_DURATION.store(
+            self.theme_transition_duration.unwrap_or(0),
+            Ordering::SeqCst,
+        );
+
         if let Some(theme) = &self.theme {
-            theme_manager::send_notification(*theme);
+            theme_manager::send_notification(theme.clone());
         }
 
         if let Some(path) = &self.app_specific_configuration_path {
-            match path.extension() {
-                None => {}
-                Some(ext) => match ext.to_string_lossy().to_string().as_str() {
-                    "yaml" => {
-                        tracing::info!("loading applications.yaml from: {}", path.display());
-                        let path = resolve_home_path(path)?;
-                        let content = std::fs::read_to_string(path)?;
-                        let asc = ApplicationConfigurationGenerator::load(&content)?;
-
-                        for mut entry in asc {
-                            if let Some(rules) = &mut entry.ignore_identifiers {
-                                populate_rules(
-                                    rules,
-                                    &mut ignore_identifiers,
-                                    &mut regex_identifiers,
-                                )?;
-                            }
-
-                            if let Some(ref options) = entry.options {
-                                let options = options.clone();
-                                for o in options {
-                                    match o {
-                                        ApplicationOptions::ObjectNameChange => {
-                                            populate_option(
-                                                &mut entry,
-                                                &mut object_name_change_identifiers,
-                                                &mut regex_identifiers,
-                                            )?;
-                                        }
-                                        ApplicationOptions::Layered => {
-                                            populate_option(
-                                                &mut entry,
-                                                &mut layered_identifiers,
-                                                &mut regex_identifiers,
-                                            )?;
-                                        }
-                                        ApplicationOptions::TrayAndMultiWindow => {
-                                            populate_option(
-                                                &mut entry,
-                                                &mut tray_and_multi_window_identifiers,
-                                                &mut regex_identifiers,
-                                            )?;
-                                        }
-                                        ApplicationOptions::Force => {
-                                            populate_option(
-                                                &mut entry,
-                                                &mut manage_identifiers,
-                                                &mut regex_identifiers,
-                                            )?;
-                                        }
-                                        ApplicationOptions::BorderOverflow => {} // deprecated
-                                    }
-                                }
-                            }
+            let (mut asc, conflicts) =
+                ApplicationSpecificConfiguration::load_merged(&path.paths())?;
+            for conflict in conflicts {
+                tracing::info!("{conflict}");
+            }
+
+            for entry in asc.values_mut() {
+                match entry {
+                    AscApplicationRulesOrSchema::Schema(_) => {}
+                    AscApplicationRulesOrSchema::AscApplicationRules(entry) => {
+                        if let Some(rules) = &mut entry.ignore {
+                            populate_rules(rules, &mut ignore_identifiers, &mut regex_identifiers)?;
                         }
-                    }
-                    "json" => {
-                        tracing::info!("loading applications.json from: {}", path.display());
-                        let path = resolve_home_path(path)?;
-                        let mut asc = ApplicationSpecificConfiguration::load(&path)?;
-
-                        for entry in asc.values_mut() {
-                            match entry {
-                                AscApplicationRulesOrSchema::Schema(_) => {}
-                                AscApplicationRulesOrSchema::AscApplicationRules(entry) => {
-                                    if let Some(rules) = &mut entry.ignore {
-                                        populate_rules(
-                                            rules,
-                                            &mut ignore_identifiers,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-
-                                    if let Some(rules) = &mut entry.manage {
-                                        populate_rules(
-                                            rules,
-                                            &mut manage_identifiers,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-
-                                    if let Some(rules) = &mut entry.floating {
-                                        populate_rules(
-                                            rules,
-                                            &mut floating_applications,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-
-                                    if let Some(rules) = &mut entry.transparency_ignore {
-                                        populate_rules(
-                                            rules,
-                                            &mut transparency_blacklist,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-
-                                    if let Some(rules) = &mut entry.tray_and_multi_window {
-                                        populate_rules(
-                                            rules,
-                                            &mut tray_and_multi_window_identifiers,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-
-                                    if let Some(rules) = &mut entry.layered {
-                                        populate_rules(
-                                            rules,
-                                            &mut layered_identifiers,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-
-                                    if let Some(rules) = &mut entry.object_name_change {
-                                        populate_rules(
-                                            rules,
-                                            &mut object_name_change_identifiers,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-
-                                    if let Some(rules) = &mut entry.slow_application {
-                                        populate_rules(
-                                            rules,
-                                            &mut slow_application_identifiers,
-                                            &mut regex_identifiers,
-                                        )?;
-                                    }
-                                }
-                            }
+
+                        if let Some(rules) = &mut entry.manage {
+                            populate_rules(rules, &mut manage_identifiers, &mut regex_identifiers)?;
+                        }
+
+                        if let Some(rules) = &mut entry.floating {
+                            populate_rules(
+                                rules,
+                                &mut floating_applications,
+                                &mut regex_identifiers,
+                            )?;
+                        }
+
+                        if let Some(rules) = &mut entry.transparency_ignore {
+                            populate_rules(
+                                rules,
+                                &mut transparency_blacklist,
+                                &mut regex_identifiers,
+                            )?;
+                        }
+
+                        if let Some(rules) = &mut entry.tray_and_multi_window {
+                            populate_rules(
+                                rules,
+                                &mut tray_and_multi_window_identifiers,
+                                &mut regex_identifiers,
+                            )?;
+                        }
+
+                        if let Some(rules) = &mut entry.layered {
+                            populate_rules(
+                                rules,
+                                &mut layered_identifiers,
+                                &mut regex_identifiers,
+                            )?;
+                        }
+
+                        if let Some(rules) = &mut entry.object_name_change {
+                            populate_rules(
+                                rules,
+                                &mut object_name_change_identifiers,
+                                &mut regex_identifiers,
+                            )?;
+                        }
+
+                        if let Some(rules) = &mut entry.slow_application {
+                            populate_rules(
+                                rules,
+                                &mut slow_application_identifiers,
+                                &mut regex_identifiers,
+                            )?;
                         }
                     }
-                    _ => {}
-                },
+                }
             }
+
+            // Rules from komorebi.json and runtime commands have already been recorded, so any
+            // manage or ignore rules without a provenance at this point came from this file
+            record_rule_provenance(
+                &ignore_identifiers,
+                &mut IGNORE_IDENTIFIERS_PROVENANCE.lock(),
+                RuleProvenance::AppSpecificConfiguration,
+            );
+            record_rule_provenance(
+                &manage_identifiers,
+                &mut MANAGE_IDENTIFIERS_PROVENANCE.lock(),
+                RuleProvenance::AppSpecificConfiguration,
+            );
         }
 
         Ok(())
@@ -1018,8 +1872,10 @@ impl StaticConfig {
         let content = std::fs::read_to_string(path)?;
         let mut value: Self = serde_json::from_str(&content)?;
 
-        if let Some(path) = &mut value.app_specific_configuration_path {
-            *path = resolve_home_path(&*path)?;
+        if let Some(paths) = &mut value.app_specific_configuration_path {
+            for path in paths.paths_mut() {
+                *path = resolve_home_path(&*path)?;
+            }
         }
 
         if let Some(monitors) = &mut value.monitors {
@@ -1088,6 +1944,7 @@ impl StaticConfig {
                     .window_container_behaviour
                     .unwrap_or(WindowContainerBehaviour::Create),
                 float_override: value.float_override.unwrap_or_default(),
+                new_window_placement: value.new_window_placement.unwrap_or_default(),
             },
             cross_monitor_move_behaviour: value
                 .cross_monitor_move_behaviour
@@ -1095,6 +1952,8 @@ impl StaticConfig {
             cross_boundary_behaviour: value
                 .cross_boundary_behaviour
                 .unwrap_or(CrossBoundaryBehaviour::Monitor),
+            drag_behaviour: value.drag_behaviour.unwrap_or_default(),
+            on_last_window_close: value.on_last_window_close.unwrap_or_default(),
             unmanaged_window_operation_behaviour: value
                 .unmanaged_window_operation_behaviour
                 .unwrap_or(OperationBehaviour::Op),
@@ -1105,6 +1964,7 @@ impl StaticConfig {
             has_pending_raise_op: false,
             pending_move_op: Arc::new(None),
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
+            manually_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
             uncloack_to_ignore: 0,
         };
 
@@ -1116,7 +1976,7 @@ impl StaticConfig {
             Some(FocusFollowsMouseImplementation::Komorebi) => {}
         };
 
-        let bytes = SocketMessage::ReloadStaticConfiguration(path.clone()).as_bytes()?;
+        let bytes = SocketMessage::ReloadStaticConfiguration(path.clone(), false).as_bytes()?;
 
         wm.hotwatch.watch(path, move |event| match event.kind {
             // Editing in Notepad sends a NoticeWrite while editing in (Neo)Vim sends
@@ -1139,28 +1999,46 @@ impl StaticConfig {
         let value = Self::read(path)?;
         let mut wm = wm.lock();
 
-        if let Some(monitors) = value.monitors {
+        if let Some(monitors) = &value.monitors {
             for (i, monitor) in monitors.iter().enumerate() {
                 {
                     let display_index_preferences = DISPLAY_INDEX_PREFERENCES.lock();
-                    if let Some(device_id) = display_index_preferences.get(&i) {
-                        monitor_reconciliator::insert_in_monitor_cache(device_id, monitor.clone());
+                    if let Some(preference) = display_index_preferences.get(&i) {
+                        let key = match DisplayPreference::from(preference.as_str()) {
+                            DisplayPreference::Serial(serial) => format!("serial:{serial}"),
+                            DisplayPreference::DeviceId(device_id) => device_id.to_string(),
+                        };
+
+                        monitor_reconciliator::insert_in_monitor_cache(&key, monitor.clone());
                     }
                 }
 
                 if let Some(m) = wm.monitors_mut().get_mut(i) {
                     m.ensure_workspace_count(monitor.workspaces.len());
-                    m.set_work_area_offset(monitor.work_area_offset);
+                    m.set_configured_work_area_offset(monitor.work_area_offset);
                     m.set_window_based_work_area_offset(monitor.window_based_work_area_offset);
                     m.set_window_based_work_area_offset_limit(
                         monitor.window_based_work_area_offset_limit.unwrap_or(1),
                     );
+                    m.set_window_based_work_area_offset_counting(
+                        monitor
+                            .window_based_work_area_offset_counting
+                            .unwrap_or_default(),
+                    );
+                    m.set_container_padding(monitor.container_padding);
+                    m.set_workspace_padding(monitor.workspace_padding);
+                    m.set_unmanaged(monitor.unmanaged.unwrap_or_default());
+                    if m.unmanaged() {
+                        m.release_windows();
+                    }
 
                     for (j, ws) in m.workspaces_mut().iter_mut().enumerate() {
                         if let Some(workspace_config) = monitor.workspaces.get(j) {
                             ws.load_static_config(workspace_config)?;
                         }
                     }
+
+                    m.apply_padding_defaults();
                 }
 
                 let mut workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
@@ -1172,6 +2050,10 @@ impl StaticConfig {
                                 workspace_index: j,
                                 matching_rule: r.clone(),
                                 initial_only: false,
+                                enforce_on_event: ws
+                                    .enforce_workspace_rules_on_event
+                                    .unwrap_or(true),
+                                provenance: RuleProvenance::StaticConfig,
                             });
                         }
                     }
@@ -1183,6 +2065,8 @@ impl StaticConfig {
                                 workspace_index: j,
                                 matching_rule: r.clone(),
                                 initial_only: true,
+                                enforce_on_event: true,
+                                provenance: RuleProvenance::StaticConfig,
                             });
                         }
                     }
@@ -1191,6 +2075,32 @@ impl StaticConfig {
         }
 
         wm.enforce_workspace_rules()?;
+        wm.ensure_focused_monitor_is_managed()?;
+
+        // Initial workspaces are only focused here on startup, once initial workspace rules have
+        // put windows in place, and are left alone when the configuration is reloaded
+        if let Some(monitors) = &value.monitors {
+            for (i, monitor_config) in monitors.iter().enumerate() {
+                if let Some(monitor) = wm.monitors_mut().get_mut(i) {
+                    if monitor.unmanaged() {
+                        continue;
+                    }
+
+                    if let Some(idx) = monitor_config.initial_workspace_idx(i, monitor) {
+                        if idx != monitor.focused_workspace_idx() {
+                            tracing::info!("focusing initial workspace {idx} on monitor {i}");
+                            monitor.focus_workspace(idx)?;
+                            monitor.load_focused_workspace_without_focus();
+                        }
+                    }
+                }
+            }
+        }
+
+        // Wallpapers of focused workspaces apply as soon as the configuration is (re)loaded
+        for monitor in wm.monitors().iter().filter(|monitor| !monitor.unmanaged()) {
+            monitor.apply_wallpaper();
+        }
 
         if value.border == Some(true) {
             border_manager::BORDER_ENABLED.store(true, Ordering::SeqCst);
@@ -1202,25 +2112,69 @@ impl StaticConfig {
     pub fn reload(path: &PathBuf, wm: &mut WindowManager) -> Result<()> {
         let mut value = Self::read(path)?;
 
+        // Changes to the global default or to workspace overrides leave unfocused workspaces
+        // hidden with their previous behaviour until they are hidden again
+        let previous_hiding_behaviours = wm
+            .monitors()
+            .iter()
+            .map(|m| {
+                m.workspaces()
+                    .iter()
+                    .map(Workspace::hiding_behaviour)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Rules may have moved between komorebi.json and the application specific configuration
+        // file, so only the provenance of builtin and runtime rules outlives a reload
+        for registry in [
+            &*IGNORE_IDENTIFIERS_PROVENANCE,
+            &*MANAGE_IDENTIFIERS_PROVENANCE,
+        ] {
+            registry.lock().retain(|rule| {
+                matches!(
+                    rule.provenance,
+                    RuleProvenance::Builtin | RuleProvenance::Runtime
+                )
+            });
+        }
+
         value.apply_globals()?;
 
+        wm.update_auto_workspace_names();
+
         if let Some(monitors) = value.monitors {
             for (i, monitor) in monitors.iter().enumerate() {
                 if let Some(m) = wm.monitors_mut().get_mut(i) {
                     m.ensure_workspace_count(monitor.workspaces.len());
-                    if m.work_area_offset().is_none() {
-                        m.set_work_area_offset(monitor.work_area_offset);
+                    // Space reserved for bars is not a configured offset, and is reserved again
+                    // on top of whatever is configured now
+                    if m.configured_work_area_offset().is_none() {
+                        m.set_configured_work_area_offset(monitor.work_area_offset);
                     }
                     m.set_window_based_work_area_offset(monitor.window_based_work_area_offset);
                     m.set_window_based_work_area_offset_limit(
                         monitor.window_based_work_area_offset_limit.unwrap_or(1),
                     );
+                    m.set_window_based_work_area_offset_counting(
+                        monitor
+                            .window_based_work_area_offset_counting
+                            .unwrap_or_default(),
+                    );
+                    m.set_container_padding(monitor.container_padding);
+                    m.set_workspace_padding(monitor.workspace_padding);
+                    m.set_unmanaged(monitor.unmanaged.unwrap_or_default());
+                    if m.unmanaged() {
+                        m.release_windows();
+                    }
 
                     for (j, ws) in m.workspaces_mut().iter_mut().enumerate() {
                         if let Some(workspace_config) = monitor.workspaces.get(j) {
                             ws.load_static_config(workspace_config)?;
                         }
                     }
+
+                    m.apply_padding_defaults();
                 }
 
                 let mut workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
@@ -1233,6 +2187,10 @@ impl StaticConfig {
                                 workspace_index: j,
                                 matching_rule: r.clone(),
                                 initial_only: false,
+                                enforce_on_event: ws
+                                    .enforce_workspace_rules_on_event
+                                    .unwrap_or(true),
+                                provenance: RuleProvenance::StaticConfig,
                             });
                         }
                     }
@@ -1244,6 +2202,8 @@ impl StaticConfig {
                                 workspace_index: j,
                                 matching_rule: r.clone(),
                                 initial_only: true,
+                                enforce_on_event: true,
+                                provenance: RuleProvenance::StaticConfig,
                             });
                         }
                     }
@@ -1251,7 +2211,33 @@ impl StaticConfig {
             }
         }
 
+        for (i, m) in wm.monitors_mut().iter_mut().enumerate() {
+            if m.unmanaged() {
+                continue;
+            }
+
+            let focused_workspace_idx = m.focused_workspace_idx();
+            for (j, ws) in m.workspaces_mut().iter_mut().enumerate() {
+                if j == focused_workspace_idx {
+                    continue;
+                }
+
+                if let Some(previous) = previous_hiding_behaviours
+                    .get(i)
+                    .and_then(|behaviours| behaviours.get(j))
+                {
+                    ws.rehide(*previous);
+                }
+            }
+        }
+
         wm.enforce_workspace_rules()?;
+        wm.ensure_focused_monitor_is_managed()?;
+
+        // Wallpapers of focused workspaces apply as soon as the configuration is (re)loaded
+        for monitor in wm.monitors().iter().filter(|monitor| !monitor.unmanaged()) {
+            monitor.apply_wallpaper();
+        }
 
         if let Some(enabled) = value.border {
             border_manager::BORDER_ENABLED.store(enabled, Ordering::SeqCst);
@@ -1265,6 +2251,10 @@ impl StaticConfig {
             wm.window_management_behaviour.float_override = val;
         }
 
+        if let Some(val) = value.new_window_placement {
+            wm.window_management_behaviour.new_window_placement = val;
+        }
+
         if let Some(val) = value.cross_monitor_move_behaviour {
             wm.cross_monitor_move_behaviour = val;
         }
@@ -1273,6 +2263,14 @@ impl StaticConfig {
             wm.cross_boundary_behaviour = val;
         }
 
+        if let Some(val) = value.drag_behaviour {
+            wm.drag_behaviour = val;
+        }
+
+        if let Some(val) = value.on_last_window_close {
+            wm.on_last_window_close = val;
+        }
+
         if let Some(val) = value.unmanaged_window_operation_behaviour {
             wm.unmanaged_window_operation_behaviour = val;
         }
@@ -1307,33 +2305,7 @@ impl StaticConfig {
     }
 }
 
-fn populate_option(
-    entry: &mut ApplicationConfiguration,
-    identifiers: &mut Vec<MatchingRule>,
-    regex_identifiers: &mut HashMap<String, Regex>,
-) -> Result<()> {
-    if entry.identifier.matching_strategy.is_none() {
-        entry.identifier.matching_strategy = Option::from(MatchingStrategy::Legacy);
-    }
-
-    let rule = MatchingRule::Simple(entry.identifier.clone());
-
-    if !identifiers.contains(&rule) {
-        identifiers.push(rule);
-
-        if matches!(
-            entry.identifier.matching_strategy,
-            Some(MatchingStrategy::Regex)
-        ) {
-            let re = Regex::new(&entry.identifier.id)?;
-            regex_identifiers.insert(entry.identifier.id.clone(), re);
-        }
-    }
-
-    Ok(())
-}
-
-fn populate_rules(
+pub fn populate_rules(
     matching_rules: &mut Vec<MatchingRule>,
     identifiers: &mut Vec<MatchingRule>,
     regex_identifiers: &mut HashMap<String, Regex>,
@@ -1,28 +1,57 @@
 #![deny(clippy::unwrap_used, clippy::expect_used)]
 
 use crate::border_manager;
+use crate::core::SocketMessage;
+use crate::notify_subscribers;
 use crate::stackbar_manager;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
+use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
+use crate::stackbar_manager::STACKBAR_FONT_SIZE;
 use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
+use crate::window_manager::GlobalState;
+use crate::window_manager::State;
 use crate::Colour;
 use crate::KomorebiTheme;
+use crate::NotificationEvent;
+use crate::WindowManager;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
-use crossbeam_utils::atomic::AtomicCell;
+use komorebi_themes::lerp_colour;
+use komorebi_themes::Base16ColourPalette;
+use komorebi_themes::Base16Value;
+use komorebi_themes::CatppuccinValue;
+use komorebi_themes::Color32;
+use komorebi_themes::Lerp;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
 use std::ops::Deref;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::sync::OnceLock;
+use std::time::Duration;
 
-pub struct Notification(KomorebiTheme);
+/// The maximum rate at which intermediate themes are applied and published during a transition
+const TRANSITION_FPS: u64 = 30;
 
-pub static CURRENT_THEME: AtomicCell<Option<KomorebiTheme>> = AtomicCell::new(None);
+pub struct Notification {
+    theme: KomorebiTheme,
+    /// Whether the themes applied during a transition should be published to subscribers
+    publish: bool,
+}
+
+lazy_static! {
+    pub static ref CURRENT_THEME: Mutex<Option<KomorebiTheme>> = Mutex::new(None);
+}
+
+pub static TRANSITION_DURATION: AtomicU64 = AtomicU64::new(0);
 
 impl Deref for Notification {
     type Target = KomorebiTheme;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.theme
     }
 }
 
@@ -44,14 +73,31 @@ fn event_rx() -> Receiver<Notification> {
 // when an animation finishes and we need to focus to set the cursor
 // position if the user has mouse follows focus enabled
 pub fn send_notification(theme: KomorebiTheme) {
-    if event_tx().try_send(Notification(theme)).is_err() {
+    send(Notification {
+        theme,
+        publish: false,
+    });
+}
+
+/// Used for themes set with a socket message, where subscribers are notified of the new theme,
+/// and of every intermediate theme of an animated transition, by this module after it has been
+/// applied
+pub fn send_socket_notification(theme: KomorebiTheme) {
+    send(Notification {
+        theme,
+        publish: true,
+    });
+}
+
+fn send(notification: Notification) {
+    if event_tx().try_send(notification).is_err() {
         tracing::warn!("channel is full; dropping notification")
     }
 }
 
-pub fn listen_for_notifications() {
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
-        match handle_notifications() {
+        match handle_notifications(wm.clone()) {
             Ok(()) => {
                 tracing::warn!("restarting finished thread");
             }
@@ -62,78 +108,197 @@ pub fn listen_for_notifications() {
     });
 }
 
-pub fn handle_notifications() -> color_eyre::Result<()> {
+pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()> {
     tracing::info!("listening");
 
     let receiver = event_rx();
 
-    for notification in receiver {
-        let theme = &notification.0;
-
-        let (
-            single_border,
-            stack_border,
-            monocle_border,
-            floating_border,
-            unfocused_border,
-            stackbar_focused_text,
-            stackbar_unfocused_text,
-            stackbar_background,
-        ) = match theme {
+    'receiver: for notification in receiver.iter() {
+        let duration = TRANSITION_DURATION.load(Ordering::SeqCst);
+
+        let current_theme = CURRENT_THEME.lock().clone();
+        if let (Some(current), true) = (current_theme, duration > 0) {
+            let steps = (duration * TRANSITION_FPS / 1000).max(1);
+            let interval = Duration::from_millis(duration / steps);
+
+            #[allow(clippy::cast_possible_truncation)]
+            for theme in current.interpolate(&notification, steps as usize) {
+                // A newer theme takes over the transition from wherever this one has reached
+                if !receiver.is_empty() {
+                    continue 'receiver;
+                }
+
+                apply(&theme);
+
+                if notification.publish {
+                    publish(&wm, theme)?;
+                }
+
+                std::thread::sleep(interval);
+            }
+        }
+
+        apply(&notification);
+
+        if notification.publish {
+            publish(&wm, notification.theme)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn apply(theme: &KomorebiTheme) {
+    let colours = ResolvedTheme::from(theme);
+
+    border_manager::FOCUSED.store(
+        u32::from(Colour::from(colours.single_border)),
+        Ordering::SeqCst,
+    );
+    border_manager::MONOCLE.store(
+        u32::from(Colour::from(colours.monocle_border)),
+        Ordering::SeqCst,
+    );
+    border_manager::STACK.store(
+        u32::from(Colour::from(colours.stack_border)),
+        Ordering::SeqCst,
+    );
+    border_manager::FLOATING.store(
+        u32::from(Colour::from(colours.floating_border)),
+        Ordering::SeqCst,
+    );
+    border_manager::PINNED.store(
+        u32::from(Colour::from(colours.pinned_border)),
+        Ordering::SeqCst,
+    );
+    border_manager::UNFOCUSED.store(
+        u32::from(Colour::from(colours.unfocused_border)),
+        Ordering::SeqCst,
+    );
+
+    STACKBAR_TAB_BACKGROUND_COLOUR.store(
+        u32::from(Colour::from(colours.stackbar_background)),
+        Ordering::SeqCst,
+    );
+
+    STACKBAR_FOCUSED_TEXT_COLOUR.store(
+        u32::from(Colour::from(colours.stackbar_focused_text)),
+        Ordering::SeqCst,
+    );
+
+    STACKBAR_UNFOCUSED_TEXT_COLOUR.store(
+        u32::from(Colour::from(colours.stackbar_unfocused_text)),
+        Ordering::SeqCst,
+    );
+
+    // Themes which do not set a stackbar font leave the configured font alone
+    if let Some(font_family) = theme.stackbar_font_family() {
+        *STACKBAR_FONT_FAMILY.lock() = Some(font_family.to_string());
+    }
+
+    if let Some(font_size) = theme.stackbar_font_size() {
+        STACKBAR_FONT_SIZE.store(font_size, Ordering::SeqCst);
+    }
+
+    *CURRENT_THEME.lock() = Some(theme.clone());
+
+    border_manager::send_notification(None);
+    stackbar_manager::send_notification();
+}
+
+fn publish(wm: &Arc<Mutex<WindowManager>>, theme: KomorebiTheme) -> color_eyre::Result<()> {
+    let state = State::from(&*wm.lock());
+
+    notify_subscribers(
+        crate::Notification {
+            event: NotificationEvent::Socket(SocketMessage::Theme(theme)),
+            state,
+            global_state: Option::from(GlobalState::default()),
+        },
+        false,
+    )
+}
+
+/// Every colour used by a theme, with defaults taken from its palette
+#[derive(Copy, Clone)]
+struct ResolvedTheme {
+    palette: Base16ColourPalette,
+    single_border: Color32,
+    stack_border: Color32,
+    monocle_border: Color32,
+    floating_border: Color32,
+    pinned_border: Color32,
+    unfocused_border: Color32,
+    stackbar_focused_text: Color32,
+    stackbar_unfocused_text: Color32,
+    stackbar_background: Color32,
+    bar_accent: Color32,
+}
+
+impl Lerp for ResolvedTheme {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            palette: self.palette.lerp(&other.palette, t),
+            single_border: lerp_colour(self.single_border, other.single_border, t),
+            stack_border: lerp_colour(self.stack_border, other.stack_border, t),
+            monocle_border: lerp_colour(self.monocle_border, other.monocle_border, t),
+            floating_border: lerp_colour(self.floating_border, other.floating_border, t),
+            pinned_border: lerp_colour(self.pinned_border, other.pinned_border, t),
+            unfocused_border: lerp_colour(self.unfocused_border, other.unfocused_border, t),
+            stackbar_focused_text: lerp_colour(
+                self.stackbar_focused_text,
+                other.stackbar_focused_text,
+                t,
+            ),
+            stackbar_unfocused_text: lerp_colour(
+                self.stackbar_unfocused_text,
+                other.stackbar_unfocused_text,
+                t,
+            ),
+            stackbar_background: lerp_colour(
+                self.stackbar_background,
+                other.stackbar_background,
+                t,
+            ),
+            bar_accent: lerp_colour(self.bar_accent, other.bar_accent, t),
+        }
+    }
+}
+
+impl From<&KomorebiTheme> for ResolvedTheme {
+    fn from(theme: &KomorebiTheme) -> Self {
+        match *theme {
             KomorebiTheme::Catppuccin {
                 name,
                 single_border,
                 stack_border,
                 monocle_border,
                 floating_border,
+                pinned_border,
                 unfocused_border,
                 stackbar_focused_text,
                 stackbar_unfocused_text,
                 stackbar_background,
+                bar_accent,
                 ..
             } => {
-                let single_border = single_border
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Blue)
-                    .color32(name.as_theme());
-
-                let stack_border = stack_border
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Green)
-                    .color32(name.as_theme());
-
-                let monocle_border = monocle_border
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Pink)
-                    .color32(name.as_theme());
-
-                let floating_border = floating_border
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Yellow)
-                    .color32(name.as_theme());
-
-                let unfocused_border = unfocused_border
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Base)
-                    .color32(name.as_theme());
-
-                let stackbar_focused_text = stackbar_focused_text
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Green)
-                    .color32(name.as_theme());
-
-                let stackbar_unfocused_text = stackbar_unfocused_text
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Text)
-                    .color32(name.as_theme());
-
-                let stackbar_background = stackbar_background
-                    .unwrap_or(komorebi_themes::CatppuccinValue::Base)
-                    .color32(name.as_theme());
-
-                (
-                    single_border,
-                    stack_border,
-                    monocle_border,
-                    floating_border,
-                    unfocused_border,
-                    stackbar_focused_text,
-                    stackbar_unfocused_text,
-                    stackbar_background,
-                )
+                let colour = |value: Option<CatppuccinValue>, default: CatppuccinValue| {
+                    value.unwrap_or(default).color32(name.as_theme())
+                };
+
+                Self {
+                    palette: Base16ColourPalette::from(name),
+                    single_border: colour(single_border, CatppuccinValue::Blue),
+                    stack_border: colour(stack_border, CatppuccinValue::Green),
+                    monocle_border: colour(monocle_border, CatppuccinValue::Pink),
+                    floating_border: colour(floating_border, CatppuccinValue::Yellow),
+                    pinned_border: colour(pinned_border, CatppuccinValue::Mauve),
+                    unfocused_border: colour(unfocused_border, CatppuccinValue::Base),
+                    stackbar_focused_text: colour(stackbar_focused_text, CatppuccinValue::Green),
+                    stackbar_unfocused_text: colour(stackbar_unfocused_text, CatppuccinValue::Text),
+                    stackbar_background: colour(stackbar_background, CatppuccinValue::Base),
+                    bar_accent: colour(bar_accent, CatppuccinValue::default()),
+                }
             }
             KomorebiTheme::Base16 {
                 name,
@@ -141,84 +306,141 @@ pub fn handle_notifications() -> color_eyre::Result<()> {
                 stack_border,
                 monocle_border,
                 floating_border,
+                pinned_border,
                 unfocused_border,
                 stackbar_focused_text,
                 stackbar_unfocused_text,
                 stackbar_background,
+                bar_accent,
                 ..
             } => {
-                let single_border = single_border
-                    .unwrap_or(komorebi_themes::Base16Value::Base0D)
-                    .color32(*name);
-
-                let stack_border = stack_border
-                    .unwrap_or(komorebi_themes::Base16Value::Base0B)
-                    .color32(*name);
-
-                let monocle_border = monocle_border
-                    .unwrap_or(komorebi_themes::Base16Value::Base0F)
-                    .color32(*name);
-
-                let unfocused_border = unfocused_border
-                    .unwrap_or(komorebi_themes::Base16Value::Base01)
-                    .color32(*name);
-
-                let floating_border = floating_border
-                    .unwrap_or(komorebi_themes::Base16Value::Base09)
-                    .color32(*name);
-
-                let stackbar_focused_text = stackbar_focused_text
-                    .unwrap_or(komorebi_themes::Base16Value::Base0B)
-                    .color32(*name);
-
-                let stackbar_unfocused_text = stackbar_unfocused_text
-                    .unwrap_or(komorebi_themes::Base16Value::Base05)
-                    .color32(*name);
-
-                let stackbar_background = stackbar_background
-                    .unwrap_or(komorebi_themes::Base16Value::Base01)
-                    .color32(*name);
-
-                (
-                    single_border,
-                    stack_border,
-                    monocle_border,
-                    floating_border,
-                    unfocused_border,
-                    stackbar_focused_text,
-                    stackbar_unfocused_text,
-                    stackbar_background,
-                )
+                let palette = Base16ColourPalette::from(name);
+                let colour = |value: Option<Base16Value>, default: Base16Value| {
+                    value.unwrap_or(default).color32(name)
+                };
+
+                Self {
+                    palette,
+                    single_border: colour(single_border, Base16Value::Base0D),
+                    stack_border: colour(stack_border, Base16Value::Base0B),
+                    monocle_border: colour(monocle_border, Base16Value::Base0F),
+                    floating_border: colour(floating_border, Base16Value::Base09),
+                    pinned_border: colour(pinned_border, Base16Value::Base0E),
+                    unfocused_border: colour(unfocused_border, Base16Value::Base01),
+                    stackbar_focused_text: colour(stackbar_focused_text, Base16Value::Base0B),
+                    stackbar_unfocused_text: colour(stackbar_unfocused_text, Base16Value::Base05),
+                    stackbar_background: colour(stackbar_background, Base16Value::Base01),
+                    bar_accent: colour(bar_accent, Base16Value::default()),
+                }
             }
-        };
-
-        border_manager::FOCUSED.store(u32::from(Colour::from(single_border)), Ordering::SeqCst);
-        border_manager::MONOCLE.store(u32::from(Colour::from(monocle_border)), Ordering::SeqCst);
-        border_manager::STACK.store(u32::from(Colour::from(stack_border)), Ordering::SeqCst);
-        border_manager::FLOATING.store(u32::from(Colour::from(floating_border)), Ordering::SeqCst);
-        border_manager::UNFOCUSED
-            .store(u32::from(Colour::from(unfocused_border)), Ordering::SeqCst);
-
-        STACKBAR_TAB_BACKGROUND_COLOUR.store(
-            u32::from(Colour::from(stackbar_background)),
-            Ordering::SeqCst,
-        );
-
-        STACKBAR_FOCUSED_TEXT_COLOUR.store(
-            u32::from(Colour::from(stackbar_focused_text)),
-            Ordering::SeqCst,
-        );
-
-        STACKBAR_UNFOCUSED_TEXT_COLOUR.store(
-            u32::from(Colour::from(stackbar_unfocused_text)),
-            Ordering::SeqCst,
-        );
-
-        CURRENT_THEME.store(Some(notification.0));
-
-        border_manager::send_notification(None);
-        stackbar_manager::send_notification();
+            KomorebiTheme::Custom {
+                colours,
+                single_border,
+                stack_border,
+                monocle_border,
+                floating_border,
+                pinned_border,
+                unfocused_border,
+                stackbar_focused_text,
+                stackbar_unfocused_text,
+                stackbar_background,
+                bar_accent,
+                ..
+            } => {
+                let colour = |value: Option<Colour>, default: Base16Value| {
+                    value.map_or_else(|| colours.color32(default), Color32::from)
+                };
+
+                Self {
+                    palette: colours,
+                    single_border: colour(single_border, Base16Value::Base0D),
+                    stack_border: colour(stack_border, Base16Value::Base0B),
+                    monocle_border: colour(monocle_border, Base16Value::Base0F),
+                    floating_border: colour(floating_border, Base16Value::Base09),
+                    pinned_border: colour(pinned_border, Base16Value::Base0E),
+                    unfocused_border: colour(unfocused_border, Base16Value::Base01),
+                    stackbar_focused_text: colour(stackbar_focused_text, Base16Value::Base0B),
+                    stackbar_unfocused_text: colour(stackbar_unfocused_text, Base16Value::Base05),
+                    stackbar_background: colour(stackbar_background, Base16Value::Base01),
+                    bar_accent: colour(bar_accent, Base16Value::default()),
+                }
+            }
+        }
     }
+}
 
-    Ok(())
+impl From<ResolvedTheme> for KomorebiTheme {
+    fn from(value: ResolvedTheme) -> Self {
+        Self::Custom {
+            colours: value.palette,
+            single_border: Some(Colour::from(value.single_border)),
+            stack_border: Some(Colour::from(value.stack_border)),
+            monocle_border: Some(Colour::from(value.monocle_border)),
+            floating_border: Some(Colour::from(value.floating_border)),
+            pinned_border: Some(Colour::from(value.pinned_border)),
+            unfocused_border: Some(Colour::from(value.unfocused_border)),
+            stackbar_focused_text: Some(Colour::from(value.stackbar_focused_text)),
+            stackbar_unfocused_text: Some(Colour::from(value.stackbar_unfocused_text)),
+            stackbar_background: Some(Colour::from(value.stackbar_background)),
+            bar_accent: Some(Colour::from(value.bar_accent)),
+            stackbar_font_family: None,
+            stackbar_font_size: None,
+        }
+    }
+}
+
+impl KomorebiTheme {
+    /// Resolves any theme to a `KomorebiTheme::Custom` with every colour set
+    pub fn resolve(&self) -> Self {
+        let mut resolved: Self = ResolvedTheme::from(self).into();
+        if let Self::Custom {
+            stackbar_font_family,
+            stackbar_font_size,
+            ..
+        } = &mut resolved
+        {
+            *stackbar_font_family = self.stackbar_font_family().map(String::from);
+            *stackbar_font_size = self.stackbar_font_size();
+        }
+
+        resolved
+    }
+
+    pub fn stackbar_font_family(&self) -> Option<&str> {
+        match self {
+            Self::Catppuccin {
+                stackbar_font_family,
+                ..
+            }
+            | Self::Base16 {
+                stackbar_font_family,
+                ..
+            }
+            | Self::Custom {
+                stackbar_font_family,
+                ..
+            } => stackbar_font_family.as_deref(),
+        }
+    }
+
+    pub fn stackbar_font_size(&self) -> Option<i32> {
+        match self {
+            Self::Catppuccin {
+                stackbar_font_size, ..
+            }
+            | Self::Base16 {
+                stackbar_font_size, ..
+            }
+            | Self::Custom {
+                stackbar_font_size, ..
+            } => *stackbar_font_size,
+        }
+    }
+
+    /// Produces `steps` themes which transition from this theme to `other`, ending with the
+    /// resolved colours of `other`
+    pub fn interpolate(&self, other: &Self, steps: usize) -> impl Iterator<Item = Self> {
+        komorebi_themes::transition(ResolvedTheme::from(self), ResolvedTheme::from(other), steps)
+            .map(Self::from)
+    }
 }
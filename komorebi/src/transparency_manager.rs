@@ -21,6 +21,9 @@ pub static TRANSPARENCY_ALPHA: AtomicU8 = AtomicU8::new(200);
 
 static KNOWN_HWNDS: OnceLock<Mutex<Vec<isize>>> = OnceLock::new();
 
+// Indices of monitors on which windows are always opaque
+static IGNORED_MONITORS: OnceLock<Mutex<Vec<usize>>> = OnceLock::new();
+
 pub struct Notification;
 
 static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
@@ -30,6 +33,29 @@ pub fn known_hwnds() -> Vec<isize> {
     known.iter().copied().collect()
 }
 
+fn ignored_monitors_lock() -> &'static Mutex<Vec<usize>> {
+    IGNORED_MONITORS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+pub fn ignored_monitors() -> Vec<usize> {
+    ignored_monitors_lock().lock().clone()
+}
+
+pub fn set_ignored_monitors(monitor_indices: &[usize]) {
+    let mut ignored = ignored_monitors_lock().lock();
+    ignored.clear();
+    ignored.extend_from_slice(monitor_indices);
+}
+
+pub fn set_monitor_ignored(monitor_idx: usize, ignored: bool) {
+    let mut ignored_monitors = ignored_monitors_lock().lock();
+    ignored_monitors.retain(|idx| *idx != monitor_idx);
+
+    if ignored {
+        ignored_monitors.push(monitor_idx);
+    }
+}
+
 pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
     CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
 }
@@ -85,8 +111,24 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
         let state = wm.lock();
 
         let focused_monitor_idx = state.focused_monitor_idx();
+        let ignored_monitors = ignored_monitors();
 
         'monitors: for (monitor_idx, m) in state.monitors.elements().iter().enumerate() {
+            // Windows on ignored and paused monitors are always opaque, including any which have
+            // just been moved there from a monitor where they were transparent
+            if ignored_monitors.contains(&monitor_idx) || m.is_paused() {
+                for ws in m.workspaces() {
+                    for window in ws.visible_windows().iter().flatten() {
+                        if let Err(error) = window.opaque() {
+                            let hwnd = window.hwnd;
+                            tracing::error!("failed to make window {hwnd} opaque: {error}")
+                        }
+                    }
+                }
+
+                continue 'monitors;
+            }
+
             let focused_workspace_idx = m.focused_workspace_idx();
 
             'workspaces: for (workspace_idx, ws) in m.workspaces().iter().enumerate() {
@@ -0,0 +1,110 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::com;
+use crate::core::Rect;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+pub struct Notification {
+    monitor_size: Rect,
+    wallpaper: PathBuf,
+}
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(50))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+pub fn send_notification(monitor_size: Rect, wallpaper: PathBuf) {
+    if event_tx()
+        .try_send(Notification {
+            monitor_size,
+            wallpaper,
+        })
+        .is_err()
+    {
+        tracing::warn!("channel is full; dropping notification")
+    }
+}
+
+pub fn listen_for_notifications() {
+    std::thread::spawn(move || loop {
+        match handle_notifications() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_notifications() -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    // The wallpaper which was last set on each monitor, so that switching between workspaces
+    // with the same wallpaper does not start a new transition
+    let mut applied: Vec<(Rect, PathBuf)> = vec![];
+    let mut warned_missing: HashSet<PathBuf> = HashSet::new();
+
+    for notification in receiver.iter() {
+        // Setting a wallpaper is slow, so only the latest request for each monitor is applied
+        // when several workspace switches have happened in the meantime
+        let mut pending = vec![notification];
+        for notification in receiver.try_iter() {
+            pending.retain(|n: &Notification| n.monitor_size != notification.monitor_size);
+            pending.push(notification);
+        }
+
+        for Notification {
+            monitor_size,
+            wallpaper,
+        } in pending
+        {
+            if applied
+                .iter()
+                .any(|(size, path)| *size == monitor_size && *path == wallpaper)
+            {
+                continue;
+            }
+
+            if !wallpaper.is_file() {
+                if warned_missing.insert(wallpaper.clone()) {
+                    tracing::warn!("wallpaper {} does not exist", wallpaper.display());
+                }
+
+                continue;
+            }
+
+            match com::set_monitor_wallpaper(monitor_size, &wallpaper) {
+                Ok(true) => {
+                    applied.retain(|(size, _)| *size != monitor_size);
+                    applied.push((monitor_size, wallpaper));
+                }
+                Ok(false) => {
+                    tracing::warn!("could not find a monitor to set the wallpaper on");
+                }
+                Err(error) => {
+                    tracing::error!("could not set wallpaper {}: {error}", wallpaper.display());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
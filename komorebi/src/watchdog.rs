@@ -0,0 +1,168 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::notify_subscribers;
+use crate::Notification;
+use crate::NotificationEvent;
+use crate::State;
+use crate::WindowManager;
+use crate::DATA_DIR;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// The number of panics within [`PANIC_WINDOW`] after which komorebi shuts down instead of
+/// restarting the thread which panicked
+pub const PANIC_LIMIT: usize = 3;
+pub const PANIC_WINDOW: Duration = Duration::from_secs(60);
+
+/// Set when komorebi is shutting down because of repeated panics
+pub static PANIC_SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+static SHUTDOWN_SENDER: OnceLock<Sender<()>> = OnceLock::new();
+
+lazy_static! {
+    static ref RECENT_PANICS: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+}
+
+/// A panic which was caught in one of the window manager's threads
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PanicNotification {
+    /// The name of the thread which panicked
+    pub thread: String,
+    /// The panic message
+    pub message: String,
+}
+
+#[derive(Serialize)]
+struct CrashReport<'a> {
+    timestamp: u64,
+    thread: &'a str,
+    message: &'a str,
+    state: Option<&'a State>,
+}
+
+/// Register the channel which tells the main thread to restore all hidden windows and exit
+pub fn set_shutdown_sender(sender: Sender<()>) {
+    let _ = SHUTDOWN_SENDER.set(sender);
+}
+
+pub fn is_shutting_down() -> bool {
+    PANIC_SHUTDOWN.load(Ordering::SeqCst)
+}
+
+/// Run `f`, catching any panic so that the calling loop can be restarted.
+///
+/// The window manager lock and the other global locks are parking_lot mutexes, which are not
+/// poisoned and are released while unwinding, so they can be used as they are after a panic.
+pub fn catch_panic<R>(
+    thread: &str,
+    wm: &Arc<Mutex<WindowManager>>,
+    f: impl FnOnce() -> R,
+) -> Option<R> {
+    match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            handle_panic(thread, wm, payload.as_ref());
+            None
+        }
+    }
+}
+
+fn handle_panic(thread: &str, wm: &Arc<Mutex<WindowManager>>, payload: &(dyn Any + Send)) {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| String::from("unknown panic"));
+
+    tracing::error!("caught panic in {thread} thread: {message}");
+
+    // The state may be inconsistent after a panic, so serializing it is allowed to panic too
+    let state = wm
+        .try_lock_for(Duration::from_secs(1))
+        .and_then(|wm| std::panic::catch_unwind(AssertUnwindSafe(|| State::from(&*wm))).ok());
+
+    if let Err(error) = write_crash_report(thread, &message, state.as_ref()) {
+        tracing::error!("failed to write crash report: {error}");
+    }
+
+    if let Some(state) = state {
+        let notification = Notification {
+            event: NotificationEvent::Panic(PanicNotification {
+                thread: thread.to_string(),
+                message,
+            }),
+            state,
+            global_state: None,
+        };
+
+        if let Err(error) = notify_subscribers(notification, true) {
+            tracing::error!("failed to notify subscribers of panic: {error}");
+        }
+    }
+
+    let too_many_panics = {
+        let mut recent = RECENT_PANICS.lock();
+        let now = Instant::now();
+
+        recent.push_back(now);
+        while recent
+            .front()
+            .is_some_and(|instant| now.duration_since(*instant) > PANIC_WINDOW)
+        {
+            recent.pop_front();
+        }
+
+        recent.len() >= PANIC_LIMIT
+    };
+
+    if too_many_panics && !PANIC_SHUTDOWN.swap(true, Ordering::SeqCst) {
+        tracing::error!(
+            "{PANIC_LIMIT} panics within {} seconds, shutting down",
+            PANIC_WINDOW.as_secs()
+        );
+
+        if let Some(sender) = SHUTDOWN_SENDER.get() {
+            let _ = sender.try_send(());
+        }
+    }
+}
+
+fn write_crash_report(
+    thread: &str,
+    message: &str,
+    state: Option<&State>,
+) -> color_eyre::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let path = DATA_DIR.join(format!("komorebi.crash.{timestamp}.json"));
+    std::fs::write(
+        &path,
+        serde_json::to_string_pretty(&CrashReport {
+            timestamp,
+            thread,
+            message,
+            state,
+        })?,
+    )?;
+
+    tracing::error!("wrote crash report to {}", path.display());
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use std::fmt::Write;
+
+use crate::static_config::StaticConfig;
+
+/// The number keys which can be bound to workspaces on a single monitor
+const WORKSPACE_KEYS: usize = 9;
+
+/// Keys added to the modifier to tell the workspace bindings of each monitor apart
+const MONITOR_KEYS: [Option<&str>; 4] = [None, Some("ctrl"), Some("win"), Some("ctrl + win")];
+
+/// Bindings which do not depend on the workspaces in the configuration, as (keys, command) pairs
+/// where the keys are pressed together with the modifier
+const STANDARD_BINDINGS: [(&str, &[(&str, &str)]); 8] = [
+    (
+        "Focus windows",
+        &[
+            ("h", "focus left"),
+            ("j", "focus down"),
+            ("k", "focus up"),
+            ("l", "focus right"),
+            ("shift + oem_4", "cycle-focus previous"),
+            ("shift + oem_6", "cycle-focus next"),
+        ],
+    ),
+    (
+        "Move windows",
+        &[
+            ("shift + h", "move left"),
+            ("shift + j", "move down"),
+            ("shift + k", "move up"),
+            ("shift + l", "move right"),
+            ("shift + return", "promote"),
+        ],
+    ),
+    (
+        "Stack windows",
+        &[
+            ("left", "stack left"),
+            ("down", "stack down"),
+            ("up", "stack up"),
+            ("right", "stack right"),
+            ("oem_1", "unstack"),
+            ("oem_4", "cycle-stack previous"),
+            ("oem_6", "cycle-stack next"),
+        ],
+    ),
+    (
+        "Resize",
+        &[
+            ("oem_plus", "resize-axis horizontal increase"),
+            ("oem_minus", "resize-axis horizontal decrease"),
+            ("shift + oem_plus", "resize-axis vertical increase"),
+            ("shift + oem_minus", "resize-axis vertical decrease"),
+        ],
+    ),
+    (
+        "Manipulate windows",
+        &[
+            ("q", "close"),
+            ("m", "minimize"),
+            ("t", "toggle-float"),
+            ("shift + f", "toggle-monocle"),
+        ],
+    ),
+    (
+        "Window manager options",
+        &[
+            ("shift + o", "reload-configuration"),
+            ("shift + r", "retile"),
+            ("p", "toggle-pause"),
+        ],
+    ),
+    (
+        "Layouts",
+        &[
+            ("x", "flip-layout horizontal"),
+            ("y", "flip-layout vertical"),
+        ],
+    ),
+    (
+        "Cycle workspaces",
+        &[
+            ("shift + oem_comma", "cycle-workspace previous"),
+            ("shift + oem_period", "cycle-workspace next"),
+        ],
+    ),
+];
+
+/// A whkdrc generated from a static configuration file
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedWhkdrc {
+    pub contents: String,
+    /// Workspaces and monitors which could not be given bindings
+    pub warnings: Vec<String>,
+}
+
+/// Generates whkdrc bindings for the standard komorebi commands and for focusing and moving
+/// windows to every workspace in the configuration, using the given modifier key
+pub fn generate(config: &StaticConfig, modifier: &str) -> GeneratedWhkdrc {
+    let mut generated = GeneratedWhkdrc::default();
+    let contents = &mut generated.contents;
+
+    let _ = writeln!(contents, ".shell powershell\n");
+    let _ = writeln!(contents, "# Generated by komorebic generate-whkdrc");
+
+    for (heading, bindings) in STANDARD_BINDINGS {
+        let _ = writeln!(contents, "\n# {heading}");
+        for (keys, command) in bindings {
+            write_binding(contents, &format!("{modifier} + {keys}"), command);
+        }
+    }
+
+    let monitors = config.monitors.as_deref().unwrap_or_default();
+    let multiple_monitors = monitors.len() > 1;
+
+    for (monitor_idx, monitor) in monitors.iter().enumerate() {
+        let Some(monitor_key) = MONITOR_KEYS.get(monitor_idx) else {
+            generated.warnings.push(format!(
+                "skipping monitor {monitor_idx}: only {} monitors can be given workspace bindings",
+                MONITOR_KEYS.len()
+            ));
+            continue;
+        };
+
+        if monitor.workspaces.len() > WORKSPACE_KEYS {
+            generated.warnings.push(format!(
+                "skipping {} workspaces on monitor {monitor_idx}: only the first {WORKSPACE_KEYS} can be given bindings",
+                monitor.workspaces.len() - WORKSPACE_KEYS
+            ));
+        }
+
+        let prefix = match monitor_key {
+            None => modifier.to_string(),
+            Some(key) => format!("{modifier} + {key}"),
+        };
+
+        let mut focus = vec![];
+        let mut send = vec![];
+
+        for (workspace_idx, workspace) in monitor.workspaces.iter().take(WORKSPACE_KEYS).enumerate()
+        {
+            let number = workspace_idx + 1;
+            let (focus_command, move_command) = if multiple_monitors {
+                (
+                    format!("focus-monitor-workspace {monitor_idx} {workspace_idx}"),
+                    format!("move-to-monitor-workspace {monitor_idx} {workspace_idx}"),
+                )
+            } else {
+                (
+                    format!("focus-workspace {workspace_idx}"),
+                    format!("move-to-workspace {workspace_idx}"),
+                )
+            };
+
+            focus.push((
+                format!("{prefix} + {number}"),
+                format!("{focus_command} # {}", workspace.name),
+            ));
+            send.push((
+                format!("{prefix} + shift + {number}"),
+                format!("{move_command} # {}", workspace.name),
+            ));
+        }
+
+        let suffix = if multiple_monitors {
+            format!(" on monitor {monitor_idx}")
+        } else {
+            String::new()
+        };
+
+        let _ = writeln!(contents, "\n# Workspaces{suffix}");
+        for (keys, command) in &focus {
+            write_binding(contents, keys, command);
+        }
+
+        let _ = writeln!(contents, "\n# Move windows across workspaces{suffix}");
+        for (keys, command) in &send {
+            write_binding(contents, keys, command);
+        }
+    }
+
+    generated
+}
+
+fn write_binding(contents: &mut String, keys: &str, command: &str) {
+    let _ = writeln!(contents, "{keys:<24}: komorebic {command}");
+}
@@ -3,6 +3,8 @@ use crate::animation::prefix::new_animation_key;
 use crate::animation::prefix::AnimationPrefix;
 use crate::animation::AnimationEngine;
 use crate::animation::RenderDispatcher;
+use crate::animation::ANIMATIONS_BLACKLIST;
+use crate::animation::ANIMATIONS_BLACKLIST_REGEX;
 use crate::animation::ANIMATION_DURATION_GLOBAL;
 use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
@@ -10,6 +12,8 @@ use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
 use crate::animation::ANIMATION_MANAGER;
 use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
+use crate::colour::strip_alpha;
+use crate::com;
 use crate::com::SetCloak;
 use crate::focus_manager;
 use crate::stackbar_manager;
@@ -49,16 +53,26 @@ use crate::styles::ExtendedWindowStyle;
 use crate::styles::WindowStyle;
 use crate::transparency_manager;
 use crate::window_manager_event::WindowManagerEvent;
+use crate::window_system::window_system;
 use crate::windows_api::WindowsApi;
 use crate::FLOATING_APPLICATIONS;
+use crate::FLOAT_OVERRIDE_APPLICATIONS;
+use crate::FOCUS_FOLLOWS_MOUSE_IGNORE;
 use crate::HIDDEN_HWNDS;
+use crate::HIDE_TASKBAR_ENTRIES_FOR_HIDDEN_WORKSPACES;
 use crate::HIDING_BEHAVIOUR;
+use crate::HUNG_HWNDS;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MINIMUM_SIZES;
 use crate::NO_TITLEBAR;
 use crate::PERMAIGNORE_CLASSES;
 use crate::REGEX_IDENTIFIERS;
+use crate::SESSION_WORKSPACE_RULES;
+use crate::TASKBAR_HIDDEN_HWNDS;
+use crate::TITLEBAR_ORIGINAL_STYLES;
+use crate::WORKSPACE_MATCHING_RULES;
 use crate::WSL2_UI_PROCESSES;
 
 pub static MINIMUM_WIDTH: AtomicI32 = AtomicI32::new(0);
@@ -84,8 +98,10 @@ impl From<HWND> for Window {
 }
 
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WindowDetails {
+    #[serde(default)]
+    pub hwnd: isize,
     pub title: String,
     pub exe: String,
     pub class: String,
@@ -96,6 +112,7 @@ impl TryFrom<Window> for WindowDetails {
 
     fn try_from(value: Window) -> std::result::Result<Self, Self::Error> {
         Ok(Self {
+            hwnd: value.hwnd,
             title: value.title()?,
             exe: value.exe()?,
             class: value.class()?,
@@ -130,7 +147,7 @@ impl Serialize for Window {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Window", 5)?;
+        let mut state = serializer.serialize_struct("Window", 6)?;
         state.serialize_field("hwnd", &self.hwnd)?;
         state.serialize_field(
             "title",
@@ -152,8 +169,9 @@ impl Serialize for Window {
         )?;
         state.serialize_field(
             "rect",
-            &WindowsApi::window_rect(self.hwnd).unwrap_or_default(),
+            &window_system().window_rect(self.hwnd).unwrap_or_default(),
         )?;
+        state.serialize_field("hung", &self.is_hung())?;
         state.end()
     }
 }
@@ -191,7 +209,13 @@ impl RenderDispatcher for MovementRenderDispatcher {
         new_animation_key(MovementRenderDispatcher::PREFIX, self.hwnd.to_string())
     }
 
-    fn pre_render(&self) -> Result<()> {
+    fn pre_render(&mut self) -> Result<()> {
+        // A cancelled animation may have kept moving the window after this one was requested, so
+        // this one continues from wherever the window is now instead of jumping back
+        if let Ok(rect) = window_system().window_rect(self.hwnd) {
+            self.start_rect = rect;
+        }
+
         stackbar_manager::STACKBAR_TEMPORARILY_DISABLED.store(true, Ordering::SeqCst);
         stackbar_manager::send_notification();
 
@@ -210,13 +234,13 @@ impl RenderDispatcher for MovementRenderDispatcher {
     }
 
     fn post_render(&self) -> Result<()> {
-        WindowsApi::position_window(self.hwnd, &self.target_rect, self.top)?;
+        window_system().position_window(self.hwnd, &self.target_rect, self.top)?;
         if ANIMATION_MANAGER
             .lock()
             .count_in_progress(MovementRenderDispatcher::PREFIX)
             == 0
         {
-            if WindowsApi::foreground_window().unwrap_or_default() == self.hwnd {
+            if window_system().foreground_window().unwrap_or_default() == self.hwnd {
                 focus_manager::send_notification(self.hwnd)
             }
 
@@ -263,7 +287,7 @@ impl RenderDispatcher for TransparencyRenderDispatcher {
         new_animation_key(TransparencyRenderDispatcher::PREFIX, self.hwnd.to_string())
     }
 
-    fn pre_render(&self) -> Result<()> {
+    fn pre_render(&mut self) -> Result<()> {
         //transparent
         if !self.is_opaque {
             let window = Window::from(self.hwnd);
@@ -302,7 +326,7 @@ impl Window {
     }
 
     pub fn move_to_area(&mut self, current_area: &Rect, target_area: &Rect) -> Result<()> {
-        let current_rect = WindowsApi::window_rect(self.hwnd)?;
+        let current_rect = window_system().window_rect(self.hwnd)?;
         let x_diff = target_area.left - current_area.left;
         let y_diff = target_area.top - current_area.top;
         let x_ratio = f32::abs((target_area.right as f32) / (current_area.right as f32));
@@ -384,17 +408,20 @@ impl Window {
     }
 
     pub fn set_position(&self, layout: &Rect, top: bool) -> Result<()> {
-        let window_rect = WindowsApi::window_rect(self.hwnd)?;
+        let window_rect = window_system().window_rect(self.hwnd)?;
 
-        if window_rect.eq(layout) {
+        // An animation which is still in progress has to be superseded even when the window has
+        // not moved yet, such as when a work area offset is toggled back before it has started
+        if window_rect.eq(layout) && !self.is_moving() {
             return Ok(());
         }
 
         let animation_enabled = ANIMATION_ENABLED_PER_ANIMATION.lock();
         let move_enabled = animation_enabled.get(&MovementRenderDispatcher::PREFIX);
 
-        if move_enabled.is_some_and(|enabled| *enabled)
-            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
+        if (move_enabled.is_some_and(|enabled| *enabled)
+            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst))
+            && !self.is_animation_blacklisted()
         {
             let duration = Duration::from_millis(
                 *ANIMATION_DURATION_PER_ANIMATION
@@ -412,7 +439,39 @@ impl Window {
 
             AnimationEngine::animate(render_dispatcher, duration)
         } else {
-            WindowsApi::position_window(self.hwnd, layout, top)
+            window_system().position_window(self.hwnd, layout, top)
+        }
+    }
+
+    /// Whether a movement animation is in progress for this window
+    pub fn is_moving(self) -> bool {
+        ANIMATION_MANAGER.lock().in_progress(&new_animation_key(
+            MovementRenderDispatcher::PREFIX,
+            self.hwnd.to_string(),
+        ))
+    }
+
+    /// Blacklisted windows are always moved and faded directly instead of being animated
+    pub fn is_animation_blacklisted(self) -> bool {
+        let animations_blacklist = ANIMATIONS_BLACKLIST.lock();
+        if animations_blacklist.is_empty() {
+            return false;
+        }
+
+        if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+            (self.title(), self.exe(), self.class(), self.path())
+        {
+            should_act(
+                &title,
+                &exe_name,
+                &class,
+                &path,
+                &animations_blacklist,
+                &ANIMATIONS_BLACKLIST_REGEX.lock(),
+            )
+            .is_some()
+        } else {
+            false
         }
     }
 
@@ -429,20 +488,57 @@ impl Window {
     }
 
     pub fn hide(self) {
+        let hiding_behaviour = *HIDING_BEHAVIOUR.lock();
+        self.hide_with_behaviour(hiding_behaviour);
+    }
+
+    pub fn hide_with_behaviour(self, hiding_behaviour: HidingBehaviour) {
         let mut programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
         if !programmatically_hidden_hwnds.contains(&self.hwnd) {
             programmatically_hidden_hwnds.push(self.hwnd);
         }
 
-        let hiding_behaviour = HIDING_BEHAVIOUR.lock();
-        match *hiding_behaviour {
+        match hiding_behaviour {
             HidingBehaviour::Hide => WindowsApi::hide_window(self.hwnd),
             HidingBehaviour::Minimize => WindowsApi::minimize_window(self.hwnd),
             HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 2),
         }
+
+        // Hidden windows already have no taskbar button, and the button is removed again every
+        // time since some applications recreate it when they are focused
+        if !matches!(hiding_behaviour, HidingBehaviour::Hide)
+            && HIDE_TASKBAR_ENTRIES_FOR_HIDDEN_WORKSPACES.load(Ordering::SeqCst)
+        {
+            self.set_taskbar_entry(false);
+        }
+    }
+
+    /// Adds or removes the taskbar button of the window, keeping track of removed buttons so that
+    /// they can be added back even if the option is disabled in the meantime
+    fn set_taskbar_entry(self, visible: bool) {
+        if let Err(error) = com::set_taskbar_entry(self.hwnd(), visible) {
+            tracing::warn!(
+                "could not {} the taskbar entry for hwnd {}: {error}",
+                if visible { "add" } else { "remove" },
+                self.hwnd
+            );
+
+            return;
+        }
+
+        let mut taskbar_hidden_hwnds = TASKBAR_HIDDEN_HWNDS.lock();
+        taskbar_hidden_hwnds.retain(|hwnd| *hwnd != self.hwnd);
+        if !visible {
+            taskbar_hidden_hwnds.push(self.hwnd);
+        }
     }
 
     pub fn restore(self) {
+        let hiding_behaviour = *HIDING_BEHAVIOUR.lock();
+        self.restore_with_behaviour(hiding_behaviour);
+    }
+
+    pub fn restore_with_behaviour(self, hiding_behaviour: HidingBehaviour) {
         let mut programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
         if let Some(idx) = programmatically_hidden_hwnds
             .iter()
@@ -451,13 +547,29 @@ impl Window {
             programmatically_hidden_hwnds.remove(idx);
         }
 
-        let hiding_behaviour = HIDING_BEHAVIOUR.lock();
-        match *hiding_behaviour {
+        match hiding_behaviour {
             HidingBehaviour::Hide | HidingBehaviour::Minimize => {
                 WindowsApi::restore_window(self.hwnd);
             }
             HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 0),
         }
+
+        // Only buttons which were removed by us are added back, since windows such as tool
+        // windows never had one
+        let taskbar_hidden = TASKBAR_HIDDEN_HWNDS.lock().contains(&self.hwnd);
+        if taskbar_hidden && self.is_window() {
+            self.set_taskbar_entry(true);
+        }
+    }
+
+    /// Restores a window which was hidden while maximized so that it comes back maximized rather
+    /// than at its normal position
+    pub fn restore_maximized_with_behaviour(self, hiding_behaviour: HidingBehaviour) {
+        if matches!(hiding_behaviour, HidingBehaviour::Cloak) {
+            self.restore_with_behaviour(hiding_behaviour);
+        }
+
+        self.maximize();
     }
 
     pub fn minimize(self) {
@@ -496,38 +608,44 @@ impl Window {
     }
 
     pub fn focus(self, mouse_follows_focus: bool) -> Result<()> {
-        // If the target window is already focused, do nothing.
-        if let Ok(ihwnd) = WindowsApi::foreground_window() {
-            if ihwnd == self.hwnd {
-                // Center cursor in Window
-                if mouse_follows_focus {
-                    WindowsApi::center_cursor_in_rect(&WindowsApi::window_rect(self.hwnd)?)?;
-                }
+        let previous = window_system().foreground_window().ok();
+        let mouse_follows_focus = mouse_follows_focus
+            && WindowsApi::should_follow_focus(
+                previous,
+                window_system().monitor_from_window(self.hwnd),
+            );
 
-                return Ok(());
+        // If the target window is already focused, do nothing.
+        if previous == Some(self.hwnd) {
+            // Center cursor in Window
+            if mouse_follows_focus {
+                window_system().center_cursor_in_rect(&window_system().window_rect(self.hwnd)?)?;
             }
+
+            return Ok(());
         }
 
-        WindowsApi::raise_and_focus_window(self.hwnd)?;
+        window_system().raise_and_focus_window(self.hwnd)?;
 
         // Center cursor in Window
         if mouse_follows_focus {
-            WindowsApi::center_cursor_in_rect(&WindowsApi::window_rect(self.hwnd)?)?;
+            window_system().center_cursor_in_rect(&window_system().window_rect(self.hwnd)?)?;
         }
 
         Ok(())
     }
 
     pub fn is_focused(self) -> bool {
-        WindowsApi::foreground_window().unwrap_or_default() == self.hwnd
+        window_system().foreground_window().unwrap_or_default() == self.hwnd
     }
 
     pub fn transparent(self) -> Result<()> {
         let animation_enabled = ANIMATION_ENABLED_PER_ANIMATION.lock();
         let transparent_enabled = animation_enabled.get(&TransparencyRenderDispatcher::PREFIX);
 
-        if transparent_enabled.is_some_and(|enabled| *enabled)
-            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
+        if (transparent_enabled.is_some_and(|enabled| *enabled)
+            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst))
+            && !self.is_animation_blacklisted()
         {
             let duration = Duration::from_millis(
                 *ANIMATION_DURATION_PER_ANIMATION
@@ -564,8 +682,9 @@ impl Window {
         let animation_enabled = ANIMATION_ENABLED_PER_ANIMATION.lock();
         let transparent_enabled = animation_enabled.get(&TransparencyRenderDispatcher::PREFIX);
 
-        if transparent_enabled.is_some_and(|enabled| *enabled)
-            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst)
+        if (transparent_enabled.is_some_and(|enabled| *enabled)
+            || ANIMATION_ENABLED_GLOBAL.load(Ordering::SeqCst))
+            && !self.is_animation_blacklisted()
         {
             let duration = Duration::from_millis(
                 *ANIMATION_DURATION_PER_ANIMATION
@@ -596,7 +715,7 @@ impl Window {
     }
 
     pub fn set_accent(self, colour: u32) -> Result<()> {
-        WindowsApi::set_window_accent(self.hwnd, Some(colour))
+        WindowsApi::set_window_accent(self.hwnd, Some(strip_alpha(colour)))
     }
 
     pub fn remove_accent(self) -> Result<()> {
@@ -634,7 +753,7 @@ impl Window {
     }
 
     pub fn title(self) -> Result<String> {
-        WindowsApi::window_text_w(self.hwnd)
+        window_system().window_title(self.hwnd)
     }
 
     pub fn path(self) -> Result<String> {
@@ -646,11 +765,33 @@ impl Window {
     }
 
     pub fn exe(self) -> Result<String> {
-        let (process_id, _) = WindowsApi::window_thread_process_id(self.hwnd);
-        let handle = WindowsApi::process_handle(process_id)?;
-        let exe = WindowsApi::exe(handle);
-        WindowsApi::close_process(handle)?;
-        exe
+        window_system().window_exe(self.hwnd)
+    }
+
+    /// The minimum size of this window, which is only queried from the window the first time
+    /// and whenever it has been moved or resized since
+    pub fn minimum_size(self) -> Option<(i32, i32)> {
+        if let Some(minimum_size) = MINIMUM_SIZES.lock().get(&self.hwnd) {
+            return Some(*minimum_size);
+        }
+
+        let minimum_size = WindowsApi::minimum_size(self.hwnd).ok()?;
+        MINIMUM_SIZES.lock().insert(self.hwnd, minimum_size);
+
+        Some(minimum_size)
+    }
+
+    /// Queries the minimum size of this window again, forgetting it if the window no longer
+    /// responds
+    pub fn refresh_minimum_size(self) {
+        match WindowsApi::minimum_size(self.hwnd) {
+            Ok(minimum_size) => {
+                MINIMUM_SIZES.lock().insert(self.hwnd, minimum_size);
+            }
+            Err(_) => {
+                MINIMUM_SIZES.lock().remove(&self.hwnd);
+            }
+        }
     }
 
     pub fn process_id(self) -> u32 {
@@ -658,8 +799,31 @@ impl Window {
         process_id
     }
 
+    /// The process ids of the parent, grandparent etc. of the process which owns this window,
+    /// so that windows launched from a shell running inside a terminal can be traced back to the
+    /// terminal
+    pub fn ancestor_process_ids(self) -> Vec<u32> {
+        const MAX_DEPTH: usize = 8;
+
+        let mut ancestors = vec![];
+        let mut pid = self.process_id();
+
+        // Each ancestor is queried on its own, and the walk stops at the first process which
+        // has exited or which we are not allowed to query
+        while let Ok(parent) = WindowsApi::parent_process_id(pid) {
+            if parent == 0 || ancestors.len() == MAX_DEPTH || ancestors.contains(&parent) {
+                break;
+            }
+
+            ancestors.push(parent);
+            pid = parent;
+        }
+
+        ancestors
+    }
+
     pub fn class(self) -> Result<String> {
-        WindowsApi::real_window_class_w(self.hwnd)
+        window_system().window_class(self.hwnd)
     }
 
     pub fn is_cloaked(self) -> Result<bool> {
@@ -667,16 +831,61 @@ impl Window {
     }
 
     pub fn is_window(self) -> bool {
-        WindowsApi::is_window(self.hwnd)
+        window_system().is_window(self.hwnd)
+    }
+
+    /// Whether the reaper last found this window to have stopped responding
+    pub fn is_hung(self) -> bool {
+        HUNG_HWNDS.lock().contains(&self.hwnd)
+    }
+
+    /// Whether this window matches a rule in the focus follows mouse ignore list
+    pub fn is_focus_follows_mouse_ignored(self) -> bool {
+        if FOCUS_FOLLOWS_MOUSE_IGNORE.lock().is_empty() {
+            return false;
+        }
+
+        let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+            (self.title(), self.exe(), self.class(), self.path())
+        else {
+            return false;
+        };
+
+        // Same lock order as StaticConfig::apply_globals
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let rules = FOCUS_FOLLOWS_MOUSE_IGNORE.lock();
+
+        should_act(&title, &exe_name, &class, &path, &rules, &regex_identifiers).is_some()
     }
 
     pub fn remove_title_bar(self) -> Result<()> {
         let mut style = self.style()?;
+
+        TITLEBAR_ORIGINAL_STYLES
+            .lock()
+            .entry(self.hwnd)
+            .or_insert(style & (WindowStyle::CAPTION | WindowStyle::THICKFRAME));
+
         style.remove(WindowStyle::CAPTION);
         style.remove(WindowStyle::THICKFRAME);
         self.update_style(&style)
     }
 
+    /// Give the window back the caption and frame it had before its title bar was removed, or
+    /// both of them if its title bar was already removed before komorebi first saw it
+    pub fn restore_title_bar(self) -> Result<()> {
+        match TITLEBAR_ORIGINAL_STYLES.lock().remove(&self.hwnd) {
+            None => self.add_title_bar(),
+            Some(original) => {
+                let mut style = self.style()?;
+                style.remove(WindowStyle::CAPTION);
+                style.remove(WindowStyle::THICKFRAME);
+                style.insert(original);
+                self.update_style(&style)
+            }
+        }
+    }
+
     pub fn add_title_bar(self) -> Result<()> {
         let mut style = self.style()?;
         style.insert(WindowStyle::CAPTION);
@@ -690,27 +899,48 @@ impl Window {
         event: Option<WindowManagerEvent>,
         debug: &mut RuleDebug,
     ) -> Result<bool> {
-        if !self.is_window() {
+        debug.hwnd = Option::from(self.hwnd);
+
+        let should_manage = self.is_eligible(event, debug)?;
+        if !should_manage {
+            debug.flag_decisive_check();
+        }
+
+        Ok(should_manage)
+    }
+
+    fn is_eligible(self, event: Option<WindowManagerEvent>, debug: &mut RuleDebug) -> Result<bool> {
+        if !debug.check(EligibilityCheck::IsWindow, self.is_window(), None) {
             return Ok(false);
         }
 
         debug.is_window = true;
 
-        let rect = WindowsApi::window_rect(self.hwnd).unwrap_or_default();
+        let rect = window_system().window_rect(self.hwnd).unwrap_or_default();
 
-        if rect.right < MINIMUM_WIDTH.load(Ordering::SeqCst) {
+        let minimum_width = MINIMUM_WIDTH.load(Ordering::SeqCst);
+        if !debug.check(
+            EligibilityCheck::MinimumWidth,
+            rect.right >= minimum_width,
+            Option::from(format!("width {} (minimum {minimum_width})", rect.right)),
+        ) {
             return Ok(false);
         }
 
         debug.has_minimum_width = true;
 
-        if rect.bottom < MINIMUM_HEIGHT.load(Ordering::SeqCst) {
+        let minimum_height = MINIMUM_HEIGHT.load(Ordering::SeqCst);
+        if !debug.check(
+            EligibilityCheck::MinimumHeight,
+            rect.bottom >= minimum_height,
+            Option::from(format!("height {} (minimum {minimum_height})", rect.bottom)),
+        ) {
             return Ok(false);
         }
 
         debug.has_minimum_height = true;
 
-        if self.title().is_err() {
+        if !debug.check(EligibilityCheck::Title, self.title().is_ok(), None) {
             return Ok(false);
         }
 
@@ -733,37 +963,53 @@ impl Window {
 
         debug.allow_cloaked = allow_cloaked;
 
-        match (allow_cloaked, is_cloaked) {
-            // If allowing cloaked windows, we don't need to check the cloaked status
-            (true, _) |
-            // If not allowing cloaked windows, we need to ensure the window is not cloaked
-            (false, false) => {
-                if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) = (self.title(), self.exe(), self.class(), self.path()) {
-                    debug.title = Some(title.clone());
-                    debug.exe_name = Some(exe_name.clone());
-                    debug.class = Some(class.clone());
-                    debug.path = Some(path.clone());
-                    // calls for styles can fail quite often for events with windows that aren't really "windows"
-                    // since we have moved up calls of should_manage to the beginning of the process_event handler,
-                    // we should handle failures here gracefully to be able to continue the execution of process_event
-                    if let (Ok(style), Ok(ex_style)) = (&self.style(), &self.ex_style()) {
-                        debug.window_style = Some(*style);
-                        debug.extended_window_style = Some(*ex_style);
-                        let eligible = window_is_eligible(self.hwnd, &title, &exe_name, &class, &path, style, ex_style, event, debug);
-                        debug.should_manage = eligible;
-                        return Ok(eligible);
-                    }
-                }
-            }
-            _ => {}
+        // If allowing cloaked windows, we don't need to check the cloaked status, otherwise we
+        // need to ensure the window is not cloaked
+        if !debug.check(
+            EligibilityCheck::Cloaked,
+            allow_cloaked || !is_cloaked,
+            is_cloaked.then(|| String::from("the window is cloaked")),
+        ) {
+            return Ok(false);
         }
 
-        Ok(false)
+        let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+            (self.title(), self.exe(), self.class(), self.path())
+        else {
+            debug.check(EligibilityCheck::WindowProperties, false, None);
+            return Ok(false);
+        };
+
+        debug.check(EligibilityCheck::WindowProperties, true, None);
+        debug.title = Some(title.clone());
+        debug.exe_name = Some(exe_name.clone());
+        debug.class = Some(class.clone());
+        debug.path = Some(path.clone());
+
+        // calls for styles can fail quite often for events with windows that aren't really "windows"
+        // since we have moved up calls of should_manage to the beginning of the process_event handler,
+        // we should handle failures here gracefully to be able to continue the execution of process_event
+        let (Ok(style), Ok(ex_style)) = (self.style(), self.ex_style()) else {
+            debug.check(EligibilityCheck::WindowStyles, false, None);
+            return Ok(false);
+        };
+
+        debug.check(EligibilityCheck::WindowStyles, true, None);
+        debug.window_style = Some(style);
+        debug.extended_window_style = Some(ex_style);
+        let eligible = window_is_eligible(
+            self.hwnd, &title, &exe_name, &class, &path, &style, &ex_style, event, debug,
+        );
+        debug.should_manage = eligible;
+
+        Ok(eligible)
     }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct RuleDebug {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hwnd: Option<isize>,
     pub should_manage: bool,
     pub is_window: bool,
     pub has_minimum_width: bool,
@@ -785,6 +1031,146 @@ pub struct RuleDebug {
     pub matches_floating_applications: Option<MatchingRule>,
     pub matches_wsl2_gui: Option<String>,
     pub matches_no_titlebar: Option<MatchingRule>,
+    pub ignore_rules_checked: usize,
+    pub matched_rules: Vec<RuleMatch>,
+    /// The checks which were evaluated to decide whether the window should be managed, in
+    /// evaluation order
+    #[serde(default)]
+    pub checks: Vec<EligibilityCheckResult>,
+    /// Whether every rule matching the window should be recorded in matched_rules, which
+    /// requires evaluating rules which are not needed to decide whether it should be managed
+    #[serde(skip)]
+    collect_matched_rules: bool,
+}
+
+/// A step of the decision of whether or not a window should be managed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum EligibilityCheck {
+    /// The handle refers to an existing window
+    IsWindow,
+    /// The window is at least as wide as the minimum width
+    MinimumWidth,
+    /// The window is at least as tall as the minimum height
+    MinimumHeight,
+    /// The window has a title
+    Title,
+    /// The window is not cloaked, unless it is being hidden or cloaked by komorebi
+    Cloaked,
+    /// The title, exe, class and path of the window can be read
+    WindowProperties,
+    /// The style and extended style of the window can be read
+    WindowStyles,
+    /// The window class is not one which is always ignored
+    PermaignoreClasses,
+    /// The window does not match an ignore rule, or also matches a manage rule
+    IgnoreIdentifiers,
+    /// The window has a caption and a window edge, or is a WSL2 GUI window, or has had its title
+    /// bar removed
+    CaptionStyle,
+    /// The window is not a modal dialog
+    DialogModalFrame,
+    /// The window is not layered, or matches the layered whitelist, or has been made transparent
+    /// by komorebi
+    LayeredWindow,
+}
+
+impl Display for EligibilityCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::IsWindow => "is a window",
+            Self::MinimumWidth => "minimum width",
+            Self::MinimumHeight => "minimum height",
+            Self::Title => "has a title",
+            Self::Cloaked => "not cloaked",
+            Self::WindowProperties => "window properties readable",
+            Self::WindowStyles => "window styles readable",
+            Self::PermaignoreClasses => "permanently ignored classes",
+            Self::IgnoreIdentifiers => "ignore rules",
+            Self::CaptionStyle => "caption style",
+            Self::DialogModalFrame => "not a modal dialog",
+            Self::LayeredWindow => "layered window",
+        };
+
+        write!(f, "{description}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EligibilityCheckResult {
+    /// The check which was evaluated
+    pub check: EligibilityCheck,
+    /// Whether the window passed the check
+    pub passed: bool,
+    /// Whether this is the first failed check, which caused the window not to be managed
+    #[serde(default)]
+    pub decisive: bool,
+    /// Additional information about the outcome of the check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum RuleCollection {
+    IgnoreIdentifiers,
+    ManageIdentifiers,
+    FloatingApplications,
+    FloatOverrideApplications,
+    LayeredWhitelist,
+    NoTitlebar,
+    SlowApplicationIdentifiers,
+    SessionWorkspaceRules,
+    WorkspaceRules,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RuleMatch {
+    /// The rule collection that the matching rule belongs to
+    pub collection: RuleCollection,
+    /// The index of the matching rule within its collection
+    pub index: usize,
+    /// The matching rule
+    pub rule: MatchingRule,
+}
+
+impl RuleDebug {
+    /// A RuleDebug which records every rule matching the window, for explaining the decision
+    /// to the user
+    pub fn with_matched_rules() -> Self {
+        Self {
+            collect_matched_rules: true,
+            ..Default::default()
+        }
+    }
+
+    /// Records the outcome of a check, returning whether it passed
+    fn check(&mut self, check: EligibilityCheck, passed: bool, detail: Option<String>) -> bool {
+        self.checks.push(EligibilityCheckResult {
+            check,
+            passed,
+            decisive: false,
+            detail,
+        });
+
+        passed
+    }
+
+    fn flag_decisive_check(&mut self) {
+        if let Some(check) = self.checks.iter_mut().find(|check| !check.passed) {
+            check.decisive = true;
+        }
+    }
+
+    fn record(&mut self, collection: RuleCollection, index: usize, rule: &MatchingRule) {
+        if !self.collect_matched_rules {
+            return;
+        }
+
+        self.matched_rules.push(RuleMatch {
+            collection,
+            index,
+            rule: rule.clone(),
+        });
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -801,46 +1187,61 @@ fn window_is_eligible(
 ) -> bool {
     {
         let permaignore_classes = PERMAIGNORE_CLASSES.lock();
-        if permaignore_classes.contains(class) {
+        let permaignored = permaignore_classes.contains(class);
+        if !debug.check(
+            EligibilityCheck::PermaignoreClasses,
+            !permaignored,
+            permaignored.then(|| format!("class {class} is always ignored")),
+        ) {
             debug.matches_permaignore_class = Some(class.clone());
             return false;
         }
     }
 
+    // workspace rules are locked before regex identifiers, matching the order in
+    // enforce_workspace_rules, and are only needed to explain which rules match the window
+    let workspace_rules = debug.collect_matched_rules.then(|| {
+        (
+            SESSION_WORKSPACE_RULES.lock(),
+            WORKSPACE_MATCHING_RULES.lock(),
+        )
+    });
     let regex_identifiers = REGEX_IDENTIFIERS.lock();
 
     let ignore_identifiers = IGNORE_IDENTIFIERS.lock();
-    let should_ignore = if let Some(rule) = should_act(
+    debug.ignore_rules_checked = ignore_identifiers.len();
+    let ignore_idx = should_act_with_index(
         title,
         exe_name,
         class,
         path,
         &ignore_identifiers,
         &regex_identifiers,
-    ) {
+    )
+    .map(|(idx, rule)| {
+        debug.record(RuleCollection::IgnoreIdentifiers, idx, &rule);
         debug.matches_ignore_identifier = Some(rule);
-        true
-    } else {
-        false
-    };
+        idx
+    });
 
     let manage_identifiers = MANAGE_IDENTIFIERS.lock();
-    let managed_override = if let Some(rule) = should_act(
+    let manage_idx = should_act_with_index(
         title,
         exe_name,
         class,
         path,
         &manage_identifiers,
         &regex_identifiers,
-    ) {
+    )
+    .map(|(idx, rule)| {
+        debug.record(RuleCollection::ManageIdentifiers, idx, &rule);
         debug.matches_managed_override = Some(rule);
-        true
-    } else {
-        false
-    };
+        idx
+    });
+    let managed_override = manage_idx.is_some();
 
     let floating_identifiers = FLOATING_APPLICATIONS.lock();
-    if let Some(rule) = should_act(
+    if let Some((idx, rule)) = should_act_with_index(
         title,
         exe_name,
         class,
@@ -848,15 +1249,76 @@ fn window_is_eligible(
         &floating_identifiers,
         &regex_identifiers,
     ) {
+        debug.record(RuleCollection::FloatingApplications, idx, &rule);
         debug.matches_floating_applications = Some(rule);
     }
 
-    if should_ignore && !managed_override {
+    if let Some((session_workspace_rules, workspace_matching_rules)) = &workspace_rules {
+        let float_override_identifiers = FLOAT_OVERRIDE_APPLICATIONS.lock();
+        if let Some((idx, rule)) = should_act_with_index(
+            title,
+            exe_name,
+            class,
+            path,
+            &float_override_identifiers,
+            &regex_identifiers,
+        ) {
+            debug.record(RuleCollection::FloatOverrideApplications, idx, &rule);
+        }
+
+        for (idx, rule) in session_workspace_rules.iter().enumerate() {
+            if should_act(
+                title,
+                exe_name,
+                class,
+                path,
+                std::slice::from_ref(&rule.matching_rule),
+                &regex_identifiers,
+            )
+            .is_some()
+            {
+                debug.record(
+                    RuleCollection::SessionWorkspaceRules,
+                    idx,
+                    &rule.matching_rule,
+                );
+            }
+        }
+
+        for (idx, rule) in workspace_matching_rules.iter().enumerate() {
+            if should_act(
+                title,
+                exe_name,
+                class,
+                path,
+                std::slice::from_ref(&rule.matching_rule),
+                &regex_identifiers,
+            )
+            .is_some()
+            {
+                debug.record(RuleCollection::WorkspaceRules, idx, &rule.matching_rule);
+            }
+        }
+    }
+
+    let ignore_detail = match (ignore_idx, manage_idx) {
+        (Some(ignore_idx), Some(manage_idx)) => Option::from(format!(
+            "matches ignore rule {ignore_idx}, overridden by manage rule {manage_idx}"
+        )),
+        (Some(ignore_idx), None) => Option::from(format!("matches ignore rule {ignore_idx}")),
+        _ => None,
+    };
+
+    if !debug.check(
+        EligibilityCheck::IgnoreIdentifiers,
+        ignore_idx.is_none() || managed_override,
+        ignore_detail,
+    ) {
         return false;
     }
 
     let layered_whitelist = LAYERED_WHITELIST.lock();
-    let mut allow_layered = if let Some(rule) = should_act(
+    let mut allow_layered = if let Some((idx, rule)) = should_act_with_index(
         title,
         exe_name,
         class,
@@ -864,6 +1326,7 @@ fn window_is_eligible(
         &layered_whitelist,
         &regex_identifiers,
     ) {
+        debug.record(RuleCollection::LayeredWhitelist, idx, &rule);
         debug.matches_layered_whitelist = Some(rule);
         true
     } else {
@@ -890,7 +1353,7 @@ fn window_is_eligible(
     };
 
     let titlebars_removed = NO_TITLEBAR.lock();
-    let allow_titlebar_removed = if let Some(rule) = should_act(
+    let allow_titlebar_removed = if let Some((idx, rule)) = should_act_with_index(
         title,
         exe_name,
         class,
@@ -898,6 +1361,7 @@ fn window_is_eligible(
         &titlebars_removed,
         &regex_identifiers,
     ) {
+        debug.record(RuleCollection::NoTitlebar, idx, &rule);
         debug.matches_no_titlebar = Some(rule);
         true
     } else {
@@ -906,15 +1370,19 @@ fn window_is_eligible(
 
     {
         let slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
-        let should_sleep = should_act(
+        let should_sleep = if let Some((idx, rule)) = should_act_with_index(
             title,
             exe_name,
             class,
             path,
             &slow_application_identifiers,
             &regex_identifiers,
-        )
-        .is_some();
+        ) {
+            debug.record(RuleCollection::SlowApplicationIdentifiers, idx, &rule);
+            true
+        } else {
+            false
+        };
 
         if should_sleep {
             std::thread::sleep(Duration::from_millis(
@@ -923,15 +1391,31 @@ fn window_is_eligible(
         }
     }
 
-    if (allow_wsl2_gui || allow_titlebar_removed || style.contains(WindowStyle::CAPTION) && ex_style.contains(ExtendedWindowStyle::WINDOWEDGE))
-                        && !ex_style.contains(ExtendedWindowStyle::DLGMODALFRAME)
-                        // Get a lot of dupe events coming through that make the redrawing go crazy
-                        // on FocusChange events if I don't filter out this one. But, if we are
-                        // allowing a specific layered window on the whitelist (like Steam), it should
-                        // pass this check
-                        && (allow_layered || !ex_style.contains(ExtendedWindowStyle::LAYERED))
-        || managed_override
-    {
+    let has_caption_style = debug.check(
+        EligibilityCheck::CaptionStyle,
+        allow_wsl2_gui
+            || allow_titlebar_removed
+            || style.contains(WindowStyle::CAPTION)
+                && ex_style.contains(ExtendedWindowStyle::WINDOWEDGE),
+        None,
+    );
+
+    let is_not_modal = debug.check(
+        EligibilityCheck::DialogModalFrame,
+        !ex_style.contains(ExtendedWindowStyle::DLGMODALFRAME),
+        None,
+    );
+
+    // Get a lot of dupe events coming through that make the redrawing go crazy on FocusChange
+    // events if I don't filter out this one. But, if we are allowing a specific layered window
+    // on the whitelist (like Steam), it should pass this check
+    let passes_layered_check = debug.check(
+        EligibilityCheck::LayeredWindow,
+        allow_layered || !ex_style.contains(ExtendedWindowStyle::LAYERED),
+        None,
+    );
+
+    if has_caption_style && is_not_modal && passes_layered_check || managed_override {
         return true;
     } else if let Some(event) = event {
         tracing::debug!(
@@ -954,8 +1438,21 @@ pub fn should_act(
     identifiers: &[MatchingRule],
     regex_identifiers: &HashMap<String, Regex>,
 ) -> Option<MatchingRule> {
+    should_act_with_index(title, exe_name, class, path, identifiers, regex_identifiers)
+        .map(|(_, rule)| rule)
+}
+
+/// Returns the last rule in `identifiers` which matches, along with its index
+pub fn should_act_with_index(
+    title: &str,
+    exe_name: &str,
+    class: &str,
+    path: &str,
+    identifiers: &[MatchingRule],
+    regex_identifiers: &HashMap<String, Regex>,
+) -> Option<(usize, MatchingRule)> {
     let mut matching_rule = None;
-    for rule in identifiers {
+    for (idx, rule) in identifiers.iter().enumerate() {
         match rule {
             MatchingRule::Simple(identifier) => {
                 if should_act_individual(
@@ -966,7 +1463,7 @@ pub fn should_act(
                     identifier,
                     regex_identifiers,
                 ) {
-                    matching_rule = Some(rule.clone());
+                    matching_rule = Some((idx, rule.clone()));
                 };
             }
             MatchingRule::Composite(identifiers) => {
@@ -983,7 +1480,7 @@ pub fn should_act(
                 }
 
                 if composite_results.iter().all(|&x| x) {
-                    matching_rule = Some(rule.clone());
+                    matching_rule = Some((idx, rule.clone()));
                 }
             }
         }
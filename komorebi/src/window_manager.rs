@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::env::temp_dir;
+use std::fs::File;
+use std::fs::OpenOptions;
 use std::io::ErrorKind;
 use std::net::Shutdown;
 use std::num::NonZeroUsize;
@@ -27,8 +29,13 @@ use uds_windows::UnixStream;
 use crate::animation::AnimationEngine;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
 use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
+use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
+use crate::core::config_generation::RuleProvenance;
 use crate::core::custom_layout::CustomLayout;
+use crate::core::resolve_home_path;
+use crate::core::ApplicationIdentifier;
 use crate::core::Arrangement;
 use crate::core::Axis;
 use crate::core::BorderImplementation;
@@ -38,63 +45,92 @@ use crate::core::DefaultLayout;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::HidingBehaviour;
 use crate::core::Layout;
+use crate::core::MouseFollowsFocusScope;
 use crate::core::MoveBehaviour;
 use crate::core::OperationBehaviour;
 use crate::core::OperationDirection;
+use crate::core::PredefinedPlacement;
 use crate::core::Rect;
+use crate::core::RectOrPercent;
 use crate::core::Sizing;
 use crate::core::StackbarLabel;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowManagementBehaviour;
+use crate::core::WorkspaceLayer;
 
 use crate::border_manager;
+use crate::border_manager::BorderColourOverride;
 use crate::border_manager::STYLE;
 use crate::config_generation::WorkspaceMatchingRule;
 use crate::container::Container;
+use crate::container::WorkspaceOrigin;
 use crate::core::StackbarMode;
 use crate::current_virtual_desktop;
+use crate::custom_layout_watcher;
 use crate::load_configuration;
 use crate::monitor::Monitor;
+use crate::presentation_manager;
+use crate::process_movement::FocusFollowsMouseIgnoreCache;
 use crate::ring::Ring;
 use crate::should_act;
 use crate::should_act_individual;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_LABEL;
+use crate::stackbar_manager::STACKBAR_LAST_ENABLED_MODE;
 use crate::stackbar_manager::STACKBAR_MODE;
 use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
 use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
+use crate::static_config::populate_rules;
 use crate::static_config::StaticConfig;
 use crate::transparency_manager;
 use crate::transparency_manager::TRANSPARENCY_ALPHA;
 use crate::transparency_manager::TRANSPARENCY_ENABLED;
 use crate::window::Window;
 use crate::window_manager_event::WindowManagerEvent;
+use crate::window_system::window_system;
 use crate::windows_api::WindowsApi;
 use crate::winevent_listener;
 use crate::workspace::Workspace;
+use crate::workspace::WorkspaceWindowLocation;
 use crate::BorderColours;
 use crate::Colour;
 use crate::CrossBoundaryBehaviour;
-use crate::Rgb;
+use crate::DragBehaviour;
+use crate::LastWindowCloseBehaviour;
+use crate::AUTO_STACK_EXEMPT_HWNDS;
+use crate::AUTO_WORKSPACE_NAMES;
+use crate::CROSS_LAYER_FOCUS;
 use crate::CUSTOM_FFM;
+use crate::CYCLE_STACK_WRAP;
 use crate::DATA_DIR;
 use crate::DISPLAY_INDEX_PREFERENCES;
+use crate::FULLSCREEN_APPLICATIONS;
+use crate::HIDDEN_SCRATCHPAD_HWNDS;
 use crate::HIDING_BEHAVIOUR;
 use crate::HOME_DIR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
 use crate::MONITOR_INDEX_PREFERENCES;
+use crate::MOUSE_FOLLOWS_FOCUS_SCOPE;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::PINNED_HWNDS;
 use crate::REGEX_IDENTIFIERS;
 use crate::REMOVE_TITLEBARS;
+use crate::SCRATCHPADS;
+use crate::SCRATCHPAD_ASPECT_RATIO;
+use crate::SCRATCHPAD_WIDTH_PERCENTAGE;
+use crate::SESSION_WORKSPACE_RULES;
+use crate::SPLIT_RATIO_PRESETS;
 use crate::SUBSCRIPTION_SOCKETS;
+use crate::TILE_ORIGINS;
 use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 use crate::WORKSPACE_MATCHING_RULES;
+use crate::WORKSPACE_RULE_ENFORCEMENT;
 
 #[derive(Debug)]
 pub struct WindowManager {
@@ -102,11 +138,13 @@ pub struct WindowManager {
     pub incoming_events: Receiver<WindowManagerEvent>,
     pub command_listener: UnixListener,
     pub is_paused: bool,
-    pub work_area_offset: Option<Rect>,
+    pub work_area_offset: Option<RectOrPercent>,
     pub resize_delta: i32,
     pub window_management_behaviour: WindowManagementBehaviour,
     pub cross_monitor_move_behaviour: MoveBehaviour,
     pub cross_boundary_behaviour: CrossBoundaryBehaviour,
+    pub drag_behaviour: DragBehaviour,
+    pub on_last_window_close: LastWindowCloseBehaviour,
     pub unmanaged_window_operation_behaviour: OperationBehaviour,
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
     pub mouse_follows_focus: bool,
@@ -115,6 +153,8 @@ pub struct WindowManager {
     pub has_pending_raise_op: bool,
     pub pending_move_op: Arc<Option<(usize, usize, isize)>>,
     pub already_moved_window_handles: Arc<Mutex<HashSet<isize>>>,
+    /// Windows which have been moved to another workspace or monitor by the user
+    pub manually_moved_window_handles: Arc<Mutex<HashSet<isize>>>,
     pub uncloack_to_ignore: usize,
 }
 
@@ -128,7 +168,7 @@ pub struct State {
     pub float_override: bool,
     pub cross_monitor_move_behaviour: MoveBehaviour,
     pub unmanaged_window_operation_behaviour: OperationBehaviour,
-    pub work_area_offset: Option<Rect>,
+    pub work_area_offset: Option<RectOrPercent>,
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
     pub mouse_follows_focus: bool,
     pub has_pending_raise_op: bool,
@@ -187,10 +227,13 @@ impl State {
 pub struct GlobalState {
     pub border_enabled: bool,
     pub border_colours: BorderColours,
+    #[serde(default)]
+    pub border_colour_overrides: Vec<BorderColourOverride>,
     pub border_style: BorderStyle,
     pub border_offset: i32,
     pub border_width: i32,
     pub stackbar_mode: StackbarMode,
+    pub stackbar_last_enabled_mode: StackbarMode,
     pub stackbar_label: StackbarLabel,
     pub stackbar_focused_text_colour: Colour,
     pub stackbar_unfocused_text_colour: Colour,
@@ -210,10 +253,15 @@ pub struct GlobalState {
     pub monitor_index_preferences: HashMap<usize, Rect>,
     pub display_index_preferences: HashMap<usize, String>,
     pub workspace_rules: Vec<WorkspaceMatchingRule>,
+    pub session_workspace_rules: Vec<WorkspaceMatchingRule>,
     pub window_hiding_behaviour: HidingBehaviour,
+    #[serde(default)]
+    pub mouse_follows_focus_scope: MouseFollowsFocusScope,
     pub configuration_dir: PathBuf,
     pub data_dir: PathBuf,
     pub custom_ffm: bool,
+    #[serde(default)]
+    pub presentation_mode_active: bool,
 }
 
 impl Default for GlobalState {
@@ -221,36 +269,33 @@ impl Default for GlobalState {
         Self {
             border_enabled: border_manager::BORDER_ENABLED.load(Ordering::SeqCst),
             border_colours: BorderColours {
-                single: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::FOCUSED.load(Ordering::SeqCst),
-                ))),
-                stack: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::STACK.load(Ordering::SeqCst),
-                ))),
-                monocle: Option::from(Colour::Rgb(Rgb::from(
-                    border_manager::MONOCLE.load(Ordering::SeqCst),
-                ))),
-                floating: Option::from(Colour::Rgb(Rgb::from(
+                single: Option::from(Colour::from(border_manager::FOCUSED.load(Ordering::SeqCst))),
+                stack: Option::from(Colour::from(border_manager::STACK.load(Ordering::SeqCst))),
+                monocle: Option::from(Colour::from(border_manager::MONOCLE.load(Ordering::SeqCst))),
+                floating: Option::from(Colour::from(
                     border_manager::FLOATING.load(Ordering::SeqCst),
-                ))),
-                unfocused: Option::from(Colour::Rgb(Rgb::from(
+                )),
+                pinned: Option::from(Colour::from(border_manager::PINNED.load(Ordering::SeqCst))),
+                unfocused: Option::from(Colour::from(
                     border_manager::UNFOCUSED.load(Ordering::SeqCst),
-                ))),
+                )),
             },
+            border_colour_overrides: border_manager::BORDER_COLOUR_OVERRIDES.lock().clone(),
             border_style: STYLE.load(),
             border_offset: border_manager::BORDER_OFFSET.load(Ordering::SeqCst),
             border_width: border_manager::BORDER_WIDTH.load(Ordering::SeqCst),
             stackbar_mode: STACKBAR_MODE.load(),
+            stackbar_last_enabled_mode: STACKBAR_LAST_ENABLED_MODE.load(),
             stackbar_label: STACKBAR_LABEL.load(),
-            stackbar_focused_text_colour: Colour::Rgb(Rgb::from(
+            stackbar_focused_text_colour: Colour::from(
                 STACKBAR_FOCUSED_TEXT_COLOUR.load(Ordering::SeqCst),
-            )),
-            stackbar_unfocused_text_colour: Colour::Rgb(Rgb::from(
+            ),
+            stackbar_unfocused_text_colour: Colour::from(
                 STACKBAR_UNFOCUSED_TEXT_COLOUR.load(Ordering::SeqCst),
-            )),
-            stackbar_tab_background_colour: Colour::Rgb(Rgb::from(
+            ),
+            stackbar_tab_background_colour: Colour::from(
                 STACKBAR_TAB_BACKGROUND_COLOUR.load(Ordering::SeqCst),
-            )),
+            ),
             stackbar_tab_width: STACKBAR_TAB_WIDTH.load(Ordering::SeqCst),
             stackbar_height: STACKBAR_TAB_HEIGHT.load(Ordering::SeqCst),
             transparency_enabled: TRANSPARENCY_ENABLED.load(Ordering::SeqCst),
@@ -265,10 +310,14 @@ impl Default for GlobalState {
             monitor_index_preferences: MONITOR_INDEX_PREFERENCES.lock().clone(),
             display_index_preferences: DISPLAY_INDEX_PREFERENCES.lock().clone(),
             workspace_rules: WORKSPACE_MATCHING_RULES.lock().clone(),
+            session_workspace_rules: SESSION_WORKSPACE_RULES.lock().clone(),
             window_hiding_behaviour: *HIDING_BEHAVIOUR.lock(),
+            mouse_follows_focus_scope: *MOUSE_FOLLOWS_FOCUS_SCOPE.lock(),
             configuration_dir: HOME_DIR.clone(),
             data_dir: DATA_DIR.clone(),
             custom_ffm: CUSTOM_FFM.load(Ordering::SeqCst),
+            presentation_mode_active: presentation_manager::PRESENTATION_MODE_ACTIVE
+                .load(Ordering::SeqCst),
         }
     }
 }
@@ -297,8 +346,196 @@ impl From<&WindowManager> for State {
     }
 }
 
+/// The state which is dumped to a temporary file when komorebi is stopped and which is applied
+/// again on the next start unless the state is explicitly cleaned
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DumpedState {
+    pub state: State,
+    #[serde(default)]
+    pub session_workspace_rules: Vec<WorkspaceMatchingRule>,
+    #[serde(default)]
+    pub manually_moved_window_handles: Vec<isize>,
+    /// The identity of every window referenced in the dumped state, so that a window handle which
+    /// has been reused by the OS for an unrelated window is not mistaken for the original window
+    #[serde(default)]
+    pub window_identities: HashMap<isize, WindowIdentity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowIdentity {
+    pub exe: String,
+    pub class: String,
+}
+
+impl WindowIdentity {
+    // Window titles are not used here because they change too often (e.g. browser tabs) to be
+    // able to tell a reused window handle apart from a window which has navigated somewhere else
+    fn of(window: Window) -> Option<Self> {
+        Option::from(Self {
+            exe: window.exe().ok()?,
+            class: window.class().ok()?,
+        })
+    }
+}
+
+impl DumpedState {
+    /// Whether the window still exists and, if its identity was recorded in the dumped state,
+    /// whether it is still the same window
+    fn is_same_window(&self, window: Window) -> bool {
+        match (
+            WindowIdentity::of(window),
+            self.window_identities.get(&window.hwnd),
+        ) {
+            (Some(current), Some(dumped)) => current == *dumped,
+            (Some(_), None) => self.window_identities.is_empty(),
+            (None, _) => false,
+        }
+    }
+}
+
+impl From<&WindowManager> for DumpedState {
+    fn from(wm: &WindowManager) -> Self {
+        let mut window_identities = HashMap::new();
+        for monitor in wm.monitors() {
+            for workspace in monitor.workspaces() {
+                for window in workspace.all_windows() {
+                    if let Some(identity) = WindowIdentity::of(*window) {
+                        window_identities.insert(window.hwnd, identity);
+                    }
+                }
+            }
+        }
+
+        let mut manually_moved_window_handles = vec![];
+        for hwnd in wm.manually_moved_window_handles.lock().iter() {
+            if let Some(identity) = WindowIdentity::of(Window::from(*hwnd)) {
+                window_identities.insert(*hwnd, identity);
+                manually_moved_window_handles.push(*hwnd);
+            }
+        }
+
+        Self {
+            state: State::from(wm),
+            session_workspace_rules: SESSION_WORKSPACE_RULES.lock().clone(),
+            manually_moved_window_handles,
+            window_identities,
+        }
+    }
+}
+
+/// The size and scaling of a monitor
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MonitorInformation {
+    #[serde(flatten)]
+    pub size: Rect,
+    /// The effective DPI of the monitor, which is 96 at 100% scaling
+    pub dpi: u32,
+    /// The scale factor derived from the effective DPI, which is 1.0 at 100% scaling
+    pub scale_factor: f32,
+}
+
+/// The window manager state, global state and monitor information, captured together so that
+/// all three are consistent with each other
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Snapshot {
+    pub state: State,
+    pub global_state: GlobalState,
+    pub monitor_information: HashMap<String, MonitorInformation>,
+}
+
+impl From<&WindowManager> for Snapshot {
+    fn from(wm: &WindowManager) -> Self {
+        Self {
+            state: State::from(wm),
+            global_state: GlobalState::default(),
+            monitor_information: wm.monitor_information(),
+        }
+    }
+}
+
 impl_ring_elements!(WindowManager, Monitor);
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FloatingLayoutEntry {
+    /// Rule to identify the floating window(s) to position
+    pub matching_rule: MatchingRule,
+    /// The position and size of the floating window(s)
+    pub rect: Rect,
+}
+
+/// The resize dimensions of a workspace, along with what they were calculated for so that they
+/// are only ever loaded onto a compatible workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ResizeDimensionsEntry {
+    /// Device id of the monitor
+    pub monitor: String,
+    /// Name of the workspace, used instead of the index if both workspaces are named
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_name: Option<String>,
+    /// Index of the workspace on the monitor
+    pub workspace_idx: usize,
+    /// Layout of the workspace
+    pub layout: DefaultLayout,
+    /// Number of containers on the workspace
+    pub container_count: usize,
+    /// Resize dimensions of each container
+    pub resize_dimensions: Vec<Option<Rect>>,
+}
+
+impl ResizeDimensionsEntry {
+    fn new(monitor: &Monitor, workspace_idx: usize, workspace: &Workspace) -> Option<Self> {
+        let Layout::Default(layout) = workspace.layout() else {
+            return None;
+        };
+
+        Option::from(Self {
+            monitor: monitor.device_id().clone(),
+            workspace_name: workspace.explicit_name().cloned(),
+            workspace_idx,
+            layout: *layout,
+            container_count: workspace.containers().len(),
+            resize_dimensions: workspace.resize_dimensions().clone(),
+        })
+    }
+
+    fn is_compatible_with(&self, other: &Self) -> bool {
+        let same_workspace = match (&self.workspace_name, &other.workspace_name) {
+            (Some(name), Some(other_name)) => name == other_name,
+            _ => self.workspace_idx == other.workspace_idx,
+        };
+
+        self.monitor == other.monitor
+            && same_workspace
+            && self.layout == other.layout
+            && self.container_count == other.container_count
+    }
+}
+
+/// Limits the enforcement of workspace rules to the windows of a single application and/or to
+/// the rules which target a single named workspace
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceRuleScope {
+    pub application: Option<IdWithIdentifier>,
+    pub workspace: Option<String>,
+}
+
+impl WorkspaceRuleScope {
+    pub fn new(
+        identifier: Option<ApplicationIdentifier>,
+        id: Option<String>,
+        workspace: Option<String>,
+    ) -> Self {
+        Self {
+            application: identifier.zip(id).map(|(kind, id)| IdWithIdentifier {
+                kind,
+                id,
+                matching_strategy: Option::from(MatchingStrategy::Legacy),
+            }),
+            workspace,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct EnforceWorkspaceRuleOp {
     hwnd: isize,
@@ -351,6 +588,8 @@ impl WindowManager {
             window_management_behaviour: WindowManagementBehaviour::default(),
             cross_monitor_move_behaviour: MoveBehaviour::Swap,
             cross_boundary_behaviour: CrossBoundaryBehaviour::Workspace,
+            drag_behaviour: DragBehaviour::default(),
+            on_last_window_close: LastWindowCloseBehaviour::default(),
             unmanaged_window_operation_behaviour: OperationBehaviour::Op,
             resize_delta: 50,
             focus_follows_mouse: None,
@@ -359,6 +598,7 @@ impl WindowManager {
             has_pending_raise_op: false,
             pending_move_op: Arc::new(None),
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
+            manually_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
             uncloack_to_ignore: 0,
         })
     }
@@ -370,8 +610,29 @@ impl WindowManager {
         WindowsApi::load_workspace_information(&mut self.monitors)
     }
 
-    #[tracing::instrument(skip(self, state))]
-    pub fn apply_state(&mut self, state: State) {
+    #[tracing::instrument(skip(self, dumped))]
+    pub fn apply_state(&mut self, dumped: DumpedState) {
+        // Session rules match on application identifiers rather than window handles, so they can
+        // be restored even if the rest of the state cannot
+        if !dumped.session_workspace_rules.is_empty() {
+            let mut session_workspace_rules = SESSION_WORKSPACE_RULES.lock();
+            for rule in &dumped.session_workspace_rules {
+                if !session_workspace_rules.contains(rule) {
+                    session_workspace_rules.push(rule.clone());
+                }
+            }
+        }
+
+        {
+            let mut manually_moved_window_handles = self.manually_moved_window_handles.lock();
+            for hwnd in &dumped.manually_moved_window_handles {
+                if dumped.is_same_window(Window::from(*hwnd)) {
+                    manually_moved_window_handles.insert(*hwnd);
+                }
+            }
+        }
+
+        let state = &dumped.state;
         let mut can_apply = true;
 
         let state_monitors_len = state.monitors.elements().len();
@@ -389,7 +650,7 @@ impl WindowManager {
             for workspace in monitor.workspaces() {
                 for container in workspace.containers() {
                     for window in container.windows() {
-                        if window.exe().is_err() {
+                        if !dumped.is_same_window(*window) {
                             can_apply = false;
                             break;
                         }
@@ -397,7 +658,7 @@ impl WindowManager {
                 }
 
                 if let Some(window) = workspace.maximized_window() {
-                    if window.exe().is_err() {
+                    if !dumped.is_same_window(*window) {
                         can_apply = false;
                         break;
                     }
@@ -405,7 +666,7 @@ impl WindowManager {
 
                 if let Some(container) = workspace.monocle_container() {
                     for window in container.windows() {
-                        if window.exe().is_err() {
+                        if !dumped.is_same_window(*window) {
                             can_apply = false;
                             break;
                         }
@@ -413,7 +674,7 @@ impl WindowManager {
                 }
 
                 for window in workspace.floating_windows() {
-                    if window.exe().is_err() {
+                    if !dumped.is_same_window(*window) {
                         can_apply = false;
                         break;
                     }
@@ -498,7 +759,7 @@ impl WindowManager {
             }
         } else {
             tracing::warn!(
-                "cannot apply state from {}; some windows referenced in the state file no longer exist",
+                "cannot apply state from {}; some windows referenced in the state file no longer exist or have been replaced",
                 temp_dir().join("komorebi.state.json").to_string_lossy()
             );
         }
@@ -551,9 +812,14 @@ impl WindowManager {
                     self.window_management_behaviour.float_override
                 };
 
+                let new_window_placement = workspace
+                    .new_window_placement()
+                    .unwrap_or(self.window_management_behaviour.new_window_placement);
+
                 return WindowManagementBehaviour {
                     current_behaviour,
                     float_override,
+                    new_window_placement,
                 };
             }
         }
@@ -561,6 +827,7 @@ impl WindowManager {
         WindowManagementBehaviour {
             current_behaviour: WindowContainerBehaviour::Create,
             float_override: self.window_management_behaviour.float_override,
+            new_window_placement: self.window_management_behaviour.new_window_placement,
         }
     }
 
@@ -620,6 +887,10 @@ impl WindowManager {
         let current_monitor_size = self.focused_monitor_size().ok()?;
 
         for (idx, monitor) in self.monitors.elements().iter().enumerate() {
+            if monitor.unmanaged() {
+                continue;
+            }
+
             match direction {
                 OperationDirection::Left => {
                     if monitor.size().left + monitor.size().right == current_monitor_size.left {
@@ -709,8 +980,119 @@ impl WindowManager {
         });
     }
 
-    #[tracing::instrument(skip(self), level = "debug")]
+    #[tracing::instrument(skip(self))]
+    pub fn add_session_workspace_rule(
+        &mut self,
+        identifier: ApplicationIdentifier,
+        id: &str,
+        monitor_idx: usize,
+        workspace_idx: usize,
+    ) -> Result<()> {
+        let rule = WorkspaceMatchingRule {
+            monitor_index: monitor_idx,
+            workspace_index: workspace_idx,
+            matching_rule: MatchingRule::Simple(IdWithIdentifier {
+                kind: identifier,
+                id: id.to_string(),
+                matching_strategy: Some(MatchingStrategy::Legacy),
+            }),
+            initial_only: false,
+            enforce_on_event: true,
+            provenance: RuleProvenance::Runtime,
+        };
+
+        {
+            let mut session_workspace_rules = SESSION_WORKSPACE_RULES.lock();
+            // A newer rule for the same application replaces any older one
+            session_workspace_rules.retain(|r| r.matching_rule != rule.matching_rule);
+            session_workspace_rules.push(rule);
+        }
+
+        self.enforce_workspace_rules()
+    }
+
+    /// The monitor and workspace indices targeted by the first workspace rule which matches this
+    /// window, with session rules taking precedence over rules from the configuration file
+    pub fn workspace_rule_target(&self, window: &Window) -> Option<(usize, usize)> {
+        let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+            (window.title(), window.exe(), window.class(), window.path())
+        else {
+            return None;
+        };
+
+        let session_workspace_rules = SESSION_WORKSPACE_RULES.lock();
+        let workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+
+        session_workspace_rules
+            .iter()
+            .chain(workspace_matching_rules.iter())
+            .find(|rule| {
+                should_act(
+                    &title,
+                    &exe_name,
+                    &class,
+                    &path,
+                    std::slice::from_ref(&rule.matching_rule),
+                    &regex_identifiers,
+                )
+                .is_some()
+            })
+            .filter(|rule| !self.is_unmanaged_monitor(rule.monitor_index))
+            .map(|rule| (rule.monitor_index, rule.workspace_index))
+    }
+
+    /// Whether the workspace at these indices is the one named in the scope, if there is one
+    fn is_workspace_in_rule_scope(
+        &self,
+        scope: &WorkspaceRuleScope,
+        monitor_idx: usize,
+        workspace_idx: usize,
+    ) -> bool {
+        scope.workspace.as_ref().is_none_or(|name| {
+            self.monitors()
+                .get(monitor_idx)
+                .and_then(|monitor| monitor.workspaces().get(workspace_idx))
+                .and_then(Workspace::explicit_name)
+                == Some(name)
+        })
+    }
+
+    /// Whether this window would be processed when enforcing workspace rules with the scope
+    pub fn is_in_workspace_rule_scope(&self, window: &Window, scope: &WorkspaceRuleScope) -> bool {
+        if let Some(application) = &scope.application {
+            let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+                (window.title(), window.exe(), window.class(), window.path())
+            else {
+                return false;
+            };
+
+            if !should_act_individual(
+                &title,
+                &exe_name,
+                &class,
+                &path,
+                application,
+                &REGEX_IDENTIFIERS.lock(),
+            ) {
+                return false;
+            }
+        }
+
+        scope.workspace.is_none()
+            || self
+                .workspace_rule_target(window)
+                .is_some_and(|(monitor_idx, workspace_idx)| {
+                    self.is_workspace_in_rule_scope(scope, monitor_idx, workspace_idx)
+                })
+    }
+
     pub fn enforce_workspace_rules(&mut self) -> Result<()> {
+        self.enforce_workspace_rules_for(&WorkspaceRuleScope::default())
+    }
+
+    #[tracing::instrument(skip(self), level = "debug")]
+    pub fn enforce_workspace_rules_for(&mut self, scope: &WorkspaceRuleScope) -> Result<()> {
         let mut to_move = vec![];
 
         let focused_monitor_idx = self.focused_monitor_idx();
@@ -720,8 +1102,11 @@ impl WindowManager {
             .ok_or_else(|| anyhow!("there is no monitor with that index"))?
             .focused_workspace_idx();
 
+        let session_workspace_rules = SESSION_WORKSPACE_RULES.lock();
         let workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
         let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let manually_moved_window_handles = self.manually_moved_window_handles.lock().clone();
+        let enforcement = WORKSPACE_RULE_ENFORCEMENT.load(Ordering::SeqCst);
         // Go through all the monitors and workspaces
         for (i, monitor) in self.monitors().iter().enumerate() {
             for (j, workspace) in monitor.workspaces().iter().enumerate() {
@@ -733,7 +1118,25 @@ impl WindowManager {
                     let class = window.class()?;
                     let path = window.path()?;
 
-                    for rule in &*workspace_matching_rules {
+                    if let Some(application) = &scope.application {
+                        if !should_act_individual(
+                            &title,
+                            &exe_name,
+                            &class,
+                            &path,
+                            application,
+                            &regex_identifiers,
+                        ) {
+                            continue;
+                        }
+                    }
+
+                    // Session rules take precedence over rules from the configuration file, and
+                    // only the first matching rule is applied to a window
+                    for rule in session_workspace_rules
+                        .iter()
+                        .chain(workspace_matching_rules.iter())
+                    {
                         let matched = match &rule.matching_rule {
                             MatchingRule::Simple(r) => should_act_individual(
                                 &title,
@@ -761,6 +1164,27 @@ impl WindowManager {
                         };
 
                         if matched {
+                            // Windows are never sent to a monitor that is not being managed
+                            if self.is_unmanaged_monitor(rule.monitor_index) {
+                                break;
+                            }
+
+                            // Windows whose rule targets a different workspace are left alone
+                            if !self.is_workspace_in_rule_scope(
+                                scope,
+                                rule.monitor_index,
+                                rule.workspace_index,
+                            ) {
+                                break;
+                            }
+
+                            // Manual moves stick until rules are explicitly enforced again
+                            if !(enforcement && rule.enforce_on_event)
+                                && manually_moved_window_handles.contains(&window.hwnd)
+                            {
+                                break;
+                            }
+
                             let floating = workspace.floating_windows().contains(window);
 
                             if rule.initial_only {
@@ -790,6 +1214,8 @@ impl WindowManager {
                                     &mut to_move,
                                 );
                             }
+
+                            break;
                         }
                     }
                 }
@@ -832,7 +1258,7 @@ impl WindowManager {
 
             // Hide the window we are about to remove if it is on the currently focused workspace
             if op.is_origin(focused_monitor_idx, focused_workspace_idx) {
-                window.hide();
+                window.hide_with_behaviour(origin_workspace.hiding_behaviour());
                 should_update_focused_workspace = true;
             }
 
@@ -875,7 +1301,12 @@ impl WindowManager {
                 //when moving to another workspace by a `workspace_rule` should honor that
                 //workspace `window_container_behaviour` in my opinion! Maybe this should be done
                 //on the `new_container_for_window` function instead.
-                target_workspace.new_container_for_window(Window::from(op.hwnd));
+                let window = Window::from(op.hwnd);
+                if !(target_workspace.should_auto_stack(op.hwnd)
+                    && target_workspace.auto_stack_window(window))
+                {
+                    target_workspace.new_container_for_window(window);
+                }
             }
         }
 
@@ -892,17 +1323,18 @@ impl WindowManager {
         let offset = self.work_area_offset;
 
         for monitor in self.monitors_mut() {
+            if monitor.is_paused() {
+                continue;
+            }
+
             let work_area = *monitor.work_area_size();
             let window_based_work_area_offset = (
                 monitor.window_based_work_area_offset_limit(),
                 monitor.window_based_work_area_offset(),
+                monitor.window_based_work_area_offset_counting(),
             );
 
-            let offset = if monitor.work_area_offset().is_some() {
-                monitor.work_area_offset()
-            } else {
-                offset
-            };
+            let offset = monitor.effective_work_area_offset(offset);
 
             let workspace = monitor
                 .focused_workspace_mut()
@@ -923,20 +1355,180 @@ impl WindowManager {
 
     #[tracing::instrument(skip(self))]
     pub fn manage_focused_window(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = window_system().foreground_window()?;
         let event = WindowManagerEvent::Manage(Window::from(hwnd));
         Ok(winevent_listener::event_tx().send(event)?)
     }
 
     #[tracing::instrument(skip(self))]
     pub fn unmanage_focused_window(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = window_system().foreground_window()?;
         let event = WindowManagerEvent::Unmanage(Window::from(hwnd));
         Ok(winevent_listener::event_tx().send(event)?)
     }
 
+    /// Whether the monitor at this index has been configured to not be managed at all
+    pub fn is_unmanaged_monitor(&self, idx: usize) -> bool {
+        self.monitors().get(idx).is_some_and(Monitor::unmanaged)
+    }
+
+    /// Whether the centre of this window lies on a monitor which is not being managed
+    pub fn window_is_on_unmanaged_monitor(&self, window: Window) -> bool {
+        if !self.monitors().iter().any(Monitor::unmanaged) || !window.is_window() {
+            return false;
+        }
+
+        let Ok(rect) = window_system().window_rect(window.hwnd) else {
+            return false;
+        };
+
+        let centre = (rect.left + rect.right / 2, rect.top + rect.bottom / 2);
+
+        self.monitors()
+            .iter()
+            .find(|monitor| monitor.size().contains_point(centre))
+            .is_some_and(Monitor::unmanaged)
+    }
+
+    /// Stops tracking a window which has been moved onto a monitor which is not being managed,
+    /// leaving it wherever it currently is
+    pub fn release_window_on_unmanaged_monitor(&mut self, hwnd: isize) -> Result<()> {
+        let offset = self.work_area_offset;
+
+        for monitor in self.monitors_mut() {
+            let Some(workspace_idx) = monitor
+                .workspaces()
+                .iter()
+                .position(|workspace| workspace.contains_window(hwnd))
+            else {
+                continue;
+            };
+
+            if let Some(workspace) = monitor.workspaces_mut().get_mut(workspace_idx) {
+                workspace.remove_window(hwnd)?;
+            }
+
+            if workspace_idx == monitor.focused_workspace_idx() {
+                monitor.update_focused_workspace(offset)?;
+            }
+
+            break;
+        }
+
+        Ok(())
+    }
+
+    /// Moves focus away from an unmanaged monitor onto the first monitor which is managed
+    pub fn ensure_focused_monitor_is_managed(&mut self) -> Result<()> {
+        if !self.is_unmanaged_monitor(self.focused_monitor_idx()) {
+            return Ok(());
+        }
+
+        if let Some(idx) = self.first_managed_monitor_idx() {
+            self.focus_monitor(idx)?;
+        }
+
+        Ok(())
+    }
+
+    /// The index of the first monitor which is being managed, if there is one
+    pub fn first_managed_monitor_idx(&self) -> Option<usize> {
+        self.monitors().iter().position(|m| !m.unmanaged())
+    }
+
+    /// The index of the next or previous monitor which is being managed, relative to the
+    /// focused monitor
+    pub fn cycle_monitor_idx(&self, direction: CycleDirection) -> Result<usize> {
+        let count = NonZeroUsize::new(self.monitors().len())
+            .ok_or_else(|| anyhow!("there must be at least one monitor"))?;
+
+        let mut idx = self.focused_monitor_idx();
+        for _ in 0..count.get() {
+            idx = direction.next_idx(idx, count);
+            if !self.is_unmanaged_monitor(idx) {
+                return Ok(idx);
+            }
+        }
+
+        bail!("there is no other managed monitor")
+    }
+
+    /// The focus follows mouse implementation which should currently be active, taking into
+    /// account any override set on the focused workspace
+    pub fn effective_focus_follows_mouse(&self) -> Option<FocusFollowsMouseImplementation> {
+        let workspace_override = self
+            .focused_workspace()
+            .ok()
+            .and_then(Workspace::focus_follows_mouse);
+
+        match workspace_override {
+            None => self.focus_follows_mouse,
+            Some(false) => None,
+            Some(true) => self
+                .focus_follows_mouse
+                .or(Some(FocusFollowsMouseImplementation::Komorebi)),
+        }
+    }
+
+    /// Enables or disables the Windows focus follows mouse implementation to match the override
+    /// on the focused workspace, if it is the globally selected implementation
+    pub fn apply_focus_follows_mouse_override(&self) -> Result<()> {
+        if !matches!(
+            self.focus_follows_mouse,
+            Some(FocusFollowsMouseImplementation::Windows)
+        ) {
+            return Ok(());
+        }
+
+        let should_enable = matches!(
+            self.effective_focus_follows_mouse(),
+            Some(FocusFollowsMouseImplementation::Windows)
+        );
+
+        if WindowsApi::focus_follows_mouse()? != should_enable {
+            if should_enable {
+                WindowsApi::enable_focus_follows_mouse()?;
+            } else {
+                WindowsApi::disable_focus_follows_mouse()?;
+            }
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
-    pub fn raise_window_at_cursor_pos(&mut self) -> Result<()> {
+    pub fn raise_window_at_cursor_pos(
+        &mut self,
+        ignored: &mut FocusFollowsMouseIgnoreCache,
+    ) -> Result<()> {
+        if let Some(hwnd) = self.window_to_raise_at_cursor_pos(ignored)? {
+            self.raise_window(hwnd)?;
+        }
+
+        Ok(())
+    }
+
+    /// Queue a raise of a window found by [`Self::window_to_raise_at_cursor_pos`]
+    pub fn raise_window(&mut self, hwnd: isize) -> Result<()> {
+        let event = WindowManagerEvent::Raise(Window::from(hwnd));
+        self.has_pending_raise_op = true;
+        winevent_listener::event_tx().send(event)?;
+
+        Ok(())
+    }
+
+    /// The managed window under the cursor which focus follows mouse should raise, if any
+    pub fn window_to_raise_at_cursor_pos(
+        &self,
+        ignored: &mut FocusFollowsMouseIgnoreCache,
+    ) -> Result<Option<isize>> {
+        // Focus follows mouse would fight with the cursor capture of fullscreen applications
+        if let Some(idx) = self.monitor_idx_from_current_pos() {
+            if self.monitors().get(idx).is_some_and(Monitor::is_paused) {
+                return Ok(None);
+            }
+        }
+
         let mut hwnd = None;
 
         let workspace = self.focused_workspace()?;
@@ -964,10 +1556,10 @@ impl WindowManager {
             }
         }
 
+        let cursor_pos_hwnd = WindowsApi::window_at_cursor_pos()?;
+
         // finally try matching the other way using a hwnd returned from the cursor pos
         if hwnd.is_none() {
-            let cursor_pos_hwnd = WindowsApi::window_at_cursor_pos()?;
-
             for monitor in self.monitors() {
                 for ws in monitor.workspaces() {
                     if ws.container_for_window(cursor_pos_hwnd).is_some() {
@@ -980,25 +1572,28 @@ impl WindowManager {
         if let Some(hwnd) = hwnd {
             if self.has_pending_raise_op
                     || self.focused_window()?.hwnd == hwnd
+                    // Raising a window which has stopped responding would leave focus stuck on it
+                    || Window::from(hwnd).is_hung()
                     // Sometimes we need this check, because the focus may have been given by a click
                     // to a non-window such as the taskbar or system tray, and komorebi doesn't know that
                     // the focused window of the workspace is not actually focused by the OS at that point
-                    || WindowsApi::foreground_window()? == hwnd
+                    || window_system().foreground_window()? == hwnd
+                    // Neither raise an ignored window nor raise a window from under an ignored
+                    // window, such as a popup or an overlay which sits above the layout
+                    || ignored.any_ignored(hwnd, cursor_pos_hwnd)
             {
-                return Ok(());
+                return Ok(None);
             }
 
-            let event = WindowManagerEvent::Raise(Window::from(hwnd));
-            self.has_pending_raise_op = true;
-            winevent_listener::event_tx().send(event)?;
+            Ok(Some(hwnd))
         } else {
             tracing::debug!(
                 "not raising unknown window: {}",
-                Window::from(WindowsApi::window_at_cursor_pos()?)
+                Window::from(cursor_pos_hwnd)
             );
-        }
 
-        Ok(())
+            Ok(None)
+        }
     }
 
     #[tracing::instrument(skip(self))]
@@ -1072,9 +1667,10 @@ impl WindowManager {
                     .workspaces_mut()
                     .get_mut(origin_workspace_idx)
                     .ok_or_else(|| anyhow!("there is no workspace for this monitor"))?;
+                let hiding_behaviour = origin_workspace.hiding_behaviour();
                 let mut uncloack_amount = 0;
                 for container in origin_workspace.containers_mut() {
-                    container.restore();
+                    container.restore_with_behaviour(hiding_behaviour);
                     uncloack_amount += 1;
                 }
                 origin_workspace.reintegrate_monocle_container()?;
@@ -1248,9 +1844,14 @@ impl WindowManager {
                 let desktop_window = Window::from(WindowsApi::desktop_window()?);
 
                 let rect = self.focused_monitor_size()?;
-                WindowsApi::center_cursor_in_rect(&rect)?;
+                if WindowsApi::should_follow_focus(
+                    window_system().foreground_window().ok(),
+                    window_system().monitor_from_rect(&rect),
+                ) {
+                    window_system().center_cursor_in_rect(&rect)?;
+                }
 
-                match WindowsApi::raise_and_focus_window(desktop_window.hwnd) {
+                match window_system().raise_and_focus_window(desktop_window.hwnd) {
                     Ok(()) => {}
                     Err(error) => {
                         tracing::warn!("{} {}:{}", error, file!(), line!());
@@ -1261,7 +1862,7 @@ impl WindowManager {
             if self.focused_workspace()?.is_empty() {
                 let desktop_window = Window::from(WindowsApi::desktop_window()?);
 
-                match WindowsApi::raise_and_focus_window(desktop_window.hwnd) {
+                match window_system().raise_and_focus_window(desktop_window.hwnd) {
                     Ok(()) => {}
                     Err(error) => {
                         tracing::warn!("{} {}:{}", error, file!(), line!());
@@ -1290,8 +1891,9 @@ impl WindowManager {
 
             // This is to correctly restore and focus when switching to a workspace which
             // contains a managed maximized window
+            let hiding_behaviour = self.focused_workspace()?.hiding_behaviour();
             if let Some(window) = self.focused_workspace()?.maximized_window() {
-                window.restore();
+                window.restore_with_behaviour(hiding_behaviour);
                 if trigger_focus {
                     window.focus(self.mouse_follows_focus)?;
                 }
@@ -1301,6 +1903,31 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Resizes the focused window by a percentage of the focused monitor's work area along the
+    /// axis of the given direction, which is converted to a pixel delta before being applied
+    #[tracing::instrument(skip(self))]
+    pub fn resize_window_percent(
+        &mut self,
+        direction: OperationDirection,
+        sizing: Sizing,
+        percent: f32,
+    ) -> Result<()> {
+        if percent.is_nan() || percent <= 0.0 || percent > 100.0 {
+            bail!("resize percentages must be greater than 0 and no greater than 100");
+        }
+
+        let work_area = self.focused_monitor_work_area()?;
+        let extent = match direction {
+            OperationDirection::Left | OperationDirection::Right => work_area.right,
+            OperationDirection::Up | OperationDirection::Down => work_area.bottom,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+        let delta = (extent as f32 * percent / 100.0).round() as i32;
+
+        self.resize_window(direction, sizing, delta.max(1), true)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn resize_window(
         &mut self,
@@ -1338,6 +1965,9 @@ impl WindowManager {
                         workspace.container_padding(),
                         workspace.layout_flip(),
                         &[],
+                        focused_idx,
+                        workspace.latest_layout(),
+                        workspace.scrolling_options(),
                     );
 
                     let mut direction = direction;
@@ -1392,32 +2022,196 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Sets the primary split of the focused workspace to the split ratio preset which follows
+    /// the one closest to the current split along the given axis
     #[tracing::instrument(skip(self))]
-    pub fn stop(&mut self, ignore_restore: bool) -> Result<()> {
-        tracing::info!(
-            "received stop command, restoring all hidden windows and terminating process"
-        );
+    pub fn cycle_split_ratio(&mut self, axis: Axis) -> Result<()> {
+        let presets = SPLIT_RATIO_PRESETS.lock().clone();
+        let work_area = self.focused_monitor_work_area()?;
+        let scale_factor = self
+            .focused_monitor()
+            .ok_or_else(|| anyhow!("there is no monitor"))?
+            .scale_factor();
+        let workspace = self.focused_workspace_mut()?;
 
-        let state = &State::from(&*self);
-        std::fs::write(
-            temp_dir().join("komorebi.state.json"),
-            serde_json::to_string_pretty(&state)?,
-        )?;
+        let next_preset = |current: f32| {
+            presets
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| (*a - current).abs().total_cmp(&(*b - current).abs()))
+                .and_then(|(idx, _)| presets.get((idx + 1) % presets.len()))
+                .copied()
+        };
 
-        ANIMATION_ENABLED_PER_ANIMATION.lock().clear();
-        ANIMATION_ENABLED_GLOBAL.store(false, Ordering::SeqCst);
-        self.restore_all_windows(ignore_restore)?;
-        AnimationEngine::wait_for_all_animations();
+        let default_layout = match workspace.layout() {
+            Layout::Default(layout) => Option::from(*layout),
+            Layout::Custom(_) => None,
+        };
 
-        if WindowsApi::focus_follows_mouse()? {
-            WindowsApi::disable_focus_follows_mouse()?;
-        }
+        match default_layout {
+            Some(layout) => {
+                let Some(len) = NonZeroUsize::new(workspace.containers().len()) else {
+                    return Ok(());
+                };
 
-        let sockets = SUBSCRIPTION_SOCKETS.lock();
-        for path in (*sockets).values() {
-            if let Ok(stream) = UnixStream::connect(path) {
-                stream.shutdown(Shutdown::Both)?;
-            }
+                if len.get() < 2 {
+                    return Ok(());
+                }
+
+                let unaltered = layout.calculate(
+                    &work_area,
+                    len,
+                    workspace.container_padding(),
+                    workspace.layout_flip(),
+                    &[],
+                    workspace.focused_container_idx(),
+                    workspace.latest_layout(),
+                    workspace.scrolling_options(),
+                );
+
+                let primary = *unaltered
+                    .first()
+                    .ok_or_else(|| anyhow!("there is no primary container"))?;
+                let current = workspace
+                    .latest_layout()
+                    .first()
+                    .copied()
+                    .unwrap_or(primary);
+                let mut resize = workspace.resize_dimensions().first().copied().flatten();
+
+                let axes = match axis {
+                    Axis::Horizontal => vec![Axis::Horizontal],
+                    Axis::Vertical => vec![Axis::Vertical],
+                    Axis::HorizontalAndVertical => vec![Axis::Horizontal, Axis::Vertical],
+                };
+
+                for axis in axes {
+                    let (size, primary_size, current_size, edges) = match axis {
+                        Axis::Vertical => (
+                            work_area.bottom,
+                            primary.bottom,
+                            current.bottom,
+                            [OperationDirection::Down, OperationDirection::Up],
+                        ),
+                        _ => (
+                            work_area.right,
+                            primary.right,
+                            current.right,
+                            [OperationDirection::Right, OperationDirection::Left],
+                        ),
+                    };
+
+                    // The primary split is the edge of the primary container which borders
+                    // another container
+                    let Some(edge) = edges.into_iter().find(|edge| {
+                        edge.destination(
+                            workspace.layout().as_boxed_direction().as_ref(),
+                            workspace.layout_flip(),
+                            0,
+                            len,
+                        )
+                        .is_some()
+                    }) else {
+                        continue;
+                    };
+
+                    #[allow(clippy::cast_precision_loss)]
+                    let Some(ratio) = next_preset(current_size as f32 / size as f32) else {
+                        continue;
+                    };
+
+                    // Resize adjustments are made on the unflipped layout, as in resize_window
+                    let edge = match (workspace.layout_flip(), axis) {
+                        (Some(Axis::HorizontalAndVertical), _)
+                        | (Some(Axis::Horizontal), Axis::Horizontal)
+                        | (Some(Axis::Vertical), Axis::Vertical) => edge.opposite(),
+                        _ => edge,
+                    };
+
+                    // Ratios are set absolutely, so any earlier adjustment along this axis is
+                    // discarded before the new one is calculated from the unaltered layout
+                    if let Some(resize) = &mut resize {
+                        match axis {
+                            Axis::Vertical => {
+                                resize.top = 0;
+                                resize.bottom = 0;
+                            }
+                            _ => {
+                                resize.left = 0;
+                                resize.right = 0;
+                            }
+                        }
+                    }
+
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+                    let delta = (ratio * size as f32).round() as i32 - primary_size;
+                    let sizing = if delta >= 0 {
+                        Sizing::Increase
+                    } else {
+                        Sizing::Decrease
+                    };
+
+                    resize = layout.resize(&primary, &resize, edge, sizing, delta.abs());
+                }
+
+                if let Some(primary_resize) = workspace.resize_dimensions_mut().first_mut() {
+                    *primary_resize = resize;
+                }
+            }
+            None => {
+                let Layout::Custom(ref mut custom) = workspace.layout_mut() else {
+                    return Ok(());
+                };
+
+                if matches!(axis, Axis::Vertical) {
+                    tracing::warn!("custom layouts can only cycle the split ratio horizontally");
+                    return Ok(());
+                }
+
+                #[allow(clippy::cast_precision_loss)]
+                let current = custom
+                    .primary_width_percentage_of(work_area.right, scale_factor)
+                    .unwrap_or(100.0 / (custom.len() as f32));
+
+                if let Some(ratio) = next_preset(current / 100.0) {
+                    custom.set_primary_width_percentage_of(
+                        ratio * 100.0,
+                        work_area.right,
+                        scale_factor,
+                    );
+                }
+            }
+        }
+
+        self.update_focused_workspace(false, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn stop(&mut self, ignore_restore: bool) -> Result<()> {
+        tracing::info!(
+            "received stop command, restoring all hidden windows and terminating process"
+        );
+
+        let state = &DumpedState::from(&*self);
+        std::fs::write(
+            temp_dir().join("komorebi.state.json"),
+            serde_json::to_string_pretty(&state)?,
+        )?;
+
+        ANIMATION_ENABLED_PER_ANIMATION.lock().clear();
+        ANIMATION_ENABLED_GLOBAL.store(false, Ordering::SeqCst);
+        self.restore_all_windows(ignore_restore)?;
+        AnimationEngine::wait_for_all_animations();
+
+        if WindowsApi::focus_follows_mouse()? {
+            WindowsApi::disable_focus_follows_mouse()?;
+        }
+
+        let sockets = SUBSCRIPTION_SOCKETS.lock();
+        for path in (*sockets).values() {
+            if let Ok(stream) = UnixStream::connect(path) {
+                stream.shutdown(Shutdown::Both)?;
+            }
         }
 
         let socket = DATA_DIR.join("komorebi.sock");
@@ -1445,7 +2239,17 @@ impl WindowManager {
                     }
                 }
 
+                let hiding_behaviour = workspace.hiding_behaviour();
+
                 for containers in workspace.containers_mut() {
+                    // The terminal a window was launched from is hidden for as long as the
+                    // window has swallowed its tile
+                    if let Some(swallowed) = containers.swallowed_window() {
+                        if !ignore_restore {
+                            swallowed.restore_with_behaviour(hiding_behaviour);
+                        }
+                    }
+
                     for window in containers.windows_mut() {
                         let should_remove_titlebar_for_window = should_act(
                             &window.title().unwrap_or_default(),
@@ -1470,13 +2274,20 @@ impl WindowManager {
                         }
 
                         if !ignore_restore {
-                            window.restore();
+                            window.restore_with_behaviour(hiding_behaviour);
                         }
                     }
                 }
             }
         }
 
+        // Scratchpad windows are not on any workspace while they are hidden
+        if !ignore_restore {
+            for (hwnd, hiding_behaviour) in HIDDEN_SCRATCHPAD_HWNDS.lock().drain() {
+                Window::from(hwnd).restore_with_behaviour(hiding_behaviour);
+            }
+        }
+
         Ok(())
     }
 
@@ -1510,7 +2321,7 @@ impl WindowManager {
             OperationBehaviour::NoOp
         ) {
             let workspace = self.focused_workspace()?;
-            let focused_hwnd = WindowsApi::foreground_window()?;
+            let focused_hwnd = window_system().foreground_window()?;
             if !workspace.contains_managed_window(focused_hwnd) {
                 bail!("ignoring commands while active window is not managed by komorebi");
             }
@@ -1596,6 +2407,10 @@ impl WindowManager {
     pub fn swap_focused_monitor(&mut self, idx: usize) -> Result<()> {
         tracing::info!("swapping focused monitor");
 
+        if self.is_unmanaged_monitor(idx) {
+            bail!("cannot swap with an unmanaged monitor");
+        }
+
         let focused_monitor_idx = self.focused_monitor_idx();
         let mouse_follows_focus = self.mouse_follows_focus;
 
@@ -1614,8 +2429,15 @@ impl WindowManager {
     ) -> Result<()> {
         self.handle_unmanaged_window_behaviour()?;
 
+        if self.is_unmanaged_monitor(monitor_idx) {
+            bail!("cannot move a container to an unmanaged monitor");
+        }
+
         tracing::info!("moving container");
 
+        self.record_manually_moved_container();
+        self.record_container_origin(monitor_idx, workspace_idx);
+
         let focused_monitor_idx = self.focused_monitor_idx();
 
         if focused_monitor_idx == monitor_idx {
@@ -1641,7 +2463,7 @@ impl WindowManager {
             bail!("cannot move native maximized window to another monitor or workspace");
         }
 
-        let foreground_hwnd = WindowsApi::foreground_window()?;
+        let foreground_hwnd = window_system().foreground_window()?;
         let floating_window_index = workspace
             .floating_windows()
             .iter()
@@ -1687,12 +2509,28 @@ impl WindowManager {
                 .map(|w| w.hwnd)
                 .collect::<Vec<_>>();
 
+            // Stacks should keep the same focused window after they have been moved
+            let container_id = container.id().clone();
+            let focused_window_idx = container.focused_window_idx();
+
             if let Some(direction) = move_direction {
                 target_monitor.add_container_with_direction(container, workspace_idx, direction)?;
             } else {
                 target_monitor.add_container(container, workspace_idx)?;
             }
 
+            if let Some(container) = target_monitor
+                .focused_workspace_mut()
+                .and_then(|workspace| {
+                    workspace
+                        .containers_mut()
+                        .iter_mut()
+                        .find(|container| *container.id() == container_id)
+                })
+            {
+                container.focus_window(focused_window_idx);
+            }
+
             if let Some(workspace) = target_monitor.focused_workspace() {
                 if !*workspace.tile() {
                     for hwnd in container_hwnds {
@@ -1735,6 +2573,9 @@ impl WindowManager {
 
         tracing::info!("moving container");
 
+        self.record_manually_moved_container();
+        self.record_container_origin(self.focused_monitor_idx(), Option::from(idx));
+
         let mouse_follows_focus = self.mouse_follows_focus;
         let monitor = self
             .focused_monitor_mut()
@@ -1748,6 +2589,195 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Creates a new workspace on the focused monitor and moves the focused container, monocle
+    /// container or floating window there, following focus with a single layout update
+    #[tracing::instrument(skip(self))]
+    pub fn move_container_to_new_workspace(&mut self) -> Result<()> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        tracing::info!("moving container to new workspace");
+
+        self.record_manually_moved_container();
+        self.record_container_origin(self.focused_monitor_idx(), None);
+
+        let foreground_hwnd = window_system().foreground_window()?;
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let monitor = self
+            .focused_monitor_mut()
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let new_idx = monitor.new_workspace_idx();
+        let workspace = monitor
+            .focused_workspace_mut()
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        let is_floating = workspace
+            .floating_windows()
+            .iter()
+            .any(|w| w.hwnd == foreground_hwnd);
+
+        // The monocle container lives outside of the layout, so it is put back in its place
+        // before being moved like any other container
+        if !is_floating && workspace.monocle_container().is_some() {
+            workspace.reintegrate_monocle_container()?;
+        }
+
+        monitor.move_container_to_workspace(new_idx, true, None)?;
+        monitor.load_focused_workspace(mouse_follows_focus)?;
+
+        self.update_focused_workspace(mouse_follows_focus, true)
+    }
+
+    /// Gives the parent window swallowed by the focused container a tile of its own next to it
+    #[tracing::instrument(skip(self))]
+    pub fn unswallow_focused_container(&mut self) -> Result<()> {
+        tracing::info!("unswallowing window");
+
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let workspace = self.focused_workspace_mut()?;
+        let hiding_behaviour = workspace.hiding_behaviour();
+        let focused_idx = workspace.focused_container_idx();
+
+        let window = workspace
+            .focused_container_mut()
+            .ok_or_else(|| anyhow!("there is no container"))?
+            .take_swallowed_window()
+            .ok_or_else(|| anyhow!("the focused container has not swallowed a window"))?;
+
+        if !window.is_window() {
+            return Ok(());
+        }
+
+        let mut container = Container::default();
+        container.add_window(window, hiding_behaviour);
+        workspace.insert_container_at_idx(focused_idx + 1, container);
+        window.restore_with_behaviour(hiding_behaviour);
+
+        self.update_focused_workspace(mouse_follows_focus, true)
+    }
+
+    /// Records the workspace of the focused container as the one it can be returned to, unless it
+    /// is being moved to the workspace it is already on
+    ///
+    /// A target workspace of None on the focused monitor is taken to be a new workspace.
+    fn record_container_origin(
+        &mut self,
+        target_monitor_idx: usize,
+        target_workspace_idx: Option<usize>,
+    ) {
+        let monitor_idx = self.focused_monitor_idx();
+        let Some(monitor) = self.focused_monitor_mut() else {
+            return;
+        };
+
+        let workspace_idx = monitor.focused_workspace_idx();
+        if target_monitor_idx == monitor_idx && target_workspace_idx == Some(workspace_idx) {
+            return;
+        }
+
+        let Some(workspace) = monitor.focused_workspace_mut() else {
+            return;
+        };
+
+        // Floating windows are moved on their own and do not carry a container with them
+        let foreground_hwnd = window_system().foreground_window().unwrap_or_default();
+        if workspace
+            .floating_windows()
+            .iter()
+            .any(|window| window.hwnd == foreground_hwnd)
+        {
+            return;
+        }
+
+        let origin = WorkspaceOrigin {
+            monitor_idx,
+            workspace_idx,
+            workspace_name: workspace.explicit_name().cloned(),
+        };
+
+        let container = if workspace.monocle_container().is_some() {
+            workspace.monocle_container_mut().as_mut()
+        } else {
+            workspace.focused_container_mut()
+        };
+
+        if let Some(container) = container {
+            container.set_previous_workspace(Option::from(origin));
+        }
+    }
+
+    /// Moves the focused container back to the workspace it was on before it was last explicitly
+    /// moved to another workspace
+    #[tracing::instrument(skip(self))]
+    pub fn return_container_to_previous_workspace(&mut self, follow: bool) -> Result<()> {
+        tracing::info!("returning container to previous workspace");
+
+        let workspace = self.focused_workspace()?;
+        let origin = workspace
+            .monocle_container()
+            .as_ref()
+            .or_else(|| workspace.focused_container())
+            .ok_or_else(|| anyhow!("there is no container"))?
+            .previous_workspace()
+            .clone()
+            .ok_or_else(|| {
+                anyhow!("the focused container has not been moved from another workspace")
+            })?;
+
+        // The name is preferred so that the origin survives workspaces being reordered, and the
+        // indices are used when the workspace was not named or has since been renamed
+        let target = origin
+            .workspace_name
+            .as_deref()
+            .and_then(|name| self.monitor_workspace_index_by_name(name))
+            .or_else(|| {
+                self.monitors()
+                    .get(origin.monitor_idx)
+                    .and_then(|monitor| monitor.workspaces().get(origin.workspace_idx))
+                    .map(|_| (origin.monitor_idx, origin.workspace_idx))
+            });
+
+        let Some((monitor_idx, workspace_idx)) = target else {
+            let workspace = self.focused_workspace_mut()?;
+            let container = if workspace.monocle_container().is_some() {
+                workspace.monocle_container_mut().as_mut()
+            } else {
+                workspace.focused_container_mut()
+            };
+
+            if let Some(container) = container {
+                container.set_previous_workspace(None);
+            }
+
+            bail!(
+                "the workspace this container was moved from no longer exists (monitor {}, workspace {})",
+                origin.monitor_idx,
+                origin.workspace_idx
+            );
+        };
+
+        let direction = self.direction_from_monitor_idx(monitor_idx);
+        self.move_container_to_monitor(monitor_idx, Option::from(workspace_idx), follow, direction)
+    }
+
+    /// Records the windows of the focused container as moved by the user, so that workspace rules
+    /// can leave them where they have been put
+    fn record_manually_moved_container(&self) {
+        let Ok(workspace) = self.focused_workspace() else {
+            return;
+        };
+
+        let container = workspace
+            .monocle_container()
+            .as_ref()
+            .or_else(|| workspace.focused_container());
+
+        if let Some(container) = container {
+            let mut manually_moved_window_handles = self.manually_moved_window_handles.lock();
+            manually_moved_window_handles.extend(container.windows().iter().map(|w| w.hwnd));
+        }
+    }
+
     pub fn remove_focused_workspace(&mut self) -> Option<Workspace> {
         let focused_monitor: &mut Monitor = self.focused_monitor_mut()?;
         let focused_workspace_idx = focused_monitor.focused_workspace_idx();
@@ -1757,6 +2787,11 @@ impl WindowManager {
     #[tracing::instrument(skip(self))]
     pub fn move_workspace_to_monitor(&mut self, idx: usize) -> Result<()> {
         tracing::info!("moving workspace");
+
+        if self.is_unmanaged_monitor(idx) {
+            bail!("cannot move a workspace to an unmanaged monitor");
+        }
+
         let mouse_follows_focus = self.mouse_follows_focus;
         let workspace = self
             .remove_focused_workspace()
@@ -1786,12 +2821,48 @@ impl WindowManager {
 
         tracing::info!("focusing container");
 
-        let new_idx =
-            if workspace.maximized_window().is_some() || workspace.monocle_container().is_some() {
-                None
-            } else {
-                workspace.new_idx_for_direction(direction)
-            };
+        let monocle_or_max =
+            workspace.maximized_window().is_some() || workspace.monocle_container().is_some();
+
+        let cross_layer_focus = CROSS_LAYER_FOCUS.load(Ordering::SeqCst) && !monocle_or_max;
+
+        if cross_layer_focus {
+            let foreground_window = window_system().foreground_window().unwrap_or_default();
+            let is_floating = workspace
+                .floating_windows()
+                .iter()
+                .any(|window| window.hwnd == foreground_window);
+
+            if is_floating
+                && self.focus_from_floating_window_in_direction(foreground_window, direction)?
+            {
+                return Ok(());
+            }
+        }
+
+        let workspace = self.focused_workspace()?;
+        let new_idx = if monocle_or_max {
+            None
+        } else {
+            workspace.new_idx_for_direction(direction)
+        };
+
+        // if there is no tiled container in that direction, look for a floating window before
+        // trying to cross workspace or monitor boundaries
+        if new_idx.is_none() && cross_layer_focus {
+            if let Some(origin) = workspace
+                .focused_container()
+                .and_then(Container::focused_window)
+                .and_then(|window| window_system().window_rect(window.hwnd).ok())
+            {
+                if let Some(window) = workspace.floating_window_in_direction(&origin, direction) {
+                    tracing::info!("focusing floating window");
+                    window.focus(self.mouse_follows_focus)?;
+                    border_manager::send_notification(Some(window.hwnd));
+                    return Ok(());
+                }
+            }
+        }
 
         let mut cross_monitor_monocle_or_max = false;
 
@@ -1883,9 +2954,9 @@ impl WindowManager {
                     } else if let Some(monocle) = focused_workspace.monocle_container() {
                         if let Some(window) = monocle.focused_window() {
                             window.focus(mouse_follows_focus)?;
-                            WindowsApi::center_cursor_in_rect(&WindowsApi::window_rect(
-                                window.hwnd,
-                            )?)?;
+                            window_system().center_cursor_in_rect(
+                                &window_system().window_rect(window.hwnd)?,
+                            )?;
 
                             cross_monitor_monocle_or_max = true;
                         }
@@ -1920,7 +2991,38 @@ impl WindowManager {
             }
             Some(idx) => {
                 let workspace = self.focused_workspace_mut()?;
-                workspace.focus_container(idx);
+                let origin_idx = workspace.focused_container_idx();
+                let mut visited = HashSet::from([origin_idx]);
+                let mut candidate = Option::from(idx);
+
+                // Windows which are not responding are skipped over by looking for the next
+                // container in the same direction from their position
+                let target = loop {
+                    match candidate {
+                        Some(idx) if visited.insert(idx) => {
+                            let is_hung = workspace
+                                .containers()
+                                .get(idx)
+                                .and_then(Container::focused_window)
+                                .is_some_and(|window| window.is_hung());
+
+                            if !is_hung {
+                                break Option::from(idx);
+                            }
+
+                            workspace.focus_container(idx);
+                            candidate = workspace.new_idx_for_direction(direction);
+                        }
+                        _ => break None,
+                    }
+                };
+
+                if let Some(idx) = target {
+                    workspace.focus_container(idx);
+                } else {
+                    workspace.focus_container(origin_idx);
+                    bail!("the windows in this direction are not responding");
+                }
             }
         }
 
@@ -1933,6 +3035,75 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Focus the floating window or tiled container nearest to the given floating window in the
+    /// given direction, returning false if there is nothing to focus on this workspace
+    fn focus_from_floating_window_in_direction(
+        &mut self,
+        hwnd: isize,
+        direction: OperationDirection,
+    ) -> Result<bool> {
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let origin = window_system().window_rect(hwnd)?;
+        let workspace = self.focused_workspace_mut()?;
+
+        if let Some(window) = workspace.floating_window_in_direction(&origin, direction) {
+            tracing::info!("focusing floating window");
+            window.focus(mouse_follows_focus)?;
+            border_manager::send_notification(Some(window.hwnd));
+            return Ok(true);
+        }
+
+        if let Some(idx) = workspace.container_idx_in_direction_from_rect(&origin, direction) {
+            tracing::info!("focusing tiled container");
+            workspace.focus_container(idx);
+            if let Some(window) = workspace
+                .focused_container()
+                .and_then(Container::focused_window)
+            {
+                window.focus(mouse_follows_focus)?;
+                border_manager::send_notification(Some(window.hwnd));
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn pin_focused_container_to_column(&mut self, column: usize) -> Result<()> {
+        tracing::info!("pinning container to column");
+
+        let workspace = self.focused_workspace_mut()?;
+        let id = workspace
+            .focused_container()
+            .ok_or_else(|| anyhow!("there is no container"))?
+            .id()
+            .clone();
+
+        workspace.pin_container_to_column(&id, column);
+
+        self.update_focused_workspace(self.mouse_follows_focus, true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn unpin_focused_container(&mut self) -> Result<()> {
+        tracing::info!("unpinning container");
+
+        let workspace = self.focused_workspace_mut()?;
+        let id = workspace
+            .focused_container()
+            .ok_or_else(|| anyhow!("there is no container"))?
+            .id()
+            .clone();
+
+        if !workspace.unpin_container(&id) {
+            bail!("the focused container is not pinned to a column");
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn move_container_in_direction(&mut self, direction: OperationDirection) -> Result<()> {
         self.handle_unmanaged_window_behaviour()?;
@@ -2093,7 +3264,7 @@ impl WindowManager {
                     .ok_or_else(|| anyhow!("there is no monitor at this index"))?
                     .id();
 
-                if !WindowsApi::monitors_have_same_dpi(a, b)? {
+                if !window_system().monitors_have_same_dpi(a, b)? {
                     self.update_focused_workspace(self.mouse_follows_focus, true)?;
                 }
             }
@@ -2147,12 +3318,58 @@ impl WindowManager {
     }
 
     #[tracing::instrument(skip(self))]
-    pub fn move_container_in_cycle_direction(&mut self, direction: CycleDirection) -> Result<()> {
+    pub fn focus_last_container(&mut self) -> Result<()> {
         self.handle_unmanaged_window_behaviour()?;
 
-        let workspace = self.focused_workspace_mut()?;
-        if workspace.is_focused_window_monocle_or_maximized()? {
-            bail!("ignoring command while active window is in monocle mode or maximized");
+        tracing::info!("focusing last focused container");
+        let mut maximize_next = false;
+        let mut monocle_next = false;
+
+        if self.focused_workspace_mut()?.maximized_window().is_some() {
+            maximize_next = true;
+            self.unmaximize_window()?;
+        }
+
+        if self.focused_workspace_mut()?.monocle_container().is_some() {
+            monocle_next = true;
+            self.monocle_off()?;
+        }
+
+        let workspace = self.focused_workspace_mut()?;
+
+        let new_idx = workspace
+            .last_focused_container_idx()
+            .ok_or_else(|| anyhow!("there is no previously focused container"))?;
+
+        workspace.focus_container(new_idx);
+
+        if maximize_next {
+            self.toggle_maximize()?;
+        } else if monocle_next {
+            self.toggle_monocle()?;
+        } else {
+            self.focused_window_mut()?.focus(self.mouse_follows_focus)?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the focused container of every workspace in its focus history
+    pub fn update_container_focus_history(&mut self) {
+        for monitor in self.monitors_mut() {
+            for workspace in monitor.workspaces_mut() {
+                workspace.update_container_focus_history();
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn move_container_in_cycle_direction(&mut self, direction: CycleDirection) -> Result<()> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        let workspace = self.focused_workspace_mut()?;
+        if workspace.is_focused_window_monocle_or_maximized()? {
+            bail!("ignoring command while active window is in monocle mode or maximized");
         }
 
         tracing::info!("moving container");
@@ -2173,6 +3390,8 @@ impl WindowManager {
 
         tracing::info!("cycling container windows");
 
+        let hiding_behaviour = self.focused_workspace()?.hiding_behaviour();
+
         let container =
             if let Some(container) = self.focused_workspace_mut()?.monocle_container_mut() {
                 container
@@ -2188,10 +3407,20 @@ impl WindowManager {
         }
 
         let current_idx = container.focused_window_idx();
-        let next_idx = direction.next_idx(current_idx, len);
+        let next_idx = container
+            .next_unminimized_idx(
+                current_idx,
+                direction,
+                CYCLE_STACK_WRAP.load(Ordering::SeqCst),
+            )
+            .ok_or_else(|| {
+                anyhow!(
+                    "there are no other unminimized windows in this direction in this container"
+                )
+            })?;
 
         container.focus_window(next_idx);
-        container.load_focused_window();
+        container.load_focused_window_with_behaviour(hiding_behaviour);
 
         self.update_focused_workspace(self.mouse_follows_focus, true)
     }
@@ -2205,6 +3434,8 @@ impl WindowManager {
 
         tracing::info!("cycling container window index");
 
+        let hiding_behaviour = self.focused_workspace()?.hiding_behaviour();
+
         let container =
             if let Some(container) = self.focused_workspace_mut()?.monocle_container_mut() {
                 container
@@ -2224,7 +3455,7 @@ impl WindowManager {
         container.windows_mut().swap(current_idx, next_idx);
 
         container.focus_window(next_idx);
-        container.load_focused_window();
+        container.load_focused_window_with_behaviour(hiding_behaviour);
 
         self.update_focused_workspace(self.mouse_follows_focus, true)
     }
@@ -2235,6 +3466,8 @@ impl WindowManager {
 
         tracing::info!("focusing container window at index {idx}");
 
+        let hiding_behaviour = self.focused_workspace()?.hiding_behaviour();
+
         let container =
             if let Some(container) = self.focused_workspace_mut()?.monocle_container_mut() {
                 container
@@ -2249,16 +3482,59 @@ impl WindowManager {
             bail!("there is only one window in this container");
         }
 
-        if container.windows().get(idx).is_none() {
-            bail!("there is no window in this container at index {idx}");
+        match container.windows().get(idx) {
+            None => bail!("there is no window in this container at index {idx}"),
+            Some(window) if container.is_minimized(window.hwnd) => {
+                bail!("the window in this container at index {idx} is minimized")
+            }
+            Some(_) => {}
         }
 
         container.focus_window(idx);
-        container.load_focused_window();
+        container.load_focused_window_with_behaviour(hiding_behaviour);
 
         self.update_focused_workspace(self.mouse_follows_focus, true)
     }
 
+    /// Reinstates a window which was minimized out of a stack at its original position in the
+    /// container that it was minimized from
+    #[tracing::instrument(skip(self))]
+    pub fn restore_minimized_stack_window(&mut self, hwnd: isize) -> Result<()> {
+        let focused_monitor_idx = self.focused_monitor_idx();
+        let mut should_update = false;
+
+        'monitors: for (monitor_idx, monitor) in self.monitors_mut().iter_mut().enumerate() {
+            let focused_workspace_idx = monitor.focused_workspace_idx();
+
+            for (workspace_idx, workspace) in monitor.workspaces_mut().iter_mut().enumerate() {
+                let hiding_behaviour = workspace.hiding_behaviour();
+                let is_focused_workspace =
+                    monitor_idx == focused_monitor_idx && workspace_idx == focused_workspace_idx;
+
+                if let Some(container) = workspace.container_for_window_mut(hwnd) {
+                    if container.restore_minimized_window(hwnd) {
+                        tracing::info!("restoring minimized window to its position in the stack");
+
+                        // Workspaces which are not visible will load the focused window of each
+                        // container when they are next focused
+                        if is_focused_workspace {
+                            container.load_focused_window_with_behaviour(hiding_behaviour);
+                            should_update = true;
+                        }
+                    }
+
+                    break 'monitors;
+                }
+            }
+        }
+
+        if should_update {
+            self.update_focused_workspace(false, false)?;
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn stack_all(&mut self) -> Result<()> {
         self.handle_unmanaged_window_behaviour()?;
@@ -2303,6 +3579,13 @@ impl WindowManager {
         let initial_focused_container_index = workspace.focused_container_idx();
         let mut focused_container = workspace.focused_container().cloned();
 
+        // Unstacked windows should not be immediately stacked again if they are managed anew
+        if let Some(container) = focused_container.as_ref().filter(|c| c.windows().len() > 1) {
+            AUTO_STACK_EXEMPT_HWNDS
+                .lock()
+                .extend(container.windows().iter().map(|w| w.hwnd));
+        }
+
         while let Some(focused) = &focused_container {
             if focused.windows().len() > 1 {
                 workspace.new_container_for_focused_window()?;
@@ -2372,79 +3655,720 @@ impl WindowManager {
                     workspace.move_window_to_container(adjusted_new_index)?;
                 }
             }
-
-            self.update_focused_workspace(self.mouse_follows_focus, false)?;
+
+            self.update_focused_workspace(self.mouse_follows_focus, false)?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn promote_container_to_front(&mut self) -> Result<()> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        let workspace = self.focused_workspace_mut()?;
+
+        if matches!(workspace.layout(), Layout::Default(DefaultLayout::Grid)) {
+            tracing::debug!("ignoring promote command for grid layout");
+            return Ok(());
+        }
+
+        tracing::info!("promoting container");
+
+        workspace.promote_container()?;
+        self.update_focused_workspace(self.mouse_follows_focus, true)
+    }
+
+    /// Focuses the first window matching `exe` on any monitor or workspace, returning whether
+    /// a matching window was found
+    #[tracing::instrument(skip(self))]
+    pub fn eager_focus(&mut self, exe: &str) -> Result<bool> {
+        let focused_monitor_idx = self.focused_monitor_idx();
+        let focused_workspace_idx = self.focused_workspace_idx()?;
+
+        let mut window_location = None;
+        let mut monitor_workspace_indices = None;
+
+        'search: for (monitor_idx, monitor) in self.monitors().iter().enumerate() {
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                if let Some(location) = workspace.location_from_exe(exe) {
+                    window_location = Some(location);
+                    monitor_workspace_indices = Some((monitor_idx, workspace_idx));
+                    break 'search;
+                }
+            }
+        }
+
+        if let Some((monitor_idx, workspace_idx)) = monitor_workspace_indices {
+            if monitor_idx != focused_monitor_idx {
+                self.focus_monitor(monitor_idx)?;
+            }
+
+            if workspace_idx != focused_workspace_idx {
+                self.focus_workspace(workspace_idx)?;
+            }
+        }
+
+        let Some(location) = window_location else {
+            return Ok(false);
+        };
+
+        match location {
+            WorkspaceWindowLocation::Monocle(window_idx) => {
+                self.focus_container_window(window_idx)?;
+            }
+            WorkspaceWindowLocation::Maximized => {
+                if let Some(window) = self.focused_workspace_mut()?.maximized_window_mut() {
+                    window.focus(self.mouse_follows_focus)?;
+                }
+            }
+            WorkspaceWindowLocation::Container(container_idx, window_idx) => {
+                let focused_container_idx = self.focused_container_idx()?;
+                if container_idx != focused_container_idx {
+                    self.focused_workspace_mut()?.focus_container(container_idx);
+                }
+
+                self.focus_container_window(window_idx)?;
+            }
+            WorkspaceWindowLocation::Floating(window_idx) => {
+                if let Some(window) = self
+                    .focused_workspace_mut()?
+                    .floating_windows_mut()
+                    .get_mut(window_idx)
+                {
+                    window.focus(self.mouse_follows_focus)?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn swap_with_largest_container(&mut self) -> Result<()> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        let workspace = self.focused_workspace_mut()?;
+        if workspace.is_focused_window_monocle_or_maximized()? {
+            bail!("ignoring command while active window is in monocle mode or maximized");
+        }
+
+        let focused_idx = workspace.focused_container_idx();
+        let largest_idx = workspace
+            .largest_container_idx()
+            .ok_or_else(|| anyhow!("there is no layout for this workspace"))?;
+
+        if focused_idx == largest_idx {
+            tracing::debug!("focused container is already the largest container");
+            return Ok(());
+        }
+
+        tracing::info!("swapping container with largest container");
+
+        workspace.swap_containers(focused_idx, largest_idx);
+        self.update_focused_workspace(self.mouse_follows_focus, true)
+    }
+
+    /// The foreground window, as long as it is on the floating layer or not managed by komorebi
+    fn focused_floating_window(&self) -> Result<Window> {
+        let hwnd = window_system().foreground_window()?;
+
+        let is_floating = self
+            .focused_workspace()?
+            .floating_windows()
+            .iter()
+            .any(|window| window.hwnd == hwnd);
+
+        let is_managed = self.monitors().iter().any(|monitor| {
+            monitor
+                .workspaces()
+                .iter()
+                .any(|workspace| workspace.contains_managed_window(hwnd))
+        });
+
+        if !is_floating && is_managed {
+            bail!("the focused window is tiled and must be floated first");
+        }
+
+        Ok(Window::from(hwnd))
+    }
+
+    /// The work area (including offsets) of the monitor that a rect overlaps the most
+    fn work_area_for_rect(&self, rect: &Rect) -> Result<Rect> {
+        let monitor = self
+            .monitors()
+            .iter()
+            .max_by_key(|monitor| monitor.work_area_size().overlap_area(rect))
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let mut work_area = *monitor.work_area_size();
+        if let Some(offset) = monitor.effective_work_area_offset(self.work_area_offset) {
+            work_area.left += offset.left;
+            work_area.top += offset.top;
+            work_area.right -= offset.right;
+            work_area.bottom -= offset.bottom;
+        }
+
+        Ok(work_area)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn move_floating_window(&mut self, x: i32, y: i32) -> Result<()> {
+        let window = self.focused_floating_window()?;
+
+        tracing::info!("moving floating window");
+
+        let mut rect = window_system().window_rect(window.hwnd)?;
+        rect.left += x;
+        rect.top += y;
+
+        window.set_position(&rect, true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn place_floating_window(&mut self, placement: PredefinedPlacement) -> Result<()> {
+        let window = self.focused_floating_window()?;
+
+        tracing::info!("placing floating window");
+
+        let rect = window_system().window_rect(window.hwnd)?;
+        let work_area = self.work_area_for_rect(&rect)?;
+
+        window.set_position(&placement.rect(&work_area, &rect), true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn resize_floating_window_to(&mut self, width: i32, height: i32) -> Result<()> {
+        if width <= 0 || height <= 0 {
+            bail!("the width and height of a window must be greater than zero");
+        }
+
+        let window = self.focused_floating_window()?;
+
+        tracing::info!("resizing floating window");
+
+        let mut rect = window_system().window_rect(window.hwnd)?;
+        let work_area = self.work_area_for_rect(&rect)?;
+
+        rect.right = width.min(work_area.right);
+        rect.bottom = height.min(work_area.bottom);
+
+        // Keep the top left corner where it is unless the new size would push the window off
+        // the work area
+        let right_edge = work_area.left + work_area.right;
+        let bottom_edge = work_area.top + work_area.bottom;
+
+        if rect.left + rect.right > right_edge {
+            rect.left = right_edge - rect.right;
+        }
+
+        if rect.top + rect.bottom > bottom_edge {
+            rect.top = bottom_edge - rect.bottom;
+        }
+
+        window.set_position(&rect, true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn save_floating_layout(&self, path: &PathBuf) -> Result<()> {
+        let mut entries = vec![];
+
+        for monitor in self.monitors() {
+            for workspace in monitor.workspaces() {
+                for window in workspace.floating_windows() {
+                    // Windows which have closed or can't be queried shouldn't stop the others
+                    // from being saved
+                    let (Ok(exe), Ok(title), Ok(rect)) = (
+                        window.exe(),
+                        window.title(),
+                        window_system().window_rect(window.hwnd),
+                    ) else {
+                        tracing::warn!("skipping floating window {}", window.hwnd);
+                        continue;
+                    };
+
+                    entries.push(FloatingLayoutEntry {
+                        matching_rule: MatchingRule::Composite(vec![
+                            IdWithIdentifier {
+                                kind: ApplicationIdentifier::Exe,
+                                id: exe,
+                                matching_strategy: Option::from(MatchingStrategy::Equals),
+                            },
+                            IdWithIdentifier {
+                                kind: ApplicationIdentifier::Title,
+                                id: title,
+                                matching_strategy: Option::from(MatchingStrategy::Equals),
+                            },
+                        ]),
+                        rect,
+                    });
+                }
+            }
+        }
+
+        tracing::info!("saving {} floating window positions", entries.len());
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+
+        serde_json::to_writer_pretty(&file, &entries)?;
+
+        Ok(())
+    }
+
+    fn focused_resize_dimensions_entry(&self) -> Result<ResizeDimensionsEntry> {
+        let monitor = self
+            .focused_monitor()
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+        let workspace_idx = monitor.focused_workspace_idx();
+        let workspace = monitor
+            .focused_workspace()
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        ResizeDimensionsEntry::new(monitor, workspace_idx, workspace)
+            .ok_or_else(|| anyhow!("resize dimensions can only be saved for default layouts"))
+    }
+
+    /// Saves the resize dimensions of the focused workspace to a file, replacing any compatible
+    /// entry which has previously been saved to the same file
+    #[tracing::instrument(skip(self))]
+    pub fn save_resize_dimensions(&self, path: &PathBuf) -> Result<()> {
+        let entry = self.focused_resize_dimensions_entry()?;
+
+        let mut entries: Vec<ResizeDimensionsEntry> = File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(file).ok())
+            .unwrap_or_default();
+
+        entries.retain(|existing| !existing.is_compatible_with(&entry));
+        entries.push(entry);
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+
+        serde_json::to_writer_pretty(&file, &entries)?;
+
+        Ok(())
+    }
+
+    /// Saves the resize dimensions of every workspace with a default layout to a file
+    #[tracing::instrument(skip(self))]
+    pub fn save_all_resize_dimensions(&self, path: &PathBuf) -> Result<()> {
+        let mut entries = vec![];
+
+        for monitor in self.monitors() {
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                if let Some(entry) = ResizeDimensionsEntry::new(monitor, workspace_idx, workspace) {
+                    entries.push(entry);
+                }
+            }
+        }
+
+        tracing::info!("saving resize dimensions for {} workspaces", entries.len());
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+
+        serde_json::to_writer_pretty(&file, &entries)?;
+
+        Ok(())
+    }
+
+    /// Loads the resize dimensions saved for the focused workspace, its current layout and its
+    /// current number of containers from a file
+    #[tracing::instrument(skip(self))]
+    pub fn load_resize_dimensions(&mut self, path: &PathBuf) -> Result<()> {
+        let file = File::open(path).map_err(|_| anyhow!("no file found at {}", path.display()))?;
+        let entries: Vec<ResizeDimensionsEntry> = serde_json::from_reader(file).map_err(|_| {
+            anyhow!(
+                "{} does not contain layout-aware resize dimensions, please save them again",
+                path.display()
+            )
+        })?;
+
+        let current = self.focused_resize_dimensions_entry()?;
+
+        let entry = entries
+            .into_iter()
+            .find(|entry| entry.is_compatible_with(&current))
+            .ok_or_else(|| {
+                anyhow!(
+                    "{} has no resize dimensions for workspace {} on monitor {} with the {} layout and {} containers",
+                    path.display(),
+                    current
+                        .workspace_name
+                        .clone()
+                        .unwrap_or_else(|| current.workspace_idx.to_string()),
+                    current.monitor,
+                    current.layout,
+                    current.container_count,
+                )
+            })?;
+
+        self.focused_workspace_mut()?
+            .set_resize_dimensions(entry.resize_dimensions);
+
+        self.update_focused_workspace(false, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn load_floating_layout(&mut self, path: &PathBuf) -> Result<()> {
+        let file = File::open(path).map_err(|_| anyhow!("no file found at {}", path.display()))?;
+        let entries: Vec<FloatingLayoutEntry> = serde_json::from_reader(file)?;
+
+        let mut matching_rules = entries
+            .iter()
+            .map(|entry| entry.matching_rule.clone())
+            .collect::<Vec<_>>();
+
+        let mut regex_identifiers = REGEX_IDENTIFIERS.lock();
+        // Fills in default matching strategies and compiles any regex identifiers
+        populate_rules(&mut matching_rules, &mut vec![], &mut regex_identifiers)?;
+
+        for monitor in self.monitors() {
+            let focused_workspace_idx = monitor.focused_workspace_idx();
+
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                for window in workspace.floating_windows() {
+                    let (Ok(title), Ok(exe_name), Ok(class), Ok(window_path)) =
+                        (window.title(), window.exe(), window.class(), window.path())
+                    else {
+                        continue;
+                    };
+
+                    for (entry, matching_rule) in entries.iter().zip(&matching_rules) {
+                        if should_act(
+                            &title,
+                            &exe_name,
+                            &class,
+                            &window_path,
+                            std::slice::from_ref(matching_rule),
+                            &regex_identifiers,
+                        )
+                        .is_some()
+                        {
+                            window.set_position(
+                                &entry.rect,
+                                workspace_idx == focused_workspace_idx,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn promote_focus_to_front(&mut self) -> Result<()> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        let workspace = self.focused_workspace_mut()?;
+
+        if matches!(workspace.layout(), Layout::Default(DefaultLayout::Grid)) {
+            tracing::info!("ignoring promote focus command for grid layout");
+            return Ok(());
+        }
+
+        tracing::info!("promoting focus");
+
+        let target_idx = match workspace.layout() {
+            Layout::Default(_) => 0,
+            Layout::Custom(custom) => custom
+                .first_container_idx(custom.primary_idx().map_or(0, |primary_idx| primary_idx)),
+        };
+
+        workspace.focus_container(target_idx);
+        self.update_focused_workspace(self.mouse_follows_focus, true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn remove_window_from_container(&mut self) -> Result<()> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        tracing::info!("removing window");
+
+        if self.focused_container()?.windows().len() == 1 {
+            bail!("a container must have at least one window");
+        }
+
+        // Unstacked windows should not be immediately stacked again if they are managed anew
+        let hwnd = self.focused_window()?.hwnd;
+        AUTO_STACK_EXEMPT_HWNDS.lock().push(hwnd);
+
+        let workspace = self.focused_workspace_mut()?;
+
+        workspace.new_container_for_focused_window()?;
+        self.update_focused_workspace(self.mouse_follows_focus, false)
+    }
+
+    /// Moves focus to the given layer of the focused workspace, focusing the foreground floating
+    /// window (or the first floating window) for the floating layer and the focused tiled window
+    /// for the tiling layer
+    #[tracing::instrument(skip(self))]
+    pub fn set_workspace_layer(&mut self, layer: WorkspaceLayer) -> Result<()> {
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let workspace = self.focused_workspace_mut()?;
+
+        let target = match layer {
+            WorkspaceLayer::Tiling => workspace
+                .monocle_container()
+                .as_ref()
+                .or_else(|| workspace.focused_container())
+                .and_then(Container::focused_window)
+                .copied(),
+            WorkspaceLayer::Floating => {
+                let foreground_hwnd = window_system().foreground_window().unwrap_or_default();
+                let floating_windows = workspace.floating_windows();
+
+                Option::from(
+                    floating_windows
+                        .iter()
+                        .find(|w| w.hwnd == foreground_hwnd)
+                        .or_else(|| floating_windows.first())
+                        .copied()
+                        .ok_or_else(|| {
+                            anyhow!("there are no floating windows on this workspace")
+                        })?,
+                )
+            }
+        };
+
+        workspace.set_layer(layer);
+
+        if let Some(window) = target {
+            window.focus(mouse_follows_focus)?;
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn toggle_tiling(&mut self) -> Result<()> {
+        let workspace = self.focused_workspace_mut()?;
+        workspace.set_tile(!*workspace.tile());
+        self.update_focused_workspace(false, false)
+    }
+
+    /// The size and scaling of each monitor, keyed by device id
+    pub fn monitor_information(&self) -> HashMap<String, MonitorInformation> {
+        self.monitors()
+            .iter()
+            .map(|monitor| {
+                (
+                    monitor.device_id().clone(),
+                    MonitorInformation {
+                        size: *monitor.size(),
+                        dpi: monitor.dpi(),
+                        scale_factor: monitor.scale_factor(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn toggle_always_on_top(&mut self) -> Result<()> {
+        let hwnd = window_system().foreground_window()?;
+
+        let mut pinned_hwnds = PINNED_HWNDS.lock();
+        let always_on_top = if let Some(idx) = pinned_hwnds.iter().position(|h| *h == hwnd) {
+            pinned_hwnds.remove(idx);
+            false
+        } else {
+            pinned_hwnds.push(hwnd);
+            true
+        };
+
+        drop(pinned_hwnds);
+
+        tracing::info!("setting always on top: {always_on_top}");
+
+        WindowsApi::set_always_on_top(hwnd, always_on_top)?;
+
+        // Only floating windows are kept visible across workspace switches, so a tiled window
+        // is moved to the floating layer instead of leaving a hole in the layout of every other
+        // workspace
+        if always_on_top {
+            let workspace = self.focused_workspace()?;
+            let is_focused_tiled_window = workspace.monocle_container().is_none()
+                && workspace.maximized_window().is_none()
+                && workspace
+                    .focused_container()
+                    .and_then(|c| c.focused_window())
+                    .is_some_and(|w| w.hwnd == hwnd);
+
+            if is_focused_tiled_window {
+                self.float_window()?;
+                self.update_focused_workspace(false, false)?;
+            }
         }
 
+        border_manager::send_notification(Some(hwnd));
+
         Ok(())
     }
 
-    #[tracing::instrument(skip(self))]
-    pub fn promote_container_to_front(&mut self) -> Result<()> {
-        self.handle_unmanaged_window_behaviour()?;
-
-        let workspace = self.focused_workspace_mut()?;
+    /// Whether this window is currently managed on any workspace
+    fn is_managed_window(&self, hwnd: isize) -> bool {
+        self.monitors().iter().any(|monitor| {
+            monitor
+                .workspaces()
+                .iter()
+                .any(|workspace| workspace.contains_window(hwnd))
+        })
+    }
 
-        if matches!(workspace.layout(), Layout::Default(DefaultLayout::Grid)) {
-            tracing::debug!("ignoring promote command for grid layout");
-            return Ok(());
+    fn remove_window_from_workspaces(&mut self, hwnd: isize) -> Result<()> {
+        for monitor in self.monitors_mut() {
+            for workspace in monitor.workspaces_mut() {
+                if workspace.contains_window(hwnd) {
+                    workspace.remove_window(hwnd)?;
+                }
+            }
         }
 
-        tracing::info!("promoting container");
+        Ok(())
+    }
 
-        workspace.promote_container()?;
-        self.update_focused_workspace(self.mouse_follows_focus, true)
+    /// The rect in which windows popped from a scratchpad are placed, centred in the work area
+    fn scratchpad_rect(work_area: &Rect) -> Rect {
+        let width_percentage = SCRATCHPAD_WIDTH_PERCENTAGE.load(Ordering::SeqCst) as i32;
+        let width = work_area.right * width_percentage / 100;
+        let height = SCRATCHPAD_ASPECT_RATIO
+            .lock()
+            .map_or(work_area.bottom * width_percentage / 100, |aspect_ratio| {
+                aspect_ratio.height_for_width(width)
+            })
+            .min(work_area.bottom);
+
+        Rect {
+            left: work_area.left + (work_area.right - width) / 2,
+            top: work_area.top + (work_area.bottom - height) / 2,
+            right: width,
+            bottom: height,
+        }
     }
 
+    /// Hides the windows of the named scratchpad if any of them are currently shown, otherwise
+    /// hides the focused window in the named scratchpad
     #[tracing::instrument(skip(self))]
-    pub fn promote_focus_to_front(&mut self) -> Result<()> {
-        self.handle_unmanaged_window_behaviour()?;
-
-        let workspace = self.focused_workspace_mut()?;
+    pub fn push_to_scratchpad(&mut self, name: &str) -> Result<()> {
+        let shown = SCRATCHPADS
+            .lock()
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|hwnd| self.is_managed_window(*hwnd))
+            .collect::<Vec<_>>();
+
+        let hwnds = if shown.is_empty() {
+            let hwnd = window_system().foreground_window()?;
+            if hwnd == WindowsApi::desktop_window()? {
+                bail!("there is no window to push to scratchpad {name}");
+            }
 
-        if matches!(workspace.layout(), Layout::Default(DefaultLayout::Grid)) {
-            tracing::info!("ignoring promote focus command for grid layout");
-            return Ok(());
-        }
+            let mut scratchpads = SCRATCHPADS.lock();
+            for hwnds in scratchpads.values_mut() {
+                hwnds.retain(|h| *h != hwnd);
+            }
 
-        tracing::info!("promoting focus");
+            scratchpads.entry(name.to_string()).or_default().push(hwnd);
 
-        let target_idx = match workspace.layout() {
-            Layout::Default(_) => 0,
-            Layout::Custom(custom) => custom
-                .first_container_idx(custom.primary_idx().map_or(0, |primary_idx| primary_idx)),
+            vec![hwnd]
+        } else {
+            shown
         };
 
-        workspace.focus_container(target_idx);
+        tracing::info!("pushing {} windows to scratchpad {name}", hwnds.len());
+
+        let hiding_behaviour = self.focused_workspace()?.hiding_behaviour();
+
+        for hwnd in hwnds {
+            self.remove_window_from_workspaces(hwnd)?;
+            Window::from(hwnd).hide_with_behaviour(hiding_behaviour);
+            HIDDEN_SCRATCHPAD_HWNDS
+                .lock()
+                .insert(hwnd, hiding_behaviour);
+        }
+
         self.update_focused_workspace(self.mouse_follows_focus, true)
     }
 
+    /// Shows the hidden windows of the named scratchpad on the floating layer of the focused
+    /// workspace
     #[tracing::instrument(skip(self))]
-    pub fn remove_window_from_container(&mut self) -> Result<()> {
-        self.handle_unmanaged_window_behaviour()?;
-
-        tracing::info!("removing window");
+    pub fn pop_from_scratchpad(&mut self, name: &str) -> Result<()> {
+        let hidden = SCRATCHPADS
+            .lock()
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|hwnd| window_system().is_window(*hwnd) && !self.is_managed_window(*hwnd))
+            .collect::<Vec<_>>();
 
-        if self.focused_container()?.windows().len() == 1 {
-            bail!("a container must have at least one window");
+        if hidden.is_empty() {
+            bail!("there are no hidden windows in scratchpad {name}");
         }
 
+        tracing::info!("popping {} windows from scratchpad {name}", hidden.len());
+
+        let rect = Self::scratchpad_rect(&self.focused_monitor_work_area()?);
+        let mouse_follows_focus = self.mouse_follows_focus;
         let workspace = self.focused_workspace_mut()?;
 
-        workspace.new_container_for_focused_window()?;
-        self.update_focused_workspace(self.mouse_follows_focus, false)
+        for hwnd in &hidden {
+            let window = Window::from(*hwnd);
+            workspace.floating_windows_mut().push(window);
+
+            match HIDDEN_SCRATCHPAD_HWNDS.lock().remove(hwnd) {
+                Some(hiding_behaviour) => window.restore_with_behaviour(hiding_behaviour),
+                None => window.restore(),
+            }
+
+            window.set_position(&rect, true)?;
+        }
+
+        self.update_focused_workspace(false, false)?;
+
+        if let Some(hwnd) = hidden.last() {
+            Window::from(*hwnd).focus(mouse_follows_focus)?;
+        }
+
+        Ok(())
     }
 
+    /// Pops the named scratchpad if it has hidden windows, otherwise pushes to it
     #[tracing::instrument(skip(self))]
-    pub fn toggle_tiling(&mut self) -> Result<()> {
-        let workspace = self.focused_workspace_mut()?;
-        workspace.set_tile(!*workspace.tile());
-        self.update_focused_workspace(false, false)
+    pub fn toggle_scratchpad(&mut self, name: &str) -> Result<()> {
+        let has_hidden_windows = SCRATCHPADS.lock().get(name).is_some_and(|hwnds| {
+            hwnds
+                .iter()
+                .any(|hwnd| window_system().is_window(*hwnd) && !self.is_managed_window(*hwnd))
+        });
+
+        if has_hidden_windows {
+            self.pop_from_scratchpad(name)
+        } else {
+            self.push_to_scratchpad(name)
+        }
     }
 
     #[tracing::instrument(skip(self))]
     pub fn toggle_float(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = window_system().foreground_window()?;
         let workspace = self.focused_workspace_mut()?;
 
         let mut is_floating_window = false;
@@ -2471,6 +4395,19 @@ impl WindowManager {
         let work_area = self.focused_monitor_work_area()?;
 
         let workspace = self.focused_workspace_mut()?;
+
+        // Monocle and maximized windows are not tiled, so there is nowhere to put them back
+        if workspace.monocle_container().is_none() && workspace.maximized_window().is_none() {
+            if let Some(window) = workspace
+                .focused_container()
+                .and_then(|c| c.focused_window())
+            {
+                if let Some(origin) = workspace.tile_origin(window.hwnd) {
+                    TILE_ORIGINS.lock().insert(window.hwnd, origin);
+                }
+            }
+        }
+
         workspace.new_floating_window()?;
 
         let window = workspace
@@ -2481,6 +4418,9 @@ impl WindowManager {
         window.center(&work_area)?;
         window.focus(self.mouse_follows_focus)?;
 
+        // The window keeps focus, so there is no focus change event to update the layer
+        workspace.set_layer(WorkspaceLayer::Floating);
+
         Ok(())
     }
 
@@ -2489,7 +4429,10 @@ impl WindowManager {
         tracing::info!("unfloating window");
 
         let workspace = self.focused_workspace_mut()?;
-        workspace.new_container_for_floating_window()
+        workspace.new_container_for_floating_window()?;
+        workspace.set_layer(WorkspaceLayer::Tiling);
+
+        Ok(())
     }
 
     #[tracing::instrument(skip(self))]
@@ -2514,8 +4457,9 @@ impl WindowManager {
         let workspace = self.focused_workspace_mut()?;
         workspace.new_monocle_container()?;
 
+        let hiding_behaviour = workspace.hiding_behaviour();
         for container in workspace.containers_mut() {
-            container.hide(None);
+            container.hide_with_behaviour(None, hiding_behaviour);
         }
 
         Ok(())
@@ -2527,8 +4471,9 @@ impl WindowManager {
 
         let workspace = self.focused_workspace_mut()?;
 
+        let hiding_behaviour = workspace.hiding_behaviour();
         for container in workspace.containers_mut() {
-            container.restore();
+            container.restore_with_behaviour(hiding_behaviour);
         }
 
         workspace.reintegrate_monocle_container()
@@ -2662,7 +4607,8 @@ impl WindowManager {
     {
         tracing::info!("changing layout");
 
-        let layout = CustomLayout::from_path(path)?;
+        let layout = CustomLayout::from_path(&path)?;
+        custom_layout_watcher::register(&path, &layout);
         let workspace = self.focused_workspace_mut()?;
 
         match workspace.layout() {
@@ -2680,6 +4626,9 @@ impl WindowManager {
             Layout::Custom(_) => {}
         }
 
+        workspace
+            .custom_layout_paths_mut()
+            .insert(None, path.as_ref().to_path_buf());
         workspace.set_layout(Layout::Custom(layout));
         workspace.set_layout_flip(None);
         self.update_focused_workspace(self.mouse_follows_focus, false)
@@ -2696,6 +4645,7 @@ impl WindowManager {
             .ok_or_else(|| anyhow!("there is no workspace padding"))?;
 
         workspace.set_workspace_padding(Option::from(sizing.adjust_by(padding, adjustment)));
+        workspace.set_explicit_workspace_padding(true);
 
         self.update_focused_workspace(false, false)
     }
@@ -2711,6 +4661,7 @@ impl WindowManager {
             .ok_or_else(|| anyhow!("there is no container padding"))?;
 
         workspace.set_container_padding(Option::from(sizing.adjust_by(padding, adjustment)));
+        workspace.set_explicit_container_padding(true);
 
         self.update_focused_workspace(false, false)
     }
@@ -2759,14 +4710,11 @@ impl WindowManager {
         let window_based_work_area_offset = (
             monitor.window_based_work_area_offset_limit(),
             monitor.window_based_work_area_offset(),
+            monitor.window_based_work_area_offset_counting(),
         );
 
         let focused_workspace_idx = monitor.focused_workspace_idx();
-        let offset = if monitor.work_area_offset().is_some() {
-            monitor.work_area_offset()
-        } else {
-            offset
-        };
+        let offset = monitor.effective_work_area_offset(offset);
 
         let workspace = monitor
             .workspaces_mut()
@@ -2812,27 +4760,29 @@ impl WindowManager {
         let window_based_work_area_offset = (
             monitor.window_based_work_area_offset_limit(),
             monitor.window_based_work_area_offset(),
+            monitor.window_based_work_area_offset_counting(),
         );
 
         let focused_workspace_idx = monitor.focused_workspace_idx();
-        let offset = if monitor.work_area_offset().is_some() {
-            monitor.work_area_offset()
-        } else {
-            offset
-        };
+        let offset = monitor.effective_work_area_offset(offset);
 
         let workspace = monitor
             .workspaces_mut()
             .get_mut(workspace_idx)
             .ok_or_else(|| anyhow!("there is no monitor"))?;
 
-        let layout = CustomLayout::from_path(path)?;
+        let layout = CustomLayout::from_path(&path)?;
+        custom_layout_watcher::register(&path, &layout);
 
         let rules: &mut Vec<(usize, Layout)> = workspace.layout_rules_mut();
         rules.retain(|pair| pair.0 != at_container_count);
         rules.push((at_container_count, Layout::Custom(layout)));
         rules.sort_by(|a, b| a.0.cmp(&b.0));
 
+        workspace
+            .custom_layout_paths_mut()
+            .insert(Some(at_container_count), path.as_ref().to_path_buf());
+
         // If this is the focused workspace on a non-focused screen, let's update it
         if focused_monitor_idx != monitor_idx && focused_workspace_idx == workspace_idx {
             workspace.update(&work_area, offset, window_based_work_area_offset)?;
@@ -2862,14 +4812,11 @@ impl WindowManager {
         let window_based_work_area_offset = (
             monitor.window_based_work_area_offset_limit(),
             monitor.window_based_work_area_offset(),
+            monitor.window_based_work_area_offset_counting(),
         );
 
         let focused_workspace_idx = monitor.focused_workspace_idx();
-        let offset = if monitor.work_area_offset().is_some() {
-            monitor.work_area_offset()
-        } else {
-            offset
-        };
+        let offset = monitor.effective_work_area_offset(offset);
 
         let workspace = monitor
             .workspaces_mut()
@@ -2909,14 +4856,11 @@ impl WindowManager {
         let window_based_work_area_offset = (
             monitor.window_based_work_area_offset_limit(),
             monitor.window_based_work_area_offset(),
+            monitor.window_based_work_area_offset_counting(),
         );
 
         let focused_workspace_idx = monitor.focused_workspace_idx();
-        let offset = if monitor.work_area_offset().is_some() {
-            monitor.work_area_offset()
-        } else {
-            offset
-        };
+        let offset = monitor.effective_work_area_offset(offset);
 
         let workspace = monitor
             .workspaces_mut()
@@ -2945,7 +4889,8 @@ impl WindowManager {
         P: AsRef<Path> + std::fmt::Debug,
     {
         tracing::info!("setting workspace layout");
-        let layout = CustomLayout::from_path(path)?;
+        let layout = CustomLayout::from_path(&path)?;
+        custom_layout_watcher::register(&path, &layout);
         let offset = self.work_area_offset;
         let focused_monitor_idx = self.focused_monitor_idx();
 
@@ -2958,20 +4903,20 @@ impl WindowManager {
         let window_based_work_area_offset = (
             monitor.window_based_work_area_offset_limit(),
             monitor.window_based_work_area_offset(),
+            monitor.window_based_work_area_offset_counting(),
         );
 
         let focused_workspace_idx = monitor.focused_workspace_idx();
-        let offset = if monitor.work_area_offset().is_some() {
-            monitor.work_area_offset()
-        } else {
-            offset
-        };
+        let offset = monitor.effective_work_area_offset(offset);
 
         let workspace = monitor
             .workspaces_mut()
             .get_mut(workspace_idx)
             .ok_or_else(|| anyhow!("there is no monitor"))?;
 
+        workspace
+            .custom_layout_paths_mut()
+            .insert(None, path.as_ref().to_path_buf());
         workspace.set_layout(Layout::Custom(layout));
         workspace.set_layout_flip(None);
 
@@ -3046,10 +4991,59 @@ impl WindowManager {
             .ok_or_else(|| anyhow!("there is no monitor"))?;
 
         workspace.set_workspace_padding(Option::from(size));
+        workspace.set_explicit_workspace_padding(true);
 
         self.update_focused_workspace(false, false)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn set_monitor_workspace_padding(&mut self, monitor_idx: usize, size: i32) -> Result<()> {
+        tracing::info!("setting monitor workspace padding");
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        monitor.set_workspace_padding(Option::from(size));
+        monitor.apply_padding_defaults();
+
+        self.retile_all(true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn set_workspace_window_hiding_behaviour(
+        &mut self,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        behaviour: HidingBehaviour,
+    ) -> Result<()> {
+        tracing::info!("setting workspace window hiding behaviour");
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let is_focused_workspace = monitor.focused_workspace_idx() == workspace_idx;
+
+        let workspace = monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        let previous_behaviour = workspace.hiding_behaviour();
+        workspace.set_window_hiding_behaviour(Option::from(behaviour));
+
+        // Windows on a workspace that is not currently visible have been hidden with the
+        // previous behaviour, so they need to be hidden again using the new one
+        if !is_focused_workspace {
+            workspace.rehide(previous_behaviour);
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn set_workspace_name(
         &mut self,
@@ -3075,6 +5069,34 @@ impl WindowManager {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn set_workspace_wallpaper(
+        &mut self,
+        monitor_idx: usize,
+        workspace_idx: usize,
+        path: &Path,
+    ) -> Result<()> {
+        tracing::info!("setting workspace wallpaper");
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let workspace = monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        workspace.set_wallpaper(Option::from(resolve_home_path(path)?));
+
+        if monitor.focused_workspace_idx() == workspace_idx {
+            monitor.apply_wallpaper();
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn set_container_padding(
         &mut self,
@@ -3095,10 +5117,26 @@ impl WindowManager {
             .ok_or_else(|| anyhow!("there is no monitor"))?;
 
         workspace.set_container_padding(Option::from(size));
+        workspace.set_explicit_container_padding(true);
 
         self.update_focused_workspace(false, false)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn set_monitor_container_padding(&mut self, monitor_idx: usize, size: i32) -> Result<()> {
+        tracing::info!("setting monitor container padding");
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        monitor.set_container_padding(Option::from(size));
+        monitor.apply_padding_defaults();
+
+        self.retile_all(true)
+    }
+
     pub fn focused_monitor_size(&self) -> Result<Rect> {
         Ok(*self
             .focused_monitor()
@@ -3117,6 +5155,10 @@ impl WindowManager {
     pub fn focus_monitor(&mut self, idx: usize) -> Result<()> {
         tracing::info!("focusing monitor");
 
+        if self.is_unmanaged_monitor(idx) {
+            bail!("cannot focus an unmanaged monitor");
+        }
+
         if self.monitors().get(idx).is_some() {
             self.monitors.focus(idx);
         } else {
@@ -3127,7 +5169,7 @@ impl WindowManager {
     }
 
     pub fn monitor_idx_from_window(&mut self, window: Window) -> Option<usize> {
-        let hmonitor = WindowsApi::monitor_from_window(window.hwnd);
+        let hmonitor = window_system().monitor_from_window(window.hwnd);
 
         for (i, monitor) in self.monitors().iter().enumerate() {
             if monitor.id() == hmonitor {
@@ -3149,8 +5191,98 @@ impl WindowManager {
         None
     }
 
+    /// Pauses the monitor of a foreground window which matches a fullscreen application rule and
+    /// covers the whole monitor, resuming monitors with a single retile once their fullscreen
+    /// window is no longer fullscreen or in the foreground
+    #[tracing::instrument(skip(self))]
+    pub fn update_fullscreen_pauses(&mut self) -> Result<()> {
+        let fullscreen = self.foreground_fullscreen_application();
+
+        let mut changed = false;
+        let mut resumed = false;
+
+        for (idx, monitor) in self.monitors_mut().iter_mut().enumerate() {
+            let hwnd =
+                fullscreen.and_then(|(monitor_idx, hwnd)| (monitor_idx == idx).then_some(hwnd));
+
+            if monitor.fullscreen_hwnd() != hwnd {
+                match hwnd {
+                    Some(hwnd) => {
+                        tracing::info!("pausing monitor {idx} for fullscreen window {hwnd}")
+                    }
+                    None => {
+                        tracing::info!("resuming monitor {idx}");
+                        resumed = true;
+                    }
+                }
+
+                monitor.set_fullscreen_hwnd(hwnd);
+                changed = true;
+            }
+        }
+
+        if resumed {
+            self.retile_all(false)?;
+        }
+
+        if changed {
+            border_manager::send_notification(None);
+            transparency_manager::send_notification();
+        }
+
+        Ok(())
+    }
+
+    fn foreground_fullscreen_application(&mut self) -> Option<(usize, isize)> {
+        let window = Window::from(window_system().foreground_window().ok()?);
+
+        {
+            let fullscreen_applications = FULLSCREEN_APPLICATIONS.lock();
+            if fullscreen_applications.is_empty() {
+                return None;
+            }
+
+            let regex_identifiers = REGEX_IDENTIFIERS.lock();
+
+            should_act(
+                &window.title().ok()?,
+                &window.exe().ok()?,
+                &window.class().ok()?,
+                &window.path().ok()?,
+                &fullscreen_applications,
+                &regex_identifiers,
+            )?;
+        }
+
+        // Only the monitor bounds count as fullscreen, a window covering just the work area is
+        // an ordinary maximized window
+        let rect = window_system().window_rect(window.hwnd).ok()?;
+        let idx = self.monitor_idx_from_window(window)?;
+
+        (*self.monitors().get(idx)?.size() == rect).then_some((idx, window.hwnd))
+    }
+
+    /// Toggles the user pause of a monitor, which also clears any pause from a fullscreen window
+    #[tracing::instrument(skip(self))]
+    pub fn toggle_monitor_pause(&mut self, monitor_idx: usize) -> Result<()> {
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let paused = !monitor.is_paused();
+        monitor.set_paused(paused);
+
+        if !paused {
+            monitor.set_fullscreen_hwnd(None);
+            self.retile_all(false)?;
+        }
+
+        Ok(())
+    }
+
     pub fn monitor_idx_from_current_pos(&mut self) -> Option<usize> {
-        let hmonitor = WindowsApi::monitor_from_point(WindowsApi::cursor_pos().ok()?);
+        let hmonitor = window_system().monitor_from_point(window_system().cursor_pos().ok()?);
 
         for (i, monitor) in self.monitors().iter().enumerate() {
             if monitor.id() == hmonitor {
@@ -3232,13 +5364,115 @@ impl WindowManager {
         self.update_focused_workspace(false, true)
     }
 
+    /// Applies the configured `on_last_window_close` behaviour after the last window on the
+    /// focused workspace has been closed
+    #[tracing::instrument(skip(self))]
+    pub fn handle_last_window_closed(&mut self) -> Result<()> {
+        let monitor = self
+            .focused_monitor()
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        let focused_idx = monitor.focused_workspace_idx();
+        let target_idx = match self.on_last_window_close {
+            LastWindowCloseBehaviour::DoNothing => None,
+            LastWindowCloseBehaviour::FocusLastWorkspace => monitor.last_focused_workspace(),
+            LastWindowCloseBehaviour::FocusPreviousNonEmpty => {
+                monitor.previous_non_empty_workspace_idx()
+            }
+        };
+
+        let Some(idx) = target_idx
+            .filter(|idx| *idx != focused_idx && monitor.workspaces().get(*idx).is_some())
+        else {
+            return Ok(());
+        };
+
+        tracing::info!("focusing workspace {idx} after the last window was closed");
+
+        if let Some(monitor) = self.focused_monitor_mut() {
+            monitor.set_last_focused_workspace(Option::from(focused_idx));
+        }
+
+        self.focus_workspace(idx)
+    }
+
+    /// Switches to the workspace without taking focus away from the foreground window if it is
+    /// floating or unmanaged, eg. an always-on-top utility which closes when it loses focus
+    #[tracing::instrument(skip(self))]
+    pub fn focus_workspace_keep_focus(&mut self, idx: usize) -> Result<()> {
+        let foreground_hwnd = window_system().foreground_window()?;
+
+        let is_tiled = self.monitors().iter().any(|monitor| {
+            monitor
+                .workspaces()
+                .iter()
+                .any(|workspace| workspace.contains_managed_window(foreground_hwnd))
+        });
+
+        if is_tiled {
+            return self.focus_workspace(idx);
+        }
+
+        tracing::info!("focusing workspace without changing the foreground window");
+
+        let offset = self.work_area_offset;
+        let monitor = self
+            .focused_monitor_mut()
+            .ok_or_else(|| anyhow!("there is no workspace"))?;
+
+        monitor.focus_workspace(idx)?;
+        monitor.load_focused_workspace_without_focus();
+        monitor.update_focused_workspace(offset)
+    }
+
+    /// Names every workspace without a configured name after the application of its focused
+    /// window, or restores the index-based names when auto naming is disabled
+    pub fn update_auto_workspace_names(&mut self) {
+        let enabled = AUTO_WORKSPACE_NAMES.load(Ordering::SeqCst);
+
+        for monitor in self.monitors_mut() {
+            for workspace in monitor.workspaces_mut() {
+                if enabled {
+                    workspace.update_auto_name();
+                } else {
+                    workspace.clear_auto_name();
+                }
+            }
+        }
+    }
+
+    /// Asks every window on a workspace to close, including stacked, monocle and floating windows,
+    /// without waiting for windows which prompt before closing
+    #[tracing::instrument(skip(self))]
+    pub fn close_workspace_windows(&self, monitor_idx: usize, workspace_idx: usize) -> Result<()> {
+        tracing::info!("closing workspace windows");
+
+        let workspace = self
+            .monitors()
+            .get(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor at index {monitor_idx}"))?
+            .workspaces()
+            .get(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace at index {workspace_idx}"))?;
+
+        for window in workspace.all_windows() {
+            // A window which can no longer be asked to close should not stop the others from being
+            // asked
+            if let Err(error) = window.close() {
+                tracing::warn!("could not close window {}: {error}", window.hwnd);
+            }
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn monitor_workspace_index_by_name(&mut self, name: &str) -> Option<(usize, usize)> {
         tracing::info!("looking up workspace by name");
 
         for (monitor_idx, monitor) in self.monitors().iter().enumerate() {
             for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
-                if let Some(workspace_name) = workspace.name() {
+                if let Some(workspace_name) = workspace.explicit_name() {
                     if workspace_name == name {
                         return Option::from((monitor_idx, workspace_idx));
                     }
@@ -4,6 +4,8 @@ use std::fmt::Formatter;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::IntoStaticStr;
+use strum::VariantNames;
 
 use crate::window::should_act;
 use crate::window::Window;
@@ -11,7 +13,7 @@ use crate::winevent::WinEvent;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
 use crate::REGEX_IDENTIFIERS;
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, IntoStaticStr, VariantNames, JsonSchema)]
 #[serde(tag = "type", content = "content")]
 pub enum WindowManagerEvent {
     Destroy(WinEvent, Window),
@@ -106,6 +108,17 @@ impl WindowManagerEvent {
         self.window().hwnd
     }
 
+    /// Events which some applications emit in storms, and which can be coalesced per window
+    /// because handling the most recent one gives the same result as handling all of them
+    pub const fn is_coalescable(self) -> bool {
+        matches!(
+            self,
+            Self::TitleUpdate(_, _)
+                | Self::MouseCapture(_, _)
+                | Self::Show(WinEvent::ObjectNameChange, _)
+        )
+    }
+
     pub const fn title(self) -> &'static str {
         match self {
             WindowManagerEvent::Destroy(_, _) => "Destroy",
@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use color_eyre::Result;
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use windows::Win32::Foundation::POINT;
+
+use crate::core::Rect;
+use crate::windows_api::WindowsApi;
+
+lazy_static! {
+    static ref WINDOW_SYSTEM: RwLock<Arc<dyn WindowSystem>> = RwLock::new(Arc::new(Win32));
+}
+
+/// The window system backing the focus, movement and workspace switching logic of the
+/// window manager
+pub fn window_system() -> Arc<dyn WindowSystem> {
+    WINDOW_SYSTEM.read().clone()
+}
+
+/// Replace the window system backing the window manager, such as with a
+/// [`mock::MockWindowSystem`] when exercising window manager logic without a live Windows
+/// session
+pub fn set_window_system(window_system: Arc<dyn WindowSystem>) {
+    *WINDOW_SYSTEM.write() = window_system;
+}
+
+/// The subset of [`WindowsApi`] used to position, focus and query windows and monitors
+/// from the window manager, its workspaces and its windows
+pub trait WindowSystem: Send + Sync {
+    fn foreground_window(&self) -> Result<isize>;
+    fn raise_and_focus_window(&self, hwnd: isize) -> Result<()>;
+    fn window_rect(&self, hwnd: isize) -> Result<Rect>;
+    fn position_window(&self, hwnd: isize, layout: &Rect, top: bool) -> Result<()>;
+    fn cursor_pos(&self) -> Result<POINT>;
+    fn center_cursor_in_rect(&self, rect: &Rect) -> Result<()>;
+    fn monitor_from_window(&self, hwnd: isize) -> isize;
+    fn monitor_from_point(&self, point: POINT) -> isize;
+    fn monitor_from_rect(&self, rect: &Rect) -> isize;
+    fn monitors_have_same_dpi(&self, hmonitor_a: isize, hmonitor_b: isize) -> Result<bool>;
+    fn is_window(&self, hwnd: isize) -> bool;
+    fn window_title(&self, hwnd: isize) -> Result<String>;
+    fn window_exe(&self, hwnd: isize) -> Result<String>;
+    fn window_class(&self, hwnd: isize) -> Result<String>;
+}
+
+/// The window system of a live Windows session, delegating to [`WindowsApi`]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Win32;
+
+impl WindowSystem for Win32 {
+    fn foreground_window(&self) -> Result<isize> {
+        WindowsApi::foreground_window()
+    }
+
+    fn raise_and_focus_window(&self, hwnd: isize) -> Result<()> {
+        WindowsApi::raise_and_focus_window(hwnd)
+    }
+
+    fn window_rect(&self, hwnd: isize) -> Result<Rect> {
+        WindowsApi::window_rect(hwnd)
+    }
+
+    fn position_window(&self, hwnd: isize, layout: &Rect, top: bool) -> Result<()> {
+        WindowsApi::position_window(hwnd, layout, top)
+    }
+
+    fn cursor_pos(&self) -> Result<POINT> {
+        WindowsApi::cursor_pos()
+    }
+
+    fn center_cursor_in_rect(&self, rect: &Rect) -> Result<()> {
+        WindowsApi::center_cursor_in_rect(rect)
+    }
+
+    fn monitor_from_window(&self, hwnd: isize) -> isize {
+        WindowsApi::monitor_from_window(hwnd)
+    }
+
+    fn monitor_from_point(&self, point: POINT) -> isize {
+        WindowsApi::monitor_from_point(point)
+    }
+
+    fn monitor_from_rect(&self, rect: &Rect) -> isize {
+        WindowsApi::monitor_from_rect(rect)
+    }
+
+    fn monitors_have_same_dpi(&self, hmonitor_a: isize, hmonitor_b: isize) -> Result<bool> {
+        WindowsApi::monitors_have_same_dpi(hmonitor_a, hmonitor_b)
+    }
+
+    fn is_window(&self, hwnd: isize) -> bool {
+        WindowsApi::is_window(hwnd)
+    }
+
+    fn window_title(&self, hwnd: isize) -> Result<String> {
+        WindowsApi::window_text_w(hwnd)
+    }
+
+    fn window_exe(&self, hwnd: isize) -> Result<String> {
+        let (process_id, _) = WindowsApi::window_thread_process_id(hwnd);
+        let handle = WindowsApi::process_handle(process_id)?;
+        let exe = WindowsApi::exe(handle);
+        WindowsApi::close_process(handle)?;
+        exe
+    }
+
+    fn window_class(&self, hwnd: isize) -> Result<String> {
+        WindowsApi::real_window_class_w(hwnd)
+    }
+}
+
+#[cfg(feature = "mock_window_system")]
+pub mod mock {
+    use std::collections::HashMap;
+
+    use color_eyre::eyre::anyhow;
+    use color_eyre::Result;
+    use parking_lot::Mutex;
+    use windows::Win32::Foundation::POINT;
+
+    use super::WindowSystem;
+    use crate::core::Rect;
+
+    /// A call made against the [`MockWindowSystem`] which changes the state of the fake
+    /// window model
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Call {
+        RaiseAndFocusWindow(isize),
+        PositionWindow(isize, Rect, bool),
+        CenterCursorInRect(Rect),
+    }
+
+    #[derive(Debug, Clone, Default)]
+    pub struct MockWindow {
+        pub title: String,
+        pub exe: String,
+        pub class: String,
+        pub rect: Rect,
+    }
+
+    #[derive(Debug, Default)]
+    struct State {
+        monitors: Vec<(isize, Rect)>,
+        windows: HashMap<isize, MockWindow>,
+        foreground: Option<isize>,
+        cursor: POINT,
+        calls: Vec<Call>,
+    }
+
+    /// A fake monitor and window model which records every call that would move, resize
+    /// or focus a window in a live Windows session
+    #[derive(Debug, Default)]
+    pub struct MockWindowSystem {
+        state: Mutex<State>,
+    }
+
+    impl MockWindowSystem {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn add_monitor(&self, hmonitor: isize, size: Rect) {
+            self.state.lock().monitors.push((hmonitor, size));
+        }
+
+        pub fn add_window(&self, hwnd: isize, window: MockWindow) {
+            self.state.lock().windows.insert(hwnd, window);
+        }
+
+        pub fn remove_window(&self, hwnd: isize) {
+            let mut state = self.state.lock();
+            state.windows.remove(&hwnd);
+            if state.foreground == Some(hwnd) {
+                state.foreground = None;
+            }
+        }
+
+        pub fn set_foreground_window(&self, hwnd: Option<isize>) {
+            self.state.lock().foreground = hwnd;
+        }
+
+        pub fn set_cursor_pos(&self, x: i32, y: i32) {
+            self.state.lock().cursor = POINT { x, y };
+        }
+
+        pub fn calls(&self) -> Vec<Call> {
+            self.state.lock().calls.clone()
+        }
+
+        pub fn take_calls(&self) -> Vec<Call> {
+            std::mem::take(&mut self.state.lock().calls)
+        }
+
+        fn window(&self, hwnd: isize) -> Result<MockWindow> {
+            self.state
+                .lock()
+                .windows
+                .get(&hwnd)
+                .cloned()
+                .ok_or_else(|| anyhow!("there is no window with hwnd {hwnd}"))
+        }
+
+        fn monitor_containing(state: &State, x: i32, y: i32) -> isize {
+            let contains = |size: &Rect| {
+                x >= size.left
+                    && x < size.left + size.right
+                    && y >= size.top
+                    && y < size.top + size.bottom
+            };
+
+            // Mirror MONITOR_DEFAULTTONEAREST by falling back to the first monitor
+            state
+                .monitors
+                .iter()
+                .find(|(_, size)| contains(size))
+                .or_else(|| state.monitors.first())
+                .map_or(0, |(hmonitor, _)| *hmonitor)
+        }
+    }
+
+    impl WindowSystem for MockWindowSystem {
+        fn foreground_window(&self) -> Result<isize> {
+            self.state
+                .lock()
+                .foreground
+                .ok_or_else(|| anyhow!("there is no foreground window"))
+        }
+
+        fn raise_and_focus_window(&self, hwnd: isize) -> Result<()> {
+            let mut state = self.state.lock();
+            if !state.windows.contains_key(&hwnd) {
+                return Err(anyhow!("there is no window with hwnd {hwnd}"));
+            }
+
+            state.foreground = Some(hwnd);
+            state.calls.push(Call::RaiseAndFocusWindow(hwnd));
+
+            Ok(())
+        }
+
+        fn window_rect(&self, hwnd: isize) -> Result<Rect> {
+            self.state
+                .lock()
+                .windows
+                .get(&hwnd)
+                .map(|window| window.rect)
+                .ok_or_else(|| anyhow!("there is no window with hwnd {hwnd}"))
+        }
+
+        fn position_window(&self, hwnd: isize, layout: &Rect, top: bool) -> Result<()> {
+            let mut state = self.state.lock();
+            let window = state
+                .windows
+                .get_mut(&hwnd)
+                .ok_or_else(|| anyhow!("there is no window with hwnd {hwnd}"))?;
+
+            window.rect = *layout;
+            state.calls.push(Call::PositionWindow(hwnd, *layout, top));
+
+            Ok(())
+        }
+
+        fn cursor_pos(&self) -> Result<POINT> {
+            Ok(self.state.lock().cursor)
+        }
+
+        fn center_cursor_in_rect(&self, rect: &Rect) -> Result<()> {
+            let mut state = self.state.lock();
+            state.cursor = POINT {
+                x: rect.left + (rect.right / 2),
+                y: rect.top + (rect.bottom / 2),
+            };
+            state.calls.push(Call::CenterCursorInRect(*rect));
+
+            Ok(())
+        }
+
+        fn monitor_from_window(&self, hwnd: isize) -> isize {
+            let state = self.state.lock();
+            let rect = state.windows.get(&hwnd).map(|window| window.rect);
+            rect.map_or_else(
+                || state.monitors.first().map_or(0, |(hmonitor, _)| *hmonitor),
+                |rect| {
+                    Self::monitor_containing(
+                        &state,
+                        rect.left + (rect.right / 2),
+                        rect.top + (rect.bottom / 2),
+                    )
+                },
+            )
+        }
+
+        fn monitor_from_point(&self, point: POINT) -> isize {
+            Self::monitor_containing(&self.state.lock(), point.x, point.y)
+        }
+
+        fn monitor_from_rect(&self, rect: &Rect) -> isize {
+            Self::monitor_containing(
+                &self.state.lock(),
+                rect.left + (rect.right / 2),
+                rect.top + (rect.bottom / 2),
+            )
+        }
+
+        // Every fake monitor is taken to have the same DPI
+        fn monitors_have_same_dpi(&self, hmonitor_a: isize, hmonitor_b: isize) -> Result<bool> {
+            let state = self.state.lock();
+            for hmonitor in [hmonitor_a, hmonitor_b] {
+                if !state.monitors.iter().any(|(id, _)| *id == hmonitor) {
+                    return Err(anyhow!("there is no monitor with hmonitor {hmonitor}"));
+                }
+            }
+
+            Ok(true)
+        }
+
+        fn is_window(&self, hwnd: isize) -> bool {
+            self.state.lock().windows.contains_key(&hwnd)
+        }
+
+        fn window_title(&self, hwnd: isize) -> Result<String> {
+            self.window(hwnd).map(|window| window.title)
+        }
+
+        fn window_exe(&self, hwnd: isize) -> Result<String> {
+            self.window(hwnd).map(|window| window.exe)
+        }
+
+        fn window_class(&self, hwnd: isize) -> Result<String> {
+            self.window(hwnd).map(|window| window.class)
+        }
+    }
+}
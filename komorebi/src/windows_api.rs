@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::convert::TryFrom;
 use std::ffi::c_void;
@@ -10,6 +11,8 @@ use color_eyre::Result;
 use windows::core::Result as WindowsCrateResult;
 use windows::core::PCWSTR;
 use windows::core::PWSTR;
+use windows::Wdk::System::Threading::NtQueryInformationProcess;
+use windows::Wdk::System::Threading::ProcessBasicInformation;
 use windows::Win32::Foundation::CloseHandle;
 use windows::Win32::Foundation::BOOL;
 use windows::Win32::Foundation::COLORREF;
@@ -38,6 +41,7 @@ use windows::Win32::Graphics::Gdi::EnumDisplayMonitors;
 use windows::Win32::Graphics::Gdi::GetMonitorInfoW;
 use windows::Win32::Graphics::Gdi::InvalidateRect;
 use windows::Win32::Graphics::Gdi::MonitorFromPoint;
+use windows::Win32::Graphics::Gdi::MonitorFromRect;
 use windows::Win32::Graphics::Gdi::MonitorFromWindow;
 use windows::Win32::Graphics::Gdi::Rectangle;
 use windows::Win32::Graphics::Gdi::RoundRect;
@@ -54,9 +58,12 @@ use windows::Win32::System::Threading::GetCurrentProcessId;
 use windows::Win32::System::Threading::OpenProcess;
 use windows::Win32::System::Threading::QueryFullProcessImageNameW;
 use windows::Win32::System::Threading::PROCESS_ACCESS_RIGHTS;
+use windows::Win32::System::Threading::PROCESS_BASIC_INFORMATION;
 use windows::Win32::System::Threading::PROCESS_NAME_WIN32;
 use windows::Win32::System::Threading::PROCESS_QUERY_INFORMATION;
+use windows::Win32::System::Threading::PROCESS_QUERY_LIMITED_INFORMATION;
 use windows::Win32::UI::HiDpi::GetDpiForMonitor;
+use windows::Win32::UI::HiDpi::GetDpiForSystem;
 use windows::Win32::UI::HiDpi::SetProcessDpiAwarenessContext;
 use windows::Win32::UI::HiDpi::DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2;
 use windows::Win32::UI::HiDpi::MDT_EFFECTIVE_DPI;
@@ -68,8 +75,16 @@ use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_MOUSE;
 use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_LEFTDOWN;
 use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEEVENTF_LEFTUP;
 use windows::Win32::UI::Input::KeyboardAndMouse::MOUSEINPUT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_CONTROL;
 use windows::Win32::UI::Input::KeyboardAndMouse::VK_LBUTTON;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LWIN;
 use windows::Win32::UI::Input::KeyboardAndMouse::VK_MENU;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RWIN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_SHIFT;
+use windows::Win32::UI::Shell::SHQueryUserNotificationState;
+use windows::Win32::UI::Shell::QUNS_BUSY;
+use windows::Win32::UI::Shell::QUNS_PRESENTATION_MODE;
+use windows::Win32::UI::Shell::QUNS_RUNNING_D3D_FULL_SCREEN;
 use windows::Win32::UI::WindowsAndMessaging::AllowSetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::BringWindowToTop;
 use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
@@ -84,6 +99,7 @@ use windows::Win32::UI::WindowsAndMessaging::GetWindowLongPtrW;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowRect;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowTextW;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+use windows::Win32::UI::WindowsAndMessaging::IsHungAppWindow;
 use windows::Win32::UI::WindowsAndMessaging::IsIconic;
 use windows::Win32::UI::WindowsAndMessaging::IsWindow;
 use windows::Win32::UI::WindowsAndMessaging::IsWindowVisible;
@@ -92,6 +108,7 @@ use windows::Win32::UI::WindowsAndMessaging::MoveWindow;
 use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
 use windows::Win32::UI::WindowsAndMessaging::RealGetWindowClassW;
 use windows::Win32::UI::WindowsAndMessaging::RegisterClassW;
+use windows::Win32::UI::WindowsAndMessaging::SendMessageTimeoutW;
 use windows::Win32::UI::WindowsAndMessaging::SetCursorPos;
 use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::SetLayeredWindowAttributes;
@@ -104,10 +121,15 @@ use windows::Win32::UI::WindowsAndMessaging::CW_USEDEFAULT;
 use windows::Win32::UI::WindowsAndMessaging::GWL_EXSTYLE;
 use windows::Win32::UI::WindowsAndMessaging::GWL_STYLE;
 use windows::Win32::UI::WindowsAndMessaging::GW_HWNDNEXT;
+use windows::Win32::UI::WindowsAndMessaging::GW_HWNDPREV;
+use windows::Win32::UI::WindowsAndMessaging::HWND_NOTOPMOST;
 use windows::Win32::UI::WindowsAndMessaging::HWND_TOP;
+use windows::Win32::UI::WindowsAndMessaging::HWND_TOPMOST;
 use windows::Win32::UI::WindowsAndMessaging::LWA_ALPHA;
+use windows::Win32::UI::WindowsAndMessaging::MINMAXINFO;
 use windows::Win32::UI::WindowsAndMessaging::SET_WINDOW_POS_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::SHOW_WINDOW_CMD;
+use windows::Win32::UI::WindowsAndMessaging::SMTO_ABORTIFHUNG;
 use windows::Win32::UI::WindowsAndMessaging::SPIF_SENDCHANGE;
 use windows::Win32::UI::WindowsAndMessaging::SPI_GETACTIVEWINDOWTRACKING;
 use windows::Win32::UI::WindowsAndMessaging::SPI_GETFOREGROUNDLOCKTIMEOUT;
@@ -125,6 +147,7 @@ use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_ACTION;
 use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::WINDOW_LONG_PTR_INDEX;
 use windows::Win32::UI::WindowsAndMessaging::WM_CLOSE;
+use windows::Win32::UI::WindowsAndMessaging::WM_GETMINMAXINFO;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
 use windows::Win32::UI::WindowsAndMessaging::WNDENUMPROC;
 use windows::Win32::UI::WindowsAndMessaging::WS_DISABLED;
@@ -133,7 +156,11 @@ use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOOLWINDOW;
 use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOPMOST;
 use windows::Win32::UI::WindowsAndMessaging::WS_POPUP;
 use windows::Win32::UI::WindowsAndMessaging::WS_SYSMENU;
+use winreg::enums::HKEY_LOCAL_MACHINE;
+use winreg::RegKey;
 
+use crate::core::DragModifier;
+use crate::core::MouseFollowsFocusScope;
 use crate::core::Rect;
 
 use crate::container::Container;
@@ -144,7 +171,9 @@ use crate::set_window_position::SetWindowPosition;
 use crate::windows_callbacks;
 use crate::Window;
 use crate::DISPLAY_INDEX_PREFERENCES;
+use crate::DUPLICATE_MONITOR_SERIAL_IDS;
 use crate::MONITOR_INDEX_PREFERENCES;
+use crate::MOUSE_FOLLOWS_FOCUS_SCOPE;
 
 macro_rules! as_ptr {
     ($value:expr) => {
@@ -252,8 +281,89 @@ impl WindowsApi {
             .collect::<Vec<_>>())
     }
 
+    /// Reads the serial number from the EDID stored in the registry for a display device path
+    ///
+    /// The alphanumeric serial number descriptor is preferred, and the numeric serial number in
+    /// the EDID header is used if the monitor does not report one.
+    pub fn monitor_serial_number_id(device_path: &str) -> Option<String> {
+        // \\?\DISPLAY#DEL4123#5&1a2b3c4d&0&UID4352#{e6f07b5f-ee97-4a90-b076-33f57bf4eaa7}
+        let mut split = device_path.split('#').skip(1);
+        let hardware_id = split.next()?;
+        let instance_id = split.next()?;
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let parameters = hklm
+            .open_subkey(format!(
+                r"SYSTEM\CurrentControlSet\Enum\DISPLAY\{hardware_id}\{instance_id}\Device Parameters"
+            ))
+            .ok()?;
+
+        let edid = parameters.get_raw_value("EDID").ok()?.bytes;
+        if edid.len() < 128 {
+            return None;
+        }
+
+        // The four 18 byte display descriptors start at offset 54, and a descriptor with the tag
+        // 0xFF holds up to 13 bytes of the serial number, terminated by a newline
+        for offset in [54, 72, 90, 108] {
+            let descriptor = &edid[offset..offset + 18];
+            if descriptor[..4] == [0x00, 0x00, 0x00, 0xFF] {
+                let serial = String::from_utf8_lossy(&descriptor[5..])
+                    .split('\n')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+
+                if !serial.is_empty() {
+                    return Option::from(serial);
+                }
+            }
+        }
+
+        let serial = u32::from_le_bytes([edid[12], edid[13], edid[14], edid[15]]);
+        if serial == 0 {
+            None
+        } else {
+            Option::from(serial.to_string())
+        }
+    }
+
+    /// Records the serial numbers which are reported by more than one attached monitor
+    pub fn update_duplicate_monitor_serial_ids(serial_number_ids: &[Option<String>]) {
+        let mut seen = vec![];
+        let mut duplicates = vec![];
+
+        for serial in serial_number_ids.iter().flatten() {
+            if seen.contains(&serial) {
+                if !duplicates.contains(serial) {
+                    tracing::warn!(
+                        "serial number {serial} is reported by more than one attached monitor, display index preferences using it will fall back to device ids"
+                    );
+
+                    duplicates.push(serial.clone());
+                }
+            } else {
+                seen.push(serial);
+            }
+        }
+
+        *DUPLICATE_MONITOR_SERIAL_IDS.lock() = duplicates;
+    }
+
     pub fn load_monitor_information(monitors: &mut Ring<Monitor>) -> Result<()> {
-        'read: for display in win32_display_data::connected_displays_all().flatten() {
+        let displays = win32_display_data::connected_displays_all()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        let serial_number_ids = displays
+            .iter()
+            .map(|display| Self::monitor_serial_number_id(&display.device_path))
+            .collect::<Vec<_>>();
+
+        Self::update_duplicate_monitor_serial_ids(&serial_number_ids);
+
+        'read: for (display, serial_number_id) in displays.into_iter().zip(serial_number_ids) {
             let path = display.device_path.clone();
 
             let (device, device_id) = if path.is_empty() {
@@ -283,37 +393,36 @@ impl WindowsApi {
                 name,
                 device,
                 device_id,
+                serial_number_id,
             );
 
             let mut index_preference = None;
             let monitor_index_preferences = MONITOR_INDEX_PREFERENCES.lock();
             for (index, monitor_size) in &*monitor_index_preferences {
                 if m.size() == monitor_size {
-                    index_preference = Option::from(index);
+                    index_preference = Option::from(*index);
                 }
             }
 
             let display_index_preferences = DISPLAY_INDEX_PREFERENCES.lock();
-            for (index, id) in &*display_index_preferences {
-                if id.eq(m.device_id()) {
-                    index_preference = Option::from(index);
-                }
+            if let Some(index) = m.display_index_preference(&display_index_preferences) {
+                index_preference = Option::from(index);
             }
 
             if let Some(preference) = index_preference {
-                while *preference >= monitors.elements().len() {
+                while preference >= monitors.elements().len() {
                     monitors.elements_mut().push_back(Monitor::placeholder());
                 }
 
                 let current_name = monitors
                     .elements_mut()
-                    .get(*preference)
+                    .get(preference)
                     .map_or("", |m| m.name());
                 if current_name == "PLACEHOLDER" {
-                    let _ = monitors.elements_mut().remove(*preference);
-                    monitors.elements_mut().insert(*preference, m);
+                    let _ = monitors.elements_mut().remove(preference);
+                    monitors.elements_mut().insert(preference, m);
                 } else {
-                    monitors.elements_mut().insert(*preference, m);
+                    monitors.elements_mut().insert(preference, m);
                 }
             } else {
                 monitors.elements_mut().push_back(m);
@@ -392,6 +501,13 @@ impl WindowsApi {
         unsafe { MonitorFromPoint(point, MONITOR_DEFAULTTONEAREST) }.0 as isize
     }
 
+    pub fn monitor_from_rect(rect: &Rect) -> isize {
+        // MONITOR_DEFAULTTONEAREST ensures that the return value will never be NULL
+        // https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-monitorfromrect
+        let rect = RECT::from(*rect);
+        unsafe { MonitorFromRect(&rect, MONITOR_DEFAULTTONEAREST) }.0 as isize
+    }
+
     /// position window resizes the target window to the given layout, adjusting
     /// the layout to account for any window shadow borders (the window painted
     /// region will match layout on completion).
@@ -451,6 +567,27 @@ impl WindowsApi {
         )
     }
 
+    /// Sets or clears the sticky WS_EX_TOPMOST z-order of a window without moving, resizing or
+    /// activating it
+    pub fn set_always_on_top(hwnd: isize, always_on_top: bool) -> Result<()> {
+        let flags = SetWindowPosition::NO_MOVE
+            | SetWindowPosition::NO_SIZE
+            | SetWindowPosition::NO_ACTIVATE;
+
+        let position = if always_on_top {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+
+        Self::set_window_pos(
+            HWND(as_ptr!(hwnd)),
+            &Rect::default(),
+            position,
+            flags.bits(),
+        )
+    }
+
     pub fn set_border_pos(hwnd: isize, layout: &Rect, position: isize) -> Result<()> {
         let flags = {
             SetWindowPosition::NO_SEND_CHANGING
@@ -513,6 +650,36 @@ impl WindowsApi {
         unsafe { PostMessageW(hwnd, message, wparam, lparam) }.process()
     }
 
+    /// The smallest visible width and height that a window will allow itself to be resized to,
+    /// as reported by its response to WM_GETMINMAXINFO
+    pub fn minimum_size(hwnd: isize) -> Result<(i32, i32)> {
+        let mut info = MINMAXINFO::default();
+
+        let result = unsafe {
+            SendMessageTimeoutW(
+                HWND(as_ptr!(hwnd)),
+                WM_GETMINMAXINFO,
+                WPARAM(0),
+                LPARAM(std::ptr::addr_of_mut!(info) as isize),
+                SMTO_ABORTIFHUNG,
+                50,
+                None,
+            )
+        };
+
+        if result.0 == 0 {
+            return Err(anyhow!("could not query the minimum size of window {hwnd}"));
+        }
+
+        // The tracking size includes any invisible shadow borders, which layouts do not
+        let shadow_rect = Self::shadow_rect(HWND(as_ptr!(hwnd))).unwrap_or_default();
+
+        Ok((
+            info.ptMinTrackSize.x - shadow_rect.right,
+            info.ptMinTrackSize.y - shadow_rect.bottom,
+        ))
+    }
+
     pub fn close_window(hwnd: isize) -> Result<()> {
         match Self::post_message(HWND(as_ptr!(hwnd)), WM_CLOSE, WPARAM(0), LPARAM(0)) {
             Ok(()) => Ok(()),
@@ -567,7 +734,6 @@ impl WindowsApi {
         .process()
     }
 
-    #[allow(dead_code)]
     pub fn top_window() -> Result<isize> {
         unsafe { GetTopWindow(HWND::default())? }.process()
     }
@@ -576,11 +742,41 @@ impl WindowsApi {
         unsafe { GetDesktopWindow() }.process()
     }
 
-    #[allow(dead_code)]
     pub fn next_window(hwnd: isize) -> Result<isize> {
         unsafe { GetWindow(HWND(as_ptr!(hwnd)), GW_HWNDNEXT)? }.process()
     }
 
+    pub fn previous_window(hwnd: isize) -> Result<isize> {
+        unsafe { GetWindow(HWND(as_ptr!(hwnd)), GW_HWNDPREV)? }.process()
+    }
+
+    /// Sorts window handles from the top of the z-order to the bottom, leaving any handles which
+    /// are not found among the top-level windows at the end
+    pub fn sort_by_z_order(hwnds: &mut [isize]) {
+        if hwnds.len() < 2 {
+            return;
+        }
+
+        let mut positions = HashMap::new();
+        let mut current = Self::top_window();
+        let mut position = 0_usize;
+
+        while let Ok(hwnd) = current {
+            if hwnds.contains(&hwnd) {
+                positions.insert(hwnd, position);
+
+                if positions.len() == hwnds.len() {
+                    break;
+                }
+            }
+
+            position += 1;
+            current = Self::next_window(hwnd);
+        }
+
+        hwnds.sort_by_key(|hwnd| positions.get(hwnd).copied().unwrap_or(usize::MAX));
+    }
+
     pub fn alt_tab_windows() -> Result<Vec<Window>> {
         let mut hwnds = vec![];
         Self::enum_windows(
@@ -591,6 +787,16 @@ impl WindowsApi {
         Ok(hwnds)
     }
 
+    pub fn visible_windows() -> Result<Vec<Window>> {
+        let mut hwnds = vec![];
+        Self::enum_windows(
+            Some(windows_callbacks::visible_windows),
+            &mut hwnds as *mut Vec<Window> as isize,
+        )?;
+
+        Ok(hwnds)
+    }
+
     #[allow(dead_code)]
     pub fn top_visible_window() -> Result<isize> {
         let hwnd = Self::top_window()?;
@@ -684,6 +890,19 @@ impl WindowsApi {
         Self::set_cursor_pos(rect.left + (rect.right / 2), rect.top + (rect.bottom / 2))
     }
 
+    /// Whether mouse follows focus should move the cursor when focus moves from the
+    /// `previous` window to a target on the `target` monitor handle, according to the
+    /// configured mouse follows focus scope
+    pub fn should_follow_focus(previous: Option<isize>, target: isize) -> bool {
+        match *MOUSE_FOLLOWS_FOCUS_SCOPE.lock() {
+            MouseFollowsFocusScope::Always => true,
+            MouseFollowsFocusScope::Never => false,
+            MouseFollowsFocusScope::CrossMonitorOnly => {
+                previous.is_none_or(|hwnd| Self::monitor_from_window(hwnd) != target)
+            }
+        }
+    }
+
     pub fn window_thread_process_id(hwnd: isize) -> (u32, u32) {
         let mut process_id: u32 = 0;
 
@@ -825,6 +1044,28 @@ impl WindowsApi {
         Self::open_process(PROCESS_QUERY_INFORMATION, false, process_id)
     }
 
+    /// The id of the process which created the given process, without having to take a snapshot
+    /// of every running process
+    pub fn parent_process_id(process_id: u32) -> Result<u32> {
+        let handle = Self::open_process(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id)?;
+
+        let mut information = PROCESS_BASIC_INFORMATION::default();
+        let status = unsafe {
+            NtQueryInformationProcess(
+                handle,
+                ProcessBasicInformation,
+                std::ptr::addr_of_mut!(information).cast(),
+                u32::try_from(std::mem::size_of::<PROCESS_BASIC_INFORMATION>())?,
+                std::ptr::null_mut(),
+            )
+        };
+
+        Self::close_process(handle)?;
+        status.ok()?;
+
+        Ok(u32::try_from(information.InheritedFromUniqueProcessId)?)
+    }
+
     pub fn exe_path(handle: HANDLE) -> Result<String> {
         let mut len = 260_u32;
         let mut path: Vec<u16> = vec![0; len as usize];
@@ -888,6 +1129,10 @@ impl WindowsApi {
         unsafe { IsWindow(HWND(as_ptr!(hwnd))) }.into()
     }
 
+    pub fn is_hung_app_window(hwnd: isize) -> bool {
+        unsafe { IsHungAppWindow(HWND(as_ptr!(hwnd))) }.into()
+    }
+
     pub fn is_window_visible(hwnd: isize) -> bool {
         unsafe { IsWindowVisible(HWND(as_ptr!(hwnd))) }.into()
     }
@@ -936,6 +1181,7 @@ impl WindowsApi {
                     name,
                     device,
                     device_id,
+                    Self::monitor_serial_number_id(&display.device_path),
                 );
 
                 return Ok(monitor);
@@ -1043,7 +1289,7 @@ impl WindowsApi {
         Result::from(WindowsResult::from(unsafe { RegisterClassW(window_class) }))
     }
 
-    pub fn dpi_for_monitor(hmonitor: isize) -> Result<f32> {
+    pub fn effective_dpi_for_monitor(hmonitor: isize) -> Result<u32> {
         let mut dpi_x = u32::default();
         let mut dpi_y = u32::default();
 
@@ -1057,8 +1303,27 @@ impl WindowsApi {
         }
         .process()?;
 
+        Ok(dpi_y)
+    }
+
+    pub fn system_dpi() -> u32 {
+        unsafe { GetDpiForSystem() }
+    }
+
+    /// Whether the shell reports that the user should not be disturbed, because a fullscreen
+    /// application, a Direct3D game or presentation mode is currently in use
+    pub fn is_presenting() -> Result<bool> {
+        let state = unsafe { SHQueryUserNotificationState() }?;
+
+        Ok(matches!(
+            state,
+            QUNS_BUSY | QUNS_PRESENTATION_MODE | QUNS_RUNNING_D3D_FULL_SCREEN
+        ))
+    }
+
+    pub fn dpi_for_monitor(hmonitor: isize) -> Result<f32> {
         #[allow(clippy::cast_precision_loss)]
-        Ok(dpi_y as f32 / 96.0)
+        Ok(Self::effective_dpi_for_monitor(hmonitor)? as f32 / 96.0)
     }
 
     pub fn monitors_have_same_dpi(hmonitor_a: isize, hmonitor_b: isize) -> Result<bool> {
@@ -1180,6 +1445,22 @@ impl WindowsApi {
         actual != 0
     }
 
+    pub fn drag_modifier_is_pressed(modifier: DragModifier) -> bool {
+        let keys = match modifier {
+            DragModifier::Alt => vec![VK_MENU],
+            DragModifier::Ctrl => vec![VK_CONTROL],
+            DragModifier::Shift => vec![VK_SHIFT],
+            DragModifier::Win => vec![VK_LWIN, VK_RWIN],
+        };
+
+        keys.into_iter().any(|key| {
+            let state = unsafe { GetKeyState(i32::from(key.0)) };
+            #[allow(clippy::cast_sign_loss)]
+            let actual = (state as u16) & 0x8000;
+            actual != 0
+        })
+    }
+
     pub fn lbutton_is_pressed() -> bool {
         let state = unsafe { GetKeyState(i32::from(VK_LBUTTON.0)) };
         #[allow(clippy::cast_sign_loss)]
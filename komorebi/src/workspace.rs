@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 
 use color_eyre::eyre::anyhow;
@@ -11,33 +14,57 @@ use getset::Setters;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use serde::Serializer;
 
+use crate::core::arrangement::redistribute_minimum_sizes;
+use crate::core::resolve_home_path;
 use crate::core::Axis;
 use crate::core::CustomLayout;
 use crate::core::CycleDirection;
 use crate::core::DefaultLayout;
+use crate::core::HidingBehaviour;
 use crate::core::Layout;
+use crate::core::MinimumSizePolicy;
+use crate::core::NewWindowPlacement;
 use crate::core::OperationDirection;
 use crate::core::Rect;
-
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
+use crate::core::ScrollingLayoutOptions;
+use crate::core::Sizing;
+use crate::core::WindowBasedWorkAreaOffsetCounting;
+use crate::core::WindowKind;
+use crate::core::WorkspaceLayer;
+
+use crate::border_manager;
+use crate::border_manager::TILED_WINDOW_KINDS;
 use crate::container::Container;
+use crate::custom_layout_watcher;
 use crate::ring::Ring;
 use crate::should_act;
-use crate::stackbar_manager;
-use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
+use crate::should_act_with_index;
+use crate::stackbar_manager::StackbarOptions;
 use crate::static_config::WorkspaceConfig;
+use crate::static_config::WorkspaceStackbarConfig;
+use crate::transparency_manager;
 use crate::window::Window;
 use crate::window::WindowDetails;
+use crate::window_system::window_system;
 use crate::windows_api::WindowsApi;
 use crate::WindowContainerBehaviour;
+use crate::AUTO_STACK_BY_EXE;
+use crate::AUTO_STACK_EXEMPT_HWNDS;
+use crate::AUTO_STACK_GROUPS;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
+use crate::HIDDEN_HWNDS;
+use crate::HIDING_BEHAVIOUR;
 use crate::INITIAL_CONFIGURATION_LOADED;
+use crate::MINIMUM_SIZE_POLICY;
 use crate::NO_TITLEBAR;
+use crate::PINNED_HWNDS;
 use crate::REGEX_IDENTIFIERS;
 use crate::REMOVE_TITLEBARS;
+use crate::SWALLOWING_RULES;
+use crate::TILE_ORIGINS;
 
 #[allow(clippy::struct_field_names)]
 #[derive(
@@ -53,7 +80,7 @@ use crate::REMOVE_TITLEBARS;
     PartialEq,
 )]
 pub struct Workspace {
-    #[getset(get = "pub", set = "pub")]
+    #[getset(get = "pub")]
     name: Option<String>,
     containers: Ring<Container>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
@@ -74,10 +101,21 @@ pub struct Workspace {
     layout_rules: Vec<(usize, Layout)>,
     #[getset(get_copy = "pub", set = "pub")]
     layout_flip: Option<Axis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    scrolling_options: Option<ScrollingLayoutOptions>,
     #[getset(get_copy = "pub", set = "pub")]
     workspace_padding: Option<i32>,
     #[getset(get_copy = "pub", set = "pub")]
     container_padding: Option<i32>,
+    // Whether the paddings have been set specifically for this workspace, rather than inherited
+    // from the monitor or global defaults
+    #[serde(skip)]
+    #[getset(get_copy = "pub", set = "pub")]
+    explicit_workspace_padding: bool,
+    #[serde(skip)]
+    #[getset(get_copy = "pub", set = "pub")]
+    explicit_container_padding: bool,
     #[getset(get = "pub", set = "pub")]
     latest_layout: Vec<Rect>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
@@ -86,14 +124,104 @@ pub struct Workspace {
     tile: bool,
     #[getset(get_copy = "pub", set = "pub")]
     apply_window_based_work_area_offset: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    window_based_work_area_offset_counting: Option<WindowBasedWorkAreaOffsetCounting>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     window_container_behaviour: Option<WindowContainerBehaviour>,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     float_override: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    auto_stack_by_exe: Option<bool>,
+    #[serde(default)]
+    #[getset(get_copy = "pub", set = "pub")]
+    layer: WorkspaceLayer,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    new_window_placement: Option<NewWindowPlacement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    window_hiding_behaviour: Option<HidingBehaviour>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    focus_follows_mouse: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub", set = "pub")]
+    wallpaper: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    stackbar_overrides: Option<WorkspaceStackbarConfig>,
+    // The geometry from the last time this workspace was laid out, which is None if it has not
+    // been laid out since it was created or since the monitor work area last changed
+    #[serde(default)]
+    #[getset(get = "pub", set = "pub")]
+    preview: Option<WorkspacePreview>,
+    // Ids of containers ordered from the most to the least recently focused
+    #[serde(default)]
+    #[getset(get = "pub")]
+    container_focus_history: VecDeque<String>,
+    // Ids of containers which should always occupy a specific column in column based layouts
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[getset(get = "pub")]
+    column_pins: HashMap<String, usize>,
+    // Whether the name was taken from the focused window rather than set explicitly
+    #[serde(default)]
+    #[getset(get_copy = "pub")]
+    auto_named: bool,
+    // Ids of containers which have been hidden because they have scrolled out of the work area
+    // in the Scrolling layout
+    #[serde(skip)]
+    scrolled_out_of_view: HashSet<String>,
+    // The files that custom layouts were loaded from, keyed by the container count of the layout
+    // rule they were loaded for, or None for the workspace layout itself
+    #[serde(skip)]
+    #[getset(get = "pub", get_mut = "pub")]
+    custom_layout_paths: HashMap<Option<usize>, PathBuf>,
 }
 
 impl_ring_elements!(Workspace, Container);
 
+/// The number of containers remembered in the focus history of a workspace
+const CONTAINER_FOCUS_HISTORY_LIMIT: usize = 10;
+
+/// The last applied geometry of a workspace, for drawing miniature previews of its layout
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct WorkspacePreview {
+    /// The rect of each container, in the same order as the workspace's containers
+    pub containers: Vec<Rect>,
+    /// The rect of the monocle container, if there is one
+    pub monocle_container: Option<Rect>,
+    /// The rect of each floating window, ordered from the top of the z-order to the bottom
+    #[serde(serialize_with = "serialize_by_z_order")]
+    pub floating_windows: Vec<FloatingWindowPreview>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct FloatingWindowPreview {
+    pub hwnd: isize,
+    pub rect: Rect,
+}
+
+// The z-order changes without workspaces being laid out again, so floating window previews are
+// only sorted when the state is serialized rather than every time a workspace is updated
+fn serialize_by_z_order<S: Serializer>(
+    previews: &[FloatingWindowPreview],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let mut hwnds = previews
+        .iter()
+        .map(|preview| preview.hwnd)
+        .collect::<Vec<_>>();
+    WindowsApi::sort_by_z_order(&mut hwnds);
+
+    serializer.collect_seq(
+        hwnds
+            .iter()
+            .filter_map(|hwnd| previews.iter().find(|preview| preview.hwnd == *hwnd)),
+    )
+}
+
 impl Default for Workspace {
     fn default() -> Self {
         Self {
@@ -107,14 +235,31 @@ impl Default for Workspace {
             layout: Layout::Default(DefaultLayout::BSP),
             layout_rules: vec![],
             layout_flip: None,
+            scrolling_options: None,
             workspace_padding: Option::from(DEFAULT_WORKSPACE_PADDING.load(Ordering::SeqCst)),
             container_padding: Option::from(DEFAULT_CONTAINER_PADDING.load(Ordering::SeqCst)),
+            explicit_workspace_padding: false,
+            explicit_container_padding: false,
             latest_layout: vec![],
             resize_dimensions: vec![],
             tile: true,
             apply_window_based_work_area_offset: true,
+            window_based_work_area_offset_counting: None,
             window_container_behaviour: None,
             float_override: None,
+            auto_stack_by_exe: None,
+            layer: WorkspaceLayer::default(),
+            new_window_placement: None,
+            window_hiding_behaviour: None,
+            focus_follows_mouse: None,
+            wallpaper: None,
+            stackbar_overrides: None,
+            preview: None,
+            container_focus_history: VecDeque::default(),
+            column_pins: HashMap::default(),
+            scrolled_out_of_view: HashSet::default(),
+            auto_named: false,
+            custom_layout_paths: HashMap::default(),
         }
     }
 }
@@ -127,9 +272,17 @@ pub enum WorkspaceWindowLocation {
     Floating(usize),         // idx in floating_windows
 }
 
+/// Where a window was tiled before it was floated or unmanaged, so that it can be put back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileOrigin {
+    pub container_idx: usize,
+    /// The index of the window within its container, if it was part of a stack
+    pub window_idx: Option<usize>,
+}
+
 impl Workspace {
     pub fn load_static_config(&mut self, config: &WorkspaceConfig) -> Result<()> {
-        self.name = Option::from(config.name.clone());
+        self.set_name(Option::from(config.name.clone()));
 
         if config.container_padding.is_some() {
             self.set_container_padding(config.container_padding);
@@ -139,6 +292,10 @@ impl Workspace {
             self.set_workspace_padding(config.workspace_padding);
         }
 
+        // Workspaces without their own paddings inherit them from the monitor
+        self.set_explicit_container_padding(config.container_padding.is_some());
+        self.set_explicit_workspace_padding(config.workspace_padding.is_some());
+
         if let Some(layout) = &config.layout {
             self.layout = Layout::Default(*layout);
             self.tile = true;
@@ -146,6 +303,8 @@ impl Workspace {
 
         if let Some(pathbuf) = &config.custom_layout {
             let layout = CustomLayout::from_path(pathbuf)?;
+            custom_layout_watcher::register(pathbuf, &layout);
+            self.custom_layout_paths.insert(None, pathbuf.clone());
             self.layout = Layout::Custom(layout);
             self.tile = true;
         }
@@ -166,10 +325,12 @@ impl Workspace {
         }
 
         if let Some(layout_rules) = &config.custom_layout_rules {
-            let rules = self.layout_rules_mut();
             for (count, pathbuf) in layout_rules {
                 let rule = CustomLayout::from_path(pathbuf)?;
-                rules.push((*count, Layout::Custom(rule)));
+                custom_layout_watcher::register(pathbuf, &rule);
+                self.custom_layout_paths
+                    .insert(Some(*count), pathbuf.clone());
+                self.layout_rules.push((*count, Layout::Custom(rule)));
             }
 
             self.tile = true;
@@ -179,6 +340,11 @@ impl Workspace {
             config.apply_window_based_work_area_offset.unwrap_or(true),
         );
 
+        // Removing the override from the configuration should restore the monitor's counting
+        self.set_window_based_work_area_offset_counting(
+            config.window_based_work_area_offset_counting,
+        );
+
         if config.window_container_behaviour.is_some() {
             self.set_window_container_behaviour(config.window_container_behaviour);
         }
@@ -187,15 +353,65 @@ impl Workspace {
             self.set_float_override(config.float_override);
         }
 
-        if config.layout_flip.is_some() {
-            self.set_layout_flip(config.layout_flip);
-        }
+        self.set_auto_stack_by_exe(config.auto_stack_by_exe);
+
+        self.set_new_window_placement(config.new_window_placement);
+
+        // Removing the flip from the configuration should unflip the layout on reload
+        self.set_layout_flip(config.layout_flip);
+
+        self.set_scrolling_options(config.scrolling);
+
+        self.set_window_hiding_behaviour(config.window_hiding_behaviour);
+
+        self.set_wallpaper(
+            config
+                .wallpaper
+                .as_ref()
+                .map(resolve_home_path)
+                .transpose()?,
+        );
+
+        // Removing the override from the configuration should restore the global behaviour
+        self.set_focus_follows_mouse(config.focus_follows_mouse);
+
+        self.set_stackbar_overrides(config.stackbar);
 
         Ok(())
     }
 
+    /// The stackbar options for this workspace, falling back to the global values for anything
+    /// which has not been overridden
+    pub fn stackbar_options(&self) -> StackbarOptions {
+        StackbarOptions::with_overrides(self.stackbar_overrides)
+    }
+
+    /// Whether the container is a column of the Scrolling layout which has been hidden because it
+    /// has scrolled out of the work area
+    pub fn is_scrolled_out_of_view(&self, container: &Container) -> bool {
+        self.scrolled_out_of_view.contains(container.id())
+    }
+
+    /// The hiding behaviour for this workspace, falling back to the global default
+    pub fn hiding_behaviour(&self) -> HidingBehaviour {
+        self.window_hiding_behaviour
+            .unwrap_or_else(|| *HIDING_BEHAVIOUR.lock())
+    }
+
     pub fn hide(&mut self, omit: Option<isize>) {
+        let hiding_behaviour = self.hiding_behaviour();
+
+        // Every container is hidden from here on, so none of them need to be shown again when
+        // they scroll back into view
+        self.scrolled_out_of_view.clear();
+        let pinned_hwnds = PINNED_HWNDS.lock().clone();
+
         for window in self.floating_windows_mut().iter_mut().rev() {
+            // Pinned windows stay visible across workspace switches
+            if pinned_hwnds.contains(&window.hwnd) {
+                continue;
+            }
+
             let mut should_hide = omit.is_none();
 
             if !should_hide {
@@ -207,27 +423,43 @@ impl Workspace {
             }
 
             if should_hide {
-                window.hide();
+                window.hide_with_behaviour(hiding_behaviour);
             }
         }
 
         for container in self.containers_mut() {
-            container.hide(omit)
+            container.hide_with_behaviour(omit, hiding_behaviour)
         }
 
         if let Some(window) = self.maximized_window() {
-            window.hide();
+            window.hide_with_behaviour(hiding_behaviour);
         }
 
         if let Some(container) = self.monocle_container_mut() {
-            container.hide(omit)
+            container.hide_with_behaviour(omit, hiding_behaviour)
+        }
+    }
+
+    /// Hides the windows of this workspace again using its current hiding behaviour, after they
+    /// were hidden using `previous_behaviour`
+    pub fn rehide(&mut self, previous_behaviour: HidingBehaviour) {
+        if previous_behaviour == self.hiding_behaviour() {
+            return;
         }
+
+        for window in self.all_windows() {
+            window.restore_with_behaviour(previous_behaviour);
+        }
+
+        self.hide(None);
     }
 
     pub fn restore(&mut self, mouse_follows_focus: bool) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
+
         if let Some(container) = self.monocle_container_mut() {
             if let Some(window) = container.focused_window() {
-                container.restore();
+                container.restore_with_behaviour(hiding_behaviour);
                 window.focus(mouse_follows_focus)?;
                 return Ok(());
             }
@@ -243,7 +475,7 @@ impl Workspace {
                 }
             }
 
-            container.restore();
+            container.restore_with_behaviour(hiding_behaviour);
         }
 
         if let Some(container) = self.focused_container_mut() {
@@ -251,37 +483,131 @@ impl Workspace {
         }
 
         for window in self.floating_windows() {
-            window.restore();
+            window.restore_with_behaviour(hiding_behaviour);
         }
 
+        self.restore_maximized_window(hiding_behaviour);
+
         // Do this here to make sure that an error doesn't stop the restoration of other windows
         // Maximised windows and floating windows should always be drawn at the top of the Z order
         // when switching to a workspace
-        if let Some(window) = to_focus {
-            if self.maximized_window().is_none() && self.floating_windows().is_empty() {
-                window.focus(mouse_follows_focus)?;
-            } else if let Some(maximized_window) = self.maximized_window() {
-                maximized_window.focus(mouse_follows_focus)?;
-            } else if let Some(floating_window) = self.floating_windows().first() {
+        if let Some(maximized_window) = self.maximized_window() {
+            maximized_window.focus(mouse_follows_focus)?;
+        } else if let Some(window) = to_focus {
+            if let Some(floating_window) = self.floating_windows().first() {
                 floating_window.focus(mouse_follows_focus)?;
+            } else {
+                window.focus(mouse_follows_focus)?;
             }
         }
 
         Ok(())
     }
 
+    /// Restores the windows of this workspace in the same way as restore, but leaves the
+    /// foreground window as it is
+    pub fn restore_without_focus(&mut self) {
+        let hiding_behaviour = self.hiding_behaviour();
+
+        if let Some(container) = self.monocle_container() {
+            container.restore_with_behaviour(hiding_behaviour);
+            return;
+        }
+
+        for container in self.containers() {
+            container.restore_with_behaviour(hiding_behaviour);
+        }
+
+        for window in self.floating_windows() {
+            window.restore_with_behaviour(hiding_behaviour);
+        }
+
+        self.restore_maximized_window(hiding_behaviour);
+    }
+
+    /// Shows every window on this workspace, removes any transparency applied to them and stops
+    /// tracking them, so that they are left as if they had never been managed
+    pub fn release_windows(&mut self) {
+        let hiding_behaviour = self.hiding_behaviour();
+        let transparent_hwnds = transparency_manager::known_hwnds();
+
+        let mut windows = vec![];
+        for container in self.containers() {
+            windows.extend(container.windows().iter().copied());
+        }
+
+        if let Some(container) = self.monocle_container() {
+            windows.extend(container.windows().iter().copied());
+        }
+
+        if let Some(window) = self.maximized_window() {
+            windows.push(*window);
+        }
+
+        windows.extend(self.floating_windows().iter().copied());
+
+        for window in windows {
+            window.restore_with_behaviour(hiding_behaviour);
+
+            if transparent_hwnds.contains(&window.hwnd) {
+                if let Err(error) = window.opaque() {
+                    tracing::error!("failed to make window {} opaque: {error}", window.hwnd);
+                }
+            }
+        }
+
+        self.containers_mut().clear();
+        self.containers.focus(0);
+        self.set_monocle_container(None);
+        self.set_monocle_container_restore_idx(None);
+        self.set_maximized_window(None);
+        self.set_maximized_window_restore_idx(None);
+        self.floating_windows_mut().clear();
+        self.resize_dimensions_mut().clear();
+    }
+
+    /// Shows the maximized window of this workspace maximized again, forgetting it instead if it
+    /// was closed while the workspace was hidden
+    fn restore_maximized_window(&mut self, hiding_behaviour: HidingBehaviour) {
+        let Some(window) = self.maximized_window().copied() else {
+            return;
+        };
+
+        if window.is_window() {
+            window.restore_maximized_with_behaviour(hiding_behaviour);
+        } else {
+            self.set_maximized_window(None);
+            self.set_maximized_window_restore_idx(None);
+        }
+    }
+
     pub fn update(
         &mut self,
         work_area: &Rect,
         work_area_offset: Option<Rect>,
-        window_based_work_area_offset: (isize, Option<Rect>),
+        window_based_work_area_offset: (isize, Option<Rect>, WindowBasedWorkAreaOffsetCounting),
     ) -> Result<()> {
         if !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
             return Ok(());
         }
 
-        let (window_based_work_area_offset_limit, window_based_work_area_offset) =
-            window_based_work_area_offset;
+        let (
+            window_based_work_area_offset_limit,
+            window_based_work_area_offset,
+            window_based_work_area_offset_counting,
+        ) = window_based_work_area_offset;
+
+        let window_count = match self
+            .window_based_work_area_offset_counting
+            .unwrap_or(window_based_work_area_offset_counting)
+        {
+            WindowBasedWorkAreaOffsetCounting::Containers => self.containers().len(),
+            WindowBasedWorkAreaOffsetCounting::VisibleWindows => self
+                .containers()
+                .iter()
+                .map(|container| container.windows().len())
+                .sum(),
+        };
 
         let container_padding = self.container_padding();
         let mut adjusted_work_area = work_area_offset.map_or_else(
@@ -297,7 +623,7 @@ impl Workspace {
             },
         );
 
-        if (self.containers().len() <= window_based_work_area_offset_limit as usize
+        if (window_count <= window_based_work_area_offset_limit as usize
             || self.monocle_container().is_some() && window_based_work_area_offset_limit > 0)
             && self.apply_window_based_work_area_offset
         {
@@ -337,24 +663,40 @@ impl Workspace {
             }
         }
 
+        self.enforce_column_pins();
+
         let managed_maximized_window = self.maximized_window().is_some();
+        let mut preview = WorkspacePreview::default();
 
         if *self.tile() {
             if let Some(container) = self.monocle_container_mut() {
                 if let Some(window) = container.focused_window_mut() {
                     adjusted_work_area.add_padding(container_padding.unwrap_or_default());
-                    {
-                        let border_offset = BORDER_OFFSET.load(Ordering::SeqCst);
-                        adjusted_work_area.add_padding(border_offset);
-                        let width = BORDER_WIDTH.load(Ordering::SeqCst);
-                        adjusted_work_area.add_padding(width);
-                    }
+                    adjusted_work_area.add_padding(border_manager::border_padding(&[
+                        WindowKind::Monocle,
+                        WindowKind::Unfocused,
+                    ]));
                     window.set_position(&adjusted_work_area, true)?;
+                    preview.monocle_container = Option::from(adjusted_work_area);
                 };
             } else if let Some(window) = self.maximized_window_mut() {
                 window.maximize();
             } else if !self.containers().is_empty() {
-                let mut layouts = self.layout().as_boxed_arrangement().calculate(
+                // DPI-aware custom layouts express fixed column widths in DPI-independent units
+                // which must be scaled for the monitor that this workspace is being laid out on
+                let layout = match self.layout() {
+                    Layout::Custom(custom) if custom.dpi_aware() => {
+                        let scale_factor = WindowsApi::dpi_for_monitor(
+                            window_system().monitor_from_rect(work_area),
+                        )
+                        .unwrap_or(1.0);
+
+                        Layout::Custom(custom.scaled(scale_factor))
+                    }
+                    layout => layout.clone(),
+                };
+
+                let mut layouts = layout.as_boxed_arrangement().calculate(
                     &adjusted_work_area,
                     NonZeroUsize::new(self.containers().len()).ok_or_else(|| {
                         anyhow!(
@@ -364,6 +706,9 @@ impl Workspace {
                     self.container_padding(),
                     self.layout_flip(),
                     self.resize_dimensions(),
+                    self.focused_container_idx(),
+                    self.latest_layout(),
+                    self.scrolling_options(),
                 );
 
                 let should_remove_titlebars = REMOVE_TITLEBARS.load(Ordering::SeqCst);
@@ -371,28 +716,119 @@ impl Workspace {
                 let regex_identifiers = REGEX_IDENTIFIERS.lock().clone();
 
                 let container_padding = self.container_padding().unwrap_or(0);
+                // Every container is padded for the largest tiled border, regardless of the kind
+                // of border it currently has
+                let border_padding = border_manager::border_padding(&TILED_WINDOW_KINDS);
+                let stackbar_options = self.stackbar_options();
+                let hiding_behaviour = self.hiding_behaviour();
+                let is_scrolling = matches!(layout, Layout::Default(DefaultLayout::Scrolling));
+                let mut scrolled_out_of_view = std::mem::take(&mut self.scrolled_out_of_view);
                 let containers = self.containers_mut();
 
+                // Stackbars are scaled for the DPI of the monitor they are drawn on
+                let stackbar_tab_height = WindowsApi::effective_dpi_for_monitor(
+                    window_system().monitor_from_rect(work_area),
+                )
+                .map_or_else(
+                    |_| stackbar_options.height,
+                    |dpi| stackbar_options.tab_height_for_dpi(dpi),
+                );
+
+                // The minimum size of the focused window of each container, which is only
+                // queried when a policy has been set for windows which cannot fit their tiles
+                let minimum_size_policy = *MINIMUM_SIZE_POLICY.lock();
+                let minimum_sizes = if minimum_size_policy.is_some() {
+                    containers
+                        .iter()
+                        .map(|container| {
+                            container
+                                .focused_window()
+                                .and_then(|window| window.minimum_size())
+                        })
+                        .collect::<Vec<_>>()
+                } else {
+                    vec![]
+                };
+
+                if matches!(minimum_size_policy, Some(MinimumSizePolicy::Redistribute)) {
+                    // Tiles also have to fit the border padding and any stackbar around the window
+                    let tile_minimum_sizes = containers
+                        .iter()
+                        .zip(minimum_sizes.iter())
+                        .map(|(container, minimum_size)| {
+                            minimum_size.map(|(width, height)| {
+                                let stackbar_height = if stackbar_options
+                                    .should_have_stackbar(container.windows().len())
+                                {
+                                    stackbar_tab_height + container_padding
+                                } else {
+                                    0
+                                };
+
+                                (
+                                    width + border_padding * 2,
+                                    height + border_padding * 2 + stackbar_height,
+                                )
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    redistribute_minimum_sizes(
+                        &mut layouts,
+                        &tile_minimum_sizes,
+                        container_padding * 2 + 1,
+                    );
+                }
+
                 for (i, container) in containers.iter_mut().enumerate() {
                     let window_count = container.windows().len();
 
                     if let Some(layout) = layouts.get_mut(i) {
-                        {
-                            let border_offset = BORDER_OFFSET.load(Ordering::SeqCst);
-                            layout.add_padding(border_offset);
+                        // Columns which have scrolled out of the work area are hidden instead of
+                        // being placed on whichever monitor is next to this one
+                        let is_out_of_view = is_scrolling
+                            && (layout.left + layout.right <= adjusted_work_area.left
+                                || layout.left
+                                    >= adjusted_work_area.left + adjusted_work_area.right);
+
+                        if is_out_of_view {
+                            // Windows which are already hidden, such as those of a workspace
+                            // which is not visible, are not shown again when they scroll back
+                            let is_visible = container
+                                .focused_window()
+                                .is_some_and(|w| !HIDDEN_HWNDS.lock().contains(&w.hwnd));
+
+                            if is_visible {
+                                container.hide_with_behaviour(None, hiding_behaviour);
+                                scrolled_out_of_view.insert(container.id().clone());
+                            }
+
+                            continue;
+                        }
 
-                            let width = BORDER_WIDTH.load(Ordering::SeqCst);
-                            layout.add_padding(width);
+                        if scrolled_out_of_view.remove(container.id()) {
+                            container.restore_with_behaviour(hiding_behaviour);
                         }
 
-                        if stackbar_manager::should_have_stackbar(window_count) {
-                            let tab_height = STACKBAR_TAB_HEIGHT.load(Ordering::SeqCst);
+                        layout.add_padding(border_padding);
+
+                        if stackbar_options.should_have_stackbar(window_count) {
+                            let tab_height = stackbar_tab_height;
                             let total_height = tab_height + container_padding;
 
                             layout.top += total_height;
                             layout.bottom -= total_height;
                         }
 
+                        // Windows which still cannot fit their tiles are grown to their minimum
+                        // size from the top-left of the tile, overlapping their neighbours, so
+                        // that the layout (and the borders and stackbars drawn from it) match
+                        // the rect that the window actually occupies
+                        if let Some(Some((min_width, min_height))) = minimum_sizes.get(i) {
+                            layout.right = layout.right.max(*min_width);
+                            layout.bottom = layout.bottom.max(*min_height);
+                        }
+
                         for window in container.windows() {
                             if container
                                 .focused_window()
@@ -425,10 +861,18 @@ impl Workspace {
                     }
                 }
 
+                // Containers which have since left this workspace are no longer tracked
+                scrolled_out_of_view.retain(|id| self.containers().iter().any(|c| c.id() == id));
+                self.scrolled_out_of_view = scrolled_out_of_view;
+
+                preview.containers.clone_from(&layouts);
                 self.set_latest_layout(layouts);
             }
         }
 
+        preview.floating_windows = self.floating_window_previews();
+        self.set_preview(Option::from(preview));
+
         // Always make sure that the length of the resize dimensions vec is the same as the
         // number of layouts / containers. This should never actually truncate as the remove_window
         // function takes care of cleaning up resize dimensions when destroying empty containers
@@ -438,6 +882,22 @@ impl Workspace {
         Ok(())
     }
 
+    /// The current rects of the floating windows on this workspace
+    fn floating_window_previews(&self) -> Vec<FloatingWindowPreview> {
+        self.floating_windows()
+            .iter()
+            .filter_map(|window| {
+                window_system()
+                    .window_rect(window.hwnd)
+                    .ok()
+                    .map(|rect| FloatingWindowPreview {
+                        hwnd: window.hwnd,
+                        rect,
+                    })
+            })
+            .collect()
+    }
+
     pub fn reap_orphans(&mut self) -> Result<(usize, usize)> {
         let mut hwnds = vec![];
         let mut floating_hwnds = vec![];
@@ -464,7 +924,8 @@ impl Workspace {
             }
         }
 
-        for window in self.visible_windows().into_iter().flatten() {
+        // Every window in a stack is checked, not just the focused one
+        for window in self.containers().iter().flat_map(|c| c.windows()) {
             if !window.is_window() {
                 hwnds.push(window.hwnd);
             }
@@ -512,7 +973,23 @@ impl Workspace {
         self.containers().get(self.container_idx_for_window(hwnd)?)
     }
 
+    /// Unlike container_for_window, this will also return the monocle container if it holds the
+    /// window
+    pub fn container_for_window_mut(&mut self, hwnd: isize) -> Option<&mut Container> {
+        if self
+            .monocle_container
+            .as_ref()
+            .is_some_and(|c| c.contains_window(hwnd))
+        {
+            return self.monocle_container.as_mut();
+        }
+
+        let idx = self.container_idx_for_window(hwnd)?;
+        self.containers_mut().get_mut(idx)
+    }
+
     pub fn focus_container_by_window(&mut self, hwnd: isize) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let container_idx = self
             .container_idx_for_window(hwnd)
             .ok_or_else(|| anyhow!("there is no container/window"))?;
@@ -535,7 +1012,7 @@ impl Workspace {
         container.focus_window(window_idx);
 
         if should_load {
-            container.load_focused_window();
+            container.load_focused_window_with_behaviour(hiding_behaviour);
         }
 
         self.focus_container(container_idx);
@@ -546,7 +1023,7 @@ impl Workspace {
     pub fn container_idx_from_current_point(&self) -> Option<usize> {
         let mut idx = None;
 
-        let point = WindowsApi::cursor_pos().ok()?;
+        let point = window_system().cursor_pos().ok()?;
 
         for (i, _container) in self.containers().iter().enumerate() {
             if let Some(rect) = self.latest_layout().get(i) {
@@ -649,7 +1126,7 @@ impl Workspace {
     }
 
     pub fn is_focused_window_monocle_or_maximized(&self) -> Result<bool> {
-        let hwnd = WindowsApi::foreground_window()?;
+        let hwnd = window_system().foreground_window()?;
         if let Some(window) = self.maximized_window() {
             if hwnd == window.hwnd {
                 return Ok(true);
@@ -665,6 +1142,65 @@ impl Workspace {
         Ok(false)
     }
 
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+        self.auto_named = false;
+    }
+
+    /// The name of the workspace, unless it was taken from the focused window
+    pub fn explicit_name(&self) -> Option<&String> {
+        if self.auto_named {
+            None
+        } else {
+            self.name.as_ref().filter(|name| !name.is_empty())
+        }
+    }
+
+    /// Names the workspace after the application of its focused window, unless it already has
+    /// an explicit name
+    pub fn update_auto_name(&mut self) {
+        if self.explicit_name().is_some() {
+            return;
+        }
+
+        let window = self
+            .maximized_window
+            .or_else(|| {
+                self.monocle_container()
+                    .as_ref()
+                    .and_then(|container| container.focused_window().copied())
+            })
+            .or_else(|| {
+                self.focused_container()
+                    .and_then(|container| container.focused_window().copied())
+            });
+
+        let name = window.and_then(|window| {
+            window
+                .exe()
+                .ok()
+                .map(|exe| exe.trim_end_matches(".exe").to_string())
+                .or_else(|| window.title().ok())
+                .filter(|name| !name.is_empty())
+        });
+
+        match name {
+            Some(name) => {
+                self.name = Option::from(name);
+                self.auto_named = true;
+            }
+            None => self.clear_auto_name(),
+        }
+    }
+
+    /// Removes a name which was taken from the focused window
+    pub fn clear_auto_name(&mut self) {
+        if self.auto_named {
+            self.name = None;
+            self.auto_named = false;
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.containers().is_empty()
             && self.maximized_window().is_none()
@@ -761,7 +1297,49 @@ impl Workspace {
         idx
     }
 
+    /// The container (and, for stacks, in-stack) index of a tiled window on this workspace
+    pub fn tile_origin(&self, hwnd: isize) -> Option<TileOrigin> {
+        let container_idx = self.container_idx_for_window(hwnd)?;
+        let container = self.containers().get(container_idx)?;
+
+        Option::from(TileOrigin {
+            container_idx,
+            window_idx: if container.windows().len() > 1 {
+                container.idx_for_window(hwnd)
+            } else {
+                None
+            },
+        })
+    }
+
+    /// Tile a window where it was before it was floated or unmanaged, stacking it back into its
+    /// original container if it was part of a stack which is still there, with indices clamped
+    /// to the containers which currently exist
+    pub fn restore_window_to_tile_origin(&mut self, window: Window, origin: TileOrigin) {
+        let hiding_behaviour = self.hiding_behaviour();
+
+        if let Some(window_idx) = origin.window_idx {
+            if let Some(container) = self.containers_mut().get_mut(origin.container_idx) {
+                let window_idx = window_idx.min(container.windows().len());
+                container.windows_mut().insert(window_idx, window);
+                container.focus_window(window_idx);
+                container.load_focused_window_with_behaviour(hiding_behaviour);
+                self.focus_container(origin.container_idx);
+
+                return;
+            }
+        }
+
+        let container_idx = origin.container_idx.min(self.containers().len());
+        let mut container = Container::default();
+        container.add_window(window, hiding_behaviour);
+        self.containers_mut().insert(container_idx, container);
+        self.resize_dimensions_mut().insert(container_idx, None);
+        self.focus_container(container_idx);
+    }
+
     pub fn remove_window(&mut self, hwnd: isize) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         if self.floating_windows().iter().any(|w| w.hwnd == hwnd) {
             self.floating_windows_mut().retain(|w| w.hwnd != hwnd);
             return Ok(());
@@ -783,7 +1361,7 @@ impl Workspace {
                 }
 
                 for c in self.containers() {
-                    c.restore();
+                    c.restore_with_behaviour(hiding_behaviour);
                 }
 
                 return Ok(());
@@ -818,6 +1396,15 @@ impl Workspace {
             .remove_window_by_idx(window_idx)
             .ok_or_else(|| anyhow!("there is no window"))?;
 
+        // A window which swallowed its parent gives the tile back to the parent when it is closed
+        if container.windows().is_empty() {
+            if let Some(swallowed) = container.take_swallowed_window() {
+                if swallowed.is_window() {
+                    container.add_window(swallowed, hiding_behaviour);
+                }
+            }
+        }
+
         if container.windows().is_empty() {
             self.containers_mut()
                 .remove(container_idx)
@@ -830,7 +1417,7 @@ impl Workspace {
 
             self.focus_previous_container();
         } else {
-            container.load_focused_window();
+            container.load_focused_window_with_behaviour(hiding_behaviour);
             if let Some(window) = container.focused_window() {
                 window.focus(false)?;
             }
@@ -839,6 +1426,106 @@ impl Workspace {
         Ok(())
     }
 
+    /// Whether newly managed windows on this workspace should be added to an existing container
+    /// holding windows of the same application, taking the workspace override into account
+    pub fn should_auto_stack(&self, hwnd: isize) -> bool {
+        self.auto_stack_by_exe
+            .unwrap_or_else(|| AUTO_STACK_BY_EXE.load(Ordering::SeqCst))
+            && !AUTO_STACK_EXEMPT_HWNDS.lock().contains(&hwnd)
+    }
+
+    /// Adds a newly managed window to the first container which holds a window of the same
+    /// application, or of the same auto stack group, returning true if it did
+    pub fn auto_stack_window(&mut self, window: Window) -> bool {
+        let Some(key) = AutoStackKey::for_window(&window) else {
+            return false;
+        };
+
+        let Some(idx) = self.containers().iter().position(|container| {
+            container.swallowed_window().is_none()
+                && container
+                    .windows()
+                    .iter()
+                    .any(|w| AutoStackKey::for_window(w).is_some_and(|k| k == key))
+        }) else {
+            return false;
+        };
+
+        let hiding_behaviour = self.hiding_behaviour();
+        let Some(container) = self.containers_mut().get_mut(idx) else {
+            return false;
+        };
+
+        tracing::info!("auto stacking {} with {:?}", window.hwnd, key);
+        container.add_window(window, hiding_behaviour);
+        self.focus_container(idx);
+
+        true
+    }
+
+    /// Lets a newly opened window take over the tile of the parent window it was launched from,
+    /// if the parent matches a swallowing rule, returning true if it did
+    pub fn swallow_window(&mut self, window: Window) -> bool {
+        let hiding_behaviour = self.hiding_behaviour();
+        let swallowing_rules = SWALLOWING_RULES.lock();
+        if swallowing_rules.is_empty() {
+            return false;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let matches_rules = |window: &Window| {
+            if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+                (window.title(), window.exe(), window.class(), window.path())
+            {
+                should_act(
+                    &title,
+                    &exe_name,
+                    &class,
+                    &path,
+                    &swallowing_rules,
+                    &regex_identifiers,
+                )
+                .is_some()
+            } else {
+                false
+            }
+        };
+
+        // Windows which can swallow are never swallowed themselves, which keeps swallowing
+        // one level deep
+        if matches_rules(&window) {
+            return false;
+        }
+
+        let ancestor_process_ids = window.ancestor_process_ids();
+        if ancestor_process_ids.is_empty() {
+            return false;
+        }
+
+        let Some(idx) = self.containers().iter().position(|container| {
+            container.swallowed_window().is_none()
+                && container.windows().len() == 1
+                && container.focused_window().is_some_and(|parent| {
+                    ancestor_process_ids.contains(&parent.process_id()) && matches_rules(parent)
+                })
+        }) else {
+            return false;
+        };
+
+        let Some(container) = self.containers_mut().get_mut(idx) else {
+            return false;
+        };
+
+        match container.swallow(window, hiding_behaviour) {
+            None => false,
+            Some(parent) => {
+                tracing::info!("{} has swallowed {}", window.hwnd, parent.hwnd);
+                self.focus_container(idx);
+                true
+            }
+        }
+    }
+
     pub fn remove_focused_container(&mut self) -> Option<Container> {
         let focused_idx = self.focused_container_idx();
         let container = self.remove_container_by_idx(focused_idx);
@@ -854,6 +1541,32 @@ impl Workspace {
         container
     }
 
+    /// Grows or shrinks the share of the visible width taken by the focused column in the
+    /// Scrolling layout
+    pub fn resize_focused_column(&mut self, sizing: Sizing) {
+        let mut options = self.scrolling_options().unwrap_or_default();
+        let ratio = options.effective_focused_column_ratio();
+
+        options.focused_column_ratio = Option::from(
+            match sizing {
+                Sizing::Increase => ratio + 0.05,
+                Sizing::Decrease => ratio - 0.05,
+            }
+            .clamp(
+                ScrollingLayoutOptions::MIN_FOCUSED_COLUMN_RATIO,
+                ScrollingLayoutOptions::MAX_FOCUSED_COLUMN_RATIO,
+            ),
+        );
+
+        self.set_scrolling_options(Option::from(options));
+    }
+
+    pub fn set_scrolling_visible_columns(&mut self, visible_columns: usize) {
+        let mut options = self.scrolling_options().unwrap_or_default();
+        options.visible_columns = visible_columns.max(1);
+        self.set_scrolling_options(Option::from(options));
+    }
+
     pub fn new_idx_for_direction(&self, direction: OperationDirection) -> Option<usize> {
         let len = NonZeroUsize::new(self.containers().len())?;
 
@@ -864,6 +1577,40 @@ impl Workspace {
             len,
         )
     }
+
+    /// The floating window geometrically nearest to the origin rect in the given direction
+    pub fn floating_window_in_direction(
+        &self,
+        origin: &Rect,
+        direction: OperationDirection,
+    ) -> Option<Window> {
+        self.floating_windows()
+            .iter()
+            .filter_map(|window| {
+                let rect = window_system().window_rect(window.hwnd).ok()?;
+                let distance = origin.distance_in_direction(&rect, direction)?;
+                Some((*window, distance))
+            })
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(window, _)| window)
+    }
+
+    /// The index of the tiled container geometrically nearest to the origin rect in the given
+    /// direction, based on the most recently calculated layout
+    pub fn container_idx_in_direction_from_rect(
+        &self,
+        origin: &Rect,
+        direction: OperationDirection,
+    ) -> Option<usize> {
+        self.latest_layout()
+            .iter()
+            .take(self.containers().len())
+            .enumerate()
+            .filter_map(|(idx, rect)| Some((idx, origin.distance_in_direction(rect, direction)?)))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(idx, _)| idx)
+    }
+
     pub fn new_idx_for_cycle_direction(&self, direction: CycleDirection) -> Option<usize> {
         Option::from(direction.next_idx(
             self.focused_container_idx(),
@@ -872,6 +1619,7 @@ impl Workspace {
     }
 
     pub fn move_window_to_container(&mut self, target_container_idx: usize) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let focused_idx = self.focused_container_idx();
 
         let container = self
@@ -893,7 +1641,7 @@ impl Workspace {
                 target_container_idx
             }
         } else {
-            container.load_focused_window();
+            container.load_focused_window_with_behaviour(hiding_behaviour);
             target_container_idx
         };
 
@@ -902,17 +1650,18 @@ impl Workspace {
             .get_mut(adjusted_target_container_index)
             .ok_or_else(|| anyhow!("there is no container"))?;
 
-        target_container.add_window(window);
+        target_container.add_window(window, hiding_behaviour);
 
         self.focus_container(adjusted_target_container_index);
         self.focused_container_mut()
             .ok_or_else(|| anyhow!("there is no container"))?
-            .load_focused_window();
+            .load_focused_window_with_behaviour(hiding_behaviour);
 
         Ok(())
     }
 
     pub fn new_container_for_focused_window(&mut self) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let focused_container_idx = self.focused_container_idx();
 
         let container = self
@@ -927,13 +1676,13 @@ impl Workspace {
             self.containers_mut().remove(focused_container_idx);
             self.resize_dimensions_mut().remove(focused_container_idx);
         } else {
-            container.load_focused_window();
+            container.load_focused_window_with_behaviour(hiding_behaviour);
         }
 
         self.new_container_for_window(window);
 
         let mut container = Container::default();
-        container.add_window(window);
+        container.add_window(window, hiding_behaviour);
         Ok(())
     }
 
@@ -943,8 +1692,13 @@ impl Workspace {
             .remove_focused_floating_window()
             .ok_or_else(|| anyhow!("there is no floating window"))?;
 
+        if let Some(origin) = TILE_ORIGINS.lock().remove(&window.hwnd) {
+            self.restore_window_to_tile_origin(window, origin);
+            return Ok(());
+        }
+
         let mut container = Container::default();
-        container.add_window(window);
+        container.add_window(window, self.hiding_behaviour());
         self.containers_mut().insert(focused_idx, container);
         self.resize_dimensions_mut().insert(focused_idx, None);
 
@@ -952,14 +1706,31 @@ impl Workspace {
     }
 
     pub fn new_container_for_window(&mut self, window: Window) {
+        self.new_container_for_window_with_placement(window, NewWindowPlacement::Adjacent);
+    }
+
+    pub fn new_container_for_window_with_placement(
+        &mut self,
+        window: Window,
+        placement: NewWindowPlacement,
+    ) {
         let next_idx = if self.containers().is_empty() {
             0
         } else {
-            self.focused_container_idx() + 1
+            match placement {
+                NewWindowPlacement::Adjacent => self.focused_container_idx() + 1,
+                NewWindowPlacement::End => self.containers().len(),
+                // Placing the new container after the largest one makes it split that area
+                NewWindowPlacement::Largest => {
+                    self.largest_container_idx()
+                        .unwrap_or_else(|| self.focused_container_idx())
+                        + 1
+                }
+            }
         };
 
         let mut container = Container::default();
-        container.add_window(window);
+        container.add_window(window, self.hiding_behaviour());
 
         if next_idx > self.containers().len() {
             self.containers_mut().push_back(container);
@@ -977,6 +1748,7 @@ impl Workspace {
     }
 
     pub fn new_floating_window(&mut self) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let window = if let Some(maximized_window) = self.maximized_window() {
             let window = *maximized_window;
             self.set_maximized_window(None);
@@ -991,7 +1763,7 @@ impl Workspace {
                 self.set_monocle_container(None);
                 self.set_monocle_container_restore_idx(None);
             } else {
-                monocle_container.load_focused_window();
+                monocle_container.load_focused_window_with_behaviour(hiding_behaviour);
             }
 
             window
@@ -1014,7 +1786,7 @@ impl Workspace {
                     self.focus_container(focused_idx.saturating_sub(1));
                 }
             } else {
-                container.load_focused_window();
+                container.load_focused_window_with_behaviour(hiding_behaviour);
             }
 
             window
@@ -1279,6 +2051,7 @@ impl Workspace {
     }
 
     pub fn new_monocle_container(&mut self) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let focused_idx = self.focused_container_idx();
         let container = self
             .containers_mut()
@@ -1296,12 +2069,13 @@ impl Workspace {
         self.monocle_container_mut()
             .as_mut()
             .ok_or_else(|| anyhow!("there is no monocle container"))?
-            .load_focused_window();
+            .load_focused_window_with_behaviour(hiding_behaviour);
 
         Ok(())
     }
 
     pub fn reintegrate_monocle_container(&mut self) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let restore_idx = self
             .monocle_container_restore_idx()
             .ok_or_else(|| anyhow!("there is no monocle restore index"))?;
@@ -1321,7 +2095,7 @@ impl Workspace {
         self.focus_container(restore_idx);
         self.focused_container_mut()
             .ok_or_else(|| anyhow!("there is no container"))?
-            .load_focused_window();
+            .load_focused_window_with_behaviour(hiding_behaviour);
 
         self.set_monocle_container(None);
         self.set_monocle_container_restore_idx(None);
@@ -1330,8 +2104,9 @@ impl Workspace {
     }
 
     pub fn new_maximized_window(&mut self) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let focused_idx = self.focused_container_idx();
-        let foreground_hwnd = WindowsApi::foreground_window()?;
+        let foreground_hwnd = window_system().foreground_window()?;
         let mut floating_window = None;
 
         if !self.floating_windows().is_empty() {
@@ -1367,7 +2142,7 @@ impl Workspace {
                 self.set_monocle_container(None);
                 self.set_monocle_container_restore_idx(None);
             } else {
-                monocle_container.load_focused_window();
+                monocle_container.load_focused_window_with_behaviour(hiding_behaviour);
             }
 
             self.set_maximized_window(Option::from(window));
@@ -1393,7 +2168,7 @@ impl Workspace {
                 self.resize_dimensions_mut().remove(focused_idx);
             }
         } else {
-            container.load_focused_window();
+            container.load_focused_window_with_behaviour(hiding_behaviour);
         }
 
         self.set_maximized_window(Option::from(window));
@@ -1409,6 +2184,7 @@ impl Workspace {
     }
 
     pub fn reintegrate_maximized_window(&mut self) -> Result<()> {
+        let hiding_behaviour = self.hiding_behaviour();
         let restore_idx = self
             .maximized_window_restore_idx()
             .ok_or_else(|| anyhow!("there is no monocle restore index"))?;
@@ -1433,7 +2209,7 @@ impl Workspace {
 
         self.focused_container_mut()
             .ok_or_else(|| anyhow!("there is no container"))?
-            .load_focused_window();
+            .load_focused_window_with_behaviour(hiding_behaviour);
 
         self.set_maximized_window(None);
         self.set_maximized_window_restore_idx(None);
@@ -1448,13 +2224,158 @@ impl Workspace {
         self.containers.focus(idx);
     }
 
+    /// Moves the focused container to the front of the focus history, forgetting any containers
+    /// which are no longer on this workspace
+    pub fn update_container_focus_history(&mut self) {
+        let monocle_id = self.monocle_container().as_ref().map(|c| c.id().clone());
+        let container_ids = self
+            .containers()
+            .iter()
+            .map(|c| c.id().clone())
+            .chain(monocle_id.clone())
+            .collect::<Vec<_>>();
+
+        self.container_focus_history
+            .retain(|id| container_ids.contains(id));
+
+        let focused_id = match monocle_id {
+            Some(id) => Some(id),
+            None => self.focused_container().map(|c| c.id().clone()),
+        };
+
+        if let Some(id) = focused_id {
+            if self.container_focus_history.front() != Some(&id) {
+                self.container_focus_history.retain(|i| *i != id);
+                self.container_focus_history.push_front(id);
+                self.container_focus_history
+                    .truncate(CONTAINER_FOCUS_HISTORY_LIMIT);
+            }
+        }
+    }
+
+    /// The index of the most recently focused container other than the focused container
+    pub fn last_focused_container_idx(&self) -> Option<usize> {
+        let focused_id = self.focused_container().map(Container::id);
+
+        self.container_focus_history
+            .iter()
+            .filter(|id| Some(*id) != focused_id)
+            .find_map(|id| self.containers().iter().position(|c| c.id() == id))
+    }
+
+    /// The index of the container occupying the largest area in the most recently computed layout
+    pub fn largest_container_idx(&self) -> Option<usize> {
+        self.latest_layout()
+            .iter()
+            .take(self.containers().len())
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, rect)| i64::from(rect.right) * i64::from(rect.bottom))
+            .map(|(idx, _)| idx)
+    }
+
+    /// Pins a container to a column, demoting any container which was previously pinned to the
+    /// same column
+    pub fn pin_container_to_column(&mut self, id: &str, column: usize) {
+        self.column_pins.retain(|pinned_id, pinned_column| {
+            if *pinned_column == column && pinned_id != id {
+                tracing::info!("unpinning container {pinned_id} from column {column}");
+                false
+            } else {
+                true
+            }
+        });
+
+        self.column_pins.insert(id.to_string(), column);
+    }
+
+    pub fn unpin_container(&mut self, id: &str) -> bool {
+        self.column_pins.remove(id).is_some()
+    }
+
+    /// Reorders the containers so that pinned containers occupy their columns in the current
+    /// layout, with unpinned containers filling the remaining positions in their existing order
+    fn enforce_column_pins(&mut self) {
+        let container_ids = self
+            .containers()
+            .iter()
+            .map(|c| c.id().clone())
+            .chain(self.monocle_container().as_ref().map(|c| c.id().clone()))
+            .collect::<Vec<_>>();
+
+        self.column_pins.retain(|id, _| container_ids.contains(id));
+
+        let Layout::Default(layout) = self.layout() else {
+            return;
+        };
+
+        if self.column_pins.is_empty() {
+            return;
+        }
+
+        let len = self.containers().len();
+        let mut slots: Vec<Option<usize>> = vec![None; len];
+        let mut pinned = vec![false; len];
+
+        for (idx, container) in self.containers().iter().enumerate() {
+            if let Some(column) = self.column_pins.get(container.id()) {
+                if let Some(slot) = layout.column_slot(*column, len) {
+                    if slots[slot].is_none() {
+                        slots[slot] = Some(idx);
+                        pinned[idx] = true;
+                    }
+                }
+            }
+        }
+
+        let mut unpinned = (0..len).filter(|idx| !pinned[*idx]);
+        let order = slots
+            .into_iter()
+            .map(|slot| slot.or_else(|| unpinned.next()))
+            .collect::<Option<Vec<_>>>();
+
+        let Some(order) = order else {
+            return;
+        };
+
+        if order.iter().enumerate().all(|(slot, idx)| slot == *idx) {
+            return;
+        }
+
+        let focused_id = self.focused_container().map(|c| c.id().clone());
+
+        let mut containers = self
+            .containers_mut()
+            .drain(..)
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut resize_dimensions = self.resize_dimensions().clone();
+        resize_dimensions.resize(len, None);
+
+        let mut reordered_resize_dimensions = Vec::with_capacity(len);
+        for idx in order {
+            if let Some(container) = containers[idx].take() {
+                self.containers_mut().push_back(container);
+                reordered_resize_dimensions.push(resize_dimensions[idx]);
+            }
+        }
+
+        self.set_resize_dimensions(reordered_resize_dimensions);
+
+        if let Some(focused_id) = focused_id {
+            if let Some(idx) = self.containers().iter().position(|c| *c.id() == focused_id) {
+                self.focus_container(idx);
+            }
+        }
+    }
+
     pub fn swap_containers(&mut self, i: usize, j: usize) {
         self.containers.swap(i, j);
         self.focus_container(j);
     }
 
     pub fn remove_focused_floating_window(&mut self) -> Option<Window> {
-        let hwnd = WindowsApi::foreground_window().ok()?;
+        let hwnd = window_system().foreground_window().ok()?;
 
         let mut idx = None;
         for (i, window) in self.floating_windows.iter().enumerate() {
@@ -1475,6 +2396,26 @@ impl Workspace {
         }
     }
 
+    pub fn all_windows(&self) -> Vec<&Window> {
+        let mut vec = vec![];
+
+        if let Some(maximized) = self.maximized_window() {
+            vec.push(maximized);
+        }
+
+        if let Some(monocle) = self.monocle_container() {
+            vec.extend(monocle.windows());
+        }
+
+        for container in self.containers() {
+            vec.extend(container.windows());
+        }
+
+        vec.extend(self.floating_windows());
+
+        vec
+    }
+
     pub fn visible_windows(&self) -> Vec<Option<&Window>> {
         let mut vec = vec![];
 
@@ -1542,3 +2483,37 @@ impl Workspace {
         self.focus_container(0);
     }
 }
+
+/// Windows which share a key are automatically stacked together; windows matching an auto stack
+/// group rule are keyed by that rule, and all other windows are keyed by their exe
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AutoStackKey {
+    Group(usize),
+    Exe(String),
+}
+
+impl AutoStackKey {
+    fn for_window(window: &Window) -> Option<Self> {
+        let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+            (window.title(), window.exe(), window.class(), window.path())
+        else {
+            return None;
+        };
+
+        let auto_stack_groups = AUTO_STACK_GROUPS.lock();
+        if !auto_stack_groups.is_empty() {
+            if let Some((idx, _)) = should_act_with_index(
+                &title,
+                &exe_name,
+                &class,
+                &path,
+                &auto_stack_groups,
+                &REGEX_IDENTIFIERS.lock(),
+            ) {
+                return Some(Self::Group(idx));
+            }
+        }
+
+        Some(Self::Exe(exe_name.to_lowercase()))
+    }
+}
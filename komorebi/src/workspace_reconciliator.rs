@@ -83,7 +83,8 @@ pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result
         let focused_pair = (focused_monitor_idx, focused_workspace_idx);
         let updated_pair = (notification.monitor_idx, notification.workspace_idx);
 
-        if focused_pair != updated_pair {
+        // Workspaces on unmanaged monitors have no windows of their own to switch to
+        if focused_pair != updated_pair && !wm.is_unmanaged_monitor(notification.monitor_idx) {
             wm.focus_monitor(notification.monitor_idx)?;
             let mouse_follows_focus = wm.mouse_follows_focus;
 
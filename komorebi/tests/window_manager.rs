@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use hotwatch::Hotwatch;
+use parking_lot::Mutex;
+use parking_lot::MutexGuard;
+use uds_windows::UnixListener;
+
+use komorebi::container::Container;
+use komorebi::core::CrossBoundaryBehaviour;
+use komorebi::core::DefaultLayout;
+use komorebi::core::DragBehaviour;
+use komorebi::core::HidingBehaviour;
+use komorebi::core::LastWindowCloseBehaviour;
+use komorebi::core::Layout;
+use komorebi::core::MoveBehaviour;
+use komorebi::core::OperationBehaviour;
+use komorebi::core::OperationDirection;
+use komorebi::core::Rect;
+use komorebi::core::WindowManagementBehaviour;
+use komorebi::monitor;
+use komorebi::ring::Ring;
+use komorebi::window::Window;
+use komorebi::window_manager::WindowManager;
+use komorebi::window_system::mock::MockWindow;
+use komorebi::window_system::mock::MockWindowSystem;
+use komorebi::window_system::set_window_system;
+use komorebi::window_system::WindowSystem;
+
+/// The window system and the window manager globals are shared by every test in this file
+static SERIAL: Mutex<()> = Mutex::new(());
+static SOCKETS: AtomicUsize = AtomicUsize::new(0);
+
+const LEFT_MONITOR: isize = 1;
+const RIGHT_MONITOR: isize = 2;
+
+struct Harness {
+    wm: WindowManager,
+    mock: Arc<MockWindowSystem>,
+    _serial: MutexGuard<'static, ()>,
+}
+
+/// Each workspace is a list of containers, and each container is a list of window handles
+type Workspaces<'a> = &'a [&'a [&'a [isize]]];
+
+impl Harness {
+    /// A window manager with one monitor on the left and one on the right, each laying out the
+    /// given workspaces in columns and focusing the last container of their first workspace
+    fn new(left: Workspaces, right: Workspaces) -> Self {
+        let serial = SERIAL.lock();
+
+        let mock = Arc::new(MockWindowSystem::new());
+        set_window_system(mock.clone());
+
+        let socket = std::env::temp_dir().join(format!(
+            "komorebi-test-{}-{}.sock",
+            std::process::id(),
+            SOCKETS.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_file(&socket);
+
+        let mut wm = WindowManager {
+            monitors: Ring::default(),
+            incoming_events: crossbeam_channel::unbounded().1,
+            command_listener: UnixListener::bind(&socket).unwrap(),
+            is_paused: false,
+            work_area_offset: None,
+            resize_delta: 50,
+            window_management_behaviour: WindowManagementBehaviour::default(),
+            cross_monitor_move_behaviour: MoveBehaviour::Swap,
+            cross_boundary_behaviour: CrossBoundaryBehaviour::Monitor,
+            drag_behaviour: DragBehaviour::default(),
+            on_last_window_close: LastWindowCloseBehaviour::default(),
+            unmanaged_window_operation_behaviour: OperationBehaviour::Op,
+            focus_follows_mouse: None,
+            mouse_follows_focus: false,
+            hotwatch: Hotwatch::new().unwrap(),
+            virtual_desktop_id: None,
+            has_pending_raise_op: false,
+            pending_move_op: Arc::new(None),
+            already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
+            manually_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
+            uncloack_to_ignore: 0,
+        };
+
+        for (idx, (hmonitor, workspaces)) in [(LEFT_MONITOR, left), (RIGHT_MONITOR, right)]
+            .into_iter()
+            .enumerate()
+        {
+            let size = Rect {
+                left: 1920 * idx as i32,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            };
+
+            mock.add_monitor(hmonitor, size);
+
+            let mut monitor = monitor::new(
+                hmonitor,
+                size,
+                size,
+                format!("MOCK{idx}"),
+                format!("MOCK{idx}"),
+                format!("MOCK{idx}"),
+                None,
+            );
+
+            monitor.ensure_workspace_count(workspaces.len().max(1));
+
+            for (workspace, containers) in monitor.workspaces_mut().iter_mut().zip(workspaces) {
+                workspace.set_layout(Layout::Default(DefaultLayout::Columns));
+
+                for hwnds in *containers {
+                    let mut container = Container::default();
+                    for hwnd in *hwnds {
+                        mock.add_window(
+                            *hwnd,
+                            MockWindow {
+                                title: format!("window {hwnd}"),
+                                exe: String::from("mock.exe"),
+                                class: String::from("Mock"),
+                                rect: size,
+                            },
+                        );
+
+                        container.add_window(Window::from(*hwnd), HidingBehaviour::Cloak);
+                    }
+
+                    workspace.add_container_to_back(container);
+                }
+            }
+
+            wm.monitors_mut().push_back(monitor);
+            wm.update_focused_workspace_by_monitor_idx(idx).unwrap();
+        }
+
+        let harness = Self {
+            wm,
+            mock,
+            _serial: serial,
+        };
+
+        harness.mock.set_foreground_window(harness.focused_hwnd());
+        harness.mock.take_calls();
+
+        harness
+    }
+
+    fn focused_hwnd(&self) -> Option<isize> {
+        self.wm.focused_window().ok().map(|window| window.hwnd)
+    }
+
+    /// The window handles of the containers on the focused workspace of a monitor
+    fn layout(&self, monitor_idx: usize) -> Vec<Vec<isize>> {
+        self.wm.monitors()[monitor_idx]
+            .focused_workspace()
+            .unwrap()
+            .containers()
+            .iter()
+            .map(|container| container.windows().iter().map(|w| w.hwnd).collect())
+            .collect()
+    }
+
+    fn is_on_monitor(&self, hwnd: isize, hmonitor: isize) -> bool {
+        let rect = self.mock.window_rect(hwnd).unwrap();
+        self.mock.monitor_from_rect(&rect) == hmonitor
+    }
+}
+
+#[test]
+fn move_across_monitors_with_insert() {
+    let mut harness = Harness::new(&[&[&[1], &[2]]], &[&[&[3], &[4]]]);
+    harness.wm.cross_monitor_move_behaviour = MoveBehaviour::Insert;
+
+    harness
+        .wm
+        .move_container_in_direction(OperationDirection::Right)
+        .unwrap();
+
+    assert_eq!(harness.layout(0), vec![vec![1]]);
+    assert_eq!(harness.layout(1), vec![vec![2], vec![3], vec![4]]);
+    assert_eq!(harness.wm.focused_monitor_idx(), 1);
+    assert_eq!(harness.focused_hwnd(), Some(2));
+    assert_eq!(harness.mock.foreground_window().unwrap(), 2);
+    assert!(harness.is_on_monitor(2, RIGHT_MONITOR));
+}
+
+#[test]
+fn move_across_monitors_with_swap() {
+    let mut harness = Harness::new(&[&[&[1], &[2]]], &[&[&[3], &[4]]]);
+    harness.wm.cross_monitor_move_behaviour = MoveBehaviour::Swap;
+
+    harness
+        .wm
+        .move_container_in_direction(OperationDirection::Right)
+        .unwrap();
+
+    assert_eq!(harness.layout(0), vec![vec![1], vec![3]]);
+    assert_eq!(harness.layout(1), vec![vec![2], vec![4]]);
+    assert_eq!(harness.wm.focused_monitor_idx(), 1);
+    assert_eq!(harness.focused_hwnd(), Some(2));
+    assert!(harness.is_on_monitor(2, RIGHT_MONITOR));
+    assert!(harness.is_on_monitor(3, LEFT_MONITOR));
+}
+
+#[test]
+fn swap_falls_back_to_move_onto_an_empty_workspace() {
+    let mut harness = Harness::new(&[&[&[1], &[2]]], &[]);
+    harness.wm.cross_monitor_move_behaviour = MoveBehaviour::Swap;
+
+    harness
+        .wm
+        .move_container_in_direction(OperationDirection::Right)
+        .unwrap();
+
+    assert_eq!(harness.layout(0), vec![vec![1]]);
+    assert_eq!(harness.layout(1), vec![vec![2]]);
+    assert_eq!(harness.focused_hwnd(), Some(2));
+}
+
+#[test]
+fn focus_crosses_monitor_edges() {
+    let mut harness = Harness::new(&[&[&[1], &[2]]], &[&[&[3], &[4]]]);
+
+    // The leftmost container is focused when entering a monitor from its left edge
+    harness
+        .wm
+        .focus_container_in_direction(OperationDirection::Right)
+        .unwrap();
+
+    assert_eq!(harness.wm.focused_monitor_idx(), 1);
+    assert_eq!(harness.focused_hwnd(), Some(3));
+    assert_eq!(harness.mock.foreground_window().unwrap(), 3);
+
+    // The rightmost container is focused when entering a monitor from its right edge
+    harness
+        .wm
+        .focus_container_in_direction(OperationDirection::Left)
+        .unwrap();
+
+    assert_eq!(harness.wm.focused_monitor_idx(), 0);
+    assert_eq!(harness.focused_hwnd(), Some(2));
+    assert_eq!(harness.mock.foreground_window().unwrap(), 2);
+}
+
+#[test]
+fn focus_stops_at_the_outer_screen_edges() {
+    let mut harness = Harness::new(&[&[&[1], &[2]]], &[&[&[3], &[4]]]);
+    harness.wm.focus_monitor(1).unwrap();
+
+    assert_eq!(harness.focused_hwnd(), Some(4));
+    assert!(harness
+        .wm
+        .focus_container_in_direction(OperationDirection::Right)
+        .is_err());
+
+    assert_eq!(harness.wm.focused_monitor_idx(), 1);
+    assert_eq!(harness.focused_hwnd(), Some(4));
+    assert!(harness.mock.calls().is_empty());
+}
+
+#[test]
+fn focus_wraps_around_workspaces() {
+    let mut harness = Harness::new(&[&[&[1], &[2]], &[], &[&[5], &[6]]], &[]);
+    harness.wm.cross_boundary_behaviour = CrossBoundaryBehaviour::Workspace;
+    harness
+        .wm
+        .focused_workspace_mut()
+        .unwrap()
+        .focus_container(0);
+
+    // Moving left from the first workspace wraps around to the rightmost container of the last
+    harness
+        .wm
+        .focus_container_in_direction(OperationDirection::Left)
+        .unwrap();
+
+    assert_eq!(harness.wm.focused_workspace_idx().unwrap(), 2);
+    assert_eq!(harness.focused_hwnd(), Some(6));
+
+    // Moving right from the last workspace wraps around to the leftmost container of the first
+    harness
+        .wm
+        .focus_container_in_direction(OperationDirection::Right)
+        .unwrap();
+
+    assert_eq!(harness.wm.focused_workspace_idx().unwrap(), 0);
+    assert_eq!(harness.focused_hwnd(), Some(1));
+}
+
+#[test]
+fn promote_stack_to_front() {
+    let mut harness = Harness::new(&[&[&[1], &[2, 3], &[4]]], &[]);
+    harness
+        .wm
+        .focused_workspace_mut()
+        .unwrap()
+        .focus_container(1);
+
+    harness.wm.promote_container_to_front().unwrap();
+
+    // The stack is promoted as a whole and keeps its focused window
+    assert_eq!(harness.layout(0), vec![vec![2, 3], vec![1], vec![4]]);
+    assert_eq!(
+        harness
+            .wm
+            .focused_workspace()
+            .unwrap()
+            .focused_container_idx(),
+        0
+    );
+    assert_eq!(harness.focused_hwnd(), Some(3));
+    assert_eq!(harness.mock.foreground_window().unwrap(), 3);
+
+    let promoted = harness.mock.window_rect(3).unwrap();
+    for hwnd in [1, 4] {
+        assert!(promoted.left < harness.mock.window_rect(hwnd).unwrap().left);
+    }
+}
@@ -2,12 +2,12 @@
 #![allow(clippy::missing_errors_doc, clippy::doc_markdown)]
 
 use chrono::Utc;
+use std::collections::HashMap;
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Write;
-use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::atomic::AtomicBool;
@@ -22,10 +22,13 @@ use color_eyre::eyre::bail;
 use color_eyre::Result;
 use dirs::data_local_dir;
 use fs_tail::TailedFile;
+use komorebi_client::render_layout_preview;
 use komorebi_client::resolve_home_path;
+use komorebi_client::send_batch;
 use komorebi_client::send_message;
 use komorebi_client::send_query;
 use komorebi_client::ApplicationSpecificConfiguration;
+use komorebi_client::AscApplicationRulesOrSchema;
 use komorebi_client::Notification;
 use lazy_static::lazy_static;
 use miette::NamedSource;
@@ -45,19 +48,28 @@ use windows::Win32::UI::WindowsAndMessaging::SW_RESTORE;
 
 use komorebi_client::ApplicationConfigurationGenerator;
 use komorebi_client::ApplicationIdentifier;
+use komorebi_client::AspectRatio;
 use komorebi_client::Axis;
 use komorebi_client::CycleDirection;
 use komorebi_client::DefaultLayout;
+use komorebi_client::DragMode;
+use komorebi_client::DragModifier;
 use komorebi_client::FocusFollowsMouseImplementation;
 use komorebi_client::HidingBehaviour;
 use komorebi_client::MoveBehaviour;
 use komorebi_client::OperationBehaviour;
 use komorebi_client::OperationDirection;
+use komorebi_client::PredefinedPlacement;
 use komorebi_client::Rect;
+use komorebi_client::RuleDebug;
 use komorebi_client::Sizing;
 use komorebi_client::SocketMessage;
+use komorebi_client::State;
 use komorebi_client::StateQuery;
 use komorebi_client::StaticConfig;
+use komorebi_client::SubscribeOptions;
+use komorebi_client::WindowBasedWorkAreaOffsetCounting;
+use komorebi_client::WindowDetails;
 use komorebi_client::WindowKind;
 
 lazy_static! {
@@ -104,6 +116,11 @@ lazy_static! {
     };
 }
 
+use crate::table::truncate;
+use crate::table::Table;
+
+mod table;
+
 shadow_rs::shadow!(build);
 
 #[derive(thiserror::Error, Debug, miette::Diagnostic)]
@@ -117,12 +134,46 @@ struct ConfigurationError {
     bad_bit: SourceSpan,
 }
 
+#[derive(thiserror::Error, Debug, miette::Diagnostic)]
+#[error("{message}")]
+#[diagnostic(
+    code(komorebi::validation),
+    severity(Warning),
+    help("this will not cause an error, but it will not work as expected")
+)]
+struct ValidationWarning {
+    message: String,
+    #[source_code]
+    src: NamedSource<String>,
+    #[label("This bit here")]
+    bad_bit: Option<SourceSpan>,
+}
+
 #[derive(Copy, Clone, ValueEnum)]
 enum BooleanState {
     Enable,
     Disable,
 }
 
+#[derive(Copy, Clone, Default, ValueEnum)]
+enum OutputFormat {
+    #[default]
+    Json,
+    Table,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum RecordingState {
+    On,
+    Off,
+}
+
+impl From<RecordingState> for bool {
+    fn from(state: RecordingState) -> Self {
+        matches!(state, RecordingState::On)
+    }
+}
+
 impl From<BooleanState> for bool {
     fn from(b: BooleanState) -> Self {
         match b {
@@ -163,6 +214,7 @@ gen_enum_subcommand_args! {
     CycleStack: CycleDirection,
     CycleStackIndex: CycleDirection,
     FlipLayout: Axis,
+    CycleSplitRatio: Axis,
     ChangeLayout: DefaultLayout,
     CycleLayout: CycleDirection,
     WatchConfiguration: BooleanState,
@@ -172,6 +224,7 @@ gen_enum_subcommand_args! {
     CrossMonitorMoveBehaviour: MoveBehaviour,
     UnmanagedWindowOperationBehaviour: OperationBehaviour,
     PromoteWindow: OperationDirection,
+    PlaceFloating: PredefinedPlacement,
 }
 
 macro_rules! gen_target_subcommand_args {
@@ -191,13 +244,33 @@ gen_target_subcommand_args! {
     MoveToMonitor,
     MoveToWorkspace,
     SendToMonitor,
+    SendStackToMonitor,
     SendToWorkspace,
     FocusMonitor,
-    FocusWorkspace,
     FocusWorkspaces,
     MoveWorkspaceToMonitor,
     SwapWorkspacesWithMonitor,
     FocusStackWindow,
+    PinToColumn,
+    ToggleMonitorPause,
+}
+
+#[derive(Parser)]
+struct DragBehaviour {
+    #[clap(value_enum)]
+    mode: DragMode,
+    /// Modifier key which switches from swapping to stacking when held while a window is dropped
+    #[clap(long, value_enum)]
+    modifier: Option<DragModifier>,
+}
+
+#[derive(Parser)]
+struct FocusWorkspace {
+    /// Target index (zero-indexed)
+    target: usize,
+    /// Don't take focus away from a floating or unmanaged window which currently has focus
+    #[clap(long)]
+    keep_focus: bool,
 }
 
 macro_rules! gen_named_target_subcommand_args {
@@ -252,6 +325,8 @@ gen_workspace_subcommand_args! {
     Name: String,
     Layout: #[enum] DefaultLayout,
     Tiling: #[enum] BooleanState,
+    WindowHidingBehaviour: #[enum] HidingBehaviour,
+    Wallpaper: PathBuf,
 }
 
 macro_rules! gen_named_workspace_subcommand_args {
@@ -372,6 +447,9 @@ struct Resize {
     edge: OperationDirection,
     #[clap(value_enum)]
     sizing: Sizing,
+    /// Resize by a percentage of the monitor work area instead of the configured resize delta
+    #[clap(long)]
+    percent: Option<f32>,
 }
 
 #[derive(Parser)]
@@ -382,6 +460,58 @@ struct ResizeAxis {
     sizing: Sizing,
 }
 
+#[derive(Parser)]
+struct MoveFloating {
+    /// Number of pixels to move the window by on the x axis
+    #[clap(allow_hyphen_values = true)]
+    x: i32,
+    /// Number of pixels to move the window by on the y axis
+    #[clap(allow_hyphen_values = true)]
+    y: i32,
+}
+
+#[derive(Parser)]
+struct ResizeWindowTo {
+    /// Width of the window in pixels
+    #[clap(long)]
+    width: i32,
+    /// Height of the window in pixels
+    #[clap(long, required_unless_present = "aspect", conflicts_with = "aspect")]
+    height: Option<i32>,
+    /// Aspect ratio (eg. 16:9) used to calculate the height of the window from its width
+    #[clap(long)]
+    aspect: Option<AspectRatio>,
+}
+
+#[derive(Parser)]
+struct Scratchpad {
+    /// Name of the scratchpad
+    name: String,
+}
+
+#[derive(Parser)]
+struct CloseWorkspaceWindows {
+    /// Index of a workspace on the focused monitor to close the windows of (zero-indexed)
+    #[clap(short, long, conflicts_with = "named_workspace")]
+    workspace: Option<usize>,
+    /// Name of a workspace to close the windows of
+    #[clap(short, long)]
+    named_workspace: Option<String>,
+}
+
+#[derive(Parser)]
+struct ReturnToPreviousWorkspace {
+    /// Follow the container to the workspace it is returned to
+    #[clap(short, long)]
+    follow: bool,
+}
+
+#[derive(Parser)]
+struct ScrollingLayoutColumns {
+    /// Number of columns visible at once
+    count: usize,
+}
+
 #[derive(Parser)]
 struct ResizeDelta {
     /// The delta of pixels by which to increase or decrease window dimensions when resizing
@@ -426,6 +556,31 @@ struct MonitorWorkAreaOffset {
     bottom: i32,
 }
 
+#[derive(Parser)]
+struct RegisterAppBar {
+    /// Window handle of the bar
+    hwnd: isize,
+    /// The monitor edge that the bar is docked to
+    #[clap(value_enum)]
+    edge: komorebi_client::ReservedSpaceEdge,
+    /// Thickness of the bar in pixels
+    thickness: i32,
+}
+
+#[derive(Parser)]
+struct UnregisterAppBar {
+    /// Window handle of the bar
+    hwnd: isize,
+}
+
+#[derive(Parser)]
+struct MonitorWindowBasedWorkAreaOffsetCounting {
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    #[clap(value_enum)]
+    counting: WindowBasedWorkAreaOffsetCounting,
+}
+
 #[derive(Parser)]
 struct MonitorIndexPreference {
     /// Preferred monitor index (zero-indexed)
@@ -444,7 +599,7 @@ struct MonitorIndexPreference {
 struct DisplayIndexPreference {
     /// Preferred monitor index (zero-indexed)
     index_preference: usize,
-    /// Display name as identified in komorebic state
+    /// Display name as identified in komorebic state, or an EDID serial number prefixed with "serial:"
     display: String,
 }
 
@@ -528,6 +683,26 @@ gen_padding_subcommand_args! {
     WorkspacePadding,
 }
 
+macro_rules! gen_monitor_padding_subcommand_args {
+    // SubCommand Pattern
+    ( $( $name:ident ),+ $(,)? ) => {
+        $(
+            #[derive(clap::Parser)]
+            pub struct $name {
+                /// Monitor index (zero-indexed)
+                monitor: usize,
+                /// Pixels to pad with as an integer
+                size: i32,
+            }
+        )+
+    };
+}
+
+gen_monitor_padding_subcommand_args! {
+    MonitorContainerPadding,
+    MonitorWorkspacePadding,
+}
+
 macro_rules! gen_named_padding_subcommand_args {
     // SubCommand Pattern
     ( $( $name:ident ),+ $(,)? ) => {
@@ -587,11 +762,14 @@ macro_rules! gen_application_target_subcommand_args {
 gen_application_target_subcommand_args! {
     IgnoreRule,
     ManageRule,
+    FloatOverrideRule,
     IdentifyTrayApplication,
     IdentifyLayeredApplication,
+    AnimationBlacklist,
     IdentifyObjectNameChangeApplication,
     IdentifyBorderOverflowApplication,
     RemoveTitleBar,
+    RestoreTitleBar,
 }
 
 #[derive(Parser)]
@@ -638,6 +816,26 @@ struct NamedWorkspaceRule {
     workspace: String,
 }
 
+#[derive(Parser)]
+struct SessionWorkspaceRule {
+    #[clap(value_enum)]
+    identifier: ApplicationIdentifier,
+    /// Identifier as a string
+    id: String,
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    /// Workspace index on the specified monitor (zero-indexed)
+    workspace: usize,
+}
+
+#[derive(Parser)]
+struct FocusedWindowSessionWorkspaceRule {
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    /// Workspace index on the specified monitor (zero-indexed)
+    workspace: usize,
+}
+
 #[derive(Parser)]
 struct ClearWorkspaceRules {
     /// Monitor index (zero-indexed)
@@ -652,6 +850,22 @@ struct ClearNamedWorkspaceRules {
     workspace: String,
 }
 
+#[derive(Parser)]
+struct EnforceWorkspaceRules {
+    /// Only enforce rules for windows with this exe
+    #[clap(long, conflicts_with_all = ["class", "title"])]
+    exe: Option<String>,
+    /// Only enforce rules for windows with this class
+    #[clap(long, conflicts_with = "title")]
+    class: Option<String>,
+    /// Only enforce rules for windows with this title
+    #[clap(long)]
+    title: Option<String>,
+    /// Only enforce rules which target the workspace with this name
+    #[clap(long)]
+    workspace: Option<String>,
+}
+
 #[derive(Parser)]
 struct ToggleFocusFollowsMouse {
     #[clap(value_enum, short, long, default_value = "windows")]
@@ -672,6 +886,19 @@ struct Border {
     boolean_state: BooleanState,
 }
 
+#[derive(Parser)]
+struct BorderHitTest {
+    #[clap(value_enum)]
+    boolean_state: BooleanState,
+}
+
+#[derive(Parser)]
+struct BorderZOrder {
+    /// Desired border z-order
+    #[clap(value_enum)]
+    z_order: komorebi_client::ZOrder,
+}
+
 #[derive(Parser)]
 struct Transparency {
     #[clap(value_enum)]
@@ -684,6 +911,14 @@ struct TransparencyAlpha {
     alpha: u8,
 }
 
+#[derive(Parser)]
+struct TransparencyIgnoreMonitor {
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    #[clap(value_enum)]
+    boolean_state: BooleanState,
+}
+
 #[derive(Parser)]
 struct BorderColour {
     #[clap(value_enum, short, long, default_value = "single")]
@@ -696,16 +931,36 @@ struct BorderColour {
     b: u32,
 }
 
+#[derive(Parser)]
+struct BorderColourOverride {
+    #[clap(value_enum)]
+    identifier: ApplicationIdentifier,
+    /// Identifier as a string
+    id: String,
+    /// Red
+    r: u32,
+    /// Green
+    g: u32,
+    /// Blue
+    b: u32,
+}
+
 #[derive(Parser)]
 struct BorderWidth {
     /// Desired width of the window border
     width: i32,
+    /// Only set the width of the border for this kind of window
+    #[clap(short, long, value_enum)]
+    window_kind: Option<WindowKind>,
 }
 
 #[derive(Parser)]
 struct BorderOffset {
     /// Desired offset of the window border
     offset: i32,
+    /// Only set the offset of the border for this kind of window
+    #[clap(short, long, value_enum)]
+    window_kind: Option<WindowKind>,
 }
 #[derive(Parser)]
 struct BorderStyle {
@@ -714,6 +969,20 @@ struct BorderStyle {
     style: komorebi_client::BorderStyle,
 }
 
+#[derive(Parser)]
+struct WorkspaceLayer {
+    /// The layer to move focus to
+    #[clap(value_enum)]
+    layer: komorebi_client::WorkspaceLayer,
+}
+
+#[derive(Parser)]
+struct MouseFollowsFocusScope {
+    /// When the cursor should follow focus
+    #[clap(value_enum)]
+    scope: komorebi_client::MouseFollowsFocusScope,
+}
+
 #[derive(Parser)]
 struct BorderImplementation {
     /// Desired border implementation
@@ -750,6 +1019,15 @@ struct AnimationStyle {
     /// Desired ease function for animation
     #[clap(value_enum, short, long, default_value = "linear")]
     style: komorebi_client::AnimationStyle,
+    /// Custom cubic bézier ease function given as control points, overriding --style (eg. 0.25,0.1,0.25,1)
+    #[clap(
+        long,
+        value_name = "X1,Y1,X2,Y2",
+        value_delimiter = ',',
+        num_args = 4,
+        allow_negative_numbers = true
+    )]
+    cubic_bezier: Option<Vec<f64>>,
     /// Animation type to apply the style to. If not specified, sets global style
     #[clap(value_enum, short, long)]
     animation_type: Option<komorebi_client::AnimationPrefix>,
@@ -829,12 +1107,30 @@ struct SaveResize {
     path: PathBuf,
 }
 
+#[derive(Parser)]
+struct SaveAllResize {
+    /// File to which the resize layout dimensions of every workspace should be saved
+    path: PathBuf,
+}
+
 #[derive(Parser)]
 struct LoadResize {
     /// File from which the resize layout dimensions should be loaded
     path: PathBuf,
 }
 
+#[derive(Parser)]
+struct SaveFloatingLayout {
+    /// File to which the floating window positions should be saved
+    path: PathBuf,
+}
+
+#[derive(Parser)]
+struct LoadFloatingLayout {
+    /// File from which the floating window positions should be loaded
+    path: PathBuf,
+}
+
 #[derive(Parser)]
 struct LoadCustomLayout {
     /// JSON or YAML file from which the custom layout definition should be loaded
@@ -845,6 +1141,12 @@ struct LoadCustomLayout {
 struct SubscribeSocket {
     /// Name of the socket to send event notifications to
     socket: String,
+    /// Only send notifications when the window manager state has changed
+    #[clap(long)]
+    filter_state_changes: bool,
+    /// Only send notifications for these comma-separated event types (e.g. FocusChange,FocusWorkspaceNumber)
+    #[clap(long, value_delimiter = ',')]
+    events: Vec<String>,
 }
 
 #[derive(Parser)]
@@ -881,6 +1183,13 @@ struct PwshAppSpecificConfiguration {
     override_path: Option<PathBuf>,
 }
 
+#[derive(Parser)]
+struct FetchAppSpecificConfiguration {
+    /// Warn about entries in other app-specific configuration files which shadow entries that changed upstream
+    #[clap(long)]
+    merge_check: bool,
+}
+
 #[derive(Parser)]
 struct FormatAppSpecificConfiguration {
     /// YAML file from which the application-specific configurations should be loaded
@@ -899,6 +1208,19 @@ struct AltFocusHack {
     boolean_state: BooleanState,
 }
 
+#[derive(Parser)]
+struct GenerateWhkdrc {
+    /// Path to a static configuration JSON file (default: komorebi.json in the config home)
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// Modifier key to use for all generated bindings
+    #[clap(short, long, default_value = "alt")]
+    modifier: String,
+    /// Write the generated bindings to this file instead of printing them
+    #[clap(short, long)]
+    out: Option<PathBuf>,
+}
+
 #[derive(Parser)]
 struct EnableAutostart {
     /// Path to a static configuration JSON file
@@ -933,6 +1255,16 @@ struct Check {
 struct ReplaceConfiguration {
     /// Static configuration JSON file from which the configuration should be loaded
     path: PathBuf,
+    /// Apply initial workspace rules again to windows which have already been placed by them
+    #[clap(long)]
+    force_initial_rules: bool,
+}
+
+#[derive(Parser)]
+struct ReloadConfiguration {
+    /// Apply initial workspace rules again to windows which have already been placed by them
+    #[clap(long)]
+    force_initial_rules: bool,
 }
 
 #[derive(Parser)]
@@ -941,6 +1273,69 @@ struct EagerFocus {
     exe: String,
 }
 
+#[derive(Parser)]
+struct EagerFocusOrLaunch {
+    /// Case-sensitive exe identifier
+    exe: String,
+    /// Program to launch if no window matching the exe identifier is found
+    program: String,
+    /// Arguments to pass to the program
+    #[clap(allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+#[derive(Parser)]
+struct Log {
+    /// Print the last N lines of the logs and exit instead of following them
+    #[clap(long)]
+    tail: Option<usize>,
+}
+
+#[derive(Parser)]
+struct Record {
+    #[clap(value_enum)]
+    state: RecordingState,
+}
+
+#[derive(Parser)]
+struct VisibleWindows {
+    /// Output format
+    #[clap(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+}
+
+#[derive(Parser)]
+struct PreviewLayout {
+    #[clap(value_enum)]
+    default_layout: DefaultLayout,
+    /// Number of containers to arrange
+    #[clap(long, default_value_t = 3)]
+    containers: usize,
+    /// Width of the preview in characters
+    #[clap(long, default_value_t = 80)]
+    width: usize,
+    /// Height of the preview in characters
+    #[clap(long, default_value_t = 24)]
+    height: usize,
+}
+
+#[derive(Parser)]
+struct WhyIgnored {
+    /// Window handle (decimal or 0x-prefixed hexadecimal), or the exe name of an application to
+    /// explain all of its visible windows
+    target: String,
+    /// Print the raw JSON explanation
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Parser)]
+struct MonitorInformation {
+    /// Output format
+    #[clap(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+}
+
 #[derive(Parser)]
 #[clap(author, about, version = build::CLAP_LONG_VERSION)]
 struct Opts {
@@ -978,11 +1373,14 @@ enum SubCommand {
     GlobalState,
     /// Launch the komorebi-gui debugging tool
     Gui,
-    /// Show a JSON representation of visible windows
-    VisibleWindows,
+    /// Show a JSON or table representation of visible windows
+    VisibleWindows(VisibleWindows),
     /// Show information about connected monitors
     #[clap(alias = "monitor-info")]
-    MonitorInformation,
+    MonitorInformation(MonitorInformation),
+    /// Explain why a window is or is not managed by komorebi
+    #[clap(arg_required_else_help = true)]
+    WhyIgnored(WhyIgnored),
     /// Query the current window manager state
     #[clap(arg_required_else_help = true)]
     Query(Query),
@@ -1001,7 +1399,10 @@ enum SubCommand {
     #[clap(alias = "unsubscribe")]
     UnsubscribePipe(UnsubscribePipe),
     /// Tail komorebi.exe's process logs (cancel with Ctrl-C)
-    Log,
+    Log(Log),
+    /// Start or stop recording processed events and state changes to the state recording file
+    #[clap(arg_required_else_help = true)]
+    Record(Record),
     /// Quicksave the current resize layout dimensions
     #[clap(alias = "quick-save")]
     QuickSaveResize,
@@ -1012,10 +1413,19 @@ enum SubCommand {
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "save")]
     SaveResize(SaveResize),
+    /// Save the current resize layout dimensions of every workspace to a file
+    #[clap(arg_required_else_help = true)]
+    SaveAllResize(SaveAllResize),
     /// Load the resize layout dimensions from a file
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "load")]
     LoadResize(LoadResize),
+    /// Save the positions of all floating windows on all workspaces to a file
+    #[clap(arg_required_else_help = true)]
+    SaveFloatingLayout(SaveFloatingLayout),
+    /// Restore the positions of matching floating windows from a file
+    #[clap(arg_required_else_help = true)]
+    LoadFloatingLayout(LoadFloatingLayout),
     /// Change focus to the window in the specified direction
     #[clap(arg_required_else_help = true)]
     Focus(Focus),
@@ -1026,6 +1436,10 @@ enum SubCommand {
     Minimize,
     /// Close the focused window
     Close,
+    /// Close every window on the focused workspace, or on the given workspace
+    CloseWorkspaceWindows(CloseWorkspaceWindows),
+    /// Give the window swallowed by the focused window a tile of its own again
+    Unswallow,
     /// Forcibly focus the window at the cursor with a left mouse click
     ForceFocus,
     /// Change focus to the window in the specified cycle direction
@@ -1034,9 +1448,14 @@ enum SubCommand {
     /// Move the focused window in the specified cycle direction
     #[clap(arg_required_else_help = true)]
     CycleMove(CycleMove),
+    /// Focus the last focused window on the focused workspace
+    FocusLastWindow,
     /// Focus the first managed window matching the given exe
     #[clap(arg_required_else_help = true)]
     EagerFocus(EagerFocus),
+    /// Focus the first managed window matching the given exe, or launch the given program if none is found
+    #[clap(arg_required_else_help = true)]
+    EagerFocusOrLaunch(EagerFocusOrLaunch),
     /// Stack the focused window in the specified direction
     #[clap(arg_required_else_help = true)]
     Stack(Stack),
@@ -1055,6 +1474,20 @@ enum SubCommand {
     StackAll,
     /// Unstack all windows in the focused container
     UnstackAll,
+    /// Pin the focused container to a column in the Columns, VerticalStack and UltrawideVerticalStack layouts
+    #[clap(arg_required_else_help = true)]
+    PinToColumn(PinToColumn),
+    /// Unpin the focused container from its column
+    UnpinContainer,
+    /// Hide the focused window in the named scratchpad, or hide the scratchpad if it is shown
+    #[clap(arg_required_else_help = true)]
+    ScratchpadPush(Scratchpad),
+    /// Show the hidden windows of the named scratchpad as centred floating windows on the focused workspace
+    #[clap(arg_required_else_help = true)]
+    ScratchpadPop(Scratchpad),
+    /// Show the named scratchpad if it is hidden, otherwise hide it
+    #[clap(arg_required_else_help = true)]
+    ScratchpadToggle(Scratchpad),
     /// Resize the focused window in the specified direction
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "resize")]
@@ -1062,6 +1495,9 @@ enum SubCommand {
     /// Resize the focused window or primary column along the specified axis
     #[clap(arg_required_else_help = true)]
     ResizeAxis(ResizeAxis),
+    /// Cycle the primary split of the focused workspace through the split ratio presets
+    #[clap(arg_required_else_help = true)]
+    CycleSplitRatio(CycleSplitRatio),
     /// Move the focused window to the specified monitor
     #[clap(arg_required_else_help = true)]
     MoveToMonitor(MoveToMonitor),
@@ -1077,9 +1513,16 @@ enum SubCommand {
     /// Move the focused window to the workspace in the given cycle direction
     #[clap(arg_required_else_help = true)]
     CycleMoveToWorkspace(CycleMoveToWorkspace),
+    /// Create a new workspace on the focused monitor and move the focused window there
+    FocusedContainerToNewWorkspace,
+    /// Move the focused container back to the workspace it was on before it was last moved
+    ReturnToPreviousWorkspace(ReturnToPreviousWorkspace),
     /// Send the focused window to the specified monitor
     #[clap(arg_required_else_help = true)]
     SendToMonitor(SendToMonitor),
+    /// Send the focused stack to the specified monitor, keeping its focused window
+    #[clap(arg_required_else_help = true)]
+    SendStackToMonitor(SendStackToMonitor),
     /// Send the focused window to the monitor in the given cycle direction
     #[clap(arg_required_else_help = true)]
     CycleSendToMonitor(CycleSendToMonitor),
@@ -1146,6 +1589,15 @@ enum SubCommand {
     /// Set offsets for a monitor to exclude parts of the work area from tiling
     #[clap(arg_required_else_help = true)]
     MonitorWorkAreaOffset(MonitorWorkAreaOffset),
+    /// Reserve space for a bar on the edge of the monitor it is on until it unregisters or closes
+    #[clap(arg_required_else_help = true)]
+    RegisterAppBar(RegisterAppBar),
+    /// Release the space reserved for a bar registered with register-app-bar
+    #[clap(arg_required_else_help = true)]
+    UnregisterAppBar(UnregisterAppBar),
+    /// Set how windows on a monitor are counted towards the window based work area offset limit
+    #[clap(arg_required_else_help = true)]
+    MonitorWindowBasedWorkAreaOffsetCounting(MonitorWindowBasedWorkAreaOffsetCounting),
     /// Set container padding on the focused workspace
     #[clap(arg_required_else_help = true)]
     FocusedWorkspaceContainerPadding(FocusedWorkspaceContainerPadding),
@@ -1161,6 +1613,9 @@ enum SubCommand {
     /// Set the layout on the focused workspace
     #[clap(arg_required_else_help = true)]
     ChangeLayout(ChangeLayout),
+    /// Print a text preview of how a layout arranges a number of containers
+    #[clap(arg_required_else_help = true)]
+    PreviewLayout(PreviewLayout),
     /// Cycle between available layouts
     #[clap(arg_required_else_help = true)]
     CycleLayout(CycleLayout),
@@ -1171,18 +1626,35 @@ enum SubCommand {
     /// Flip the layout on the focused workspace (BSP only)
     #[clap(arg_required_else_help = true)]
     FlipLayout(FlipLayout),
+    /// Set the number of visible columns of the Scrolling layout on the focused workspace
+    #[clap(arg_required_else_help = true)]
+    ScrollingLayoutColumns(ScrollingLayoutColumns),
     /// Promote the focused window to the top of the tree
     Promote,
     /// Promote the user focus to the top of the tree
     PromoteFocus,
     /// Promote the window in the specified direction
     PromoteWindow(PromoteWindow),
+    /// Swap the focused window with the window occupying the largest area in the current layout
+    SwapWithLargest,
+    /// Move the focused floating window by the specified number of pixels
+    #[clap(arg_required_else_help = true)]
+    MoveFloating(MoveFloating),
+    /// Move the focused floating window to a predefined placement on its monitor
+    #[clap(arg_required_else_help = true)]
+    PlaceFloating(PlaceFloating),
+    /// Resize the focused floating window to the specified dimensions in pixels
+    #[clap(arg_required_else_help = true)]
+    ResizeWindowTo(ResizeWindowTo),
     /// Force the retiling of all managed windows
     Retile,
+    /// Immediately remove windows which have since been closed and print how many entries were removed
+    #[clap(alias = "gc")]
+    GarbageCollect,
     /// Set the monitor index preference for a monitor identified using its size
     #[clap(arg_required_else_help = true)]
     MonitorIndexPreference(MonitorIndexPreference),
-    /// Set the display index preference for a monitor identified using its display name
+    /// Set the display index preference for a monitor identified using its display name or serial number
     #[clap(arg_required_else_help = true)]
     DisplayIndexPreference(DisplayIndexPreference),
     /// Create at least this many workspaces for the specified monitor
@@ -1203,6 +1675,12 @@ enum SubCommand {
     /// Set the workspace padding for the specified workspace
     #[clap(arg_required_else_help = true)]
     NamedWorkspacePadding(NamedWorkspacePadding),
+    /// Set the container padding for all workspaces on the specified monitor which do not set their own
+    #[clap(arg_required_else_help = true)]
+    MonitorContainerPadding(MonitorContainerPadding),
+    /// Set the workspace padding for all workspaces on the specified monitor which do not set their own
+    #[clap(arg_required_else_help = true)]
+    MonitorWorkspacePadding(MonitorWorkspacePadding),
     /// Set the layout for the specified workspace
     #[clap(arg_required_else_help = true)]
     WorkspaceLayout(WorkspaceLayout),
@@ -1246,6 +1724,9 @@ enum SubCommand {
     /// Set the workspace name for the specified workspace
     #[clap(arg_required_else_help = true)]
     WorkspaceName(WorkspaceName),
+    /// Set the desktop wallpaper shown on the monitor when the specified workspace is focused
+    #[clap(arg_required_else_help = true)]
+    WorkspaceWallpaper(WorkspaceWallpaper),
     /// Toggle the behaviour for new windows (stacking or dynamic tiling)
     ToggleWindowContainerBehaviour,
     /// Enable or disable float override, which makes it so every new window opens in floating mode
@@ -1258,12 +1739,22 @@ enum SubCommand {
     /// mode, for the currently focused workspace. If there was no override value set for the
     /// workspace previously it takes the opposite of the global value.
     ToggleWorkspaceFloatOverride,
+    /// Move focus to the tiling or floating layer of the focused workspace
+    #[clap(arg_required_else_help = true)]
+    WorkspaceLayer(WorkspaceLayer),
+    /// Toggle focus between the tiling and floating layers of the focused workspace
+    ToggleWorkspaceLayer,
     /// Toggle window tiling on the focused workspace
     TogglePause,
+    /// Toggle tiling, borders, transparency and focus follows mouse on the specified monitor
+    #[clap(arg_required_else_help = true)]
+    ToggleMonitorPause(ToggleMonitorPause),
     /// Toggle window tiling on the focused workspace
     ToggleTiling,
     /// Toggle floating mode for the focused window
     ToggleFloat,
+    /// Toggle pinning the focused window above all other windows across workspace switches, floating it if it is tiled
+    ToggleAlwaysOnTop,
     /// Toggle monocle mode for the focused container
     ToggleMonocle,
     /// Toggle native maximization for the focused window
@@ -1278,7 +1769,7 @@ enum SubCommand {
     #[clap(arg_required_else_help = true)]
     ReplaceConfiguration(ReplaceConfiguration),
     /// Reload legacy komorebi.ahk or komorebi.ps1 configurations (if they exist)
-    ReloadConfiguration,
+    ReloadConfiguration(ReloadConfiguration),
     /// Enable or disable watching of legacy komorebi.ahk or komorebi.ps1 configurations (if they exist)
     #[clap(arg_required_else_help = true)]
     WatchConfiguration(WatchConfiguration),
@@ -1291,6 +1782,9 @@ enum SubCommand {
     /// Set the window behaviour when switching workspaces / cycling stacks
     #[clap(arg_required_else_help = true)]
     WindowHidingBehaviour(WindowHidingBehaviour),
+    /// Set the window behaviour when switching workspaces / cycling stacks for the specified workspace
+    #[clap(arg_required_else_help = true)]
+    WorkspaceWindowHidingBehaviour(WorkspaceWindowHidingBehaviour),
     /// Set the behaviour when moving windows across monitor boundaries
     #[clap(arg_required_else_help = true)]
     CrossMonitorMoveBehaviour(CrossMonitorMoveBehaviour),
@@ -1299,6 +1793,9 @@ enum SubCommand {
     /// Set the operation behaviour when the focused window is not managed
     #[clap(arg_required_else_help = true)]
     UnmanagedWindowOperationBehaviour(UnmanagedWindowOperationBehaviour),
+    /// Set the behaviour when a tiled window is dragged and dropped with the mouse
+    #[clap(arg_required_else_help = true)]
+    DragBehaviour(DragBehaviour),
     /// Add a rule to ignore the specified application
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "float-rule")]
@@ -1306,6 +1803,9 @@ enum SubCommand {
     /// Add a rule to always manage the specified application
     #[clap(arg_required_else_help = true)]
     ManageRule(ManageRule),
+    /// Add a rule to always open new windows of the specified application in floating mode
+    #[clap(arg_required_else_help = true)]
+    FloatOverrideRule(FloatOverrideRule),
     /// Add a rule to associate an application with a workspace on first show
     #[clap(arg_required_else_help = true)]
     InitialWorkspaceRule(InitialWorkspaceRule),
@@ -1327,7 +1827,21 @@ enum SubCommand {
     /// Remove all application association rules for all workspaces
     ClearAllWorkspaceRules,
     /// Enforce all workspace rules, including initial workspace rules that have already been applied
-    EnforceWorkspaceRules,
+    EnforceWorkspaceRules(EnforceWorkspaceRules),
+    /// Toggle whether windows which have been moved manually are moved back by workspace rules
+    ToggleWorkspaceRuleEnforcementOnMove,
+    /// Add a workspace rule for the specified application for the rest of the current session (takes precedence over configuration file rules)
+    #[clap(arg_required_else_help = true)]
+    SessionWorkspaceRule(SessionWorkspaceRule),
+    /// Add a workspace rule for the exe of the focused window for the rest of the current session
+    #[clap(arg_required_else_help = true)]
+    FocusedWindowSessionWorkspaceRule(FocusedWindowSessionWorkspaceRule),
+    /// Show all session workspace rules
+    SessionWorkspaceRules,
+    /// Remove all session workspace rules
+    ClearSessionWorkspaceRules,
+    /// Show all workspace, manage and ignore rules currently in effect and where each was defined
+    WorkspaceRules,
     /// Identify an application that sends EVENT_OBJECT_NAMECHANGE on launch
     #[clap(arg_required_else_help = true)]
     IdentifyObjectNameChangeApplication(IdentifyObjectNameChangeApplication),
@@ -1337,9 +1851,17 @@ enum SubCommand {
     /// Identify an application that has WS_EX_LAYERED, but should still be managed
     #[clap(arg_required_else_help = true)]
     IdentifyLayeredApplication(IdentifyLayeredApplication),
+    /// Move windows of the specified application into place without animation
+    #[clap(arg_required_else_help = true)]
+    AnimationBlacklist(AnimationBlacklist),
     /// Whitelist an application for title bar removal
     #[clap(arg_required_else_help = true)]
     RemoveTitleBar(RemoveTitleBar),
+    /// Remove an application from the title bar removal whitelist and give its windows their title bars back
+    #[clap(arg_required_else_help = true)]
+    RestoreTitleBar(RestoreTitleBar),
+    /// Show the applications currently whitelisted for title bar removal
+    TitleBarWhitelist,
     /// Toggle title bars for whitelisted applications
     ToggleTitleBars,
     /// Identify an application that has overflowing borders
@@ -1354,6 +1876,9 @@ enum SubCommand {
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "active-window-border-colour")]
     BorderColour(BorderColour),
+    /// Override the focused border colour for an application
+    #[clap(arg_required_else_help = true)]
+    BorderColourOverride(BorderColourOverride),
     /// Set the border width
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "active-window-border-width")]
@@ -1368,6 +1893,12 @@ enum SubCommand {
     /// Set the border implementation
     #[clap(arg_required_else_help = true)]
     BorderImplementation(BorderImplementation),
+    /// Enable or disable borders receiving clicks instead of passing them through
+    #[clap(arg_required_else_help = true)]
+    BorderHitTest(BorderHitTest),
+    /// Set whether borders are drawn above or below the windows they track
+    #[clap(arg_required_else_help = true)]
+    BorderZOrder(BorderZOrder),
     /// Enable or disable transparency for unfocused windows
     #[clap(arg_required_else_help = true)]
     Transparency(Transparency),
@@ -1376,6 +1907,11 @@ enum SubCommand {
     TransparencyAlpha(TransparencyAlpha),
     /// Toggle transparency for unfocused windows
     ToggleTransparency,
+    /// Keep all windows on the specified monitor opaque, regardless of focus
+    #[clap(arg_required_else_help = true)]
+    TransparencyIgnoreMonitor(TransparencyIgnoreMonitor),
+    /// Toggle the stackbar on and off, restoring the previously enabled stackbar mode
+    ToggleStackbar,
     /// Enable or disable movement animations
     #[clap(arg_required_else_help = true)]
     Animation(Animation),
@@ -1401,6 +1937,9 @@ enum SubCommand {
     MouseFollowsFocus(MouseFollowsFocus),
     /// Toggle mouse follows focus on all workspaces
     ToggleMouseFollowsFocus,
+    /// Set when mouse follows focus should move the cursor
+    #[clap(arg_required_else_help = true)]
+    MouseFollowsFocusScope(MouseFollowsFocusScope),
     /// Generate common app-specific configurations and fixes to use in komorebi.ahk
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "ahk-asc")]
@@ -1420,7 +1959,7 @@ enum SubCommand {
     FormatAppSpecificConfiguration(FormatAppSpecificConfiguration),
     /// Fetch the latest version of applications.json from komorebi-application-specific-configuration
     #[clap(alias = "fetch-asc")]
-    FetchAppSpecificConfiguration,
+    FetchAppSpecificConfiguration(FetchAppSpecificConfiguration),
     /// Generate a JSON Schema for applications.json
     #[clap(alias = "asc-schema")]
     ApplicationSpecificConfigurationSchema,
@@ -1432,6 +1971,8 @@ enum SubCommand {
     StaticConfigSchema,
     /// Generates a static configuration JSON file based on the current window manager state
     GenerateStaticConfig,
+    /// Generates whkdrc bindings for the workspaces in a static configuration JSON file
+    GenerateWhkdrc(GenerateWhkdrc),
     /// Generates the komorebi.lnk shortcut in shell:startup to autostart komorebi
     EnableAutostart(EnableAutostart),
     /// Deletes the komorebi.lnk shortcut in shell:startup to disable autostart
@@ -1447,6 +1988,137 @@ fn print_query(message: &SocketMessage) {
     }
 }
 
+fn query_state() -> Result<State> {
+    Ok(serde_json::from_str(&send_query(&SocketMessage::State)?)?)
+}
+
+fn format_rect(rect: &Rect) -> String {
+    format!(
+        "{}x{} @ {},{}",
+        rect.right, rect.bottom, rect.left, rect.top
+    )
+}
+
+/// Renders the visible windows response, which is keyed by monitor device id, using the state
+/// only to look up the index and focused workspace of each monitor
+fn visible_windows_table(
+    visible_windows: &HashMap<String, Vec<WindowDetails>>,
+    state: &State,
+) -> Table {
+    let mut table = Table::new(&["hwnd", "exe", "title", "monitor", "workspace", "managed"]);
+
+    let mut monitors = visible_windows
+        .iter()
+        .map(|(device_id, windows)| {
+            let position = state
+                .monitors
+                .elements()
+                .iter()
+                .enumerate()
+                .find(|(_, monitor)| monitor.device_id() == device_id)
+                .map(|(idx, monitor)| (idx, monitor.focused_workspace_idx()));
+
+            (position, device_id, windows)
+        })
+        .collect::<Vec<_>>();
+
+    monitors.sort_by_key(|(position, device_id, _)| (position.is_none(), *position, *device_id));
+
+    for (position, device_id, windows) in monitors {
+        let (monitor, workspace) = position.map_or_else(
+            || (device_id.clone(), String::new()),
+            |(monitor_idx, workspace_idx)| (monitor_idx.to_string(), workspace_idx.to_string()),
+        );
+
+        // Every visible window in the response, whether tiled or floating, is managed
+        for window in windows {
+            table.add_row(vec![
+                window.hwnd.to_string(),
+                window.exe.clone(),
+                truncate(&window.title, 60),
+                monitor.clone(),
+                workspace.clone(),
+                String::from("y"),
+            ]);
+        }
+    }
+
+    table
+}
+
+fn monitor_information_table(state: &State) -> Table {
+    let mut table = Table::new(&[
+        "index",
+        "device id",
+        "serial",
+        "size",
+        "work area",
+        "dpi",
+        "scale",
+    ]);
+
+    for (monitor_idx, monitor) in state.monitors.elements().iter().enumerate() {
+        table.add_row(vec![
+            monitor_idx.to_string(),
+            monitor.device_id().clone(),
+            monitor
+                .serial_number_id()
+                .clone()
+                .unwrap_or_else(|| String::from("-")),
+            format_rect(monitor.size()),
+            format_rect(monitor.work_area_size()),
+            monitor.dpi().to_string(),
+            format!("{}%", (monitor.scale_factor() * 100.0).round()),
+        ]);
+    }
+
+    table
+}
+
+fn why_ignored_report(debug: &RuleDebug) -> String {
+    let mut report = format!(
+        "hwnd {} ({}, class {}): {}\nmanaged: {}\n\n",
+        debug
+            .hwnd
+            .map_or_else(|| String::from("-"), |hwnd| hwnd.to_string()),
+        debug.exe_name.as_deref().unwrap_or("-"),
+        debug.class.as_deref().unwrap_or("-"),
+        truncate(debug.title.as_deref().unwrap_or_default(), 60),
+        if debug.should_manage { "yes" } else { "no" },
+    );
+
+    let mut table = Table::new(&["check", "result", "detail"]);
+    for check in &debug.checks {
+        let result = match (check.passed, check.decisive) {
+            (true, _) => "pass",
+            (false, true) => "FAIL (decisive)",
+            (false, false) => "fail",
+        };
+
+        table.add_row(vec![
+            check.check.to_string(),
+            String::from(result),
+            check.detail.clone().unwrap_or_default(),
+        ]);
+    }
+
+    report.push_str(&table.to_string());
+
+    if !debug.matched_rules.is_empty() {
+        report.push_str("\nmatched rules:\n");
+        for rule_match in &debug.matched_rules {
+            report.push_str(&format!(
+                "  {:?} {}: {}\n",
+                rule_match.collection,
+                rule_match.index,
+                serde_json::to_string(&rule_match.rule).unwrap_or_default()
+            ));
+        }
+    }
+
+    report
+}
+
 fn startup_dir() -> Result<PathBuf> {
     let startup = dirs::home_dir()
         .expect("unable to obtain user's home folder")
@@ -1465,10 +2137,48 @@ fn startup_dir() -> Result<PathBuf> {
     Ok(startup)
 }
 
+/// Warns when the running komorebi process has a different major version to komorebic, as
+/// commands and queries may not be understood in the same way by both
+fn warn_on_version_mismatch() {
+    // komorebi may not be running yet, or may be too old to respond to version queries
+    let Ok(server) = komorebi_client::server_version() else {
+        return;
+    };
+
+    let client_major = build::PKG_VERSION_MAJOR.parse::<u64>().ok();
+
+    if server.major().is_some() && server.major() != client_major {
+        eprintln!(
+            "warning: komorebic v{} is being used with komorebi v{}, which has a different major version",
+            build::PKG_VERSION,
+            server.version
+        );
+    }
+}
+
 #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
 fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
 
+    // Commands which are bound to hotkeys shouldn't pay for an extra round trip to komorebi, so
+    // only warn when a user is likely to be reading the output
+    if matches!(
+        opts.subcmd,
+        SubCommand::Check(_)
+            | SubCommand::State
+            | SubCommand::GlobalState
+            | SubCommand::Gui
+            | SubCommand::VisibleWindows(_)
+            | SubCommand::MonitorInformation(_)
+            | SubCommand::WhyIgnored(_)
+            | SubCommand::Query(_)
+            | SubCommand::Log(_)
+            | SubCommand::SessionWorkspaceRules
+            | SubCommand::WorkspaceRules
+    ) {
+        warn_on_version_mismatch();
+    }
+
     match opts.subcmd {
         SubCommand::Docgen => {
             let mut cli = Opts::command();
@@ -1639,13 +2349,24 @@ fn main() -> Result<()> {
                 println!("Found komorebi.json; this file can be passed to the start command with the --config flag\n");
 
                 if let Ok(config) = StaticConfig::read(&static_config) {
-                    match config.app_specific_configuration_path {
+                    match &config.app_specific_configuration_path {
                         None => {
                             println!("Application specific configuration file path has not been set. Try running 'komorebic fetch-asc'\n");
                         }
                         Some(path) => {
-                            if !Path::exists(Path::new(&path)) {
-                                println!("Application specific configuration file path '{}' does not exist. Try running 'komorebic fetch-asc'\n", path.display());
+                            match ApplicationSpecificConfiguration::load_merged(&path.paths()) {
+                                Ok((_, conflicts)) => {
+                                    for conflict in &conflicts {
+                                        println!("{conflict}");
+                                    }
+
+                                    if !conflicts.is_empty() {
+                                        println!();
+                                    }
+                                }
+                                Err(error) => {
+                                    println!("Could not load the application specific configuration: {error}\n");
+                                }
                             }
                         }
                     }
@@ -1654,7 +2375,24 @@ fn main() -> Result<()> {
                 // Check that this file adheres to the schema static config schema as the last step,
                 // so that more basic errors above can be shown to the error before schema-specific
                 // errors
-                let _ = serde_json::from_str::<StaticConfig>(&config_source)?;
+                let config = serde_json::from_str::<StaticConfig>(&config_source)?;
+
+                for issue in config.validate() {
+                    let bad_bit = issue.needle.and_then(|needle| {
+                        config_source
+                            .match_indices(&needle)
+                            .nth(issue.occurrence)
+                            .map(|(offset, _)| SourceSpan::new(offset.into(), needle.len()))
+                    });
+
+                    let diagnostic = ValidationWarning {
+                        message: issue.message,
+                        src: NamedSource::new("komorebi.json", config_source.clone()),
+                        bad_bit,
+                    };
+
+                    println!("{:?}", Report::new(diagnostic));
+                }
 
                 let path = resolve_home_path(static_config)?;
                 let raw = std::fs::read_to_string(path)?;
@@ -1729,9 +2467,26 @@ fn main() -> Result<()> {
                 println!("{}", whkdrc.display());
             }
         }
-        SubCommand::Log => {
+        SubCommand::Log(arg) => {
             let timestamp = Utc::now().format("%Y-%m-%d").to_string();
             let color_log = std::env::temp_dir().join(format!("komorebi.log.{timestamp}"));
+
+            if let Some(count) = arg.tail {
+                // Prefer the in-memory buffer when komorebi is running, falling back to the log
+                // file on disk when it isn't
+                if let Ok(response) = send_query(&SocketMessage::LogTail(count)) {
+                    println!("{response}");
+                } else {
+                    let contents = std::fs::read_to_string(color_log)?;
+                    let lines = contents.lines().collect::<Vec<_>>();
+                    for line in &lines[lines.len().saturating_sub(count)..] {
+                        println!("{line}");
+                    }
+                }
+
+                return Ok(());
+            }
+
             let file = TailedFile::new(File::open(color_log)?);
             let locked = file.lock();
             #[allow(clippy::significant_drop_in_scrutinee, clippy::lines_filter_map_ok)]
@@ -1739,6 +2494,9 @@ fn main() -> Result<()> {
                 println!("{line}");
             }
         }
+        SubCommand::Record(arg) => {
+            send_message(&SocketMessage::StateRecording(arg.state.into()))?;
+        }
         SubCommand::Focus(arg) => {
             send_message(&SocketMessage::FocusWindow(arg.operation_direction))?;
         }
@@ -1748,6 +2506,16 @@ fn main() -> Result<()> {
         SubCommand::Close => {
             send_message(&SocketMessage::Close)?;
         }
+        SubCommand::CloseWorkspaceWindows(arg) => {
+            send_message(&match (arg.workspace, arg.named_workspace) {
+                (_, Some(name)) => SocketMessage::CloseWindowsOnNamedWorkspace(name),
+                (Some(idx), None) => SocketMessage::CloseWindowsOnWorkspaceNumber(idx),
+                (None, None) => SocketMessage::CloseWorkspaceWindows,
+            })?;
+        }
+        SubCommand::Unswallow => {
+            send_message(&SocketMessage::Unswallow)?;
+        }
         SubCommand::Minimize => {
             send_message(&SocketMessage::Minimize)?;
         }
@@ -1760,9 +2528,35 @@ fn main() -> Result<()> {
         SubCommand::PromoteWindow(arg) => {
             send_message(&SocketMessage::PromoteWindow(arg.operation_direction))?;
         }
+        SubCommand::SwapWithLargest => {
+            send_message(&SocketMessage::SwapWithLargest)?;
+        }
+        SubCommand::MoveFloating(arg) => {
+            send_message(&SocketMessage::MoveFloatingWindow(arg.x, arg.y))?;
+        }
+        SubCommand::ResizeWindowTo(arg) => {
+            let height = match (arg.height, arg.aspect) {
+                (Some(height), _) => height,
+                (None, Some(aspect)) => aspect.height_for_width(arg.width),
+                (None, None) => bail!("either --height or --aspect must be given"),
+            };
+
+            send_message(&SocketMessage::ResizeFloatingWindowTo(arg.width, height))?;
+        }
+        SubCommand::PlaceFloating(arg) => {
+            send_message(&SocketMessage::PlaceFloatingWindow(
+                arg.predefined_placement,
+            ))?;
+        }
         SubCommand::TogglePause => {
             send_message(&SocketMessage::TogglePause)?;
         }
+        SubCommand::ToggleMonitorPause(arg) => {
+            send_message(&SocketMessage::ToggleMonitorPause(arg.target))?;
+        }
+        SubCommand::GarbageCollect => {
+            print_query(&SocketMessage::GarbageCollect);
+        }
         SubCommand::Retile => {
             send_message(&SocketMessage::Retile)?;
         }
@@ -1775,9 +2569,19 @@ fn main() -> Result<()> {
         SubCommand::CycleMove(arg) => {
             send_message(&SocketMessage::CycleMoveWindow(arg.cycle_direction))?;
         }
+        SubCommand::FocusLastWindow => {
+            send_message(&SocketMessage::FocusLastWindow)?;
+        }
         SubCommand::EagerFocus(arg) => {
             send_message(&SocketMessage::EagerFocus(arg.exe))?;
         }
+        SubCommand::EagerFocusOrLaunch(arg) => {
+            send_message(&SocketMessage::EagerFocusOrLaunch(
+                arg.exe,
+                arg.program,
+                arg.args,
+            ))?;
+        }
         SubCommand::MoveToMonitor(arg) => {
             send_message(&SocketMessage::MoveContainerToMonitorNumber(arg.target))?;
         }
@@ -1797,9 +2601,20 @@ fn main() -> Result<()> {
                 arg.cycle_direction,
             ))?;
         }
+        SubCommand::FocusedContainerToNewWorkspace => {
+            send_message(&SocketMessage::MoveContainerToNewWorkspace)?;
+        }
+        SubCommand::ReturnToPreviousWorkspace(arg) => {
+            send_message(&SocketMessage::ReturnContainerToPreviousWorkspace(
+                arg.follow,
+            ))?;
+        }
         SubCommand::SendToMonitor(arg) => {
             send_message(&SocketMessage::SendContainerToMonitorNumber(arg.target))?;
         }
+        SubCommand::SendStackToMonitor(arg) => {
+            send_message(&SocketMessage::SendStackToMonitorNumber(arg.target))?;
+        }
         SubCommand::CycleSendToMonitor(arg) => {
             send_message(&SocketMessage::CycleSendContainerToMonitor(
                 arg.cycle_direction,
@@ -1858,6 +2673,22 @@ fn main() -> Result<()> {
                 },
             ))?;
         }
+        SubCommand::RegisterAppBar(arg) => {
+            send_message(&SocketMessage::RegisterAppBar(
+                arg.hwnd,
+                arg.edge,
+                arg.thickness,
+            ))?;
+        }
+        SubCommand::UnregisterAppBar(arg) => {
+            send_message(&SocketMessage::UnregisterAppBar(arg.hwnd))?;
+        }
+        SubCommand::MonitorWindowBasedWorkAreaOffsetCounting(arg) => {
+            send_message(&SocketMessage::MonitorWindowBasedWorkAreaOffsetCounting(
+                arg.monitor,
+                arg.counting,
+            ))?;
+        }
         SubCommand::GlobalWorkAreaOffset(arg) => {
             send_message(&SocketMessage::WorkAreaOffset(Rect {
                 left: arg.left,
@@ -1892,6 +2723,18 @@ fn main() -> Result<()> {
                 arg.size,
             ))?;
         }
+        SubCommand::MonitorContainerPadding(arg) => {
+            send_message(&SocketMessage::MonitorContainerPadding(
+                arg.monitor,
+                arg.size,
+            ))?;
+        }
+        SubCommand::MonitorWorkspacePadding(arg) => {
+            send_message(&SocketMessage::MonitorWorkspacePadding(
+                arg.monitor,
+                arg.size,
+            ))?;
+        }
         SubCommand::FocusedWorkspacePadding(arg) => {
             send_message(&SocketMessage::FocusedWorkspacePadding(arg.size))?;
         }
@@ -1919,6 +2762,9 @@ fn main() -> Result<()> {
         SubCommand::ToggleFloat => {
             send_message(&SocketMessage::ToggleFloat)?;
         }
+        SubCommand::ToggleAlwaysOnTop => {
+            send_message(&SocketMessage::ToggleAlwaysOnTop)?;
+        }
         SubCommand::ToggleMonocle => {
             send_message(&SocketMessage::ToggleMonocle)?;
         }
@@ -2495,6 +3341,9 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::ManageRule(arg) => {
             send_message(&SocketMessage::ManageRule(arg.identifier, arg.id))?;
         }
+        SubCommand::FloatOverrideRule(arg) => {
+            send_message(&SocketMessage::FloatOverrideRule(arg.identifier, arg.id))?;
+        }
         SubCommand::InitialWorkspaceRule(arg) => {
             send_message(&SocketMessage::InitialWorkspaceRule(
                 arg.identifier,
@@ -2537,8 +3386,51 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::ClearAllWorkspaceRules => {
             send_message(&SocketMessage::ClearAllWorkspaceRules)?;
         }
-        SubCommand::EnforceWorkspaceRules => {
-            send_message(&SocketMessage::EnforceWorkspaceRules)?;
+        SubCommand::EnforceWorkspaceRules(arg) => {
+            let application = if let Some(exe) = arg.exe {
+                Some((ApplicationIdentifier::Exe, exe))
+            } else if let Some(class) = arg.class {
+                Some((ApplicationIdentifier::Class, class))
+            } else {
+                arg.title.map(|title| (ApplicationIdentifier::Title, title))
+            };
+
+            if application.is_none() && arg.workspace.is_none() {
+                send_message(&SocketMessage::EnforceWorkspaceRules)?;
+            } else {
+                let (identifier, id) = application.unzip();
+                send_message(&SocketMessage::EnforceWorkspaceRulesFor(
+                    identifier,
+                    id,
+                    arg.workspace,
+                ))?;
+            }
+        }
+        SubCommand::ToggleWorkspaceRuleEnforcementOnMove => {
+            send_message(&SocketMessage::ToggleWorkspaceRuleEnforcement)?;
+        }
+        SubCommand::SessionWorkspaceRule(arg) => {
+            send_message(&SocketMessage::SessionWorkspaceRule(
+                arg.identifier,
+                arg.id,
+                arg.monitor,
+                arg.workspace,
+            ))?;
+        }
+        SubCommand::FocusedWindowSessionWorkspaceRule(arg) => {
+            send_message(&SocketMessage::FocusedWindowSessionWorkspaceRule(
+                arg.monitor,
+                arg.workspace,
+            ))?;
+        }
+        SubCommand::SessionWorkspaceRules => {
+            print_query(&SocketMessage::SessionWorkspaceRules);
+        }
+        SubCommand::ClearSessionWorkspaceRules => {
+            send_message(&SocketMessage::ClearSessionWorkspaceRules)?;
+        }
+        SubCommand::WorkspaceRules => {
+            print_query(&SocketMessage::WorkspaceRules);
         }
         SubCommand::Stack(arg) => {
             send_message(&SocketMessage::StackWindow(arg.operation_direction))?;
@@ -2552,6 +3444,21 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::UnstackAll => {
             send_message(&SocketMessage::UnstackAll)?;
         }
+        SubCommand::PinToColumn(arg) => {
+            send_message(&SocketMessage::PinContainerToColumn(arg.target))?;
+        }
+        SubCommand::UnpinContainer => {
+            send_message(&SocketMessage::UnpinContainer)?;
+        }
+        SubCommand::ScratchpadPush(arg) => {
+            send_message(&SocketMessage::PushToScratchpad(arg.name))?;
+        }
+        SubCommand::ScratchpadPop(arg) => {
+            send_message(&SocketMessage::PopFromScratchpad(arg.name))?;
+        }
+        SubCommand::ScratchpadToggle(arg) => {
+            send_message(&SocketMessage::ToggleScratchpad(arg.name))?;
+        }
         SubCommand::FocusStackWindow(arg) => {
             send_message(&SocketMessage::FocusStackWindow(arg.target))?;
         }
@@ -2564,6 +3471,12 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::ChangeLayout(arg) => {
             send_message(&SocketMessage::ChangeLayout(arg.default_layout))?;
         }
+        SubCommand::PreviewLayout(arg) => {
+            println!(
+                "{}",
+                render_layout_preview(arg.default_layout, arg.containers, arg.width, arg.height)
+            );
+        }
         SubCommand::CycleLayout(arg) => {
             send_message(&SocketMessage::CycleLayout(arg.cycle_direction))?;
         }
@@ -2575,6 +3488,9 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::FlipLayout(arg) => {
             send_message(&SocketMessage::FlipLayout(arg.axis))?;
         }
+        SubCommand::ScrollingLayoutColumns(arg) => {
+            send_message(&SocketMessage::ScrollingLayoutColumns(arg.count))?;
+        }
         SubCommand::FocusMonitor(arg) => {
             send_message(&SocketMessage::FocusMonitorNumber(arg.target))?;
         }
@@ -2582,7 +3498,11 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             send_message(&SocketMessage::FocusLastWorkspace)?;
         }
         SubCommand::FocusWorkspace(arg) => {
-            send_message(&SocketMessage::FocusWorkspaceNumber(arg.target))?;
+            if arg.keep_focus {
+                send_message(&SocketMessage::FocusWorkspaceNumberKeepFocus(arg.target))?;
+            } else {
+                send_message(&SocketMessage::FocusWorkspaceNumber(arg.target))?;
+            }
         }
         SubCommand::FocusWorkspaces(arg) => {
             send_message(&SocketMessage::FocusWorkspaceNumbers(arg.target))?;
@@ -2615,6 +3535,13 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
                 name.value,
             ))?;
         }
+        SubCommand::WorkspaceWallpaper(arg) => {
+            send_message(&SocketMessage::WorkspaceWallpaper(
+                arg.monitor,
+                arg.workspace,
+                arg.value,
+            ))?;
+        }
         SubCommand::MonitorIndexPreference(arg) => {
             send_message(&SocketMessage::MonitorIndexPreference(
                 arg.index_preference,
@@ -2651,11 +3578,44 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::Gui => {
             Command::new("komorebi-gui").spawn()?;
         }
-        SubCommand::VisibleWindows => {
-            print_query(&SocketMessage::VisibleWindows);
-        }
-        SubCommand::MonitorInformation => {
-            print_query(&SocketMessage::MonitorInformation);
+        SubCommand::VisibleWindows(arg) => match arg.format {
+            OutputFormat::Json => print_query(&SocketMessage::VisibleWindows),
+            OutputFormat::Table => {
+                let visible_windows =
+                    serde_json::from_str(&send_query(&SocketMessage::VisibleWindows)?)?;
+                print!(
+                    "{}",
+                    visible_windows_table(&visible_windows, &query_state()?)
+                );
+            }
+        },
+        SubCommand::MonitorInformation(arg) => match arg.format {
+            OutputFormat::Json => print_query(&SocketMessage::MonitorInformation),
+            OutputFormat::Table => print!("{}", monitor_information_table(&query_state()?)),
+        },
+        SubCommand::WhyIgnored(arg) => {
+            let hwnd = match arg.target.strip_prefix("0x") {
+                Some(hex) => isize::from_str_radix(hex, 16).ok(),
+                None => arg.target.parse::<isize>().ok(),
+            };
+
+            let message = match hwnd {
+                Some(hwnd) => SocketMessage::WhyIgnored(hwnd),
+                None => SocketMessage::WhyIgnoredExe(arg.target.clone()),
+            };
+
+            if arg.json {
+                print_query(&message);
+            } else {
+                let explanations: Vec<RuleDebug> = serde_json::from_str(&send_query(&message)?)?;
+                if explanations.is_empty() {
+                    println!("No visible windows found for {}", arg.target);
+                }
+
+                for explanation in &explanations {
+                    println!("{}", why_ignored_report(explanation));
+                }
+            }
         }
         SubCommand::Query(arg) => {
             print_query(&SocketMessage::Query(arg.state_query));
@@ -2671,12 +3631,24 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
                 restore_window(hwnd);
             }
         }
-        SubCommand::ResizeEdge(resize) => {
-            send_message(&SocketMessage::ResizeWindowEdge(resize.edge, resize.sizing))?;
-        }
+        SubCommand::ResizeEdge(resize) => match resize.percent {
+            Some(percent) => {
+                send_message(&SocketMessage::ResizeWindowEdgePercent(
+                    resize.edge,
+                    resize.sizing,
+                    percent,
+                ))?;
+            }
+            None => {
+                send_message(&SocketMessage::ResizeWindowEdge(resize.edge, resize.sizing))?;
+            }
+        },
         SubCommand::ResizeAxis(arg) => {
             send_message(&SocketMessage::ResizeWindowAxis(arg.axis, arg.sizing))?;
         }
+        SubCommand::CycleSplitRatio(arg) => {
+            send_message(&SocketMessage::CycleSplitRatio(arg.axis))?;
+        }
         SubCommand::FocusFollowsMouse(arg) => {
             send_message(&SocketMessage::FocusFollowsMouse(
                 arg.implementation,
@@ -2684,10 +3656,26 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             ))?;
         }
         SubCommand::ReplaceConfiguration(arg) => {
-            send_message(&SocketMessage::ReplaceConfiguration(arg.path))?;
+            if arg.force_initial_rules {
+                // Workspace rules are enforced when the configuration is loaded, so forgetting
+                // which windows initial rules have been applied to must come first
+                send_batch([
+                    SocketMessage::ResetInitialWorkspaceRules,
+                    SocketMessage::ReplaceConfiguration(arg.path),
+                ])?;
+            } else {
+                send_message(&SocketMessage::ReplaceConfiguration(arg.path))?;
+            }
         }
-        SubCommand::ReloadConfiguration => {
-            send_message(&SocketMessage::ReloadConfiguration)?;
+        SubCommand::ReloadConfiguration(arg) => {
+            if arg.force_initial_rules {
+                send_batch([
+                    SocketMessage::ResetInitialWorkspaceRules,
+                    SocketMessage::ReloadConfiguration,
+                ])?;
+            } else {
+                send_message(&SocketMessage::ReloadConfiguration)?;
+            }
         }
         SubCommand::WatchConfiguration(arg) => {
             send_message(&SocketMessage::WatchConfiguration(arg.boolean_state.into()))?;
@@ -2713,6 +3701,12 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
                 target.id,
             ))?;
         }
+        SubCommand::AnimationBlacklist(target) => {
+            send_message(&SocketMessage::AnimationBlacklist(
+                target.identifier,
+                target.id,
+            ))?;
+        }
         SubCommand::RemoveTitleBar(target) => {
             match target.identifier {
                 ApplicationIdentifier::Exe => {}
@@ -2723,6 +3717,15 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
 
             send_message(&SocketMessage::RemoveTitleBar(target.identifier, target.id))?;
         }
+        SubCommand::RestoreTitleBar(target) => {
+            send_message(&SocketMessage::RestoreTitleBar(
+                target.identifier,
+                target.id,
+            ))?;
+        }
+        SubCommand::TitleBarWhitelist => {
+            print_query(&SocketMessage::TitleBarWhitelist);
+        }
         SubCommand::ToggleTitleBars => {
             send_message(&SocketMessage::ToggleTitleBars)?;
         }
@@ -2736,16 +3739,49 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             send_message(&SocketMessage::QuickSave)?;
         }
         SubCommand::QuickLoadResize => {
-            send_message(&SocketMessage::QuickLoad)?;
+            let response = send_query(&SocketMessage::QuickLoad)?;
+            if !response.is_empty() {
+                bail!(response);
+            }
         }
         SubCommand::SaveResize(arg) => {
             send_message(&SocketMessage::Save(resolve_home_path(arg.path)?))?;
         }
+        SubCommand::SaveAllResize(arg) => {
+            send_message(&SocketMessage::SaveAll(resolve_home_path(arg.path)?))?;
+        }
         SubCommand::LoadResize(arg) => {
-            send_message(&SocketMessage::Load(resolve_home_path(arg.path)?))?;
+            let response = send_query(&SocketMessage::Load(resolve_home_path(arg.path)?))?;
+            if !response.is_empty() {
+                bail!(response);
+            }
+        }
+        SubCommand::SaveFloatingLayout(arg) => {
+            send_message(&SocketMessage::SaveFloatingLayout(resolve_home_path(
+                arg.path,
+            )?))?;
+        }
+        SubCommand::LoadFloatingLayout(arg) => {
+            send_message(&SocketMessage::LoadFloatingLayout(resolve_home_path(
+                arg.path,
+            )?))?;
         }
         SubCommand::SubscribeSocket(arg) => {
-            send_message(&SocketMessage::AddSubscriberSocket(arg.socket))?;
+            if arg.filter_state_changes || !arg.events.is_empty() {
+                let response = send_query(&SocketMessage::AddSubscriberSocketWithOptions(
+                    arg.socket,
+                    SubscribeOptions {
+                        filter_state_changes: arg.filter_state_changes,
+                        event_types: arg.events,
+                    },
+                ))?;
+
+                if !response.is_empty() {
+                    bail!(response);
+                }
+            } else {
+                send_message(&SocketMessage::AddSubscriberSocket(arg.socket))?;
+            }
         }
         SubCommand::UnsubscribeSocket(arg) => {
             send_message(&SocketMessage::RemoveSubscriberSocket(arg.socket))?;
@@ -2762,6 +3798,9 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::MouseFollowsFocus(arg) => {
             send_message(&SocketMessage::MouseFollowsFocus(arg.boolean_state.into()))?;
         }
+        SubCommand::MouseFollowsFocusScope(arg) => {
+            send_message(&SocketMessage::MouseFollowsFocusScope(arg.scope))?;
+        }
         SubCommand::Border(arg) => {
             send_message(&SocketMessage::Border(arg.boolean_state.into()))?;
         }
@@ -2773,18 +3812,41 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
                 arg.b,
             ))?;
         }
-        SubCommand::BorderWidth(arg) => {
-            send_message(&SocketMessage::BorderWidth(arg.width))?;
-        }
-        SubCommand::BorderOffset(arg) => {
-            send_message(&SocketMessage::BorderOffset(arg.offset))?;
+        SubCommand::BorderColourOverride(arg) => {
+            send_message(&SocketMessage::BorderColourOverride(
+                arg.identifier,
+                arg.id,
+                arg.r,
+                arg.g,
+                arg.b,
+            ))?;
         }
+        SubCommand::BorderWidth(arg) => match arg.window_kind {
+            None => send_message(&SocketMessage::BorderWidth(arg.width))?,
+            Some(window_kind) => send_message(&SocketMessage::BorderWidthForWindowKind(
+                window_kind,
+                arg.width,
+            ))?,
+        },
+        SubCommand::BorderOffset(arg) => match arg.window_kind {
+            None => send_message(&SocketMessage::BorderOffset(arg.offset))?,
+            Some(window_kind) => send_message(&SocketMessage::BorderOffsetForWindowKind(
+                window_kind,
+                arg.offset,
+            ))?,
+        },
         SubCommand::BorderStyle(arg) => {
             send_message(&SocketMessage::BorderStyle(arg.style))?;
         }
         SubCommand::BorderImplementation(arg) => {
             send_message(&SocketMessage::BorderImplementation(arg.style))?;
         }
+        SubCommand::BorderHitTest(arg) => {
+            send_message(&SocketMessage::BorderHitTest(arg.boolean_state.into()))?;
+        }
+        SubCommand::BorderZOrder(arg) => {
+            send_message(&SocketMessage::BorderZOrder(arg.z_order))?;
+        }
         SubCommand::Transparency(arg) => {
             send_message(&SocketMessage::Transparency(arg.boolean_state.into()))?;
         }
@@ -2794,6 +3856,15 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::ToggleTransparency => {
             send_message(&SocketMessage::ToggleTransparency)?;
         }
+        SubCommand::TransparencyIgnoreMonitor(arg) => {
+            send_message(&SocketMessage::TransparencyIgnoreMonitor(
+                arg.monitor,
+                arg.boolean_state.into(),
+            ))?;
+        }
+        SubCommand::ToggleStackbar => {
+            send_message(&SocketMessage::ToggleStackbar)?;
+        }
         SubCommand::Animation(arg) => {
             send_message(&SocketMessage::Animation(
                 arg.boolean_state.into(),
@@ -2810,10 +3881,15 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             send_message(&SocketMessage::AnimationFps(arg.fps))?;
         }
         SubCommand::AnimationStyle(arg) => {
-            send_message(&SocketMessage::AnimationStyle(
-                arg.style,
-                arg.animation_type,
-            ))?;
+            let style = match arg.cubic_bezier.as_deref() {
+                Some(&[x1, y1, x2, y2]) => {
+                    komorebi_client::AnimationStyle::CubicBezier(x1, y1, x2, y2).validate()?
+                }
+                Some(_) => bail!("--cubic-bezier must be given as x1,y1,x2,y2"),
+                None => arg.style,
+            };
+
+            send_message(&SocketMessage::AnimationStyle(style, arg.animation_type))?;
         }
 
         SubCommand::ResizeDelta(arg) => {
@@ -2831,14 +3907,30 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::ToggleWorkspaceFloatOverride => {
             send_message(&SocketMessage::ToggleWorkspaceFloatOverride)?;
         }
+        SubCommand::WorkspaceLayer(arg) => {
+            send_message(&SocketMessage::WorkspaceLayer(arg.layer))?;
+        }
+        SubCommand::ToggleWorkspaceLayer => {
+            send_message(&SocketMessage::ToggleWorkspaceLayer)?;
+        }
         SubCommand::WindowHidingBehaviour(arg) => {
             send_message(&SocketMessage::WindowHidingBehaviour(arg.hiding_behaviour))?;
         }
+        SubCommand::WorkspaceWindowHidingBehaviour(arg) => {
+            send_message(&SocketMessage::WorkspaceWindowHidingBehaviour(
+                arg.monitor,
+                arg.workspace,
+                arg.value,
+            ))?;
+        }
         SubCommand::CrossMonitorMoveBehaviour(arg) => {
             send_message(&SocketMessage::CrossMonitorMoveBehaviour(
                 arg.move_behaviour,
             ))?;
         }
+        SubCommand::DragBehaviour(arg) => {
+            send_message(&SocketMessage::DragBehaviour(arg.mode, arg.modifier))?;
+        }
         SubCommand::ToggleCrossMonitorMoveBehaviour => {
             send_message(&SocketMessage::ToggleCrossMonitorMoveBehaviour)?;
         }
@@ -2924,12 +4016,20 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
 
             println!("File successfully formatted for PRs to https://github.com/LGUG2Z/komorebi-application-specific-configuration");
         }
-        SubCommand::FetchAppSpecificConfiguration => {
+        SubCommand::FetchAppSpecificConfiguration(arg) => {
             let content = reqwest::blocking::get("https://raw.githubusercontent.com/LGUG2Z/komorebi-application-specific-configuration/master/applications.json")?
                 .text()?;
 
             let output_file = HOME_DIR.join("applications.json");
 
+            // The previous download has to be read before it is overwritten to find out what
+            // changed upstream
+            let previous = if arg.merge_check && output_file.is_file() {
+                ApplicationSpecificConfiguration::load(&output_file).ok()
+            } else {
+                None
+            };
+
             let mut file = OpenOptions::new()
                 .write(true)
                 .create(true)
@@ -2939,6 +4039,10 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             file.write_all(content.as_bytes())?;
 
             println!("Latest version of applications.json from https://github.com/LGUG2Z/komorebi-application-specific-configuration downloaded\n");
+
+            if arg.merge_check {
+                merge_check(&output_file, previous, &content)?;
+            }
             println!(
                "You can add this to your komorebi.json static configuration file like this: \n\n\"app_specific_configuration_path\": \"{}\"",
                output_file.display().to_string().replace("\\", "/")
@@ -2974,6 +4078,33 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::GenerateStaticConfig => {
             print_query(&SocketMessage::GenerateStaticConfig);
         }
+        SubCommand::GenerateWhkdrc(args) => {
+            let config_path = args
+                .config
+                .unwrap_or_else(|| HOME_DIR.join("komorebi.json"));
+
+            let config = StaticConfig::read(&config_path)?;
+            let generated = komorebi_client::generate_whkdrc(&config, &args.modifier);
+
+            for warning in &generated.warnings {
+                eprintln!("warning: {warning}");
+            }
+
+            match args.out {
+                None => print!("{}", generated.contents),
+                Some(out) => {
+                    if out.exists()
+                        && !confirm(&format!("{} already exists, overwrite?", out.display()))?
+                    {
+                        println!("Leaving {} unchanged", out.display());
+                        return Ok(());
+                    }
+
+                    std::fs::write(&out, generated.contents)?;
+                    println!("Generated whkdrc written to {}", out.display());
+                }
+            }
+        }
         // Deprecated
         SubCommand::AltFocusHack(_) | SubCommand::IdentifyBorderOverflowApplication(_) => {
             println!("Command deprecated - this is now automatically handled by komorebi! 🎉");
@@ -2983,6 +4114,77 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
     Ok(())
 }
 
+/// Warns about entries in the other files listed in app_specific_configuration_path which shadow
+/// entries of the downloaded applications.json that changed since the previous download
+fn merge_check(
+    output_file: &PathBuf,
+    previous: Option<ApplicationSpecificConfiguration>,
+    content: &str,
+) -> Result<()> {
+    let Some(previous) = previous else {
+        println!("There was no previous download of applications.json to compare against\n");
+        return Ok(());
+    };
+
+    let upstream: ApplicationSpecificConfiguration = serde_json::from_str(content)?;
+    let config = StaticConfig::read(&HOME_DIR.join("komorebi.json"))?;
+    let Some(asc_path) = config.app_specific_configuration_path else {
+        println!("Application specific configuration file path has not been set\n");
+        return Ok(());
+    };
+
+    let output_file = resolve_home_path(output_file)?;
+
+    let mut shadowed = 0;
+    for path in asc_path.paths() {
+        // Paths in komorebi.json may use $Env:USERPROFILE or ~ and must be resolved before they
+        // can be read or compared
+        let path = resolve_home_path(path)?;
+        if path == output_file {
+            continue;
+        }
+
+        let Some(overrides) = ApplicationSpecificConfiguration::load_file(&path)? else {
+            continue;
+        };
+
+        for (name, entry) in overrides.iter() {
+            if matches!(entry, AscApplicationRulesOrSchema::Schema(_)) {
+                continue;
+            }
+
+            let before = previous.get(name).map(serde_json::to_value).transpose()?;
+            let after = upstream.get(name).map(serde_json::to_value).transpose()?;
+
+            if before != after {
+                println!(
+                    "\"{name}\" in {} shadows an entry which changed upstream",
+                    path.display()
+                );
+                shadowed += 1;
+            }
+        }
+    }
+
+    if shadowed == 0 {
+        println!("No overridden entries changed upstream");
+    }
+
+    println!();
+
+    Ok(())
+}
+
+fn confirm(question: &str) -> Result<bool> {
+    print!("{question} [y/N] ");
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"))
+}
+
 fn show_window(hwnd: HWND, command: SHOW_WINDOW_CMD) {
     // BOOL is returned but does not signify whether or not the operation was succesful
     // https://docs.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-showwindow
@@ -0,0 +1,80 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// A plain-text table with left-aligned columns sized to fit their widest cell
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(ToString::to_string).collect(),
+            rows: vec![],
+        }
+    }
+
+    /// Rows with fewer cells than there are headers are padded with empty cells, and any cells
+    /// beyond the number of headers are dropped
+    pub fn add_row(&mut self, row: Vec<String>) {
+        let mut row = row;
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+
+        widths
+    }
+
+    fn write_row(f: &mut Formatter<'_>, cells: &[String], widths: &[usize]) -> std::fmt::Result {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        writeln!(f, "{}", line.trim_end())
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let widths = self.widths();
+
+        Self::write_row(f, &self.headers, &widths)?;
+
+        let separators = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+        Self::write_row(f, &separators, &widths)?;
+
+        if self.rows.is_empty() {
+            return writeln!(f, "(no results)");
+        }
+
+        for row in &self.rows {
+            Self::write_row(f, row, &widths)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Truncates a string to at most `max` characters, marking the truncation with an ellipsis
+pub fn truncate(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        return value.to_string();
+    }
+
+    let mut truncated: String = value.chars().take(max.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}